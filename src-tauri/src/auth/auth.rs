@@ -35,7 +35,7 @@ impl Authenticator {
         )
         .set_redirect_uri(RedirectUrl::new(REDIRECT_URL.to_string()).unwrap());
 
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::utils::http::client_builder()
             .timeout(Duration::from_secs(30))
             .build()
             .unwrap();
@@ -368,11 +368,11 @@ impl Authenticator {
             .await?;
 
         let msa_token = token_response.access_token().secret();
+        // Microsoft doesn't always rotate the refresh token, the old one stays valid then
         let new_refresh_token = token_response
             .refresh_token()
-            .ok_or("No refresh token in response")?
-            .secret()
-            .to_string();
+            .map(|token| token.secret().to_string())
+            .unwrap_or_else(|| refresh_token.to_string());
         
         println!("✓ Microsoft token refreshed");
 
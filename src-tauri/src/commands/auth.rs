@@ -1,5 +1,6 @@
 use crate::auth::Authenticator;
 use crate::services::accounts::AccountManager;
+use crate::services::auth::{DeviceLogin, DeviceLoginPoll, DeviceLoginStart};
 use crate::models::{AuthResponse, AccountInfo};
 
 #[tauri::command]
@@ -71,39 +72,8 @@ pub async fn microsoft_login_and_store() -> Result<AccountInfo, String> {
         .await
         .map_err(|e| format!("Authentication failed: {}", e))?;
     
-    let account_exists = AccountManager::account_exists(&auth_response.uuid)
-        .map_err(|e| format!("Failed to check account: {}", e))?;
-    
-    if account_exists {
-        // Update existing account with new tokens
-        AccountManager::update_account_tokens(
-            &auth_response.uuid,
-            auth_response.access_token.clone(),
-            auth_response.refresh_token.clone(),
-            auth_response.token_expiry,
-        )
-        .map_err(|e| format!("Failed to update account: {}", e))?;
-        
-        AccountManager::set_active_account(&auth_response.uuid)
-            .map_err(|e| format!("Failed to switch account: {}", e))?;
-    } else {
-        AccountManager::add_account(
-            auth_response.uuid.clone(),
-            auth_response.username.clone(),
-            auth_response.access_token.clone(),
-            auth_response.refresh_token.clone(),
-            auth_response.token_expiry,
-        )
-        .map_err(|e| format!("Failed to store account: {}", e))?;
-    }
-    
-    let accounts = AccountManager::get_all_accounts()
-        .map_err(|e| format!("Failed to get accounts: {}", e))?;
-    
-    accounts
-        .into_iter()
-        .find(|acc| acc.uuid == auth_response.uuid)
-        .ok_or_else(|| "Failed to retrieve account info".to_string())
+    AccountManager::store_login(&auth_response)
+        .map_err(|e| format!("Failed to store account: {}", e))
 }
 
 #[tauri::command]
@@ -112,7 +82,7 @@ pub async fn get_launch_token() -> Result<String, String> {
         .map_err(|e| format!("Failed to get active account: {}", e))?
         .ok_or("No active account selected")?;
     
-    AccountManager::get_valid_token(&active.uuid)
+    AccountManager::ensure_fresh_token(&active.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))
 }
@@ -123,9 +93,31 @@ pub async fn refresh_account_token(uuid: String) -> Result<String, String> {
         return Err("Invalid UUID format".to_string());
     }
     
-    AccountManager::get_valid_token(&uuid)
+    AccountManager::ensure_fresh_token(&uuid)
         .await
         .map_err(|e| format!("Failed to refresh token: {}", e))?;
     
     Ok("Token refreshed successfully".to_string())
-}
\ No newline at end of file
+}
+
+/// Start signing in with a code entered on microsoft.com/link, no local callback needed
+#[tauri::command]
+pub async fn begin_device_login() -> Result<DeviceLoginStart, String> {
+    DeviceLogin::begin()
+        .await
+        .map_err(|e| format!("Failed to start device login: {}", e))
+}
+
+/// Call every `interval` seconds until the login is complete, expired or declined
+#[tauri::command]
+pub async fn poll_device_login(user_code: String) -> Result<DeviceLoginPoll, String> {
+    DeviceLogin::poll(&user_code)
+        .await
+        .map_err(|e| format!("Device login failed: {}", e))
+}
+
+#[tauri::command]
+pub async fn cancel_device_login(user_code: String) -> Result<(), String> {
+    DeviceLogin::cancel(&user_code);
+    Ok(())
+}
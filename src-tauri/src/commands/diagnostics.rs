@@ -0,0 +1,154 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::services::antivirus::{AntivirusDetector, AntivirusReport, DefenderExclusionInfo};
+use crate::services::case_check::{CaseChecker, CaseConflictReport};
+use crate::services::gc_log::{GcLogAnalyzer, GcReport};
+use crate::services::instance::InstanceManager;
+use crate::services::integrity::{InstanceVerifier, IntegrityReport, RepairReport};
+use crate::services::network_diagnostics::{NetworkDiagnostics, NetworkDiagnosticsReport};
+use crate::services::settings::SettingsManager;
+use crate::services::profiler::{SparkProfileLink, SparkProfiler};
+use crate::services::preflight::{PathPreflight, PathPreflightReport};
+use crate::utils::get_instance_dir;
+
+/// Paths affected by the last detected antivirus interference, with the folders to exclude
+#[tauri::command]
+pub async fn get_antivirus_report() -> Result<Option<AntivirusReport>, String> {
+    AntivirusDetector::load_report()
+        .map_err(|e| format!("Failed to load antivirus report: {}", e))
+}
+
+#[tauri::command]
+pub async fn clear_antivirus_report() -> Result<(), String> {
+    AntivirusDetector::clear_report()
+        .map_err(|e| format!("Failed to clear antivirus report: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_defender_exclusion_info() -> Result<DefenderExclusionInfo, String> {
+    Ok(AntivirusDetector::defender_exclusion_info())
+}
+
+/// Requires the frontend to pass `confirmed: true` after showing the consent steps
+#[tauri::command]
+pub async fn add_defender_exclusion(confirmed: bool) -> Result<String, String> {
+    if !confirmed {
+        return Err("Adding a Defender exclusion requires explicit confirmation".to_string());
+    }
+
+    AntivirusDetector::add_defender_exclusion()
+        .map_err(|e| format!("Failed to add Defender exclusion: {}", e))?;
+
+    Ok("Launcher directory added to Windows Defender exclusions".to_string())
+}
+
+/// Warns about launcher paths that are too long or contain characters some mods choke on
+#[tauri::command]
+pub async fn check_launcher_path() -> Result<PathPreflightReport, String> {
+    Ok(PathPreflight::check())
+}
+
+/// Reachability and latency of the login, download and mod services, for "downloads don't work" reports
+#[tauri::command]
+pub async fn run_network_diagnostics() -> Result<NetworkDiagnosticsReport, String> {
+    NetworkDiagnostics::run()
+        .await
+        .map_err(|e| format!("Failed to run network diagnostics: {}", e))
+}
+
+/// Files that only differ by case and resource paths Minecraft can't load
+#[tauri::command]
+pub async fn check_instance_case_conflicts(instance_name: String) -> Result<CaseConflictReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    CaseChecker::check_instance(&instance_dir)
+        .map_err(|e| format!("Failed to check case conflicts: {}", e))
+}
+
+#[tauri::command]
+pub async fn check_pack_case_conflicts(file_path: String) -> Result<CaseConflictReport, String> {
+    let path = std::path::Path::new(&file_path);
+
+    if !path.exists() {
+        return Err("Modpack file does not exist".to_string());
+    }
+
+    CaseChecker::check_pack_archive(path)
+        .map_err(|e| format!("Failed to check case conflicts: {}", e))
+}
+
+/// Report from the last GC-logged session, if any
+#[tauri::command]
+pub async fn get_gc_report(instance_name: String) -> Result<Option<GcReport>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    GcLogAnalyzer::load_report(&safe_name)
+        .map_err(|e| format!("Failed to load GC report: {}", e))
+}
+
+#[tauri::command]
+pub async fn analyze_gc_log(instance_name: String) -> Result<GcReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    let memory_mb = match instance.settings_override {
+        Some(settings) => settings.memory_mb,
+        None => SettingsManager::load().unwrap_or_default().memory_mb,
+    };
+
+    GcLogAnalyzer::analyze(&safe_name, memory_mb)
+        .map_err(|e| format!("Failed to analyze GC log: {}", e))
+}
+
+/// Install the spark profiler mod matching the instance's loader
+#[tauri::command]
+pub async fn install_spark_profiler(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    SparkProfiler::install(&instance)
+        .await
+        .map_err(|e| format!("Failed to install spark: {}", e))
+}
+
+/// Profile links spark printed to the instance logs, newest first
+#[tauri::command]
+pub async fn get_spark_profiles(instance_name: String) -> Result<Vec<SparkProfileLink>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    SparkProfiler::find_profile_links(&safe_name)
+        .map_err(|e| format!("Failed to read logs: {}", e))
+}
+
+/// Check instance.json, the game files and locked mods for missing or corrupt files
+#[tauri::command]
+pub async fn verify_instance(instance_name: String) -> Result<IntegrityReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        InstanceVerifier::verify(&safe_name).map_err(|e| format!("Failed to verify instance: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Verification task failed: {}", e))?
+}
+
+/// Download the files `verify_instance` reported as repairable again
+#[tauri::command]
+pub async fn repair_instance(instance_name: String) -> Result<RepairReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Stop '{}' before repairing it", safe_name));
+    }
+
+    InstanceVerifier::repair(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to repair instance: {}", e))
+}
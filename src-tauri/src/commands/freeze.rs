@@ -0,0 +1,162 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::pack_library::PackKind;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::Runtime;
+
+/// Commands that only read, run or copy an instance. Everything else that names an instance is
+/// refused while it's frozen, so commands added later are blocked until they're listed here.
+const ALLOWED_WHILE_FROZEN: &[&str] = &[
+    "set_instance_frozen",
+    // Playing
+    "launch_instance",
+    "launch_instance_with_active_account",
+    "queue_launches",
+    "launch_instances_synchronized",
+    "prewarm_instance",
+    "cancel_queued_launch",
+    "kill_instance",
+    "big_picture_activate",
+    // Reading
+    "get_instance_banner",
+    "get_instance_directory_layout",
+    "get_instance_disk_usage",
+    "get_instance_icon",
+    "get_instance_notes",
+    "get_instance_recordings",
+    "get_instance_settings",
+    "get_instance_setup_steps",
+    "get_instance_worlds",
+    "get_instance_credits",
+    "get_installed_mods",
+    "get_installed_mod_details",
+    "get_jar_mods",
+    "get_mod_config_files",
+    "get_pending_curseforge_downloads",
+    "list_config_files",
+    "read_config_file",
+    "get_practice_maps",
+    "preview_pack_update",
+    "get_gc_report",
+    "get_spark_profiles",
+    "list_instance_snapshots",
+    "list_mods",
+    "list_resource_packs",
+    "list_world_datapacks",
+    "analyze_gc_log",
+    "analyze_mod_conflicts",
+    "check_instance_case_conflicts",
+    "check_managed_instance_update",
+    "check_mod_updates",
+    "check_version_compatibility",
+    "verify_instance",
+    // Copies and exports leave the instance as it is
+    "clone_instance",
+    "duplicate_instance",
+    "create_ephemeral_instance",
+    "create_template_from_instance",
+    "attest_instance",
+    "export_instance",
+    "export_instance_code",
+    "export_instance_credits",
+    "export_mod_list",
+    // Outside the instance: folders and shortcuts
+    "open_instance_file",
+    "open_instance_folder",
+    "open_mods_folder",
+    "open_world_folder",
+    "open_worlds_folder",
+    "reveal_instance_path",
+    "add_instance_to_steam",
+    "create_desktop_shortcut",
+];
+
+/// Wrap the command handler so every call naming a frozen instance is refused before it runs
+pub fn guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let payload = match invoke.message.payload() {
+            InvokeBody::Json(value) => Some(value),
+            _ => None,
+        };
+        if let Err(e) = check_command(invoke.message.command(), payload) {
+            invoke.resolver.reject(e);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+fn check_command(command: &str, payload: Option<&serde_json::Value>) -> Result<(), String> {
+    if ALLOWED_WHILE_FROZEN.contains(&command) {
+        return Ok(());
+    }
+    let Some(payload) = payload else {
+        return Ok(());
+    };
+
+    let mut names: Vec<&str> = Vec::new();
+    if let Some(name) = payload.get("instanceName").and_then(|n| n.as_str()) {
+        names.push(name);
+    }
+    if let Some(list) = payload.get("instanceNames").and_then(|n| n.as_array()) {
+        names.extend(list.iter().filter_map(|n| n.as_str()));
+    }
+    if command == "rename_instance" {
+        names.extend(payload.get("oldName").and_then(|n| n.as_str()));
+    }
+
+    for name in names {
+        ensure_not_frozen(name)?;
+    }
+
+    // Commands that reach instances without naming them
+    match command {
+        "rename_group" => {
+            let group = payload.get("oldName").and_then(|n| n.as_str());
+            ensure_none_frozen(|i| group.is_some() && i.group.as_deref() == group)
+        }
+        "remove_library_pack" => {
+            let kind = payload.get("kind").and_then(|k| serde_json::from_value::<PackKind>(k.clone()).ok());
+            let name = payload.get("name").and_then(|n| n.as_str());
+            ensure_none_frozen(|i| match (kind, name) {
+                (Some(PackKind::ResourcePacks), Some(name)) => i.library_resource_packs.iter().any(|p| p == name),
+                (Some(PackKind::ShaderPacks), Some(name)) => i.library_shader_packs.iter().any(|p| p == name),
+                _ => false,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Err when any instance the command would touch is frozen
+fn ensure_none_frozen(touches: impl Fn(&Instance) -> bool) -> Result<(), String> {
+    match InstanceManager::get_all()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|i| i.frozen && touches(i))
+    {
+        Some(instance) => Err(format!(
+            "Instance '{}' is frozen, unfreeze it before making changes",
+            instance.name
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Err when the instance exists and is frozen
+fn ensure_not_frozen(instance_name: &str) -> Result<(), String> {
+    let Ok(safe_name) = sanitize_instance_name(instance_name) else {
+        // The command rejects the name itself
+        return Ok(());
+    };
+    match InstanceManager::load(&safe_name) {
+        Ok(instance) if instance.frozen => Err(format!(
+            "Instance '{}' is frozen, unfreeze it before making changes",
+            safe_name
+        )),
+        _ => Ok(()),
+    }
+}
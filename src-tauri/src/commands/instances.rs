@@ -1,12 +1,45 @@
 use crate::services::instance::InstanceManager;
 use crate::services::installer::MinecraftInstaller;
 use crate::services::fabric::FabricInstaller;
+use crate::services::attestation::{AttestationCheck, AttestationStage, RunAttestor, SignedAttestation};
+use crate::services::forge::{ForgeFlavor, ForgeInstaller};
+use crate::services::liteloader::LiteLoaderInstaller;
+use crate::services::optifine::{OptiFineInstaller, OptiFineSource};
+use crate::services::java::JavaManager;
+use crate::services::orphans::{OrphanScanner, OrphanedInstance, RepairedInstance};
+use crate::services::journal::{JournalTask, TaskJournal};
 use crate::services::accounts::AccountManager;
+use crate::services::layout::LayoutManager;
+use crate::services::audio::{AudioProfile, AudioProfileManager};
+use crate::services::worlds::{CreatedWorld, WorldConfig, WorldCreator};
+use crate::services::practice_maps::{CatalogMap, InstalledPracticeMap, PracticeMapCatalogs};
+use crate::services::datapacks::{DataPack, DataPackManager};
+use crate::services::pinning::{PinReport, VersionPinner};
+use crate::services::auto_update::ModAutoUpdater;
+use crate::services::language::LanguageManager;
+use crate::services::export::{ExportManager, ExportOptions, ExportSummary};
+use crate::services::archival::{ArchiveManager, ArchivedInstance};
+use crate::services::compare::{InstanceComparer, InstanceComparison};
+use crate::services::disk_usage::{DiskUsageAnalyzer, DiskUsageReport};
+use crate::services::snapshot::{InstanceSnapshot, SnapshotManager};
+use crate::services::version_compat::{ModCompatibility, VersionCompatChecker};
+use crate::services::recordings::{Recording, RecordingManager};
+use crate::services::icons::{BundledIcon, InstanceIcons};
+use crate::services::shortcuts::{ShortcutManager, SteamShortcutReport};
+use crate::services::big_picture::{BigPicture, BigPictureModel, PlayAction};
+use crate::services::launch_queue::{LaunchQueue, LaunchQueueState, LaunchRequestOutcome};
+use crate::services::prewarm::{InstancePrewarmer, PrewarmReport};
+use crate::services::sync_launch::{StartGate, SyncLaunchReport, SyncLaunchStatus, CALLED_OFF};
+use crate::services::share_code::{ShareCodeExport, ShareCodeImport, ShareCodeManager};
 use crate::models::Instance;
 use crate::utils::*;
+use crate::utils::links;
 use std::sync::Mutex;
 use tauri::State;
-use crate::commands::validation::sanitize_instance_name;
+use crate::commands::validation::{
+    sanitize_filename, sanitize_instance_name, validate_accent_color, validate_emoji, validate_game_arguments,
+    validate_group_name, validate_memory_allocation,
+};
 use tauri::Emitter;
 use base64::{Engine as _, engine::general_purpose};
 
@@ -15,7 +48,7 @@ pub async fn create_instance(
     instance_name: String,
     version: String,
     loader: Option<String>,
-    loader_version: Option<String>,
+    mut loader_version: Option<String>,
     app_handle: tauri::AppHandle,
 ) -> Result<String, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -25,7 +58,7 @@ pub async fn create_instance(
     }
     
     if let Some(ref loader_type) = loader {
-        if loader_type != "fabric" && loader_type != "vanilla" {
+        if !["fabric", "liteloader", "forge", "neoforge", "vanilla"].contains(&loader_type.as_str()) {
             return Err("Invalid loader type".to_string());
         }
     }
@@ -83,45 +116,56 @@ pub async fn create_instance(
 
     println!("✓ Minecraft {} is ready", version);
 
-    let final_version = if let Some(loader_type) = &loader {
-        if loader_type == "fabric" {
-            if let Some(fabric_version) = &loader_version {
-                let _ = app_handle.emit("creation-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 70,
-                    "stage": format!("Installing Fabric {}...", fabric_version)
-                }));
-
-                println!("Installing Fabric loader {}...", fabric_version);
-                let fabric_installer = FabricInstaller::new(meta_dir);
-                
-                match fabric_installer
-                    .install_fabric(&version, fabric_version)
-                    .await
-                {
-                    Ok(fabric_id) => {
-                        println!("✓ Fabric {} is ready", fabric_version);
-                        fabric_id
-                    }
-                    Err(e) => {
-                        let err_msg = format!("Failed to install Fabric: {}", e);
-                        println!("ERROR: {}", err_msg);
-                        return Err(err_msg);
-                    }
-                }
-            } else {
+    // Fabric needs its version picked, the others default to the newest build for the version
+    if loader_version.is_none() {
+        match loader.as_deref() {
+            Some("fabric") => {
                 let err_msg = "Fabric loader version not specified".to_string();
                 println!("ERROR: {}", err_msg);
                 return Err(err_msg);
             }
-        } else {
-            println!("Using vanilla version (no mod loader)");
-            version.clone()
+            Some("liteloader") => {
+                loader_version = Some(
+                    LiteLoaderInstaller::new(meta_dir.clone())
+                        .get_compatible_loader_for_minecraft(&version)
+                        .await
+                        .map_err(|e| format!("Failed to resolve LiteLoader version: {}", e))?,
+                );
+            }
+            Some(loader_type) => {
+                if let Some(flavor) = ForgeFlavor::from_loader(loader_type) {
+                    loader_version = Some(
+                        ForgeInstaller::new(meta_dir.clone())
+                            .get_compatible_loader_for_minecraft(flavor, &version)
+                            .await
+                            .map_err(|e| format!("Failed to resolve {} version: {}", loader_type, e))?,
+                    );
+                }
+            }
+            None => {}
         }
-    } else {
-        println!("Using vanilla version (no mod loader)");
-        version.clone()
-    };
+    }
+
+    let final_version = install_loader(
+        meta_dir,
+        &version,
+        loader.as_deref(),
+        loader_version.as_deref(),
+        &app_handle,
+        &safe_name,
+        |stage| {
+            let _ = app_handle.emit("creation-progress", serde_json::json!({
+                "instance": safe_name,
+                "progress": 70,
+                "stage": stage
+            }));
+        },
+    )
+    .await
+    .map_err(|err_msg| {
+        println!("ERROR: {}", err_msg);
+        err_msg
+    })?;
 
     let _ = app_handle.emit("creation-progress", serde_json::json!({
         "instance": safe_name,
@@ -130,13 +174,30 @@ pub async fn create_instance(
     }));
 
     println!("Creating instance with version: {}", final_version);
-    InstanceManager::create(&safe_name, &final_version, loader.clone(), loader_version.clone())
+    let mut instance = InstanceManager::create(&safe_name, &final_version, loader.clone(), loader_version.clone())
         .map_err(|e| {
             let err_msg = format!("Failed to create instance: {}", e);
             println!("ERROR: {}", err_msg);
             err_msg
         })?;
 
+    let _ = app_handle.emit("creation-progress", serde_json::json!({
+        "instance": safe_name,
+        "progress": 95,
+        "stage": "Setting up Java..."
+    }));
+
+    // Not fatal, launching falls back to the Java from settings or PATH
+    match JavaManager::ensure_for_version(&final_version).await {
+        Ok(java_path) => {
+            println!("✓ Using Java {}", java_path);
+            instance.java_path = Some(java_path);
+            InstanceManager::save(&instance)
+                .map_err(|e| format!("Failed to save instance: {}", e))?;
+        }
+        Err(e) => println!("Warning: Could not set up Java for {}: {}", final_version, e),
+    }
+
     let _ = app_handle.emit("creation-progress", serde_json::json!({
         "instance": safe_name,
         "progress": 100,
@@ -148,6 +209,57 @@ pub async fn create_instance(
     Ok(success_msg)
 }
 
+/// Create a throwaway instance, either fresh from `version` or cloned from `source_instance` without
+/// its worlds, and launch it with the active account. It's hidden from the instance list and deleted
+/// when the game exits. Returns the generated instance name.
+#[tauri::command]
+pub async fn create_ephemeral_instance(
+    source_instance: Option<String>,
+    version: Option<String>,
+    loader: Option<String>,
+    loader_version: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let mut name = format!("Test {}", chrono::Local::now().format("%Y-%m-%d %H-%M-%S"));
+    let mut suffix = 2;
+    while get_instance_dir(&name).exists() {
+        name = format!("Test {} ({})", chrono::Local::now().format("%Y-%m-%d %H-%M-%S"), suffix);
+        suffix += 1;
+    }
+
+    match (source_instance, version) {
+        (Some(source), _) => {
+            let safe_source = sanitize_instance_name(&source)?;
+            InstanceManager::clone_instance(&safe_source, &name, false)
+                .map_err(|e| format!("Failed to clone instance: {}", e))?;
+        }
+        (None, Some(version)) => {
+            create_instance(name.clone(), version, loader, loader_version, app_handle.clone()).await?;
+        }
+        (None, None) => return Err("Choose an instance to clone or a Minecraft version".to_string()),
+    }
+
+    let result = async {
+        let mut instance = InstanceManager::load(&name)
+            .map_err(|e| format!("Failed to load instance: {}", e))?;
+        instance.ephemeral = true;
+        instance.pinned = false;
+        InstanceManager::save(&instance)
+            .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+        launch_instance_with_active_account(name.clone(), app_handle.clone()).await
+    }
+    .await;
+
+    if let Err(e) = result {
+        let _ = InstanceManager::delete(&name);
+        return Err(e);
+    }
+
+    println!("✓ Launched test instance '{}'", name);
+    Ok(name)
+}
+
 lazy_static::lazy_static! {
     pub static ref RUNNING_PROCESSES: Mutex<std::collections::HashMap<String, u32>> = Mutex::new(std::collections::HashMap::new());
 }
@@ -192,6 +304,168 @@ pub async fn get_instances() -> Result<Vec<Instance>, String> {
     InstanceManager::get_all().map_err(|e| format!("Failed to get instances: {}", e))
 }
 
+/// Ordered rows of instance tiles for the controller UI, kept fresh by `big-picture-updated` events
+#[tauri::command]
+pub async fn get_big_picture_model() -> Result<BigPictureModel, String> {
+    BigPicture::build().map_err(|e| format!("Failed to build big picture data: {}", e))
+}
+
+/// Run a tile's action: launch with the active account, or stop it when it's already running
+#[tauri::command]
+pub async fn big_picture_activate(instance_name: String, app_handle: tauri::AppHandle) -> Result<PlayAction, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        kill_instance(safe_name).await?;
+        BigPicture::notify(&app_handle);
+        Ok(PlayAction::Stop)
+    } else {
+        launch_instance_with_active_account(safe_name, app_handle).await?;
+        Ok(PlayAction::Play)
+    }
+}
+
+/// Launch several instances through the queue, which holds them back while the configured
+/// number of games or memory budget is used up
+#[tauri::command]
+pub async fn queue_launches(
+    instance_names: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<(String, LaunchRequestOutcome)>, String> {
+    let mut outcomes = Vec::new();
+
+    for instance_name in instance_names {
+        let safe_name = sanitize_instance_name(&instance_name)?;
+        let outcome = LaunchQueue::request(&safe_name, &app_handle)
+            .map_err(|e| format!("Failed to queue '{}': {}", safe_name, e))?;
+        outcomes.push((safe_name, outcome));
+    }
+
+    Ok(outcomes)
+}
+
+/// Warm up a selected instance so launching it is quicker. None when it was warmed recently.
+#[tauri::command]
+pub async fn prewarm_instance(instance_name: String) -> Result<Option<PrewarmReport>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    InstancePrewarmer::prewarm(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to prewarm instance: {}", e))
+}
+
+/// Prepare several instances with the active account and start their games at the same moment,
+/// e.g. for co-op speedruns. Progress is sent as `sync-launch-status`; if one fails, none start.
+#[tauri::command]
+pub async fn launch_instances_synchronized(
+    instance_names: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<SyncLaunchReport>, String> {
+    let mut safe_names: Vec<String> = Vec::new();
+    for instance_name in &instance_names {
+        let safe_name = sanitize_instance_name(instance_name)?;
+        if !safe_names.contains(&safe_name) {
+            safe_names.push(safe_name);
+        }
+    }
+    if safe_names.is_empty() {
+        return Err("No instances to launch".to_string());
+    }
+
+    {
+        let processes = RUNNING_PROCESSES.lock().unwrap();
+        if let Some(running) = safe_names.iter().find(|n| processes.contains_key(*n)) {
+            return Err(format!("Instance '{}' is already running", running));
+        }
+    }
+
+    let active_account = AccountManager::get_active_account()
+        .map_err(|e| format!("Failed to get active account: {}", e))?
+        .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
+
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
+        .await
+        .map_err(|e| format!("Failed to get valid token: {}", e))?;
+
+    let gate = std::sync::Arc::new(StartGate::new(safe_names.len(), app_handle.clone()));
+
+    // Updates and loader installs first, so the gate only waits on building the command lines
+    for safe_name in &safe_names {
+        gate.report(safe_name, SyncLaunchStatus::Preparing);
+        ModAutoUpdater::run_on_launch(safe_name, &app_handle).await;
+        if let Err(e) = ensure_loader_installed(safe_name).await {
+            let reports: Vec<SyncLaunchReport> = safe_names
+                .iter()
+                .map(|name| SyncLaunchReport {
+                    instance_name: name.clone(),
+                    status: if name == safe_name {
+                        SyncLaunchStatus::Failed { error: e.clone() }
+                    } else {
+                        SyncLaunchStatus::Aborted
+                    },
+                })
+                .collect();
+            for report in &reports {
+                gate.report(&report.instance_name, report.status.clone());
+            }
+            return Ok(reports);
+        }
+    }
+
+    let mut handles = Vec::new();
+    for safe_name in safe_names {
+        let gate = gate.clone();
+        let app_handle = app_handle.clone();
+        let username = active_account.username.clone();
+        let uuid = active_account.uuid.clone();
+        let access_token = access_token.clone();
+
+        handles.push(tokio::task::spawn_blocking(move || {
+            let result = InstanceManager::launch_synchronized(&safe_name, &username, &uuid, &access_token, &gate, app_handle)
+                .map_err(|e| e.to_string());
+
+            let status = match result {
+                Ok(()) => SyncLaunchStatus::Started,
+                Err(e) => {
+                    gate.abandon(&safe_name);
+                    if e == CALLED_OFF {
+                        SyncLaunchStatus::Aborted
+                    } else {
+                        SyncLaunchStatus::Failed { error: e }
+                    }
+                }
+            };
+            gate.report(&safe_name, status.clone());
+            SyncLaunchReport { instance_name: safe_name, status }
+        }));
+    }
+
+    let mut reports = Vec::new();
+    for handle in handles {
+        reports.push(handle.await.map_err(|e| format!("Launch task failed: {}", e))?);
+    }
+
+    let started = reports.iter().filter(|r| r.status == SyncLaunchStatus::Started).count();
+    println!("✓ Synchronized launch started {} of {} instances", started, reports.len());
+    Ok(reports)
+}
+
+#[tauri::command]
+pub async fn get_launch_queue() -> Result<LaunchQueueState, String> {
+    Ok(LaunchQueue::state())
+}
+
+#[tauri::command]
+pub async fn cancel_queued_launch(instance_name: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if !LaunchQueue::cancel(&safe_name, &app_handle) {
+        return Err(format!("'{}' is not queued", safe_name));
+    }
+
+    Ok(format!("Removed '{}' from the launch queue", safe_name))
+}
+
 #[tauri::command]
 pub async fn delete_instance(instance_name: String) -> Result<String, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -211,41 +485,86 @@ pub async fn rename_instance(old_name: String, new_name: String) -> Result<Strin
         return Ok("Instance name unchanged".to_string());
     }
     
-    let instances_dir = get_instances_dir();
-    let old_path = instances_dir.join(&safe_old_name);
-    let new_path = instances_dir.join(&safe_new_name);
-    
-    if !old_path.exists() {
-        return Err(format!("Instance '{}' does not exist", safe_old_name));
-    }
-    
-    if new_path.exists() {
-        return Err(format!("Instance '{}' already exists", safe_new_name));
-    }
-    
-    std::fs::rename(&old_path, &new_path)
-        .map_err(|e| format!("Failed to rename instance directory: {}", e))?;
-    
-    let instance_json_path = new_path.join("instance.json");
-    if instance_json_path.exists() {
-        let content = std::fs::read_to_string(&instance_json_path)
-            .map_err(|e| format!("Failed to read instance.json: {}", e))?;
-        
-        let mut instance: Instance = serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse instance.json: {}", e))?;
-        
-        instance.name = safe_new_name.clone();
-        
-        let updated_json = serde_json::to_string_pretty(&instance)
-            .map_err(|e| format!("Failed to serialize instance.json: {}", e))?;
-        
-        std::fs::write(&instance_json_path, updated_json)
-            .map_err(|e| format!("Failed to write instance.json: {}", e))?;
-    }
+    // Also rebases the instance's own paths and the recent launches log
+    InstanceManager::rename(&safe_old_name, &safe_new_name)
+        .map_err(|e| format!("Failed to rename instance: {}", e))?;
     
+    println!("✓ Renamed instance '{}' to '{}'", safe_old_name, safe_new_name);
     Ok(format!("Successfully renamed instance to '{}'", safe_new_name))
 }
 
+/// Reinstall the instance's loader profile if the meta folder lost it
+async fn ensure_loader_installed(safe_name: &str) -> Result<(), String> {
+    let instance = InstanceManager::load(safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    FabricInstaller::new(get_meta_dir())
+        .ensure_installed(&instance)
+        .await
+        .map_err(|e| format!("Failed to install Fabric: {}", e))?;
+    ForgeInstaller::new(get_meta_dir())
+        .ensure_installed(&instance)
+        .await
+        .map_err(|e| format!("Failed to install {}: {}", instance.loader.as_deref().unwrap_or("Forge"), e))
+}
+
+/// Install a loader on top of an installed Minecraft version and return the version id to launch.
+/// No loader, or "vanilla", is Minecraft itself. `emit` gets the stage to show.
+pub(crate) async fn install_loader(
+    meta_dir: std::path::PathBuf,
+    minecraft_version: &str,
+    loader: Option<&str>,
+    loader_version: Option<&str>,
+    app_handle: &tauri::AppHandle,
+    instance_name: &str,
+    emit: impl Fn(String),
+) -> Result<String, String> {
+    let loader = match loader {
+        None | Some("vanilla") => {
+            println!("Using vanilla version (no mod loader)");
+            return Ok(minecraft_version.to_string());
+        }
+        Some("optifine") => {
+            return Err("OptiFine can't be downloaded automatically, add its jar to the instance instead".to_string());
+        }
+        Some(loader) => loader,
+    };
+    let loader_version = loader_version.ok_or_else(|| format!("Missing version for loader '{}'", loader))?;
+
+    let version_id = match loader {
+        "fabric" => {
+            emit(format!("Installing Fabric {}...", loader_version));
+            println!("Installing Fabric loader {}...", loader_version);
+            FabricInstaller::new(meta_dir)
+                .install_fabric(minecraft_version, loader_version)
+                .await
+                .map_err(|e| format!("Failed to install Fabric: {}", e))?
+        }
+        "liteloader" => {
+            emit(format!("Installing LiteLoader {}...", loader_version));
+            println!("Installing LiteLoader {}...", loader_version);
+            LiteLoaderInstaller::new(meta_dir)
+                .install_liteloader(minecraft_version, loader_version)
+                .await
+                .map_err(|e| format!("Failed to install LiteLoader: {}", e))?
+        }
+        _ => {
+            let flavor = ForgeFlavor::from_loader(loader)
+                .ok_or_else(|| format!("Loader '{}' is not supported", loader))?;
+            emit(format!("Installing {} {}...", loader, loader_version));
+            println!("Installing {} {}...", loader, loader_version);
+            ForgeInstaller::new(meta_dir)
+                .with_progress(app_handle.clone(), Some(instance_name.to_string()))
+                .install_forge(flavor, minecraft_version, loader_version)
+                .await
+                .map_err(|e| format!("Failed to install {}: {}", loader, e))?
+        }
+    };
+
+    println!("✓ {} {} is ready", loader, loader_version);
+    Ok(version_id)
+}
+
 #[tauri::command]
 pub async fn launch_instance_with_active_account(
     instance_name: String,
@@ -257,10 +576,13 @@ pub async fn launch_instance_with_active_account(
         .map_err(|e| format!("Failed to get active account: {}", e))?
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
 
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
 
+    ModAutoUpdater::run_on_launch(&safe_name, &app_handle).await;
+    ensure_loader_installed(&safe_name).await?;
+
     crate::services::instance::InstanceManager::launch(
         &safe_name,
         &active_account.username,
@@ -290,6 +612,17 @@ pub async fn launch_instance(
     if !uuid.chars().all(|c| c.is_alphanumeric() || c == '-') || uuid.len() > 36 {
         return Err("Invalid UUID format".to_string());
     }
+
+    // The token the frontend holds may have expired since it was fetched
+    let access_token = match AccountManager::account_exists(&uuid) {
+        Ok(true) => AccountManager::ensure_fresh_token(&uuid)
+            .await
+            .map_err(|e| format!("Failed to get valid token: {}", e))?,
+        _ => access_token,
+    };
+
+    ModAutoUpdater::run_on_launch(&safe_name, &app_handle).await;
+    ensure_loader_installed(&safe_name).await?;
     
     InstanceManager::launch(&safe_name, &username, &uuid, &access_token, app_handle)
         .map_err(|e| format!("Failed to launch instance: {}", e))?;
@@ -338,7 +671,7 @@ pub async fn set_instance_icon(
         .map_err(|e| format!("Failed to save icon: {}", e))?;
     
     let instance_json = instance_dir.join("instance.json");
-    let content = std::fs::read_to_string(&instance_json)
+    let content = std::fs::read_to_string(long_path(&instance_json))
         .map_err(|e| format!("Failed to read instance data: {}", e))?;
     
     let mut instance: Instance = serde_json::from_str(&content)
@@ -349,7 +682,7 @@ pub async fn set_instance_icon(
     let updated_json = serde_json::to_string_pretty(&instance)
         .map_err(|e| format!("Failed to serialize instance data: {}", e))?;
     
-    std::fs::write(&instance_json, updated_json)
+    std::fs::write(long_path(&instance_json), updated_json)
         .map_err(|e| format!("Failed to write instance data: {}", e))?;
     
     Ok("Icon set successfully".to_string())
@@ -367,12 +700,12 @@ pub async fn remove_instance_icon(instance_name: String) -> Result<String, Strin
     
     let icon_path = instance_dir.join("icon.png");
     if icon_path.exists() {
-        std::fs::remove_file(&icon_path)
+        std::fs::remove_file(long_path(&icon_path))
             .map_err(|e| format!("Failed to remove icon file: {}", e))?;
     }
     
     let instance_json = instance_dir.join("instance.json");
-    let content = std::fs::read_to_string(&instance_json)
+    let content = std::fs::read_to_string(long_path(&instance_json))
         .map_err(|e| format!("Failed to read instance data: {}", e))?;
     
     let mut instance: Instance = serde_json::from_str(&content)
@@ -383,12 +716,120 @@ pub async fn remove_instance_icon(instance_name: String) -> Result<String, Strin
     let updated_json = serde_json::to_string_pretty(&instance)
         .map_err(|e| format!("Failed to serialize instance data: {}", e))?;
     
-    std::fs::write(&instance_json, updated_json)
+    std::fs::write(long_path(&instance_json), updated_json)
         .map_err(|e| format!("Failed to write instance data: {}", e))?;
     
     Ok("Icon removed successfully".to_string())
 }
 
+/// Accent color and emoji shown on the instance card, `None` clears them
+#[tauri::command]
+pub async fn set_instance_appearance(
+    instance_name: String,
+    accent_color: Option<String>,
+    emoji: Option<String>,
+) -> Result<Instance, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let accent_color = accent_color.as_deref().map(validate_accent_color).transpose()?;
+    let emoji = emoji.as_deref().map(validate_emoji).transpose()?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.accent_color = accent_color;
+    instance.emoji = emoji;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok(instance)
+}
+
+/// Banners are cropped to fill 1200x400 so every card gets the same shape
+#[tauri::command]
+pub async fn set_instance_banner(instance_name: String, image_data: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let image_bytes = general_purpose::STANDARD
+        .decode(&image_data)
+        .map_err(|e| format!("Invalid base64 image data: {}", e))?;
+
+    if image_bytes.len() > 8 * 1024 * 1024 {
+        return Err("Image too large (max 8MB)".to_string());
+    }
+
+    let format = image::guess_format(&image_bytes)
+        .map_err(|e| format!("Invalid image format: {}", e))?;
+
+    match format {
+        image::ImageFormat::Png |
+        image::ImageFormat::Jpeg |
+        image::ImageFormat::WebP => {},
+        _ => return Err("Unsupported image format. Use PNG, JPEG, or WebP".to_string()),
+    }
+
+    let img = image::load_from_memory(&image_bytes)
+        .map_err(|e| format!("Failed to load image: {}", e))?;
+
+    let banner = img.resize_to_fill(1200, 400, image::imageops::FilterType::Lanczos3);
+
+    banner.save(instance_dir.join("banner.png"))
+        .map_err(|e| format!("Failed to save banner: {}", e))?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.banner_path = Some("banner.png".to_string());
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok("Banner set successfully".to_string())
+}
+
+#[tauri::command]
+pub async fn remove_instance_banner(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    let banner_path = get_instance_dir(&safe_name).join("banner.png");
+    if banner_path.exists() {
+        std::fs::remove_file(long_path(&banner_path))
+            .map_err(|e| format!("Failed to remove banner file: {}", e))?;
+    }
+
+    instance.banner_path = None;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok("Banner removed successfully".to_string())
+}
+
+#[tauri::command]
+pub async fn get_instance_banner(instance_name: String) -> Result<Option<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let banner_path = get_instance_dir(&safe_name).join("banner.png");
+
+    if !banner_path.is_file() {
+        return Ok(None);
+    }
+
+    let image_bytes = std::fs::read(long_path(&banner_path))
+        .map_err(|e| format!("Failed to read banner: {}", e))?;
+
+    Ok(Some(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(&image_bytes))))
+}
+
 #[tauri::command]
 pub async fn get_instance_icon(instance_name: String) -> Result<Option<String>, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -415,7 +856,7 @@ pub async fn get_instance_icon(instance_name: String) -> Result<Option<String>,
         return Err("Invalid icon path".to_string());
     }
     
-    let image_bytes = std::fs::read(&icon_path)
+    let image_bytes = std::fs::read(long_path(&icon_path))
         .map_err(|e| format!("Failed to read icon: {}", e))?;
     
     let base64_data = general_purpose::STANDARD.encode(&image_bytes);
@@ -423,6 +864,22 @@ pub async fn get_instance_icon(instance_name: String) -> Result<Option<String>,
     Ok(Some(format!("data:image/png;base64,{}", base64_data)))
 }
 
+/// Icons shipped with the launcher for the icon picker
+#[tauri::command]
+pub async fn get_bundled_icons() -> Result<Vec<BundledIcon>, String> {
+    Ok(InstanceIcons::list())
+}
+
+#[tauri::command]
+pub async fn set_instance_icon_preset(instance_name: String, icon_id: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    InstanceIcons::apply(&safe_name, &icon_id)
+        .map_err(|e| format!("Failed to set icon: {}", e))?;
+
+    Ok("Icon set successfully".to_string())
+}
+
 #[tauri::command]
 pub async fn duplicate_instance(
     instance_name: String,
@@ -480,7 +937,7 @@ pub async fn duplicate_instance(
     
     let instance_json_path = dest_path.join("instance.json");
     if instance_json_path.exists() {
-        let content = std::fs::read_to_string(&instance_json_path)
+        let content = std::fs::read_to_string(long_path(&instance_json_path))
             .map_err(|e| format!("Failed to read instance.json: {}", e))?;
         
         let mut instance: Instance = serde_json::from_str(&content)
@@ -493,7 +950,7 @@ pub async fn duplicate_instance(
         let updated_json = serde_json::to_string_pretty(&instance)
             .map_err(|e| format!("Failed to serialize instance.json: {}", e))?;
         
-        std::fs::write(&instance_json_path, updated_json)
+        std::fs::write(long_path(&instance_json_path), updated_json)
             .map_err(|e| format!("Failed to write instance.json: {}", e))?;
     }
     
@@ -507,6 +964,28 @@ pub async fn duplicate_instance(
     Ok(format!("Successfully duplicated instance to '{}'", safe_new_name))
 }
 
+#[tauri::command]
+pub async fn clone_instance(
+    instance_name: String,
+    new_name: String,
+    include_saves: Option<bool>,
+) -> Result<String, String> {
+    let safe_old_name = sanitize_instance_name(&instance_name)?;
+    let safe_new_name = sanitize_instance_name(&new_name)?;
+
+    if safe_old_name == safe_new_name {
+        return Err("Source and destination names cannot be the same".to_string());
+    }
+
+    println!("Cloning instance '{}' to '{}'", safe_old_name, safe_new_name);
+
+    InstanceManager::clone_instance(&safe_old_name, &safe_new_name, include_saves.unwrap_or(false))
+        .map_err(|e| format!("Failed to clone instance: {}", e))?;
+
+    println!("✓ Successfully cloned instance");
+    Ok(format!("Successfully cloned instance to '{}'", safe_new_name))
+}
+
 fn count_files(path: &std::path::Path) -> std::io::Result<usize> {
     use std::fs;
     
@@ -516,7 +995,7 @@ fn count_files(path: &std::path::Path) -> std::io::Result<usize> {
         return Ok(1);
     }
     
-    for entry in fs::read_dir(path)? {
+    for entry in fs::read_dir(long_path(path))? {
         let entry = entry?;
         let entry_path = entry.path();
         
@@ -546,10 +1025,10 @@ fn copy_dir_recursive_with_progress(
     use std::sync::atomic::Ordering;
     
     if !dst.exists() {
-        fs::create_dir_all(dst)?;
+        fs::create_dir_all(long_path(dst))?;
     }
     
-    for entry in fs::read_dir(src)? {
+    for entry in fs::read_dir(long_path(src))? {
         let entry = entry?;
         let file_type = entry.file_type()?;
         let src_path = entry.path();
@@ -568,7 +1047,7 @@ fn copy_dir_recursive_with_progress(
                 app_handle,
             )?;
         } else if file_type.is_file() {
-            fs::copy(&src_path, &dst_path)?;
+            fs::copy(long_path(&src_path), long_path(&dst_path))?;
             
             let current = copied_files.fetch_add(1, Ordering::Relaxed) + 1;
             let progress = ((current as f64 / total_files as f64) * 85.0) as u32;
@@ -611,16 +1090,850 @@ pub fn open_instance_folder(instance_name: String) -> Result<String, String> {
     Ok(format!("Opened folder for instance '{}'", safe_name))
 }
 
-// SYSTEM UTILITIES
+/// Folders of an instance that can be revealed, relative to the instance folder
+const REVEALABLE_ROOTS: &[(&str, &str)] = &[
+    ("instance", ""),
+    ("world", "saves"),
+    ("screenshot", "screenshots"),
+    ("log", "logs"),
+    ("crash_report", "crash-reports"),
+    ("config", "config"),
+    ("mod", "mods"),
+    ("resourcepack", "resourcepacks"),
+    ("shaderpack", "shaderpacks"),
+];
+
+/// Config files are opened in an editor, anything else (jars, scripts) is refused
+const EDITABLE_EXTENSIONS: &[&str] = &["json", "json5", "toml", "properties", "cfg", "conf", "txt", "yml", "yaml", "snbt", "md", "log"];
+
+/// Resolve `relative` inside one of the instance's revealable folders. Shared folders are links,
+/// so their targets are allowed too.
+fn resolve_managed_path(instance_name: &str, kind: &str, relative: Option<&str>) -> Result<std::path::PathBuf, String> {
+    let root = REVEALABLE_ROOTS
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, dir)| *dir)
+        .ok_or_else(|| format!("Unknown path kind '{}'", kind))?;
+
+    let instance_dir = get_instance_dir(instance_name);
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", instance_name));
+    }
 
-use sysinfo::System;
+    let root_dir = if root.is_empty() { instance_dir.clone() } else { instance_dir.join(root) };
+    let path = match relative.filter(|r| !r.is_empty()) {
+        Some(relative) => {
+            if relative.split(['/', '\\']).any(|part| part == "..") || std::path::Path::new(relative).is_absolute() {
+                return Err("Invalid path".to_string());
+            }
+            root_dir.join(relative)
+        }
+        None => root_dir.clone(),
+    };
 
-#[derive(serde::Serialize, serde::Deserialize)]
-pub struct SystemInfo {
-    pub total_memory_mb: u64,
-    pub available_memory_mb: u64,
-    pub recommended_max_memory_mb: u64,
-}
+    let canonical = path.canonicalize()
+        .map_err(|_| "Path not found".to_string())?;
+
+    let allowed = [instance_dir, get_shared_dir()]
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .any(|dir| canonical.starts_with(dir));
+    if !allowed {
+        return Err("Path is outside the instance".to_string());
+    }
+
+    // The canonical form is \\?\ prefixed on Windows, which Explorer doesn't accept
+    Ok(path)
+}
+
+/// Show a managed path (instance folder, world, screenshot, log...) selected in the file manager
+#[tauri::command]
+pub fn reveal_instance_path(instance_name: String, kind: String, relative_path: Option<String>) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let path = resolve_managed_path(&safe_name, &kind, relative_path.as_deref())?;
+
+    reveal_in_file_manager(&path).map_err(|e| format!("Failed to open file manager: {}", e))?;
+
+    Ok(format!("Revealed {}", path.display()))
+}
+
+/// Open a config or text file of an instance with the default editor
+#[tauri::command]
+pub fn open_instance_file(instance_name: String, kind: String, relative_path: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let path = resolve_managed_path(&safe_name, &kind, Some(&relative_path))?;
+
+    let editable = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| EDITABLE_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+    if !path.is_file() || !editable {
+        return Err("Only config and text files can be opened".to_string());
+    }
+
+    open_with_default_app(&path).map_err(|e| format!("Failed to open file: {}", e))?;
+
+    Ok(format!("Opened {}", path.display()))
+}
+
+/// Desktop shortcut that starts the instance through the launcher's `--launch` flag
+#[tauri::command]
+pub async fn create_desktop_shortcut(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let path = ShortcutManager::create_desktop_shortcut(&safe_name)
+        .map_err(|e| format!("Failed to create shortcut: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Add the instance to Steam as a non-Steam game with generated library artwork. Steam must be closed.
+#[tauri::command]
+pub async fn add_instance_to_steam(instance_name: String) -> Result<SteamShortcutReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    tokio::task::spawn_blocking(move || {
+        ShortcutManager::add_to_steam(&safe_name).map_err(|e| format!("Failed to add to Steam: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to add to Steam: {}", e))?
+}
+
+#[tauri::command]
+pub async fn get_instance_directory_layout(instance_name: String) -> Result<Vec<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    Ok(instance.shared_directories)
+}
+
+#[tauri::command]
+pub async fn set_instance_directory_layout(
+    instance_name: String,
+    shared_directories: Vec<String>,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    LayoutManager::validate(&shared_directories)?;
+
+    if RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err("Cannot change directory layout while the instance is running".to_string());
+    }
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    if instance.linked_game_dir.is_some() {
+        return Err("The folders of an adopted game directory cannot be shared".to_string());
+    }
+
+    instance.shared_directories = shared_directories;
+    instance.shared_directories.sort();
+    instance.shared_directories.dedup();
+
+    LayoutManager::apply(&instance)
+        .map_err(|e| format!("Failed to apply directory layout: {}", e))?;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok(format!("Updated directory layout for instance '{}'", safe_name))
+}
+
+/// One-click toggle between a fully isolated game directory and the default shared saves/resourcepacks
+#[tauri::command]
+pub async fn set_instance_isolation(instance_name: String, isolated: bool) -> Result<String, String> {
+    let shared_directories = if isolated {
+        Vec::new()
+    } else {
+        crate::services::layout::DEFAULT_SHARED_DIRECTORIES
+            .iter()
+            .map(|d| d.to_string())
+            .collect()
+    };
+
+    set_instance_directory_layout(instance_name, shared_directories).await
+}
+
+#[tauri::command]
+pub async fn repair_instance_links(instance_name: String) -> Result<Vec<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    let broken = links::find_broken_links(&instance_dir)
+        .map_err(|e| format!("Failed to scan instance directory: {}", e))?;
+
+    let mut repaired = Vec::new();
+    for path in broken {
+        links::remove_link(&path)
+            .map_err(|e| format!("Failed to remove broken link {}: {}", path.display(), e))?;
+        repaired.push(path.to_string_lossy().to_string());
+    }
+
+    // Recreates the links for shared directories that were just removed
+    LayoutManager::apply(&instance)
+        .map_err(|e| format!("Failed to restore directory layout: {}", e))?;
+
+    println!("Repaired {} broken links in instance '{}'", repaired.len(), safe_name);
+    Ok(repaired)
+}
+
+/// Set the in-game language for an instance, or None to leave options.txt alone
+#[tauri::command]
+pub async fn set_instance_language(
+    instance_name: String,
+    language: Option<String>,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if let Some(ref lang) = language {
+        LanguageManager::validate(lang)?;
+    }
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.language = language;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    LanguageManager::apply(&instance)
+        .map_err(|e| format!("Failed to update options.txt: {}", e))?;
+
+    LanguageManager::ensure_language_asset(&instance)
+        .await
+        .map_err(|e| format!("Failed to download language files: {}", e))?;
+
+    Ok(format!("Language updated for '{}'", safe_name))
+}
+
+#[tauri::command]
+pub async fn get_audio_profiles() -> Result<Vec<AudioProfile>, String> {
+    AudioProfileManager::get_all().map_err(|e| format!("Failed to load audio profiles: {}", e))
+}
+
+#[tauri::command]
+pub async fn save_audio_profile(profile: AudioProfile) -> Result<AudioProfile, String> {
+    AudioProfileManager::save(profile).map_err(|e| format!("Failed to save audio profile: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_audio_profile(profile_id: String) -> Result<(), String> {
+    AudioProfileManager::delete(&profile_id).map_err(|e| format!("Failed to delete audio profile: {}", e))
+}
+
+/// Switch the instance's audio profile, or None to leave its sound settings alone from now on.
+/// Takes effect the next time the game starts.
+#[tauri::command]
+pub async fn set_instance_audio_profile(
+    instance_name: String,
+    profile_id: Option<String>,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if let Some(ref id) = profile_id {
+        AudioProfileManager::get(id).map_err(|e| e.to_string())?;
+    }
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.audio_profile = profile_id;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    AudioProfileManager::apply(&instance)
+        .map_err(|e| format!("Failed to update options.txt: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_instance_game_arguments(
+    instance_name: String,
+    arguments: Vec<String>,
+    demo_mode: bool,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    validate_game_arguments(&arguments)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    // Demo mode has its own toggle
+    instance.extra_game_args = arguments.into_iter().filter(|a| a != "--demo").collect();
+    instance.demo_mode = demo_mode;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok(format!("Game arguments updated for '{}'", safe_name))
+}
+
+/// Move an instance into a group, or out of any group with `None`
+#[tauri::command]
+pub async fn set_instance_group(instance_name: String, group: Option<String>) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let group = group.as_deref().map(validate_group_name).transpose()?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.group = group;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok(format!("Group updated for '{}'", safe_name))
+}
+
+#[tauri::command]
+pub async fn pin_instance(instance_name: String) -> Result<String, String> {
+    set_instance_pinned(&instance_name, true)
+}
+
+#[tauri::command]
+pub async fn unpin_instance(instance_name: String) -> Result<String, String> {
+    set_instance_pinned(&instance_name, false)
+}
+
+fn set_instance_pinned(instance_name: &str, pinned: bool) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.pinned = pinned;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    let status = if pinned { "pinned" } else { "unpinned" };
+    Ok(format!("Instance '{}' {}", safe_name, status))
+}
+
+/// Persist a manual drag order. Instances not in the list keep their index.
+#[tauri::command]
+pub async fn reorder_instances(instance_names: Vec<String>) -> Result<String, String> {
+    for (index, name) in instance_names.iter().enumerate() {
+        let safe_name = sanitize_instance_name(name)?;
+
+        let mut instance = InstanceManager::load(&safe_name)
+            .map_err(|e| format!("Failed to load instance '{}': {}", safe_name, e))?;
+
+        if instance.sort_index != index as u32 {
+            instance.sort_index = index as u32;
+            InstanceManager::save(&instance)
+                .map_err(|e| format!("Failed to save instance '{}': {}", safe_name, e))?;
+        }
+    }
+
+    Ok(format!("Reordered {} instances", instance_names.len()))
+}
+
+/// Rename a group across all instances. Renaming onto an existing group merges them.
+#[tauri::command]
+pub async fn rename_group(old_name: String, new_name: String) -> Result<usize, String> {
+    let new_name = validate_group_name(&new_name)?;
+
+    let instances = InstanceManager::get_all()
+        .map_err(|e| format!("Failed to get instances: {}", e))?;
+
+    let mut updated = 0;
+    for mut instance in instances {
+        if instance.group.as_deref() != Some(old_name.as_str()) {
+            continue;
+        }
+
+        instance.group = Some(new_name.clone());
+        InstanceManager::save(&instance)
+            .map_err(|e| format!("Failed to save instance '{}': {}", instance.name, e))?;
+        updated += 1;
+    }
+
+    if updated == 0 {
+        return Err(format!("Group '{}' does not exist", old_name));
+    }
+
+    println!("✓ Renamed group '{}' to '{}' ({} instances)", old_name, new_name, updated);
+    Ok(updated)
+}
+
+/// All groups currently in use, sorted by name
+#[tauri::command]
+pub async fn get_groups() -> Result<Vec<String>, String> {
+    let instances = InstanceManager::get_all()
+        .map_err(|e| format!("Failed to get instances: {}", e))?;
+
+    let mut groups: Vec<String> = instances.into_iter().filter_map(|i| i.group).collect();
+    groups.sort_by_key(|g| g.to_lowercase());
+    groups.dedup();
+
+    Ok(groups)
+}
+
+/// Move an instance into cold storage under launcher_dir/archives
+#[tauri::command]
+pub async fn archive_instance(instance_name: String) -> Result<ArchivedInstance, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    ArchiveManager::archive(&safe_name)
+        .map_err(|e| format!("Failed to archive instance: {}", e))
+}
+
+#[tauri::command]
+pub async fn restore_instance(instance_name: String) -> Result<Instance, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    ArchiveManager::restore(&safe_name)
+        .map_err(|e| format!("Failed to restore instance: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_archived_instances() -> Result<Vec<ArchivedInstance>, String> {
+    ArchiveManager::list()
+        .map_err(|e| format!("Failed to list archived instances: {}", e))
+}
+
+/// Copy mods, config and instance.json aside so they can be restored after a bad update
+#[tauri::command]
+pub async fn create_instance_snapshot(instance_name: String, label: Option<String>) -> Result<InstanceSnapshot, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    SnapshotManager::create(&safe_name, label)
+        .map_err(|e| format!("Failed to create snapshot: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_instance_snapshots(instance_name: String) -> Result<Vec<InstanceSnapshot>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    SnapshotManager::list(&safe_name)
+        .map_err(|e| format!("Failed to list snapshots: {}", e))
+}
+
+#[tauri::command]
+pub async fn rollback_instance_snapshot(instance_name: String, snapshot_id: String) -> Result<Instance, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let safe_id = sanitize_filename(&snapshot_id)?;
+
+    SnapshotManager::rollback(&safe_name, &safe_id)
+        .map_err(|e| format!("Failed to roll back snapshot: {}", e))
+}
+
+/// Structured diff of two instances: metadata, settings, mods, config files and options.txt
+#[tauri::command]
+pub async fn compare_instances(a: String, b: String) -> Result<InstanceComparison, String> {
+    let safe_a = sanitize_instance_name(&a)?;
+    let safe_b = sanitize_instance_name(&b)?;
+
+    InstanceComparer::compare(&safe_a, &safe_b)
+        .map_err(|e| format!("Failed to compare instances: {}", e))
+}
+
+/// Size breakdown by category. Served from cache unless `refresh` is set or the cache is stale.
+#[tauri::command]
+pub async fn get_instance_disk_usage(instance_name: String, refresh: Option<bool>) -> Result<DiskUsageReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if !refresh.unwrap_or(false) {
+        if let Some(report) = DiskUsageAnalyzer::cached(&safe_name) {
+            return Ok(report);
+        }
+    }
+
+    // Large saves folders take a while to walk, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        DiskUsageAnalyzer::compute(&safe_name).map_err(|e| format!("Failed to compute disk usage: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Disk usage task failed: {}", e))?
+}
+
+/// Package an instance into a portable zip. Saves and logs are left out unless requested.
+#[tauri::command]
+pub async fn export_instance(
+    instance_name: String,
+    destination_path: String,
+    include_saves: Option<bool>,
+    include_logs: Option<bool>,
+) -> Result<ExportSummary, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if !destination_path.to_lowercase().ends_with(".zip") {
+        return Err("Export destination must be a .zip file".to_string());
+    }
+
+    let options = ExportOptions {
+        include_saves: include_saves.unwrap_or(false),
+        include_logs: include_logs.unwrap_or(false),
+    };
+
+    ExportManager::export(&safe_name, std::path::Path::new(&destination_path), &options)
+        .map_err(|e| format!("Failed to export instance: {}", e))
+}
+
+/// Import a CurseForge modpack zip (manifest.json format) into a new instance
+#[tauri::command]
+pub async fn import_curseforge_pack(
+    file_path: String,
+    instance_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    use crate::commands::validation::{sanitize_pack_filename, validate_curseforge_url};
+    use crate::models::CurseForgeManifest;
+    use crate::utils::archive::{extract_prefixed, read_entry};
+    use crate::services::curseforge::{CurseForgeInstaller, ManualDownload};
+    use crate::utils::curseforge::{folder_for_class, CurseForgeMod};
+    use sha1::{Digest, Sha1};
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    let pack_path = Path::new(&file_path);
+    if !pack_path.exists() {
+        return Err("Modpack file does not exist".to_string());
+    }
+
+    let manifest_bytes = read_entry(pack_path, "manifest.json")
+        .ok_or_else(|| "Invalid modpack: manifest.json not found".to_string())?;
+    let manifest: CurseForgeManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| format!("Failed to parse manifest: {}", e))?;
+
+    if manifest.manifest_type != "minecraftModpack" {
+        return Err(format!("Unsupported manifest type '{}'", manifest.manifest_type));
+    }
+
+    let safe_name = sanitize_instance_name(instance_name.as_deref().unwrap_or(&manifest.name))?;
+    if get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' already exists", safe_name));
+    }
+
+    let game_version = manifest.minecraft.version.clone();
+    if !game_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid game version in manifest".to_string());
+    }
+
+    // Loader ids look like "fabric-0.15.7" or "forge-47.2.0"
+    let loader = manifest.minecraft.mod_loaders.iter()
+        .find(|l| l.primary)
+        .or_else(|| manifest.minecraft.mod_loaders.first())
+        .map(|l| l.id.split_once('-').map(|(n, v)| (n.to_string(), v.to_string())).unwrap_or((l.id.clone(), String::new())));
+
+    let loader = match loader {
+        Some((name, version)) if ["fabric", "liteloader", "forge", "neoforge"].contains(&name.as_str()) => Some((name, version)),
+        Some((name, _)) => return Err(format!("Modpacks using '{}' are not supported yet", name)),
+        None => None,
+    };
+
+    let client = CurseForgeInstaller::client()?;
+    let journal = TaskJournal::begin(&safe_name, JournalTask::CurseforgePackImport {
+        file_path: file_path.clone(),
+    });
+
+    let emit = |progress: u32, stage: String| {
+        let _ = app_handle.emit("creation-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": progress,
+            "stage": stage
+        }));
+    };
+
+    println!("Importing CurseForge pack '{}' {} as '{}'", manifest.name, manifest.version, safe_name);
+    emit(5, "Resolving modpack files...".to_string());
+
+    // Optional files are disabled by default in the CurseForge app as well
+    let wanted: Vec<_> = manifest.files.iter().filter(|f| f.required).collect();
+    let file_ids: Vec<u32> = wanted.iter().map(|f| f.file_id).collect();
+
+    let files = client.get_files(&file_ids).await
+        .map_err(|e| format!("Failed to resolve CurseForge files: {}", e))?;
+
+    let mod_ids: Vec<u32> = files.iter().map(|f| f.mod_id).collect();
+    let projects: HashMap<u32, CurseForgeMod> = client.get_mods(&mod_ids).await
+        .map_err(|e| format!("Failed to resolve CurseForge projects: {}", e))?
+        .into_iter()
+        .map(|m| (m.id, m))
+        .collect();
+
+    // Without these the pack is missing mods and won't run as its author intended
+    let missing: Vec<String> = wanted
+        .iter()
+        .filter(|w| !files.iter().any(|f| f.id == w.file_id))
+        .map(|w| format!("{}/{}", w.project_id, w.file_id))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "CurseForge couldn't find {} of the pack's {} files (projectID/fileID): {}",
+            missing.len(),
+            wanted.len(),
+            missing.join(", ")
+        ));
+    }
+
+    emit(10, format!("Installing Minecraft {}...", game_version));
+    journal.step("Installing Minecraft");
+
+    let meta_dir = get_meta_dir();
+    MinecraftInstaller::new(meta_dir.clone())
+        .install_version(&game_version)
+        .await
+        .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
+
+    let final_version = install_loader(
+        meta_dir,
+        &game_version,
+        loader.as_ref().map(|(name, _)| name.as_str()),
+        loader.as_ref().map(|(_, version)| version.as_str()),
+        &app_handle,
+        &safe_name,
+        |stage| emit(25, stage),
+    )
+    .await?;
+
+    emit(30, "Creating instance...".to_string());
+    journal.step("Creating instance");
+
+    InstanceManager::create(
+        &safe_name,
+        &final_version,
+        loader.as_ref().map(|(name, _)| name.clone()),
+        loader.as_ref().map(|(_, version)| version.clone()),
+    )
+    .map_err(|e| format!("Failed to create instance: {}", e))?;
+    journal.instance_created();
+
+    let instance_dir = get_instance_dir(&safe_name);
+    let mut manual_downloads: Vec<ManualDownload> = Vec::new();
+
+    let result = async {
+        let total = files.len().max(1);
+        journal.step("Downloading files");
+        journal.downloads(files.len(), files.len());
+
+        for (idx, file) in files.iter().enumerate() {
+            let folder = folder_for_class(projects.get(&file.mod_id).and_then(|m| m.class_id));
+
+            match &file.download_url {
+                Some(url) => {
+                    validate_curseforge_url(url)?;
+                    let bytes = client.download_file(url).await
+                        .map_err(|e| format!("Failed to download {}: {}", file.file_name, e))?;
+
+                    if let Some(expected) = file.sha1() {
+                        let mut hasher = Sha1::new();
+                        hasher.update(&bytes);
+                        if format!("{:x}", hasher.finalize()) != expected {
+                            return Err(format!("Hash mismatch for {}", file.file_name));
+                        }
+                    }
+
+                    let target_dir = instance_dir.join(folder);
+                    std::fs::create_dir_all(long_path(&target_dir))
+                        .map_err(|e| format!("Failed to create directory: {}", e))?;
+                    let file_name = sanitize_pack_filename(&file.file_name)?;
+                    std::fs::write(long_path(&target_dir.join(file_name)), &bytes)
+                        .map_err(|e| format!("Failed to write {}: {}", file.file_name, e))?;
+                }
+                // Author disabled third-party downloads
+                None => manual_downloads.push(ManualDownload::for_file(file, projects.get(&file.mod_id))),
+            }
+
+            journal.downloads(files.len() - idx - 1, files.len());
+            let progress = 30 + ((idx + 1) * 60 / total) as u32;
+            let _ = app_handle.emit("creation-progress", serde_json::json!({
+                "instance": safe_name,
+                "progress": progress,
+                "stage": format!("Downloading files... ({}/{})", idx + 1, files.len()),
+                "file": file.file_name,
+                "current": idx + 1,
+                "total": files.len()
+            }));
+        }
+
+        emit(95, "Copying overrides...".to_string());
+        let overrides_prefix = format!("{}/", manifest.overrides.trim_end_matches('/'));
+        extract_prefixed(pack_path, &[overrides_prefix.as_str()], &instance_dir)
+            .map_err(|e| format!("Failed to copy overrides: {}", e))?;
+
+        Ok::<(), String>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        let _ = InstanceManager::delete(&safe_name);
+        return Err(e);
+    }
+
+    if let Err(e) = crate::commands::servers::import_pack_servers(&safe_name).await {
+        println!("⚠ Could not import the pack's servers: {}", e);
+    }
+
+    emit(100, "Instance created successfully!".to_string());
+
+    if manual_downloads.is_empty() {
+        return Ok(format!("Successfully imported modpack '{}'", safe_name));
+    }
+
+    // The pack is usable once these turn up, the watcher slots them in as they're downloaded.
+    // They're shown as one list, each page opens from there with open_curseforge_download.
+    let names: Vec<String> = manual_downloads.iter().map(|d| d.file_name.clone()).collect();
+    let _ = app_handle.emit("curseforge-manual-downloads", serde_json::json!({
+        "instance": safe_name,
+        "downloads": manual_downloads
+    }));
+    CurseForgeInstaller::watch_manual_downloads(app_handle.clone(), &safe_name, manual_downloads)
+        .map_err(|e| format!("Failed to watch downloads: {}", e))?;
+
+    Ok(format!(
+        "Imported modpack '{}'. {} file(s) must be downloaded from CurseForge, they are added as soon as they land in your downloads folder: {}",
+        safe_name,
+        names.len(),
+        names.join(", ")
+    ))
+}
+
+/// Compact code describing the instance's version, loader, mods and settings, for sharing without a zip
+#[tauri::command]
+pub async fn export_instance_code(instance_name: String) -> Result<ShareCodeExport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    ShareCodeManager::export(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to create instance code: {}", e))
+}
+
+/// Rebuild an instance from a code made by `export_instance_code`, downloading every mod
+#[tauri::command]
+pub async fn import_instance_code(
+    code: String,
+    instance_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<ShareCodeImport, String> {
+    let share = ShareCodeManager::decode(&code).map_err(|e| e.to_string())?;
+
+    let safe_name = sanitize_instance_name(instance_name.as_deref().unwrap_or(&share.name))?;
+    if get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' already exists", safe_name));
+    }
+
+    validate_game_arguments(&share.settings.extra_game_args)?;
+    if let Some(ref lang) = share.settings.language {
+        LanguageManager::validate(lang)?;
+    }
+    if let Some(memory_mb) = share.settings.memory_mb {
+        validate_memory_allocation(memory_mb as u64)?;
+    }
+
+    let loader = match (&share.loader, &share.loader_version) {
+        (Some(name), Some(version)) if ["fabric", "liteloader", "forge", "neoforge"].contains(&name.as_str()) => {
+            Some((name.clone(), version.clone()))
+        }
+        (Some(name), _) => return Err(format!("Instances using '{}' are not supported yet", name)),
+        (None, _) => None,
+    };
+
+    let emit = |progress: u32, stage: String| {
+        let _ = app_handle.emit("creation-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": progress,
+            "stage": stage
+        }));
+    };
+
+    println!("Importing instance code for '{}' as '{}'", share.name, safe_name);
+    emit(5, format!("Installing Minecraft {}...", share.minecraft_version));
+
+    let meta_dir = get_meta_dir();
+    MinecraftInstaller::new(meta_dir.clone())
+        .install_version(&share.minecraft_version)
+        .await
+        .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
+
+    let final_version = install_loader(
+        meta_dir,
+        &share.minecraft_version,
+        loader.as_ref().map(|(name, _)| name.as_str()),
+        loader.as_ref().map(|(_, version)| version.as_str()),
+        &app_handle,
+        &safe_name,
+        |stage| emit(20, stage),
+    )
+    .await?;
+
+    emit(30, "Creating instance...".to_string());
+
+    let mut instance = InstanceManager::create(
+        &safe_name,
+        &final_version,
+        loader.as_ref().map(|(name, _)| name.clone()),
+        loader.as_ref().map(|(_, version)| version.clone()),
+    )
+    .map_err(|e| format!("Failed to create instance: {}", e))?;
+
+    let result = async {
+        // Overrides replace the global settings entirely, so start from this machine's
+        if let Some(memory_mb) = share.settings.memory_mb {
+            let mut settings = crate::services::settings::SettingsManager::load()
+                .map_err(|e| format!("Failed to load settings: {}", e))?;
+            settings.memory_mb = memory_mb;
+            instance.settings_override = Some(settings);
+        }
+        instance.language = share.settings.language.clone();
+        instance.extra_game_args = share.settings.extra_game_args.clone();
+        InstanceManager::save(&instance)
+            .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+        let instance_dir = get_instance_dir(&safe_name);
+        let (installed, failed) = ShareCodeManager::download_mods(&share, &instance_dir, |done, total| {
+            let progress = 35 + (done * 60 / total.max(1)) as u32;
+            emit(progress, format!("Downloading mods... ({}/{})", done, total));
+        })
+        .await
+        .map_err(|e| format!("Failed to download mods: {}", e))?;
+
+        if instance.language.is_some() {
+            if let Err(e) = LanguageManager::apply(&instance) {
+                println!("⚠ Could not set the language: {}", e);
+            }
+            if let Err(e) = LanguageManager::ensure_language_asset(&instance).await {
+                println!("⚠ Could not download language files: {}", e);
+            }
+        }
+
+        Ok::<_, String>(ShareCodeImport {
+            instance_name: safe_name.clone(),
+            installed,
+            failed,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(report) => {
+            emit(100, "Instance created successfully!".to_string());
+            Ok(report)
+        }
+        Err(e) => {
+            // Don't leave a half-installed instance behind
+            let _ = InstanceManager::delete(&safe_name);
+            Err(e)
+        }
+    }
+}
+
+// SYSTEM UTILITIES
+
+use sysinfo::System;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SystemInfo {
+    pub total_memory_mb: u64,
+    pub available_memory_mb: u64,
+    pub recommended_max_memory_mb: u64,
+}
 
 #[tauri::command]
 pub async fn get_system_info() -> Result<SystemInfo, String> {
@@ -661,14 +1974,14 @@ pub async fn save_debug_report(version: String) -> Result<String, String> {
     let report = crate::utils::generate_debug_report(&version);
     let logs_dir = get_logs_dir();
     
-    std::fs::create_dir_all(&logs_dir)
+    std::fs::create_dir_all(long_path(&logs_dir))
         .map_err(|e| format!("Failed to create logs directory: {}", e))?;
     
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
     let filename = format!("debug_report_{}.txt", timestamp);
     let filepath = logs_dir.join(&filename);
     
-    std::fs::write(&filepath, report)
+    std::fs::write(long_path(&filepath), report)
         .map_err(|e| format!("Failed to write debug report: {}", e))?;
     
     Ok(filepath.to_string_lossy().to_string())
@@ -681,7 +1994,7 @@ pub fn open_worlds_folder(instance_name: String) -> Result<String, String> {
     let saves_dir = get_instance_dir(&safe_name).join("saves");
 
     if !saves_dir.exists() {
-        std::fs::create_dir_all(&saves_dir)
+        std::fs::create_dir_all(long_path(&saves_dir))
             .map_err(|e| format!("Failed to create saves folder: {}", e))?;
     }
 
@@ -690,6 +2003,19 @@ pub fn open_worlds_folder(instance_name: String) -> Result<String, String> {
     Ok(format!("Opened saves folder for instance '{}'", safe_name))
 }
 
+/// Pre-create a world with a seed, preset or superflat layers and gamerules. On 1.20.5+ it can
+/// also be opened directly on the next launch.
+#[tauri::command]
+pub async fn create_world(instance_name: String, config: WorldConfig) -> Result<CreatedWorld, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    WorldCreator::create(&mut instance, &config)
+        .map_err(|e| format!("Failed to create world: {}", e))
+}
+
 #[tauri::command]
 pub fn open_world_folder(instance_name: String, folder_name: String) -> Result<String, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -725,12 +2051,176 @@ pub fn delete_world(instance_name: String, folder_name: String) -> Result<String
         return Err(format!("World folder '{}' does not exist", folder_name));
     }
 
-    std::fs::remove_dir_all(&world_dir)
+    std::fs::remove_dir_all(long_path(&world_dir))
         .map_err(|e| format!("Failed to delete world folder: {}", e))?;
 
     Ok(format!("Successfully deleted world '{}'", folder_name))
 }
 
+/// Practice maps from the catalogs in settings, flagged by whether they suit the instance
+#[tauri::command]
+pub async fn get_practice_maps(instance_name: Option<String>) -> Result<Vec<CatalogMap>, String> {
+    let settings = crate::services::settings::SettingsManager::load()
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let instance = match instance_name {
+        Some(name) => {
+            let safe_name = sanitize_instance_name(&name)?;
+            Some(InstanceManager::load(&safe_name)
+                .map_err(|e| format!("Failed to load instance: {}", e))?)
+        }
+        None => None,
+    };
+
+    PracticeMapCatalogs::list(&settings.practice_map_catalogs, instance.as_ref())
+        .await
+        .map_err(|e| format!("Failed to load practice maps: {}", e))
+}
+
+/// Download a practice map into the instance's saves/, resuming an earlier partial download
+#[tauri::command]
+pub async fn install_practice_map(
+    instance_name: String,
+    map_id: String,
+    ignore_version: Option<bool>,
+) -> Result<InstalledPracticeMap, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    let settings = crate::services::settings::SettingsManager::load()
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    PracticeMapCatalogs::install(
+        &settings.practice_map_catalogs,
+        &instance,
+        &map_id,
+        ignore_version.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| format!("Failed to install practice map: {}", e))
+}
+
+/// Data packs in a world's datapacks/ with their pack_format compatibility
+#[tauri::command]
+pub fn list_world_datapacks(instance_name: String, folder_name: String) -> Result<Vec<DataPack>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    DataPackManager::list(&instance, &folder_name)
+        .map_err(|e| format!("Failed to list data packs: {}", e))
+}
+
+/// Install a data pack zip or folder into a world. `force` installs packs made for another
+/// pack_format anyway.
+#[tauri::command]
+pub fn install_datapack(
+    instance_name: String,
+    folder_name: String,
+    file_path: String,
+    force: Option<bool>,
+) -> Result<DataPack, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    DataPackManager::install(&instance, &folder_name, std::path::Path::new(&file_path), force.unwrap_or(false))
+        .map_err(|e| format!("Failed to install data pack: {}", e))
+}
+
+#[tauri::command]
+pub fn remove_datapack(instance_name: String, folder_name: String, pack_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    DataPackManager::remove(&instance, &folder_name, &pack_name)
+        .map_err(|e| format!("Failed to remove data pack: {}", e))?;
+
+    Ok(format!("Removed data pack '{}'", pack_name))
+}
+
+/// Turn archive mode on or off. Turning it on copies every game file the instance uses into
+/// pinned/, which takes a while for versions with large asset indexes.
+#[tauri::command]
+pub async fn set_archive_mode(instance_name: String, enabled: bool) -> Result<Option<PinReport>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err("Cannot change archive mode while the instance is running".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut instance = InstanceManager::load(&safe_name)
+            .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+        if enabled {
+            VersionPinner::pin(&mut instance)
+                .map(Some)
+                .map_err(|e| format!("Failed to pin game files: {}", e))
+        } else {
+            VersionPinner::unpin(&mut instance)
+                .map(|_| None)
+                .map_err(|e| format!("Failed to unpin game files: {}", e))
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to change archive mode: {}", e))?
+}
+
+/// Freeze or unfreeze an instance. While frozen, every command that would change it is refused.
+#[tauri::command]
+pub async fn set_instance_frozen(instance_name: String, frozen: bool) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    instance.frozen = frozen;
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    println!("✓ {} '{}'", if frozen { "Froze" } else { "Unfroze" }, safe_name);
+    Ok(())
+}
+
+/// Sign a manifest of the instance's game jar, mods and options every time it starts and exits
+#[tauri::command]
+pub async fn set_speedrun_attestation(instance_name: String, enabled: bool) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    instance.speedrun_attestation = enabled;
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))
+}
+
+/// Sign a manifest of the instance as it is now, stored in its attestations/ folder
+#[tauri::command]
+pub async fn attest_instance(instance_name: String) -> Result<SignedAttestation, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    tokio::task::spawn_blocking(move || {
+        let instance = InstanceManager::load(&safe_name)
+            .map_err(|e| format!("Failed to load instance: {}", e))?;
+        RunAttestor::attest(&instance, AttestationStage::Manual)
+            .map_err(|e| format!("Failed to attest instance: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to attest instance: {}", e))?
+}
+
+/// Check the signature of an attestation file, e.g. one a runner submitted with their run
+#[tauri::command]
+pub async fn verify_attestation(path: String) -> Result<AttestationCheck, String> {
+    RunAttestor::verify(std::path::Path::new(&path))
+        .map_err(|e| format!("Failed to read attestation: {}", e))
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct World {
     pub name: String,
@@ -755,7 +2245,7 @@ pub fn get_instance_worlds(instance_name: String) -> Result<Vec<World>, String>
 
     let mut worlds = Vec::new();
 
-    if let Ok(entries) = std::fs::read_dir(&saves_dir) {
+    if let Ok(entries) = std::fs::read_dir(long_path(&saves_dir)) {
         for entry in entries.flatten() {
             let path = entry.path();
             
@@ -812,7 +2302,7 @@ fn read_world_icon(world_path: &std::path::Path) -> Option<String> {
     }
     
     // Read the icon file
-    if let Ok(image_bytes) = std::fs::read(&icon_path) {
+    if let Ok(image_bytes) = std::fs::read(long_path(&icon_path)) {
         let base64_data = base64::engine::general_purpose::STANDARD.encode(&image_bytes);
         Some(format!("data:image/png;base64,{}", base64_data))
     } else {
@@ -827,7 +2317,7 @@ fn calculate_dir_size(path: &std::path::Path) -> std::io::Result<u64> {
         return Ok(path.metadata()?.len());
     }
     
-    for entry in std::fs::read_dir(path)? {
+    for entry in std::fs::read_dir(long_path(path))? {
         let entry = entry?;
         let entry_path = entry.path();
         
@@ -841,6 +2331,70 @@ fn calculate_dir_size(path: &std::path::Path) -> std::io::Result<u64> {
     Ok(size)
 }
 
+/// Add OptiFine from a jar the player picked or an optifine.net download page. Vanilla instances
+/// get an OptiFine profile, Forge instances get the jar in mods/.
+#[tauri::command]
+pub async fn install_optifine(instance_name: String, source: OptiFineSource) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+
+    let installer = OptiFineInstaller::new(get_meta_dir());
+    let (jar_path, build) = installer
+        .fetch_jar(&source)
+        .await
+        .map_err(|e| format!("Failed to get OptiFine: {}", e))?;
+
+    if build.minecraft_version != minecraft_version {
+        return Err(format!(
+            "This OptiFine build is for Minecraft {}, the instance runs {}",
+            build.minecraft_version, minecraft_version
+        ));
+    }
+
+    match instance.loader.as_deref() {
+        None | Some("vanilla") | Some("optifine") => {
+            instance.version = installer
+                .install_optifine(&jar_path, &build)
+                .await
+                .map_err(|e| format!("Failed to install OptiFine: {}", e))?;
+            instance.loader = Some("optifine".to_string());
+            instance.loader_version = Some(build.edition.clone());
+            InstanceManager::save(&instance)
+                .map_err(|e| format!("Failed to save instance: {}", e))?;
+        }
+        Some("forge") => {
+            // OptiFine loads as a regular Forge mod, older builds in mods/ would clash with it
+            let mods_dir = get_instance_dir(&safe_name).join("mods");
+            std::fs::create_dir_all(long_path(&mods_dir))
+                .map_err(|e| format!("Failed to create mods folder: {}", e))?;
+            if let Ok(entries) = std::fs::read_dir(long_path(&mods_dir)) {
+                for entry in entries.flatten() {
+                    let file_name = entry.file_name().to_string_lossy().to_string();
+                    if file_name.starts_with("OptiFine_") && file_name.ends_with(".jar") {
+                        let _ = std::fs::remove_file(long_path(&entry.path()));
+                    }
+                }
+            }
+
+            let file_name = format!("OptiFine_{}_{}.jar", build.minecraft_version, build.edition);
+            std::fs::copy(long_path(&jar_path), long_path(&mods_dir.join(&file_name)))
+                .map_err(|e| format!("Failed to copy OptiFine into mods: {}", e))?;
+        }
+        Some(other) => {
+            return Err(format!(
+                "OptiFine only runs on vanilla and Forge instances, not {}. Sodium and Iris fill the same role there.",
+                other
+            ));
+        }
+    }
+
+    println!("✓ Installed OptiFine {} into '{}'", build.edition, safe_name);
+    Ok(format!("Installed OptiFine {} into '{}'", build.edition, safe_name))
+}
+
 #[tauri::command]
 pub async fn update_instance_fabric_loader(
     instance_name: String,
@@ -860,7 +2414,7 @@ pub async fn update_instance_fabric_loader(
     
     // Load instance metadata
     let instance_json_path = instance_dir.join("instance.json");
-    let content = std::fs::read_to_string(&instance_json_path)
+    let content = std::fs::read_to_string(long_path(&instance_json_path))
         .map_err(|e| format!("Failed to read instance.json: {}", e))?;
     
     let mut instance: Instance = serde_json::from_str(&content)
@@ -907,7 +2461,7 @@ pub async fn update_instance_fabric_loader(
     let updated_json = serde_json::to_string_pretty(&instance)
         .map_err(|e| format!("Failed to serialize instance.json: {}", e))?;
     
-    std::fs::write(&instance_json_path, updated_json)
+    std::fs::write(long_path(&instance_json_path), updated_json)
         .map_err(|e| format!("Failed to write instance.json: {}", e))?;
     
     Ok(format!("Successfully updated Fabric loader to version {}", instance.loader_version.as_deref().unwrap_or("unknown")))
@@ -933,13 +2487,20 @@ pub async fn update_instance_minecraft_version(
     
     // Load instance metadata
     let instance_json_path = instance_dir.join("instance.json");
-    let content = std::fs::read_to_string(&instance_json_path)
+    let content = std::fs::read_to_string(long_path(&instance_json_path))
         .map_err(|e| format!("Failed to read instance.json: {}", e))?;
     
     let mut instance: Instance = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse instance.json: {}", e))?;
     
     println!("Updating instance '{}' from version {} to {}", safe_name, instance.version, new_minecraft_version);
+
+    if instance.loader.as_deref() == Some("optifine") {
+        return Err(format!(
+            "OptiFine builds only work on the Minecraft version they were made for, install OptiFine for {} instead",
+            new_minecraft_version
+        ));
+    }
     
     // Check if this is a Fabric instance
     let is_fabric = instance.loader == Some("fabric".to_string());
@@ -993,6 +2554,63 @@ pub async fn update_instance_minecraft_version(
         // Update instance metadata with new Fabric version and loader version
         instance.version = new_fabric_version_id;
         instance.loader_version = Some(compatible_loader);
+    } else if instance.loader.as_deref() == Some("liteloader") {
+        let _ = app_handle.emit("version-update-progress", serde_json::json!({
+            "instance": safe_name,
+            "stage": format!("Installing Minecraft {}...", new_minecraft_version)
+        }));
+
+        let meta_dir = get_meta_dir();
+        let installer = MinecraftInstaller::new(meta_dir.clone());
+
+        if !installer.check_version_installed(&new_minecraft_version) {
+            installer
+                .install_version(&new_minecraft_version)
+                .await
+                .map_err(|e| format!("Failed to install Minecraft {}: {}", new_minecraft_version, e))?;
+        }
+
+        let liteloader_installer = LiteLoaderInstaller::new(meta_dir);
+        let compatible_loader = liteloader_installer
+            .get_compatible_loader_for_minecraft(&new_minecraft_version)
+            .await
+            .map_err(|e| format!("Failed to find compatible LiteLoader: {}", e))?;
+
+        let _ = app_handle.emit("version-update-progress", serde_json::json!({
+            "instance": safe_name,
+            "stage": format!("Installing LiteLoader {}...", compatible_loader)
+        }));
+
+        instance.version = liteloader_installer
+            .install_liteloader(&new_minecraft_version, &compatible_loader)
+            .await
+            .map_err(|e| format!("Failed to install LiteLoader for Minecraft {}: {}", new_minecraft_version, e))?;
+        instance.loader_version = Some(compatible_loader);
+    } else if let Some(flavor) = instance.loader.as_deref().and_then(ForgeFlavor::from_loader) {
+        let loader_name = flavor.loader();
+        let _ = app_handle.emit("version-update-progress", serde_json::json!({
+            "instance": safe_name,
+            "stage": format!("Finding compatible {} build...", loader_name)
+        }));
+
+        // Installs the new Minecraft version itself, the processors need its jar
+        let forge_installer = ForgeInstaller::new(get_meta_dir())
+            .with_progress(app_handle.clone(), Some(safe_name.clone()));
+        let compatible_loader = forge_installer
+            .get_compatible_loader_for_minecraft(flavor, &new_minecraft_version)
+            .await
+            .map_err(|e| format!("Failed to find compatible {}: {}", loader_name, e))?;
+
+        let _ = app_handle.emit("version-update-progress", serde_json::json!({
+            "instance": safe_name,
+            "stage": format!("Installing {} {}...", loader_name, compatible_loader)
+        }));
+
+        instance.version = forge_installer
+            .install_forge(flavor, &new_minecraft_version, &compatible_loader)
+            .await
+            .map_err(|e| format!("Failed to install {} for Minecraft {}: {}", loader_name, new_minecraft_version, e))?;
+        instance.loader_version = Some(compatible_loader);
     } else {
         // Vanilla instance
         let _ = app_handle.emit("version-update-progress", serde_json::json!({
@@ -1023,7 +2641,7 @@ pub async fn update_instance_minecraft_version(
     // Clean natives directory to prevent classpath conflicts
     let natives_dir = instance_dir.join("natives");
     if natives_dir.exists() {
-        std::fs::remove_dir_all(&natives_dir)
+        std::fs::remove_dir_all(long_path(&natives_dir))
             .map_err(|e| format!("Failed to clean natives directory: {}", e))?;
         println!("✓ Cleaned natives directory");
     }
@@ -1032,7 +2650,7 @@ pub async fn update_instance_minecraft_version(
     let updated_json = serde_json::to_string_pretty(&instance)
         .map_err(|e| format!("Failed to serialize instance.json: {}", e))?;
     
-    std::fs::write(&instance_json_path, updated_json)
+    std::fs::write(long_path(&instance_json_path), updated_json)
         .map_err(|e| format!("Failed to write instance.json: {}", e))?;
     
     let _ = app_handle.emit("version-update-progress", serde_json::json!({
@@ -1041,4 +2659,283 @@ pub async fn update_instance_minecraft_version(
     }));
     
     Ok(format!("Successfully updated instance to Minecraft version {}", new_minecraft_version))
-}
\ No newline at end of file
+}
+
+/// Latest launches, newest first. `unique` keeps only the latest launch of each instance.
+#[tauri::command]
+pub async fn get_recent_launches(
+    limit: Option<usize>,
+    unique: Option<bool>,
+) -> Result<Vec<crate::services::recent::RecentLaunch>, String> {
+    Ok(crate::services::recent::RecentLaunches::get(
+        limit.unwrap_or(10),
+        unique.unwrap_or(true),
+    ))
+}
+
+// ===== NOTES =====
+
+/// Notes larger than this are almost certainly a mistake (e.g. a pasted log)
+const MAX_NOTES_BYTES: usize = 1024 * 1024;
+
+/// Markdown notes stored in the instance's notes.md, empty when there are none
+#[tauri::command]
+pub async fn get_instance_notes(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let notes_path = instance_dir.join("notes.md");
+    if !notes_path.exists() {
+        return Ok(String::new());
+    }
+
+    std::fs::read_to_string(long_path(&notes_path))
+        .map_err(|e| format!("Failed to read notes: {}", e))
+}
+
+/// Saving empty notes removes notes.md
+#[tauri::command]
+pub async fn save_instance_notes(instance_name: String, notes: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    if notes.len() > MAX_NOTES_BYTES {
+        return Err("Notes are too large (max 1 MB)".to_string());
+    }
+
+    let notes_path = instance_dir.join("notes.md");
+    if notes.trim().is_empty() {
+        if notes_path.exists() {
+            std::fs::remove_file(long_path(&notes_path))
+                .map_err(|e| format!("Failed to remove notes: {}", e))?;
+        }
+        return Ok(format!("Cleared notes for '{}'", safe_name));
+    }
+
+    std::fs::write(long_path(&notes_path), notes)
+        .map_err(|e| format!("Failed to save notes: {}", e))?;
+
+    Ok(format!("Saved notes for '{}'", safe_name))
+}
+
+// ===== RECORDINGS =====
+
+/// Replays and rendered videos from ReplayMod and similar mods
+#[tauri::command]
+pub async fn get_instance_recordings(instance_name: String) -> Result<Vec<Recording>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    RecordingManager::list(&safe_name)
+        .map_err(|e| format!("Failed to list recordings: {}", e))
+}
+
+#[tauri::command]
+pub async fn rename_recording(instance_name: String, path: String, new_name: String) -> Result<Recording, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let safe_new_name = sanitize_filename(new_name.trim())?;
+
+    RecordingManager::rename(&safe_name, &path, &safe_new_name)
+        .map_err(|e| format!("Failed to rename recording: {}", e))
+}
+
+/// Move recordings to the media directory from the settings
+#[tauri::command]
+pub async fn move_recordings_to_media(instance_name: String, paths: Vec<String>) -> Result<Vec<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let settings = crate::services::settings::SettingsManager::load()
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    let media_dir = settings
+        .media_directory
+        .filter(|d| !d.trim().is_empty())
+        .ok_or_else(|| "No media directory configured".to_string())?;
+
+    let moved = RecordingManager::move_to(&safe_name, &paths, std::path::Path::new(&media_dir))
+        .map_err(|e| format!("Failed to move recordings: {}", e))?;
+
+    Ok(moved.into_iter().map(|p| p.to_string_lossy().to_string()).collect())
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct VersionChangeReport {
+    pub previous_version: String,
+    pub new_version: String,
+    /// Snapshot taken before the change, for rolling back
+    pub snapshot_id: Option<String>,
+    pub mods: Vec<ModCompatibility>,
+}
+
+/// Compatibility of the installed mods with another Minecraft version, without changing anything
+#[tauri::command]
+pub async fn check_version_compatibility(
+    instance_name: String,
+    new_minecraft_version: String,
+) -> Result<Vec<ModCompatibility>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    VersionCompatChecker::check(&safe_name, instance.loader.as_deref(), &new_minecraft_version)
+        .await
+        .map_err(|e| format!("Failed to check mod compatibility: {}", e))
+}
+
+/// Snapshot the instance, move it to another Minecraft version and report which mods need attention.
+/// Mods and config are left in place.
+#[tauri::command]
+pub async fn change_instance_version(
+    instance_name: String,
+    new_minecraft_version: String,
+    app_handle: tauri::AppHandle,
+) -> Result<VersionChangeReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err("Cannot change the version while the instance is running".to_string());
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+    let previous_version = InstanceManager::base_minecraft_version(&instance.version);
+
+    let mods = VersionCompatChecker::check(&safe_name, instance.loader.as_deref(), &new_minecraft_version)
+        .await
+        .map_err(|e| format!("Failed to check mod compatibility: {}", e))?;
+
+    let snapshot_id = match SnapshotManager::create(
+        &safe_name,
+        Some(format!("Before changing from {} to {}", previous_version, new_minecraft_version)),
+    ) {
+        Ok(snapshot) => Some(snapshot.id),
+        Err(e) => {
+            println!("⚠ Could not snapshot '{}' before the version change: {}", safe_name, e);
+            None
+        }
+    };
+
+    update_instance_minecraft_version(safe_name, new_minecraft_version.clone(), app_handle).await?;
+
+    Ok(VersionChangeReport {
+        previous_version,
+        new_version: new_minecraft_version,
+        snapshot_id,
+        mods,
+    })
+}
+
+// ===== BULK OPERATIONS =====
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct InstanceOperationResult {
+    pub instance: String,
+    pub success: bool,
+    pub message: String,
+}
+
+impl InstanceOperationResult {
+    fn from_result(instance: String, result: Result<String, String>) -> Self {
+        match result {
+            Ok(message) => Self { instance, success: true, message },
+            Err(message) => Self { instance, success: false, message },
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn delete_instances(instance_names: Vec<String>) -> Result<Vec<InstanceOperationResult>, String> {
+    let mut results = Vec::with_capacity(instance_names.len());
+
+    for name in instance_names {
+        let result = delete_instance(name.clone()).await;
+        results.push(InstanceOperationResult::from_result(name, result));
+    }
+
+    Ok(results)
+}
+
+/// Instance folders without a usable instance.json, or left half-done by an interrupted install
+#[tauri::command]
+pub async fn get_orphaned_instances() -> Result<Vec<OrphanedInstance>, String> {
+    tokio::task::spawn_blocking(OrphanScanner::scan)
+        .await
+        .map_err(|e| format!("Orphan scan failed: {}", e))
+}
+
+/// Regenerate an orphaned folder's instance.json from its contents. `minecraft_version` is
+/// needed when nothing in the folder reveals it.
+#[tauri::command]
+pub async fn repair_orphaned_instance(
+    folder: String,
+    minecraft_version: Option<String>,
+) -> Result<RepairedInstance, String> {
+    tokio::task::spawn_blocking(move || {
+        OrphanScanner::repair(&folder, minecraft_version.as_deref())
+            .map_err(|e| format!("Failed to repair instance: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Repair task failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn remove_orphaned_instance(folder: String) -> Result<String, String> {
+    let removed = folder.clone();
+    let freed = tokio::task::spawn_blocking(move || {
+        OrphanScanner::remove(&folder).map_err(|e| format!("Failed to remove instance folder: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Remove task failed: {}", e))??;
+
+    Ok(format!("Removed '{}', freeing {} MB", removed, freed / (1024 * 1024)))
+}
+
+/// Move every listed instance to the given loader version. Instances on another loader are reported as failed.
+#[tauri::command]
+pub async fn update_instances_loader(
+    instance_names: Vec<String>,
+    loader_version: String,
+) -> Result<Vec<InstanceOperationResult>, String> {
+    let mut results = Vec::with_capacity(instance_names.len());
+
+    for name in instance_names {
+        let result = match sanitize_instance_name(&name).and_then(|n| {
+            InstanceManager::load(&n).map_err(|e| format!("Failed to load instance: {}", e))
+        }) {
+            Ok(instance) if instance.loader.as_deref() == Some("fabric") => {
+                update_instance_fabric_loader(name.clone(), loader_version.clone()).await
+            }
+            Ok(instance) => Err(format!(
+                "Updating the '{}' loader is not supported",
+                instance.loader.as_deref().unwrap_or("vanilla")
+            )),
+            Err(e) => Err(e),
+        };
+        results.push(InstanceOperationResult::from_result(name, result));
+    }
+
+    Ok(results)
+}
+
+/// Apply the same settings override to every listed instance, `None` resets them to the global settings
+#[tauri::command]
+pub async fn apply_settings_to_instances(
+    instance_names: Vec<String>,
+    settings: Option<crate::models::LauncherSettings>,
+) -> Result<Vec<InstanceOperationResult>, String> {
+    let mut results = Vec::with_capacity(instance_names.len());
+
+    for name in instance_names {
+        let result = crate::commands::settings::save_instance_settings(name.clone(), settings.clone()).await;
+        results.push(InstanceOperationResult::from_result(name, result));
+    }
+
+    Ok(results)
+}
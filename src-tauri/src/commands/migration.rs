@@ -0,0 +1,235 @@
+use crate::commands::instances::install_loader;
+use crate::commands::servers::import_pack_servers;
+use crate::commands::validation::sanitize_instance_name;
+use crate::services::adoption::{AdoptedGameDir, GameDirAdopter, GameDirInspection};
+use crate::services::installer::MinecraftInstaller;
+use crate::services::instance::InstanceManager;
+use crate::services::migration::{ImportableInstance, MigrationManager, MigrationSource};
+use crate::utils::{get_instance_dir, get_meta_dir};
+use std::path::Path;
+use tauri::Emitter;
+
+/// MultiMC, PolyMC, Prism Launcher, ATLauncher and Technic installs found on this machine
+#[tauri::command]
+pub async fn detect_launcher_installs() -> Result<Vec<MigrationSource>, String> {
+    Ok(MigrationManager::detect_sources())
+}
+
+/// Accepts either a launcher data directory or its instances folder
+#[tauri::command]
+pub async fn list_importable_instances(directory: String) -> Result<Vec<ImportableInstance>, String> {
+    let path = Path::new(&directory);
+    let instances_dir = if path.join("instances").is_dir()
+        || path.join("modpacks").is_dir()
+        || path.join("prismlauncher.cfg").exists()
+    {
+        MigrationManager::resolve_instances_dir(path)
+    } else {
+        path.to_path_buf()
+    };
+
+    MigrationManager::list_instances(&instances_dir)
+        .map_err(|e| format!("Failed to list instances: {}", e))
+}
+
+#[tauri::command]
+pub async fn import_launcher_instance(
+    instance_path: String,
+    instance_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let source = MigrationManager::read_instance(Path::new(&instance_path))
+        .map_err(|e| format!("Failed to read instance: {}", e))?;
+
+    if let Some(reason) = &source.unsupported_reason {
+        return Err(reason.clone());
+    }
+
+    let safe_name = sanitize_instance_name(instance_name.as_deref().unwrap_or(&source.name))?;
+    if get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' already exists", safe_name));
+    }
+
+    let game_version = source.minecraft_version.clone().unwrap_or_default();
+    if !game_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version in instance".to_string());
+    }
+    if let Some(ref lv) = source.loader_version {
+        if !lv.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+            return Err("Invalid loader version in instance".to_string());
+        }
+    }
+
+    let emit = |progress: u32, stage: String| {
+        let _ = app_handle.emit("creation-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": progress,
+            "stage": stage
+        }));
+    };
+
+    println!("Migrating '{}' from {} as '{}'", source.name, source.path, safe_name);
+    emit(10, format!("Installing Minecraft {}...", game_version));
+
+    let meta_dir = get_meta_dir();
+    MinecraftInstaller::new(meta_dir.clone())
+        .install_version(&game_version)
+        .await
+        .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
+
+    let final_version = install_loader(
+        meta_dir,
+        &game_version,
+        source.loader.as_deref(),
+        source.loader_version.as_deref(),
+        &app_handle,
+        &safe_name,
+        |stage| emit(40, stage),
+    )
+    .await?;
+
+    emit(60, "Copying game files...".to_string());
+
+    let mut instance = InstanceManager::create(
+        &safe_name,
+        &final_version,
+        source.loader.clone(),
+        source.loader_version.clone(),
+    )
+    .map_err(|e| format!("Failed to create instance: {}", e))?;
+
+    if let Err(e) = MigrationManager::migrate_into(&source, &mut instance) {
+        let _ = InstanceManager::delete(&safe_name);
+        return Err(format!("Failed to copy instance files: {}", e));
+    }
+
+    emit(90, "Bringing over servers and settings...".to_string());
+    carry_over_game_data(&safe_name).await;
+
+    emit(100, "Instance imported successfully!".to_string());
+
+    println!("✓ Migrated '{}' to '{}'", source.name, safe_name);
+    Ok(safe_name)
+}
+
+/// What an existing .minecraft folder would be adopted as
+#[tauri::command]
+pub async fn inspect_game_dir(path: String) -> Result<GameDirInspection, String> {
+    tokio::task::spawn_blocking(move || GameDirAdopter::inspect(Path::new(&path)).map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| format!("Failed to inspect folder: {}", e))?
+        .map_err(|e| format!("Failed to inspect folder: {}", e))
+}
+
+/// Manage an existing .minecraft folder as an instance. Its worlds, mods and packs are linked
+/// in rather than copied, so both launchers keep seeing the same files.
+#[tauri::command]
+pub async fn adopt_existing_game_dir(
+    path: String,
+    name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<AdoptedGameDir, String> {
+    let game_dir = Path::new(&path).to_path_buf();
+    let inspection = GameDirAdopter::inspect(&game_dir)
+        .map_err(|e| format!("Failed to inspect folder: {}", e))?;
+    let detected = inspection
+        .detected
+        .ok_or_else(|| "Could not tell which Minecraft version this folder was played with".to_string())?;
+
+    let default_name = game_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().trim_start_matches('.').to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Minecraft".to_string());
+    let safe_name = sanitize_instance_name(name.as_deref().unwrap_or(&default_name))?;
+    if get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' already exists", safe_name));
+    }
+
+    let game_version = detected.minecraft_version.clone();
+    if !game_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version in folder".to_string());
+    }
+    if let Some(ref lv) = detected.loader_version {
+        if !lv.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '+') {
+            return Err("Invalid loader version in folder".to_string());
+        }
+    }
+
+    let emit = |progress: u32, stage: String| {
+        let _ = app_handle.emit("creation-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": progress,
+            "stage": stage
+        }));
+    };
+
+    println!("Adopting {} as '{}'", inspection.path, safe_name);
+    emit(10, format!("Installing Minecraft {}...", game_version));
+
+    let meta_dir = get_meta_dir();
+    MinecraftInstaller::new(meta_dir.clone())
+        .install_version(&game_version)
+        .await
+        .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
+
+    let final_version = install_loader(
+        meta_dir,
+        &game_version,
+        detected.loader.as_deref(),
+        detected.loader_version.as_deref(),
+        &app_handle,
+        &safe_name,
+        |stage| emit(40, stage),
+    )
+    .await?;
+
+    emit(70, "Linking game folders...".to_string());
+
+    let mut instance = InstanceManager::create(
+        &safe_name,
+        &final_version,
+        detected.loader.clone(),
+        detected.loader_version.clone(),
+    )
+    .map_err(|e| format!("Failed to create instance: {}", e))?;
+
+    let adopted = match GameDirAdopter::link_into(&mut instance, &game_dir) {
+        Ok(adopted) => adopted,
+        Err(e) => {
+            let _ = InstanceManager::delete(&safe_name);
+            return Err(format!("Failed to link game folders: {}", e));
+        }
+    };
+
+    emit(100, "Folder adopted successfully!".to_string());
+    Ok(adopted)
+}
+
+/// Copy options.txt and servers.dat from the official launcher into an instance that has none,
+/// and add its servers to the launcher's server list. Returns the files copied.
+#[tauri::command]
+pub async fn import_global_game_files(instance_name: String) -> Result<Vec<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    if !get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    Ok(carry_over_game_data(&safe_name).await)
+}
+
+/// Best effort, an import shouldn't fail because the server list couldn't be brought over
+async fn carry_over_game_data(instance_name: &str) -> Vec<String> {
+    let copied = MigrationManager::copy_global_files(&get_instance_dir(instance_name)).unwrap_or_else(|e| {
+        println!("⚠ Failed to copy options and servers into '{}': {}", instance_name, e);
+        Vec::new()
+    });
+
+    match import_pack_servers(instance_name).await {
+        Ok(added) if added > 0 => println!("✓ Added {} servers from '{}' to the server list", added, instance_name),
+        Ok(_) => {}
+        Err(e) => println!("⚠ Failed to import servers of '{}': {}", instance_name, e),
+    }
+
+    copied
+}
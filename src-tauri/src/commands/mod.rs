@@ -9,6 +9,9 @@ pub mod templates;
 pub mod settings;
 pub mod skins;
 pub mod friends;
+pub mod diagnostics;
+pub mod migration;
+pub mod freeze;
 
 pub use auth::*;
 pub use instances::*;
@@ -19,4 +22,6 @@ pub use servers::*;
 pub use templates::*;
 pub use settings::*;
 pub use skins::*;
-pub use friends::*;
\ No newline at end of file
+pub use friends::*;
+pub use diagnostics::*;
+pub use migration::*;
\ No newline at end of file
@@ -1,6 +1,16 @@
 use crate::services::instance::InstanceManager;
 use crate::services::installer::MinecraftInstaller;
+use crate::services::journal::{JournalEntry, JournalTask, TaskJournal};
 use crate::services::fabric::FabricInstaller;
+use crate::services::forge::{ForgeFlavor, ForgeInstaller};
+use crate::services::case_check::CaseChecker;
+use crate::services::pack_validator::{PackValidationReport, PackValidator};
+use crate::services::mod_cache::ModCache;
+use crate::services::mod_lock::ModLockfile;
+use crate::services::mrpack::MrpackReader;
+use crate::services::managed::{ManagedInstanceManager, ManagedUpdate, ManagedUpdateResult};
+use crate::services::pack_update::{PackUpdatePreview, PackUpdatePreviewer, PackUpdateSource};
+use crate::services::pack_setup::{PackSetupRunner, SetupStepStatus};
 use crate::utils::modrinth::{ModrinthClient, ModrinthVersion};
 use crate::utils::*;
 use crate::commands::validation::{sanitize_instance_name, validate_download_url};
@@ -78,6 +88,12 @@ pub async fn install_modpack(
         }
     }
 
+    let journal = TaskJournal::begin(&safe_name, JournalTask::ModpackInstall {
+        modpack_slug: modpack_slug.clone(),
+        version_id: version_id.clone(),
+        preferred_game_version: preferred_game_version.clone(),
+    });
+
     let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
         "instance": safe_name,
         "progress": 0,
@@ -136,6 +152,7 @@ pub async fn install_modpack(
         .await
         .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
     
+    let mut loader_version = None;
     let final_version = if loader == "fabric" {
         let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
             "instance": safe_name,
@@ -160,6 +177,26 @@ pub async fn install_modpack(
             .install_fabric(&game_version, &fabric_version.version)
             .await
             .map_err(|e| format!("Failed to install Fabric: {}", e))?
+    } else if let Some(flavor) = ForgeFlavor::from_loader(&loader) {
+        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": 20,
+            "stage": format!("Installing {}...", loader)
+        }));
+
+        let forge_installer = ForgeInstaller::new(meta_dir)
+            .with_progress(app_handle.clone(), Some(safe_name.clone()));
+        let forge_version = forge_installer
+            .get_compatible_loader_for_minecraft(flavor, &game_version)
+            .await
+            .map_err(|e| format!("Failed to get {} versions: {}", loader, e))?;
+
+        let forge_id = forge_installer
+            .install_forge(flavor, &game_version, &forge_version)
+            .await
+            .map_err(|e| format!("Failed to install {}: {}", loader, e))?;
+        loader_version = Some(forge_version);
+        forge_id
     } else {
         game_version.clone()
     };
@@ -169,14 +206,16 @@ pub async fn install_modpack(
         "progress": 30,
         "stage": "Creating instance..."
     }));
+    journal.step("Creating instance");
     
     InstanceManager::create(
         &safe_name,
         &final_version,
         if loader == "vanilla" { None } else { Some(loader.clone()) },
-        None,
+        loader_version,
     )
     .map_err(|e| format!("Failed to create instance: {}", e))?;
+    journal.instance_created();
     
     // Fetch project details separately to avoid holding non-Send types across await
     let icon_url_opt = match client.get_project(&modpack_slug).await {
@@ -237,6 +276,8 @@ pub async fn install_modpack(
     
     let temp_dir = std::env::temp_dir();
     let modpack_file = temp_dir.join(&primary_file.filename);
+    journal.step("Downloading modpack");
+    journal.temp_path(&modpack_file);
     
     validate_download_url(&primary_file.url)?;
     
@@ -252,6 +293,8 @@ pub async fn install_modpack(
     }));
     
     let extract_dir = temp_dir.join(format!("modpack_extract_{}", safe_name));
+    journal.step("Extracting modpack");
+    journal.temp_path(&extract_dir);
     if extract_dir.exists() {
         let _ = std::fs::remove_dir_all(&extract_dir);
     }
@@ -297,6 +340,8 @@ pub async fn install_modpack(
             "progress": 70,
             "stage": format!("Downloading {} mods...", total_files)
         }));
+        journal.step("Downloading mods");
+        journal.downloads(total_files, total_files);
         
         for (idx, file) in files.iter().enumerate() {
             let downloads = file.get("downloads")
@@ -318,10 +363,21 @@ pub async fn install_modpack(
                     .map_err(|e| format!("Failed to create directory: {}", e))?;
             }
             
-            validate_download_url(download_url)?;
-            client.download_mod_file(download_url, &dest_path)
-                .await
-                .map_err(|e| format!("Failed to download mod: {}", e))?;
+            let sha512 = file.get("hashes")
+                .and_then(|h| h.get("sha512"))
+                .and_then(|h| h.as_str())
+                .filter(|_| ModCache::covers(path));
+
+            if !sha512.is_some_and(|sha512| ModCache::restore(sha512, &dest_path)) {
+                validate_download_url(download_url)?;
+                client.download_mod_file(download_url, &dest_path)
+                    .await
+                    .map_err(|e| format!("Failed to download mod: {}", e))?;
+                if ModCache::covers(path) {
+                    ModCache::adopt_or_warn(&dest_path);
+                }
+            }
+            journal.downloads(total_files - idx - 1, total_files);
             
             let progress = 70 + ((idx + 1) * 25 / total_files) as u32;
             let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
@@ -335,6 +391,10 @@ pub async fn install_modpack(
     let _ = std::fs::remove_file(&modpack_file);
     let _ = std::fs::remove_dir_all(&extract_dir);
 
+    if let Err(e) = crate::commands::servers::import_pack_servers(&safe_name).await {
+        println!("⚠ Could not import the pack's servers: {}", e);
+    }
+
     let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
         "instance": safe_name,
         "progress": 100,
@@ -351,10 +411,10 @@ fn copy_dir_recursive(
     use std::fs;
     
     if !dst.exists() {
-        fs::create_dir_all(dst)?;
+        fs::create_dir_all(long_path(dst))?;
     }
     
-    for entry in fs::read_dir(src)? {
+    for entry in fs::read_dir(long_path(src))? {
         let entry = entry?;
         let file_type = entry.file_type()?;
         let src_path = entry.path();
@@ -363,7 +423,7 @@ fn copy_dir_recursive(
         if file_type.is_dir() {
             copy_dir_recursive(&src_path, &dst_path)?;
         } else if file_type.is_file() {
-            fs::copy(&src_path, &dst_path)?;
+            fs::copy(long_path(&src_path), long_path(&dst_path))?;
         }
     }
     
@@ -392,6 +452,7 @@ fn extract_modpack(
         if !outpath.starts_with(dest_dir) {
             continue;
         }
+        let outpath = long_path(&outpath);
         
         if file.name().ends_with('/') {
             std::fs::create_dir_all(&outpath)
@@ -413,6 +474,57 @@ fn extract_modpack(
     Ok(())
 }
 
+/// What updating the instance to another version of its Modrinth or CurseForge pack would
+/// add, remove and update, before any of its files are touched
+#[tauri::command]
+pub async fn preview_pack_update(instance_name: String, source: PackUpdateSource) -> Result<PackUpdatePreview, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    PackUpdatePreviewer::preview(&instance, &source)
+        .await
+        .map_err(|e| format!("Failed to preview pack update: {}", e))
+}
+
+/// Installs and imports the launcher was closed in the middle of
+#[tauri::command]
+pub async fn get_interrupted_tasks() -> Result<Vec<JournalEntry>, String> {
+    Ok(TaskJournal::interrupted())
+}
+
+/// Remove what an interrupted task left behind, then start it over when `resume` is set.
+/// Versions and loaders it already installed are reused, so a restart picks up at the pack itself.
+#[tauri::command]
+pub async fn resolve_interrupted_task(
+    id: String,
+    resume: bool,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let entry = TaskJournal::clean_up(&id)
+        .map_err(|e| format!("Failed to clean up interrupted task: {}", e))?;
+
+    if !resume {
+        return Ok(format!("Cleaned up the interrupted install of '{}'", entry.instance_name));
+    }
+
+    match entry.task {
+        JournalTask::ModpackInstall { modpack_slug, version_id, preferred_game_version } => {
+            install_modpack(modpack_slug, entry.instance_name, version_id, preferred_game_version, app_handle).await
+        }
+        JournalTask::ModpackFileInstall { file_path, preferred_game_version } => {
+            install_modpack_from_file(file_path, entry.instance_name, preferred_game_version, app_handle).await
+        }
+        JournalTask::ModrinthPackImport { file_path } => {
+            import_modrinth_pack(file_path, Some(entry.instance_name), app_handle).await
+        }
+        JournalTask::CurseforgePackImport { file_path } => {
+            crate::commands::instances::import_curseforge_pack(file_path, Some(entry.instance_name), app_handle).await
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn get_modpack_manifest(
     modpack_slug: String,
@@ -562,6 +674,11 @@ pub async fn install_modpack_from_file(
         }
     }
 
+    let journal = TaskJournal::begin(&safe_name, JournalTask::ModpackFileInstall {
+        file_path: file_path.clone(),
+        preferred_game_version: preferred_game_version.clone(),
+    });
+
     let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
         "instance": safe_name,
         "progress": 0,
@@ -578,6 +695,7 @@ pub async fn install_modpack_from_file(
     
     let temp_dir = std::env::temp_dir();
     let extract_dir = temp_dir.join(format!("modpack_extract_{}", safe_name));
+    journal.temp_path(&extract_dir);
     if extract_dir.exists() {
         let _ = std::fs::remove_dir_all(&extract_dir);
     }
@@ -590,6 +708,19 @@ pub async fn install_modpack_from_file(
         "stage": "Extracting modpack..."
     }));
     
+    // Files differing only by case silently overwrite each other on Windows/macOS
+    match CaseChecker::check_pack_archive(file_path_obj) {
+        Ok(report) if !report.is_empty() => {
+            println!("⚠ Modpack contains {} case conflict(s)", report.collisions.len());
+            let _ = app_handle.emit("modpack-case-conflicts", serde_json::json!({
+                "instance": safe_name,
+                "report": report
+            }));
+        }
+        Ok(_) => {}
+        Err(e) => println!("✗ Could not check modpack for case conflicts: {}", e),
+    }
+    
     extract_modpack(file_path_obj, &extract_dir)
         .map_err(|e| format!("Failed to extract modpack: {}", e))?;
     
@@ -627,6 +758,8 @@ pub async fn install_modpack_from_file(
         "fabric"
     } else if dependencies.contains_key("forge") {
         "forge"
+    } else if dependencies.contains_key("neoforge") {
+        "neoforge"
     } else if dependencies.contains_key("quilt-loader") {
         "quilt"
     } else {
@@ -648,6 +781,7 @@ pub async fn install_modpack_from_file(
         .await
         .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
     
+    let mut loader_version = None;
     let final_version = if loader == "fabric" {
         let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
             "instance": safe_name,
@@ -672,6 +806,25 @@ pub async fn install_modpack_from_file(
             .install_fabric(&game_version, &fabric_version.version)
             .await
             .map_err(|e| format!("Failed to install Fabric: {}", e))?
+    } else if let Some(flavor) = ForgeFlavor::from_loader(loader) {
+        let forge_version = dependencies
+            .get(loader)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("Invalid manifest: missing {} version", loader))?
+            .to_string();
+        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": 40,
+            "stage": format!("Installing {} {}...", loader, forge_version)
+        }));
+
+        let forge_id = ForgeInstaller::new(meta_dir)
+            .with_progress(app_handle.clone(), Some(safe_name.clone()))
+            .install_forge(flavor, &game_version, &forge_version)
+            .await
+            .map_err(|e| format!("Failed to install {}: {}", loader, e))?;
+        loader_version = Some(forge_version);
+        forge_id
     } else {
         game_version.clone()
     };
@@ -681,14 +834,16 @@ pub async fn install_modpack_from_file(
         "progress": 50,
         "stage": "Creating instance..."
     }));
+    journal.step("Creating instance");
     
     InstanceManager::create(
         &safe_name,
         &final_version,
         if loader == "vanilla" { None } else { Some(loader.to_string()) },
-        None,
+        loader_version,
     )
     .map_err(|e| format!("Failed to create instance: {}", e))?;
+    journal.instance_created();
 
     let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
         "instance": safe_name,
@@ -731,6 +886,8 @@ pub async fn install_modpack_from_file(
             "progress": 70,
             "stage": format!("Downloading {} mods...", total_files)
         }));
+        journal.step("Downloading mods");
+        journal.downloads(total_files, total_files);
         
         let client = crate::utils::modrinth::ModrinthClient::new();
         
@@ -754,10 +911,21 @@ pub async fn install_modpack_from_file(
                     .map_err(|e| format!("Failed to create directory: {}", e))?;
             }
             
-            validate_download_url(download_url)?;
-            client.download_mod_file(download_url, &dest_path)
-                .await
-                .map_err(|e| format!("Failed to download mod: {}", e))?;
+            let sha512 = file.get("hashes")
+                .and_then(|h| h.get("sha512"))
+                .and_then(|h| h.as_str())
+                .filter(|_| ModCache::covers(path));
+
+            if !sha512.is_some_and(|sha512| ModCache::restore(sha512, &dest_path)) {
+                validate_download_url(download_url)?;
+                client.download_mod_file(download_url, &dest_path)
+                    .await
+                    .map_err(|e| format!("Failed to download mod: {}", e))?;
+                if ModCache::covers(path) {
+                    ModCache::adopt_or_warn(&dest_path);
+                }
+            }
+            journal.downloads(total_files - idx - 1, total_files);
             
             let progress = 70 + ((idx + 1) * 25 / total_files) as u32;
             let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
@@ -770,6 +938,10 @@ pub async fn install_modpack_from_file(
     
     let _ = std::fs::remove_dir_all(&extract_dir);
 
+    if let Err(e) = crate::commands::servers::import_pack_servers(&safe_name).await {
+        println!("⚠ Could not import the pack's servers: {}", e);
+    }
+
     let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
         "instance": safe_name,
         "progress": 100,
@@ -777,4 +949,239 @@ pub async fn install_modpack_from_file(
     }));
     
     Ok(format!("Successfully installed modpack '{}'", safe_name))
-}
\ No newline at end of file
+}
+
+/// Import a .mrpack into a new instance: exact loader version, verified downloads, overrides
+#[tauri::command]
+pub async fn import_modrinth_pack(
+    file_path: String,
+    instance_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    use std::path::Path;
+
+    let pack_path = Path::new(&file_path);
+    if !pack_path.exists() {
+        return Err("Modpack file does not exist".to_string());
+    }
+
+    if pack_path.extension().and_then(|e| e.to_str()) != Some("mrpack") {
+        return Err("Invalid modpack file format. Expected .mrpack".to_string());
+    }
+
+    let index = MrpackReader::read_index(pack_path)
+        .map_err(|e| format!("Failed to read modpack: {}", e))?;
+
+    let safe_name = sanitize_instance_name(instance_name.as_deref().unwrap_or(&index.name))?;
+    if get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' already exists", safe_name));
+    }
+
+    let game_version = index.dependencies.get("minecraft")
+        .ok_or_else(|| "Invalid manifest: missing minecraft dependency".to_string())?
+        .clone();
+
+    if !game_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid game version in manifest".to_string());
+    }
+
+    if index.dependencies.contains_key("quilt-loader") {
+        return Err("Modpacks using 'quilt-loader' are not supported yet".to_string());
+    }
+    let loader = ["fabric-loader", "forge", "neoforge"]
+        .iter()
+        .find_map(|l| index.dependencies.get(*l).map(|v| (*l, v.clone())));
+
+    let journal = TaskJournal::begin(&safe_name, JournalTask::ModrinthPackImport {
+        file_path: file_path.clone(),
+    });
+
+    let emit = |progress: u32, stage: String| {
+        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
+            "instance": safe_name,
+            "progress": progress,
+            "stage": stage
+        }));
+    };
+
+    println!("Importing Modrinth pack '{}' {} as '{}'", index.name, index.version_id, safe_name);
+    emit(5, format!("Installing Minecraft {}...", game_version));
+    journal.step("Installing Minecraft");
+
+    let meta_dir = get_meta_dir();
+    MinecraftInstaller::new(meta_dir.clone())
+        .install_version(&game_version)
+        .await
+        .map_err(|e| format!("Failed to install Minecraft: {}", e))?;
+
+    let loader_name = loader.as_ref().map(|(name, _)| if *name == "fabric-loader" { "fabric" } else { *name });
+    let final_version = crate::commands::instances::install_loader(
+        meta_dir,
+        &game_version,
+        loader_name,
+        loader.as_ref().map(|(_, version)| version.as_str()),
+        &app_handle,
+        &safe_name,
+        |stage| emit(20, stage),
+    )
+    .await?;
+
+    emit(30, "Creating instance...".to_string());
+    journal.step("Creating instance");
+
+    InstanceManager::create(
+        &safe_name,
+        &final_version,
+        loader_name.map(str::to_string),
+        loader.as_ref().map(|(_, version)| version.clone()),
+    )
+    .map_err(|e| format!("Failed to create instance: {}", e))?;
+    journal.instance_created();
+
+    let instance_dir = get_instance_dir(&safe_name);
+
+    let result = async {
+        emit(35, "Copying overrides...".to_string());
+        MrpackReader::extract_overrides(pack_path, &instance_dir)
+            .map_err(|e| format!("Failed to copy overrides: {}", e))?;
+
+        let files = MrpackReader::client_files(&index);
+        let total_files = files.len().max(1);
+        let client = crate::utils::http::client_builder()
+            .timeout(std::time::Duration::from_secs(300))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        journal.step("Downloading files");
+        journal.downloads(files.len(), files.len());
+        for (idx, file) in files.iter().enumerate() {
+            MrpackReader::download_file(&client, file, &instance_dir).await?;
+            journal.downloads(files.len() - idx - 1, files.len());
+
+            let progress = 40 + ((idx + 1) * 55 / total_files) as u32;
+            emit(progress, format!("Downloading files... ({}/{})", idx + 1, files.len()));
+        }
+
+        ModLockfile::record_mrpack_files(&instance_dir, &files)
+            .map_err(|e| format!("Failed to write mod lockfile: {}", e))?;
+
+        Ok::<(), String>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        // Don't leave a half-installed instance behind
+        let _ = InstanceManager::delete(&safe_name);
+        return Err(e);
+    }
+
+    if let Some(icon_bytes) = crate::utils::archive::read_entry(pack_path, "icon.png") {
+        use base64::{Engine as _, engine::general_purpose};
+
+        if let Err(e) = crate::commands::set_instance_icon(
+            safe_name.clone(),
+            general_purpose::STANDARD.encode(&icon_bytes),
+        ).await {
+            println!("Failed to set icon: {}", e);
+        }
+    }
+
+    if let Err(e) = crate::commands::servers::import_pack_servers(&safe_name).await {
+        println!("⚠ Could not import the pack's servers: {}", e);
+    }
+
+    emit(100, "Installation complete!".to_string());
+
+    Ok(format!("Successfully imported modpack '{}'", safe_name))
+}
+
+/// Check a pack end to end without installing it. Also available headless via `--validate-pack`.
+#[tauri::command]
+pub async fn validate_pack_file(
+    file_path: String,
+    verify_hashes: Option<bool>,
+) -> Result<PackValidationReport, String> {
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        return Err("Modpack file does not exist".to_string());
+    }
+
+    Ok(PackValidator::validate(path, verify_hashes.unwrap_or(false)).await)
+}
+
+/// The run-once setup steps the instance's pack declares and which of them already ran
+#[tauri::command]
+pub async fn get_instance_setup_steps(instance_name: String) -> Result<Vec<SetupStepStatus>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    PackSetupRunner::status(&instance)
+        .map_err(|e| format!("Failed to read pack setup: {}", e))
+}
+
+/// Forget which setup steps ran so they all run again before the next launch
+#[tauri::command]
+pub async fn reset_instance_setup(instance_name: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.completed_setup_steps.clear();
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))
+}
+
+/// Follow a remote pack manifest (a modrinth.index.json over HTTPS), or None to stop following it
+#[tauri::command]
+pub async fn set_managed_instance_source(
+    instance_name: String,
+    manifest_url: Option<String>,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if let Some(ref url) = manifest_url {
+        ManagedInstanceManager::validate_url(url)?;
+    }
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    if instance.managed_manifest_url != manifest_url {
+        instance.managed_version = None;
+    }
+    instance.managed_manifest_url = manifest_url;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))
+}
+
+#[tauri::command]
+pub async fn check_managed_instance_update(instance_name: String) -> Result<ManagedUpdate, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ManagedInstanceManager::check(&instance)
+        .await
+        .map(|(_, update)| update)
+        .map_err(|e| format!("Failed to check for pack updates: {}", e))
+}
+
+#[tauri::command]
+pub async fn apply_managed_instance_update(instance_name: String) -> Result<ManagedUpdateResult, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Stop '{}' before updating it", safe_name));
+    }
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ManagedInstanceManager::apply(&mut instance)
+        .await
+        .map_err(|e| format!("Failed to update pack: {}", e))
+}
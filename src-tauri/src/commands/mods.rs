@@ -1,6 +1,27 @@
 use crate::commands::validation::{sanitize_instance_name, sanitize_filename, validate_download_url};
-use crate::utils::{get_instance_dir, open_folder};
-use crate::utils::modrinth::{ModrinthClient, ModrinthProjectDetails, ModrinthSearchResult, ModrinthVersion};
+use crate::services::instance::InstanceManager;
+use crate::services::jarmod::JarModManager;
+use crate::services::optimize::{PerformancePreset, PresetInstallReport};
+use crate::services::mod_list::{ModListExporter, ModListFormat};
+use crate::services::credits::{CreditsCollector, CreditsReport};
+use crate::services::mod_config::{ConfigFileContent, ConfigFileEditor, ModConfigFile, ModConfigLocator};
+use crate::services::mod_details::{ModDetails, ModDetailsCache};
+use crate::services::mod_lock::ModLockfile;
+use crate::services::lock_sync::{LockfileSync, LockfileSyncResult};
+use crate::services::auto_update::{AutoUpdateSummary, ModAutoUpdater};
+use crate::models::{AutoUpdatePolicy, ModUpdatePreference};
+use crate::services::mod_metadata::{ModMetadata, ModMetadataReader};
+use crate::services::modrinth::{ModrinthInstallResult, ModrinthInstaller};
+use crate::services::modrinth_follows::{CollectionInstallResult, FollowedUpdate, ModrinthFollows};
+use crate::services::mod_updates::{ModUpdate, ModUpdateResult, ModUpdater};
+use crate::services::mod_conflicts::{ModConflict, ModConflictAnalyzer};
+use crate::services::local_mods::{LocalModImporter, LocalModResult};
+use crate::services::pack_library::{LibraryPack, PackKind, PackLibrary};
+use crate::services::resource_packs::{ResourcePack, ResourcePackManager};
+use crate::services::curseforge::{CurseForgeInstallResult, CurseForgeInstaller, ManualDownload};
+use crate::utils::curseforge::{mod_loader_type, CurseForgeSearchResult, CLASS_MODS};
+use crate::utils::{get_instance_dir, long_path, open_folder};
+use crate::utils::modrinth::{ModrinthClient, ModrinthCollection, ModrinthProjectDetails, ModrinthSearchResult, ModrinthUser, ModrinthVersion};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 
@@ -10,6 +31,15 @@ pub struct ModFile {
     pub size: u64,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct InstalledMod {
+    pub filename: String,
+    pub size: u64,
+    pub enabled: bool,
+    /// `None` when the jar has no metadata we can read
+    pub metadata: Option<ModMetadata>,
+}
+
 // ===== MOD FILE MANAGEMENT =====
 
 #[tauri::command]
@@ -25,7 +55,7 @@ pub async fn get_installed_mods(instance_name: String) -> Result<Vec<ModFile>, S
     
     let mut mods = Vec::new();
     
-    match std::fs::read_dir(&mods_dir) {
+    match std::fs::read_dir(long_path(&mods_dir)) {
         Ok(entries) => {
             for entry in entries.flatten() {
                 let path = entry.path();
@@ -37,7 +67,7 @@ pub async fn get_installed_mods(instance_name: String) -> Result<Vec<ModFile>, S
                 if path.is_file() {
                     if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
                         if filename.ends_with(".jar") || filename.ends_with(".jar.disabled") {
-                            if let Ok(metadata) = std::fs::metadata(&path) {
+                            if let Ok(metadata) = std::fs::metadata(long_path(&path)) {
                                 mods.push(ModFile {
                                     filename: filename.to_string(),
                                     size: metadata.len(),
@@ -58,6 +88,28 @@ pub async fn get_installed_mods(instance_name: String) -> Result<Vec<ModFile>, S
     Ok(mods)
 }
 
+/// Mods with what their jars declare about themselves, for the mod list
+#[tauri::command]
+pub async fn list_mods(instance_name: String) -> Result<Vec<InstalledMod>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let files = get_installed_mods(safe_name.clone()).await?;
+    let mods_dir = get_instance_dir(&safe_name).join("mods");
+
+    tauri::async_runtime::spawn_blocking(move || {
+        files
+            .into_iter()
+            .map(|file| InstalledMod {
+                enabled: !file.filename.ends_with(".disabled"),
+                metadata: ModMetadataReader::read(&mods_dir.join(&file.filename)),
+                filename: file.filename,
+                size: file.size,
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to read mod metadata: {}", e))
+}
+
 #[tauri::command]
 pub async fn delete_mod(instance_name: String, filename: String) -> Result<String, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -81,8 +133,12 @@ pub async fn delete_mod(instance_name: String, filename: String) -> Result<Strin
         return Err(format!("Mod file '{}' not found or is not a file", safe_filename));
     }
     
-    std::fs::remove_file(&canonical_mod_path)
+    std::fs::remove_file(long_path(&canonical_mod_path))
         .map_err(|e| format!("Failed to delete mod: {}", e))?;
+
+    if let Err(e) = ModLockfile::forget(&instance_dir, &format!("mods/{}", safe_filename)) {
+        println!("⚠ Failed to update mod lockfile: {}", e);
+    }
     
     Ok(format!("Successfully deleted {}", safe_filename))
 }
@@ -95,7 +151,7 @@ pub fn open_mods_folder(instance_name: String) -> Result<String, String> {
     let mods_dir = instance_dir.join("mods");
     
     if !mods_dir.exists() {
-        std::fs::create_dir_all(&mods_dir)
+        std::fs::create_dir_all(long_path(&mods_dir))
             .map_err(|e| format!("Failed to create mods directory: {}", e))?;
     }
     
@@ -140,13 +196,270 @@ pub async fn toggle_mod(instance_name: String, filename: String, disable: bool)
         return Err("Invalid mod path (path traversal detected)".to_string());
     }
     
-    std::fs::rename(&old_path, &new_path)
+    std::fs::rename(long_path(&old_path), long_path(&new_path))
         .map_err(|e| format!("Failed to toggle mod: {}", e))?;
+
+    let old_filename = old_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if let Err(e) = ModLockfile::moved(
+        &instance_dir,
+        &format!("mods/{}", old_filename),
+        &format!("mods/{}", new_filename),
+    ) {
+        println!("⚠ Failed to update mod lockfile: {}", e);
+    }
     
     let status = if disable { "disabled" } else { "enabled" };
     Ok(format!("Successfully {} mod", status))
 }
 
+/// Config files belonging to a mod, for an "edit config" button on the mod row
+#[tauri::command]
+pub async fn get_mod_config_files(instance_name: String, mod_id: String) -> Result<Vec<ModConfigFile>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    if mod_id.trim().is_empty() {
+        return Err("Mod id cannot be empty".to_string());
+    }
+
+    ModConfigLocator::find(&safe_name, &mod_id)
+        .map_err(|e| format!("Failed to find config files: {}", e))
+}
+
+/// Every text config under the instance's config/ and defaultconfigs/ folders
+#[tauri::command]
+pub async fn list_config_files(instance_name: String) -> Result<Vec<ModConfigFile>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    ConfigFileEditor::list(&safe_name)
+        .map_err(|e| format!("Failed to list config files: {}", e))
+}
+
+/// `path` is relative to the instance folder, e.g. `config/sodium-options.json`
+#[tauri::command]
+pub async fn read_config_file(instance_name: String, path: String) -> Result<ConfigFileContent, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    ConfigFileEditor::read(&safe_name, &path)
+        .map_err(|e| format!("Failed to read config file: {}", e))
+}
+
+/// Save a config file. Pass the `modified` value from `read_config_file` as `expected_modified`
+/// to avoid overwriting changes the game made in the meantime.
+#[tauri::command]
+pub async fn write_config_file(
+    instance_name: String,
+    path: String,
+    content: String,
+    expected_modified: Option<u64>,
+) -> Result<ConfigFileContent, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    ConfigFileEditor::write(&safe_name, &path, &content, expected_modified)
+        .map_err(|e| format!("Failed to save config file: {}", e))
+}
+
+/// Description, gallery and recent changelog of a Modrinth project, cached for a day
+#[tauri::command]
+pub async fn get_cached_mod_details(project_id: String, refresh: Option<bool>) -> Result<ModDetails, String> {
+    if project_id.is_empty() || !project_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("Invalid project id".to_string());
+    }
+
+    ModDetailsCache::get(&project_id, refresh.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to get mod details: {}", e))
+}
+
+/// Same as `get_cached_mod_details` for an installed jar, `None` when the file isn't from Modrinth
+#[tauri::command]
+pub async fn get_installed_mod_details(
+    instance_name: String,
+    filename: String,
+    refresh: Option<bool>,
+) -> Result<Option<ModDetails>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let safe_filename = sanitize_filename(&filename)?;
+
+    let mod_path = get_instance_dir(&safe_name).join("mods").join(&safe_filename);
+    if !mod_path.is_file() {
+        return Err(format!("Mod file '{}' not found", safe_filename));
+    }
+
+    ModDetailsCache::get_for_file(&mod_path, refresh.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to get mod details: {}", e))
+}
+
+/// Render the installed mods as markdown, CSV or HTML. Also written to `destination_path` when given.
+#[tauri::command]
+pub async fn export_mod_list(
+    instance_name: String,
+    format: ModListFormat,
+    destination_path: Option<String>,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let entries = ModListExporter::collect(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to read mods: {}", e))?;
+    let document = ModListExporter::render(&safe_name, &entries, format);
+
+    if let Some(path) = destination_path {
+        let mut path = std::path::PathBuf::from(path);
+        if path.extension().is_none() {
+            path.set_extension(format.extension());
+        }
+        std::fs::write(long_path(&path), &document)
+            .map_err(|e| format!("Failed to write mod list: {}", e))?;
+        println!("✓ Exported mod list of '{}' to {}", safe_name, path.display());
+    }
+
+    Ok(document)
+}
+
+#[tauri::command]
+pub async fn get_instance_credits(instance_name: String) -> Result<CreditsReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    CreditsCollector::collect(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to collect credits: {}", e))
+}
+
+/// Write the credits report as markdown, e.g. a CREDITS.md to publish alongside a pack
+#[tauri::command]
+pub async fn export_instance_credits(instance_name: String, destination_path: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let report = CreditsCollector::collect(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to collect credits: {}", e))?;
+
+    let mut path = std::path::PathBuf::from(destination_path);
+    if path.extension().is_none() {
+        path.set_extension("md");
+    }
+    std::fs::write(long_path(&path), CreditsCollector::render_markdown(&report))
+        .map_err(|e| format!("Failed to write credits: {}", e))?;
+
+    println!("✓ Exported credits of '{}' to {}", safe_name, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ===== JAR MODS =====
+
+#[tauri::command]
+pub async fn get_jar_mods(instance_name: String) -> Result<Vec<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    Ok(instance.jar_mods)
+}
+
+#[tauri::command]
+pub async fn add_jar_mod(instance_name: String, file_path: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    let file_name = JarModManager::add(&mut instance, std::path::Path::new(&file_path))
+        .map_err(|e| format!("Failed to add jar mod: {}", e))?;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok(format!("Added jar mod {}", file_name))
+}
+
+#[tauri::command]
+pub async fn remove_jar_mod(instance_name: String, filename: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    JarModManager::remove(&mut instance, &filename)
+        .map_err(|e| format!("Failed to remove jar mod: {}", e))?;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok(format!("Removed jar mod {}", filename))
+}
+
+#[tauri::command]
+pub async fn reorder_jar_mods(instance_name: String, order: Vec<String>) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    JarModManager::reorder(&mut instance, order)
+        .map_err(|e| format!("Failed to reorder jar mods: {}", e))?;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok("Jar mod order updated".to_string())
+}
+
+/// Throw away the patched jar so it is rebuilt from scratch on next launch
+#[tauri::command]
+pub async fn rebuild_jar_mods(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    JarModManager::invalidate(&safe_name)
+        .map_err(|e| format!("Failed to reset patched jar: {}", e))?;
+
+    Ok("Patched jar will be rebuilt on next launch".to_string())
+}
+
+/// Go back to the vanilla jar. Jar mod files stay in the jarmods folder.
+#[tauri::command]
+pub async fn revert_jar_mods(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    instance.jar_mods.clear();
+    JarModManager::invalidate(&safe_name)
+        .map_err(|e| format!("Failed to remove patched jar: {}", e))?;
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))?;
+
+    Ok("Reverted to the vanilla client jar".to_string())
+}
+
+// ===== PERFORMANCE PRESET =====
+
+/// Install the curated performance mods for the instance's loader and version
+#[tauri::command]
+pub async fn optimize_instance(instance_name: String) -> Result<PresetInstallReport, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    PerformancePreset::install(&instance)
+        .await
+        .map_err(|e| format!("Failed to install performance mods: {}", e))
+}
+
+/// Remove only the mods `optimize_instance` added
+#[tauri::command]
+pub async fn remove_performance_preset(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let removed = PerformancePreset::remove(&safe_name)
+        .map_err(|e| format!("Failed to remove performance mods: {}", e))?;
+
+    Ok(format!("Removed {} performance mod(s)", removed))
+}
+
 // ===== MODRINTH API =====
 
 #[tauri::command]
@@ -262,7 +575,7 @@ pub async fn download_mod(
     let mods_dir = instance_dir.join("mods");
 
     if !mods_dir.exists() {
-        std::fs::create_dir_all(&mods_dir)
+        std::fs::create_dir_all(long_path(&mods_dir))
             .map_err(|e| format!("Failed to create mods directory: {}", e))?;
     }
 
@@ -279,4 +592,473 @@ pub async fn download_mod(
         .map_err(|e| format!("Failed to download mod: {}", e))?;
 
     Ok(format!("Successfully downloaded {}", safe_filename))
-}
\ No newline at end of file
+}
+
+/// Install a Modrinth project and its required dependencies, recording each file in the lockfile.
+/// `install_dependencies: false` installs the project alone and reports what it still needs.
+#[tauri::command]
+pub async fn install_modrinth_mod(
+    instance_name: String,
+    project_id: String,
+    version_id: Option<String>,
+    install_dependencies: Option<bool>,
+) -> Result<ModrinthInstallResult, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    for id in std::iter::once(&project_id).chain(version_id.as_ref()) {
+        if id.is_empty() || id.len() > 100 || !id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            return Err("Invalid project or version ID format".to_string());
+        }
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ModrinthInstaller::install(&instance, &project_id, version_id.as_deref(), install_dependencies.unwrap_or(true))
+        .await
+        .map_err(|e| format!("Failed to install mod: {}", e))
+}
+
+// ===== MOD UPDATES =====
+
+#[tauri::command]
+pub async fn check_mod_updates(instance_name: String) -> Result<Vec<ModUpdate>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ModUpdater::check(&instance)
+        .await
+        .map_err(|e| format!("Failed to check for mod updates: {}", e))
+}
+
+/// Update the given projects, backing up the jars they replace into mod-backups/
+#[tauri::command]
+pub async fn update_mods(instance_name: String, project_ids: Vec<String>) -> Result<ModUpdateResult, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Instance '{}' is running", safe_name));
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ModUpdater::apply(&instance, &project_ids)
+        .await
+        .map_err(|e| format!("Failed to update mods: {}", e))
+}
+
+/// Ignore a mod's updates or pick its release channel. None goes back to the default.
+#[tauri::command]
+pub async fn set_mod_update_preference(
+    instance_name: String,
+    project_id: String,
+    preference: Option<ModUpdatePreference>,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    match preference.filter(|p| p.ignore || p.channel.is_some()) {
+        Some(preference) => {
+            instance.mod_update_preferences.insert(project_id, preference);
+        }
+        None => {
+            instance.mod_update_preferences.remove(&project_id);
+        }
+    }
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))
+}
+
+/// Rebuild the instance's mods, resource packs and shaders from an atomic.lock. Without
+/// `lockfile_path` the instance's own lockfile is used, e.g. to restore it after files were
+/// deleted. `prune` moves files the lockfile doesn't list into mod-backups/.
+#[tauri::command]
+pub async fn sync_from_lockfile(
+    instance_name: String,
+    lockfile_path: Option<String>,
+    prune: Option<bool>,
+) -> Result<LockfileSyncResult, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Instance '{}' is running", safe_name));
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    let lockfile = match lockfile_path {
+        Some(path) => {
+            let content = std::fs::read_to_string(long_path(std::path::Path::new(&path)))
+                .map_err(|e| format!("Failed to read lockfile: {}", e))?;
+            serde_json::from_str::<ModLockfile>(&content)
+                .map_err(|e| format!("Invalid lockfile: {}", e))?
+        }
+        None => ModLockfile::load(&get_instance_dir(&safe_name))
+            .map_err(|e| format!("Failed to load lockfile: {}", e))?,
+    };
+
+    LockfileSync::sync(&instance, lockfile, prune.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to sync from lockfile: {}", e))
+}
+
+/// Turn automatic mod updates on with `policy`, or off with None
+#[tauri::command]
+pub async fn set_auto_update_policy(instance_name: String, policy: Option<AutoUpdatePolicy>) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    // Keep the schedule's clock when only the selection changes
+    let last_run = instance.auto_update.as_ref().and_then(|p| p.last_run);
+    instance.auto_update = policy.map(|mut p| {
+        p.last_run = p.last_run.or(last_run);
+        p
+    });
+
+    InstanceManager::save(&instance)
+        .map_err(|e| format!("Failed to save instance: {}", e))
+}
+
+/// Apply the instance's auto-update policy now instead of waiting for its schedule
+#[tauri::command]
+pub async fn run_mod_auto_update(instance_name: String) -> Result<AutoUpdateSummary, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Instance '{}' is running", safe_name));
+    }
+
+    ModAutoUpdater::run(&safe_name)
+        .await
+        .map_err(|e| format!("Failed to auto-update mods: {}", e))
+}
+
+/// Duplicate ids, missing dependencies and loader or game version mismatches among the
+/// instance's enabled mods, read from their jar metadata
+#[tauri::command]
+pub async fn analyze_mod_conflicts(instance_name: String) -> Result<Vec<ModConflict>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    tokio::task::spawn_blocking(move || {
+        ModConflictAnalyzer::analyze(&instance).map_err(|e| format!("Failed to analyze mods: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to analyze mods: {}", e))?
+}
+
+/// Copy jars dropped onto the window into mods/, with one result per file. Jars for another
+/// loader are refused, and so are ones for another Minecraft version unless `force` is set.
+#[tauri::command]
+pub async fn add_local_mods(
+    instance_name: String,
+    paths: Vec<String>,
+    force: Option<bool>,
+) -> Result<Vec<LocalModResult>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    tokio::task::spawn_blocking(move || {
+        LocalModImporter::add(&instance, &paths, force.unwrap_or(false))
+            .map_err(|e| format!("Failed to add mods: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to add mods: {}", e))?
+}
+
+// ===== CURSEFORGE API =====
+
+#[tauri::command]
+pub async fn search_curseforge_mods(
+    query: String,
+    game_version: Option<String>,
+    loader: Option<String>,
+    class_id: Option<u32>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<CurseForgeSearchResult, String> {
+    if query.len() > 200 {
+        return Err("Search query too long (max 200 characters)".to_string());
+    }
+
+    if let Some(ref version) = game_version {
+        if !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+            return Err("Invalid game version".to_string());
+        }
+    }
+
+    let client = CurseForgeInstaller::client()?;
+    client
+        .search_mods(
+            &query,
+            class_id.unwrap_or(CLASS_MODS),
+            game_version.as_deref(),
+            loader.as_deref().and_then(mod_loader_type),
+            offset.unwrap_or(0),
+            limit.unwrap_or(20).min(50),
+        )
+        .await
+        .map_err(|e| format!("Failed to search CurseForge: {}", e))
+}
+
+/// Install a CurseForge project and its required dependencies. Files the author only allows on
+/// curseforge.com come back as `manual_downloads` for `watch_curseforge_downloads`.
+/// `install_dependencies: false` installs the project alone.
+#[tauri::command]
+pub async fn install_curseforge_mod(
+    instance_name: String,
+    mod_id: u32,
+    file_id: Option<u32>,
+    install_dependencies: Option<bool>,
+) -> Result<CurseForgeInstallResult, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    CurseForgeInstaller::install(&instance, mod_id, file_id, install_dependencies.unwrap_or(true))
+        .await
+        .map_err(|e| format!("Failed to install mod: {}", e))
+}
+
+/// Watch the downloads folder for files the player downloads by hand, moving each into the
+/// instance as it shows up. Emits `curseforge-download-found` per file and gives up after 30 minutes.
+/// Without `downloads`, resumes watching for the files the instance is still waiting for.
+#[tauri::command]
+pub async fn watch_curseforge_downloads(
+    instance_name: String,
+    downloads: Option<Vec<ManualDownload>>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let downloads = downloads.unwrap_or_default();
+    for download in &downloads {
+        crate::commands::validation::sanitize_pack_filename(&download.file_name)?;
+        if !["mods", "resourcepacks", "shaderpacks"].contains(&download.folder.as_str()) {
+            return Err(format!("Invalid folder '{}'", download.folder));
+        }
+    }
+
+    CurseForgeInstaller::watch_manual_downloads(app_handle, &safe_name, downloads)
+        .map_err(|e| format!("Failed to watch downloads: {}", e))
+}
+
+/// Files the instance still waits for the player to download from curseforge.com
+#[tauri::command]
+pub async fn get_pending_curseforge_downloads(instance_name: String) -> Result<Vec<ManualDownload>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    Ok(CurseForgeInstaller::pending_manual_downloads(&safe_name))
+}
+
+#[tauri::command]
+pub async fn open_curseforge_download(instance_name: String, file_id: u32) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let download = CurseForgeInstaller::pending_manual_downloads(&safe_name)
+        .into_iter()
+        .find(|d| d.file_id == file_id)
+        .ok_or_else(|| format!("'{}' isn't waiting for file {}", safe_name, file_id))?;
+
+    CurseForgeInstaller::open_download_page(&download)
+        .map_err(|e| format!("Failed to open download page: {}", e))
+}
+
+// ===== RESOURCE PACKS =====
+
+/// Packs in resourcepacks/, enabled ones first in priority order
+#[tauri::command]
+pub async fn list_resource_packs(instance_name: String) -> Result<Vec<ResourcePack>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ResourcePackManager::list(&instance)
+        .map_err(|e| format!("Failed to list resource packs: {}", e))
+}
+
+/// Install a resource pack from a local .zip or from Modrinth, returning its file name
+#[tauri::command]
+pub async fn install_resource_pack(
+    instance_name: String,
+    file_path: Option<String>,
+    project_id: Option<String>,
+    version_id: Option<String>,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    match (file_path, project_id) {
+        (Some(path), None) => ResourcePackManager::install_file(&instance, std::path::Path::new(&path))
+            .map_err(|e| format!("Failed to install resource pack: {}", e)),
+        (None, Some(project_id)) => ResourcePackManager::install_modrinth(&instance, &project_id, version_id.as_deref())
+            .await
+            .map_err(|e| format!("Failed to install resource pack: {}", e)),
+        _ => Err("Provide either a file or a Modrinth project".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn toggle_resource_pack(instance_name: String, name: String, enabled: bool) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    // The game writes options.txt when it closes and would undo the change
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Instance '{}' is running", safe_name));
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ResourcePackManager::set_enabled(&instance, &name, enabled)
+        .map_err(|e| format!("Failed to update resource packs: {}", e))
+}
+
+/// `order` lists the enabled packs, highest priority first
+#[tauri::command]
+pub async fn reorder_resource_packs(instance_name: String, order: Vec<String>) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Instance '{}' is running", safe_name));
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ResourcePackManager::reorder(&instance, &order)
+        .map_err(|e| format!("Failed to reorder resource packs: {}", e))
+}
+
+// ===== PACK LIBRARY =====
+
+#[tauri::command]
+pub async fn list_library_packs(kind: PackKind) -> Result<Vec<LibraryPack>, String> {
+    PackLibrary::list(kind).map_err(|e| format!("Failed to list library: {}", e))
+}
+
+/// Copy a .zip or pack folder into the library, returning its name there
+#[tauri::command]
+pub async fn import_library_pack(kind: PackKind, file_path: String) -> Result<String, String> {
+    PackLibrary::import(kind, std::path::Path::new(&file_path))
+        .map_err(|e| format!("Failed to add pack to library: {}", e))
+}
+
+/// Move one of the instance's own packs into the library, keeping it enabled there
+#[tauri::command]
+pub async fn move_pack_to_library(instance_name: String, kind: PackKind, name: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    PackLibrary::adopt_from_instance(&mut instance, kind, &name)
+        .map_err(|e| format!("Failed to move pack to library: {}", e))
+}
+
+/// `names` replaces the library packs enabled in the instance
+#[tauri::command]
+pub async fn set_instance_library_packs(instance_name: String, kind: PackKind, names: Vec<String>) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&safe_name) {
+        return Err(format!("Instance '{}' is running", safe_name));
+    }
+
+    let mut instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    PackLibrary::set_enabled(&mut instance, kind, &names)
+        .map_err(|e| format!("Failed to update library packs: {}", e))
+}
+
+/// Also removes the pack from every instance it is enabled in
+#[tauri::command]
+pub async fn remove_library_pack(kind: PackKind, name: String) -> Result<(), String> {
+    PackLibrary::remove(kind, &name).map_err(|e| format!("Failed to remove pack from library: {}", e))
+}
+
+// ===== MODRINTH ACCOUNT =====
+
+/// Link a Modrinth personal access token, it needs the user, notification and collection read scopes
+#[tauri::command]
+pub async fn link_modrinth_account(token: String) -> Result<ModrinthUser, String> {
+    ModrinthFollows::link(&token)
+        .await
+        .map_err(|e| format!("Failed to link Modrinth account: {}", e))
+}
+
+#[tauri::command]
+pub async fn unlink_modrinth_account() -> Result<(), String> {
+    ModrinthFollows::unlink().map_err(|e| format!("Failed to unlink Modrinth account: {}", e))
+}
+
+/// None when no account is linked
+#[tauri::command]
+pub async fn get_modrinth_account() -> Result<Option<ModrinthUser>, String> {
+    ModrinthFollows::account()
+        .await
+        .map_err(|e| format!("Failed to get Modrinth account: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_followed_projects() -> Result<Vec<ModrinthProjectDetails>, String> {
+    ModrinthFollows::followed_projects()
+        .await
+        .map_err(|e| format!("Failed to get followed projects: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_modrinth_collections() -> Result<Vec<ModrinthCollection>, String> {
+    ModrinthFollows::collections()
+        .await
+        .map_err(|e| format!("Failed to get collections: {}", e))
+}
+
+/// New versions of followed projects the user hasn't dismissed yet
+#[tauri::command]
+pub async fn get_followed_updates() -> Result<Vec<FollowedUpdate>, String> {
+    ModrinthFollows::updates()
+        .await
+        .map_err(|e| format!("Failed to get followed updates: {}", e))
+}
+
+#[tauri::command]
+pub async fn dismiss_followed_updates(notification_ids: Vec<String>) -> Result<(), String> {
+    ModrinthFollows::dismiss_updates(&notification_ids)
+        .await
+        .map_err(|e| format!("Failed to dismiss updates: {}", e))
+}
+
+#[tauri::command]
+pub async fn install_modrinth_collection(
+    instance_name: String,
+    collection_id: String,
+) -> Result<CollectionInstallResult, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    if !collection_id.chars().all(|c| c.is_alphanumeric()) {
+        return Err("Invalid collection id".to_string());
+    }
+
+    let instance = InstanceManager::load(&safe_name)
+        .map_err(|e| format!("Failed to load instance: {}", e))?;
+
+    ModrinthFollows::install_collection(&instance, &collection_id)
+        .await
+        .map_err(|e| format!("Failed to install collection: {}", e))
+}
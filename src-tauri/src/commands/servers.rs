@@ -1,7 +1,8 @@
 use crate::commands::validation::{sanitize_server_name, validate_server_address};
 use crate::services::accounts::AccountManager;
 use crate::services::instance::InstanceManager;
-use crate::utils::{get_launcher_dir, get_instance_dir};
+use crate::utils::{get_launcher_dir, get_instance_dir, long_path};
+use crate::utils::servers_dat::read_servers;
 use serde::{Deserialize, Serialize};
 use tauri::Emitter;
 use std::io::Write;
@@ -18,6 +19,9 @@ pub struct ServerInfo {
     pub motd: Option<String>,
     pub favicon: Option<String>,
     pub last_checked: Option<i64>,
+    /// Instance the server belongs to (e.g. advertised by its modpack), joined instead of the last played one
+    #[serde(default)]
+    pub instance: Option<String>,
 }
 
 #[tauri::command]
@@ -28,7 +32,7 @@ pub async fn get_servers() -> Result<Vec<ServerInfo>, String> {
         return Ok(Vec::new());
     }
     
-    let content = std::fs::read_to_string(&servers_file)
+    let content = std::fs::read_to_string(long_path(&servers_file))
         .map_err(|e| format!("Failed to read servers file: {}", e))?;
     
     let servers: Vec<ServerInfo> = serde_json::from_str(&content)
@@ -71,6 +75,7 @@ pub async fn add_server(
         motd: None,
         favicon: None,
         last_checked: None,
+        instance: None,
     };
     
     servers.push(new_server);
@@ -80,7 +85,7 @@ pub async fn add_server(
     let json = serde_json::to_string_pretty(&servers)
         .map_err(|e| format!("Failed to serialize servers: {}", e))?;
     
-    std::fs::write(&servers_file, json)
+    std::fs::write(long_path(&servers_file), json)
         .map_err(|e| format!("Failed to write servers file: {}", e))?;
     
     Ok(format!("Successfully added server '{}'", safe_name))
@@ -104,7 +109,7 @@ pub async fn delete_server(server_name: String) -> Result<String, String> {
     let json = serde_json::to_string_pretty(&servers)
         .map_err(|e| format!("Failed to serialize servers: {}", e))?;
     
-    std::fs::write(&servers_file, json)
+    std::fs::write(long_path(&servers_file), json)
         .map_err(|e| format!("Failed to write servers file: {}", e))?;
     
     Ok(format!("Successfully deleted server '{}'", safe_name))
@@ -138,7 +143,7 @@ pub async fn update_server_status(
     let json = serde_json::to_string_pretty(&servers)
         .map_err(|e| format!("Failed to serialize servers: {}", e))?;
     
-    std::fs::write(&servers_file, json)
+    std::fs::write(long_path(&servers_file), json)
         .map_err(|e| format!("Failed to write servers file: {}", e))?;
     
     Ok(format!("Successfully updated server '{}'", safe_name))
@@ -169,13 +174,21 @@ pub async fn launch_server(
 
     println!("Using account: {}", active_account.username);
 
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
 
     println!("✓ Token validated/refreshed");
 
-    // Try to find the most recently played instance
+    // Servers that came with a modpack are joined with that pack's instance
+    let bound_instance = get_servers()
+        .await?
+        .into_iter()
+        .find(|s| s.name == safe_name && s.address == server_address && s.port == server_port)
+        .and_then(|s| s.instance)
+        .filter(|name| get_instance_dir(name).join("instance.json").exists());
+
+    // Otherwise try to find the most recently played instance
     let instances = InstanceManager::get_all()
         .map_err(|e| format!("Failed to get instances: {}", e))?;
     
@@ -184,7 +197,10 @@ pub async fn launch_server(
         .filter(|inst| inst.last_played.is_some())
         .max_by_key(|inst| inst.last_played.as_ref());
 
-    let instance_name = if let Some(recent_inst) = most_recent_instance {
+    let instance_name = if let Some(bound) = bound_instance {
+        println!("Using the server's instance: {}", bound);
+        bound
+    } else if let Some(recent_inst) = most_recent_instance {
         println!("Using most recently played instance: {}", recent_inst.name);
         recent_inst.name.clone()
     } else {
@@ -197,8 +213,11 @@ pub async fn launch_server(
         return Err(format!("Instance '{}' not found", instance_name));
     }
 
-    // Add server to servers.dat in the instance directory
-    add_server_to_instance(&instance_dir, &safe_name, &server_address, server_port)?;
+    // Add server to servers.dat in the instance directory, frozen instances are only joined
+    let frozen = InstanceManager::load(&instance_name).map(|i| i.frozen).unwrap_or(false);
+    if !frozen {
+        add_server_to_instance(&instance_dir, &safe_name, &server_address, server_port)?;
+    }
 
     // Format server address for command line argument
     let server_arg = if server_port == 25565 {
@@ -231,6 +250,105 @@ pub async fn launch_server(
     ))
 }
 
+/// Add the servers a modpack ships in its servers.dat to the launcher's list, bound to the instance.
+/// Returns how many were added; servers already in the list are left alone.
+pub(crate) async fn import_pack_servers(instance_name: &str) -> Result<usize, String> {
+    let servers_dat = get_instance_dir(instance_name).join("servers.dat");
+    if !servers_dat.exists() {
+        return Ok(0);
+    }
+
+    let entries = read_servers(&servers_dat)
+        .map_err(|e| format!("Failed to read servers.dat: {}", e))?;
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let mut servers = get_servers().await?;
+    let mut added = 0;
+
+    for entry in entries {
+        if validate_server_address(&entry.address).is_err() || entry.port == 0 {
+            println!("⚠ Skipping pack server with invalid address: {}", entry.address);
+            continue;
+        }
+
+        if servers.iter().any(|s| {
+            s.address == entry.address && s.port == entry.port && s.instance.as_deref() == Some(instance_name)
+        }) {
+            continue;
+        }
+
+        let name_taken = |name: &str| servers.iter().any(|s| s.name.to_lowercase() == name.to_lowercase());
+        let Ok(mut name) = sanitize_server_name(entry.name.trim()) else {
+            continue;
+        };
+        if name_taken(&name) {
+            name = format!("{} ({})", name, instance_name);
+        }
+        if name_taken(&name) || sanitize_server_name(&name).is_err() {
+            continue;
+        }
+
+        println!("✓ Added pack server '{}' ({}:{})", name, entry.address, entry.port);
+        servers.push(ServerInfo {
+            name,
+            address: entry.address,
+            port: entry.port,
+            status: "unknown".to_string(),
+            players_online: None,
+            players_max: None,
+            version: None,
+            motd: None,
+            favicon: None,
+            last_checked: None,
+            instance: Some(instance_name.to_string()),
+        });
+        added += 1;
+    }
+
+    if added > 0 {
+        let servers_file = get_launcher_dir().join("servers.json");
+        let json = serde_json::to_string_pretty(&servers)
+            .map_err(|e| format!("Failed to serialize servers: {}", e))?;
+
+        std::fs::write(long_path(&servers_file), json)
+            .map_err(|e| format!("Failed to write servers file: {}", e))?;
+    }
+
+    Ok(added)
+}
+
+/// Keep servers bound to an instance in step with it: `Some` follows a rename, `None` unbinds
+/// them once the instance is deleted so they fall back to the last played one.
+pub(crate) fn rebind_instance_servers(instance_name: &str, new_name: Option<&str>) -> Result<(), String> {
+    let servers_file = get_launcher_dir().join("servers.json");
+    if !servers_file.exists() {
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(long_path(&servers_file))
+        .map_err(|e| format!("Failed to read servers file: {}", e))?;
+    let mut servers: Vec<ServerInfo> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse servers file: {}", e))?;
+
+    let mut changed = false;
+    for server in servers.iter_mut().filter(|s| s.instance.as_deref() == Some(instance_name)) {
+        server.instance = new_name.map(str::to_string);
+        changed = true;
+    }
+
+    if changed {
+        let json = serde_json::to_string_pretty(&servers)
+            .map_err(|e| format!("Failed to serialize servers: {}", e))?;
+
+        std::fs::write(long_path(&servers_file), json)
+            .map_err(|e| format!("Failed to write servers file: {}", e))?;
+    }
+
+    Ok(())
+}
+
 fn add_server_to_instance(
     instance_dir: &std::path::Path,
     server_name: &str,
@@ -257,7 +375,7 @@ fn add_server_to_instance(
     // Create NBT structure for servers.dat
     let nbt_data = create_servers_nbt(server_name, server_address, server_port);
     
-    let mut file = std::fs::File::create(&servers_dat)
+    let mut file = std::fs::File::create(long_path(&servers_dat))
         .map_err(|e| format!("Failed to create servers.dat: {}", e))?;
     
     file.write_all(&nbt_data)
@@ -1,9 +1,13 @@
 use crate::commands::validation::{
-    sanitize_instance_name, validate_java_path, validate_memory_allocation,
+    sanitize_instance_name, validate_hook_commands, validate_java_path, validate_jvm_arguments,
+    validate_memory_allocation,
 };
 use crate::models::{Instance, LauncherSettings};
+use crate::services::java::{JavaInstallation, JavaManager, JavaRecommendation};
+use crate::services::mod_cache::{ModCache, ModCachePruneResult, ModCacheStats};
 use crate::services::settings::SettingsManager;
-use crate::utils::get_instance_dir;
+use crate::services::trust_store::TrustStoreManager;
+use crate::utils::{get_instance_dir, long_path};
 use std::path::PathBuf;
 
 #[tauri::command]
@@ -19,11 +23,33 @@ pub async fn save_settings(settings: LauncherSettings) -> Result<String, String>
     }
     
     validate_memory_allocation(settings.memory_mb as u64)?;
+    validate_hook_commands(&settings)?;
+    validate_jvm_arguments(&settings.jvm_args)?;
+
+    if let Some(ref media_dir) = settings.media_directory {
+        if !media_dir.trim().is_empty() && !PathBuf::from(media_dir).is_absolute() {
+            return Err("Media directory must be an absolute path".to_string());
+        }
+    }
+    
+    if let Some(ref language) = settings.default_language {
+        crate::services::language::LanguageManager::validate(language)?;
+    }
+
+    if settings.max_concurrent_instances == Some(0) {
+        return Err("At least one instance must be allowed to run".to_string());
+    }
+    if settings.memory_budget_mb.is_some_and(|budget| budget < settings.memory_mb) {
+        return Err("Memory budget must be at least the default memory allocation".to_string());
+    }
+
+    let certificates = TrustStoreManager::load_certificates(&settings.ca_certificates)?;
     
     SettingsManager::save(&settings)
         .map_err(|e| format!("Failed to save settings: {}", e))?;
+    crate::utils::http::set_extra_certificates(certificates);
     
-    Ok("Settings saved successfully".to_string())
+    Ok(with_java_memory_warning("Settings saved successfully", &settings))
 }
 
 #[tauri::command]
@@ -37,7 +63,7 @@ pub async fn get_instance_settings(instance_name: String) -> Result<Option<Launc
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
     
-    let content = std::fs::read_to_string(&instance_json)
+    let content = std::fs::read_to_string(long_path(&instance_json))
         .map_err(|e| format!("Failed to read instance data: {}", e))?;
     
     let instance: Instance = serde_json::from_str(&content)
@@ -58,6 +84,8 @@ pub async fn save_instance_settings(
             validate_java_path(java_path)?;
         }
         validate_memory_allocation(s.memory_mb as u64)?;
+        validate_hook_commands(s)?;
+        validate_jvm_arguments(&s.jvm_args)?;
     }
     
     let instance_dir = get_instance_dir(&safe_name);
@@ -67,160 +95,54 @@ pub async fn save_instance_settings(
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
     
-    let content = std::fs::read_to_string(&instance_json)
+    let content = std::fs::read_to_string(long_path(&instance_json))
         .map_err(|e| format!("Failed to read instance data: {}", e))?;
     
     let mut instance: Instance = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse instance data: {}", e))?;
     
+    let message = match settings.as_ref() {
+        Some(s) => with_java_memory_warning("Instance settings saved successfully", s),
+        None => "Instance settings saved successfully".to_string(),
+    };
     instance.settings_override = settings;
     
     let updated_json = serde_json::to_string_pretty(&instance)
         .map_err(|e| format!("Failed to serialize instance data: {}", e))?;
     
-    std::fs::write(&instance_json, updated_json)
+    std::fs::write(long_path(&instance_json), updated_json)
         .map_err(|e| format!("Failed to write instance data: {}", e))?;
     
-    Ok("Instance settings saved successfully".to_string())
+    Ok(message)
 }
 
-#[tauri::command]
-pub async fn detect_java_installations() -> Result<Vec<String>, String> {
-    let mut java_paths = Vec::new();
-    
-    #[cfg(target_os = "windows")]
-    {
-        let common_paths = vec![
-            "C:\\Program Files\\Java",
-            "C:\\Program Files (x86)\\Java",
-            "C:\\Program Files\\Eclipse Adoptium",
-            "C:\\Program Files\\Microsoft",
-            "C:\\Program Files\\Zulu",
-            "C:\\Program Files\\Amazon Corretto",
-        ];
-        
-        for base_path in common_paths {
-            if let Ok(entries) = std::fs::read_dir(base_path) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let javaw_path = path.join("bin").join("javaw.exe");
-                        if javaw_path.exists() {
-                            if let Some(path_str) = javaw_path.to_str() {
-                                if validate_java_path(path_str).is_ok() {
-                                    java_paths.push(path_str.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        if let Ok(path_var) = std::env::var("PATH") {
-            for path in path_var.split(';') {
-                let javaw_path = PathBuf::from(path).join("javaw.exe");
-                if javaw_path.exists() {
-                    if let Some(path_str) = javaw_path.to_str() {
-                        if validate_java_path(path_str).is_ok() && !java_paths.contains(&path_str.to_string()) {
-                            java_paths.push(path_str.to_string());
-                        }
-                    }
-                }
-            }
-        }
-        
-        if let Ok(java_home) = std::env::var("JAVA_HOME") {
-            let javaw_path = PathBuf::from(java_home).join("bin").join("javaw.exe");
-            if javaw_path.exists() {
-                if let Some(path_str) = javaw_path.to_str() {
-                    if validate_java_path(path_str).is_ok() && !java_paths.contains(&path_str.to_string()) {
-                        java_paths.push(path_str.to_string());
-                    }
-                }
-            }
-        }
-    }
-    
-    #[cfg(target_os = "macos")]
-    {
-        let common_paths = vec![
-            "/Library/Java/JavaVirtualMachines",
-            "/System/Library/Java/JavaVirtualMachines",
-        ];
-        
-        for base_path in common_paths {
-            if let Ok(entries) = std::fs::read_dir(base_path) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let java_path = path.join("Contents").join("Home").join("bin").join("java");
-                        if java_path.exists() {
-                            if let Some(path_str) = java_path.to_str() {
-                                if validate_java_path(path_str).is_ok() {
-                                    java_paths.push(path_str.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        if let Ok(java_home) = std::env::var("JAVA_HOME") {
-            let java_path = PathBuf::from(java_home).join("bin").join("java");
-            if java_path.exists() {
-                if let Some(path_str) = java_path.to_str() {
-                    if validate_java_path(path_str).is_ok() && !java_paths.contains(&path_str.to_string()) {
-                        java_paths.push(path_str.to_string());
-                    }
-                }
-            }
-        }
-    }
-    
-    #[cfg(target_os = "linux")]
-    {
-        let common_paths = vec![
-            "/usr/lib/jvm",
-            "/usr/java",
-            "/opt/java",
-        ];
-        
-        for base_path in common_paths {
-            if let Ok(entries) = std::fs::read_dir(base_path) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let java_path = path.join("bin").join("java");
-                        if java_path.exists() {
-                            if let Some(path_str) = java_path.to_str() {
-                                if validate_java_path(path_str).is_ok() {
-                                    java_paths.push(path_str.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        if let Ok(java_home) = std::env::var("JAVA_HOME") {
-            let java_path = PathBuf::from(java_home).join("bin").join("java");
-            if java_path.exists() {
-                if let Some(path_str) = java_path.to_str() {
-                    if validate_java_path(path_str).is_ok() && !java_paths.contains(&path_str.to_string()) {
-                        java_paths.push(path_str.to_string());
-                    }
-                }
-            }
-        }
+/// Settings with a 32-bit Java and more memory than it can use are still saved, but the player
+/// is told why the game would crash
+fn with_java_memory_warning(message: &str, settings: &LauncherSettings) -> String {
+    let warning = settings
+        .java_path
+        .as_deref()
+        .and_then(JavaManager::probe)
+        .and_then(|java| JavaManager::memory_warning(&java, settings.memory_mb));
+
+    match warning {
+        Some(warning) => format!("{}. Warning: {}", message, warning),
+        None => message.to_string(),
     }
-    
-    java_paths.sort();
-    java_paths.dedup();
-    
-    Ok(java_paths)
+}
+
+#[tauri::command]
+pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>, String> {
+    tokio::task::spawn_blocking(JavaManager::detect)
+        .await
+        .map_err(|e| format!("Failed to detect Java installations: {}", e))
+}
+
+#[tauri::command]
+pub async fn recommend_java_for_version(mc_version: String) -> Result<JavaRecommendation, String> {
+    tokio::task::spawn_blocking(move || JavaManager::recommend(&mc_version))
+        .await
+        .map_err(|e| format!("Failed to recommend Java: {}", e))
 }
 
 use base64::{engine::general_purpose, Engine as _};
@@ -246,7 +168,7 @@ pub async fn set_sidebar_background(image_data: String) -> Result<String, String
         .map_err(|e| format!("Failed to decode image: {}", e))?;
     
     // Save to file
-    std::fs::write(&sidebar_bg_path, image_bytes)
+    std::fs::write(long_path(&sidebar_bg_path), image_bytes)
         .map_err(|e| format!("Failed to save image: {}", e))?;
     
     Ok("Background saved successfully".to_string())
@@ -261,7 +183,7 @@ pub async fn get_sidebar_background() -> Result<Option<String>, String> {
     }
     
     // Read image file
-    let image_bytes = std::fs::read(&sidebar_bg_path)
+    let image_bytes = std::fs::read(long_path(&sidebar_bg_path))
         .map_err(|e| format!("Failed to read image: {}", e))?;
     
     // Convert to base64
@@ -288,9 +210,52 @@ pub async fn remove_sidebar_background() -> Result<String, String> {
     let sidebar_bg_path = get_sidebar_bg_path();
     
     if sidebar_bg_path.exists() {
-        std::fs::remove_file(&sidebar_bg_path)
+        std::fs::remove_file(long_path(&sidebar_bg_path))
             .map_err(|e| format!("Failed to remove background: {}", e))?;
     }
     
     Ok("Background removed successfully".to_string())
-}
\ No newline at end of file
+}
+
+/// Sizes of everything the launcher stored, and removal of the chosen categories.
+/// An empty `categories` list only reports, nothing is removed.
+#[tauri::command]
+pub async fn uninstall_cleanup(
+    categories: Vec<String>,
+    use_trash: Option<bool>,
+) -> Result<crate::services::cleanup::CleanupReport, String> {
+    use crate::services::cleanup::{CleanupManager, CleanupReport};
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if categories.is_empty() {
+            return Ok(CleanupReport {
+                categories: CleanupManager::categories(),
+                removed: Vec::new(),
+                failed: Vec::new(),
+            });
+        }
+
+        CleanupManager::remove(&categories, use_trash.unwrap_or(true))
+            .map_err(|e| format!("Failed to clean up: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Cleanup task failed: {}", e))?
+}
+
+/// Size of the shared mod cache and how much the instances save by linking it
+#[tauri::command]
+pub async fn get_cache_stats() -> Result<ModCacheStats, String> {
+    tauri::async_runtime::spawn_blocking(ModCache::stats)
+        .await
+        .map_err(|e| format!("Cache task failed: {}", e))
+}
+
+/// Remove cached files no instance uses anymore
+#[tauri::command]
+pub async fn prune_cache() -> Result<ModCachePruneResult, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        ModCache::prune().map_err(|e| format!("Failed to prune cache: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Cache task failed: {}", e))?
+}
@@ -1,4 +1,5 @@
 use crate::services::accounts::AccountManager;
+use crate::utils::long_path;
 use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -116,7 +117,7 @@ fn get_recent_skins_path(account_uuid: &str) -> Result<PathBuf, String> {
     
     // Create directory if it doesn't exist
     if !skins_dir.exists() {
-        fs::create_dir_all(&skins_dir)
+        fs::create_dir_all(long_path(&skins_dir))
             .map_err(|e| format!("Failed to create skins directory: {}", e))?;
     }
     
@@ -132,7 +133,7 @@ pub async fn load_recent_skins(account_uuid: String) -> Result<Vec<RecentSkin>,
         return Ok(Vec::new());
     }
     
-    let content = fs::read_to_string(&file_path)
+    let content = fs::read_to_string(long_path(&file_path))
         .map_err(|e| format!("Failed to read recent skins file: {}", e))?;
     
     let skins: Vec<RecentSkin> = serde_json::from_str(&content)
@@ -152,7 +153,7 @@ pub async fn save_recent_skin(
     
     // Load existing skins
     let mut skins = if file_path.exists() {
-        let content = fs::read_to_string(&file_path)
+        let content = fs::read_to_string(long_path(&file_path))
             .map_err(|e| format!("Failed to read recent skins file: {}", e))?;
         
         serde_json::from_str::<Vec<RecentSkin>>(&content)
@@ -183,7 +184,7 @@ pub async fn save_recent_skin(
     let json = serde_json::to_string_pretty(&skins)
         .map_err(|e| format!("Failed to serialize recent skins: {}", e))?;
     
-    fs::write(&file_path, json)
+    fs::write(long_path(&file_path), json)
         .map_err(|e| format!("Failed to write recent skins file: {}", e))?;
     
     Ok(())
@@ -204,7 +205,7 @@ pub async fn upload_skin(
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
     
     // Get a fresh token
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
     
@@ -231,7 +232,7 @@ pub async fn upload_skin(
         return Err(format!("Invalid skin dimensions ({}x{}). Must be 64x64 or 64x32", width, height));
     }
     
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     let part = reqwest::multipart::Part::bytes(image_bytes)
         .file_name("skin.png")
@@ -267,11 +268,11 @@ pub async fn reset_skin() -> Result<String, String> {
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
     
     // Get a fresh token
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
     
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     let response = client
         .delete(MINECRAFT_SKIN_RESET_URL)
@@ -297,11 +298,11 @@ pub async fn get_current_skin() -> Result<Option<CurrentSkin>, String> {
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
     
     // Get a fresh token
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
     
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     // Get profile from Microsoft API for skin info
     let response = client
@@ -344,11 +345,11 @@ pub async fn get_user_capes() -> Result<UserCapesResponse, String> {
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
     
     // Get a fresh token
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
     
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     let response = client
         .get(MINECRAFT_PROFILE_URL)
@@ -375,7 +376,7 @@ pub async fn get_user_capes() -> Result<UserCapesResponse, String> {
 
 /// Helper function to get player's cape from session server
 async fn get_player_cape(uuid: &str) -> Result<String, String> {
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     // Remove dashes from UUID for session server
     let uuid_no_dashes = uuid.replace("-", "");
@@ -430,11 +431,11 @@ pub async fn equip_cape(cape_id: String) -> Result<String, String> {
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
     
     // Get a fresh token
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
     
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     let url = format!("https://api.minecraftservices.com/minecraft/profile/capes/active");
     
@@ -467,11 +468,11 @@ pub async fn remove_cape() -> Result<String, String> {
         .ok_or_else(|| "No active account. Please sign in first.".to_string())?;
     
     // Get a fresh token
-    let access_token = AccountManager::get_valid_token(&active_account.uuid)
+    let access_token = AccountManager::ensure_fresh_token(&active_account.uuid)
         .await
         .map_err(|e| format!("Failed to get valid token: {}", e))?;
     
-    let client = reqwest::Client::new();
+    let client = crate::utils::http::client();
     
     let url = "https://api.minecraftservices.com/minecraft/profile/capes/active";
     
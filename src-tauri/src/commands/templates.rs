@@ -1,6 +1,8 @@
+use crate::commands::validation::sanitize_instance_name;
 use crate::models::{Instance, InstanceTemplate, LauncherSettings, MinecraftOptions};
 use crate::services::template::TemplateManager;
-use crate::utils::get_instance_dir;
+use crate::utils::game_options::translate_options;
+use crate::utils::{get_current_os, get_instance_dir, long_path};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use tauri::command;
@@ -17,6 +19,8 @@ pub struct TemplateExportData {
     pub description: Option<String>,
     pub launcher_settings: Option<LauncherSettings>,
     pub minecraft_options: Option<MinecraftOptions>,
+    #[serde(default)]
+    pub source_os: Option<String>,
 }
 
 #[command]
@@ -77,14 +81,19 @@ pub async fn create_instance_from_template(
     loader: String,
     loader_version: Option<String>,
 ) -> Result<Instance, String> {
-    let template = TemplateManager::get_template(&template_id).map_err(|e| e.to_string())?;
+    let mut template = TemplateManager::get_template(&template_id).map_err(|e| e.to_string())?;
+    TemplateManager::localize(
+        &mut template.launcher_settings,
+        &mut template.minecraft_options,
+        template.source_os.as_deref(),
+    );
 
     let instance_dir = get_instance_dir(&instance_name);
     if instance_dir.exists() {
         return Err(format!("Instance '{}' already exists", instance_name));
     }
 
-    fs::create_dir_all(&instance_dir).map_err(|e| format!("Failed to create instance directory: {}", e))?;
+    fs::create_dir_all(long_path(&instance_dir)).map_err(|e| format!("Failed to create instance directory: {}", e))?;
 
     let instance = Instance {
         name: instance_name.clone(),
@@ -96,12 +105,38 @@ pub async fn create_instance_from_template(
         icon_path: None,
         settings_override: template.launcher_settings,
         total_playtime_seconds: 0,
+        shared_directories: Vec::new(),
+        language: None,
+        jar_mods: Vec::new(),
+        extra_game_args: Vec::new(),
+        demo_mode: false,
+        group: None,
+        pinned: false,
+        sort_index: 0,
+        accent_color: None,
+        banner_path: None,
+        emoji: None,
+        ephemeral: false,
+        audio_profile: None,
+        completed_setup_steps: Vec::new(),
+        managed_manifest_url: None,
+        managed_version: None,
+        quick_play_world: None,
+        java_path: None,
+        archive_mode: false,
+        auto_update: None,
+        mod_update_preferences: std::collections::HashMap::new(),
+        frozen: false,
+        speedrun_attestation: false,
+        linked_game_dir: None,
+        library_resource_packs: Vec::new(),
+        library_shader_packs: Vec::new(),
     };
 
     let instance_json = instance_dir.join("instance.json");
     let json = serde_json::to_string_pretty(&instance)
         .map_err(|e| format!("Failed to serialize instance: {}", e))?;
-    fs::write(&instance_json, json)
+    fs::write(long_path(&instance_json), json)
         .map_err(|e| format!("Failed to write instance.json: {}", e))?;
 
     if let Some(minecraft_options) = template.minecraft_options {
@@ -109,7 +144,7 @@ pub async fn create_instance_from_template(
         let mut options_content = String::new();
         TemplateManager::merge_options_txt(&mut options_content, &minecraft_options)
             .map_err(|e| format!("Failed to create options.txt: {}", e))?;
-        fs::write(&options_path, options_content)
+        fs::write(long_path(&options_path), options_content)
             .map_err(|e| format!("Failed to write options.txt: {}", e))?;
     }
 
@@ -131,13 +166,14 @@ pub async fn export_template(
             description: template.description,
             launcher_settings: template.launcher_settings,
             minecraft_options: template.minecraft_options,
+            source_os: template.source_os,
         },
     };
 
     let json = serde_json::to_string_pretty(&export)
         .map_err(|e| format!("Failed to serialize template: {}", e))?;
 
-    fs::write(&export_path, json)
+    fs::write(long_path(std::path::Path::new(&export_path)), json)
         .map_err(|e| format!("Failed to write template file: {}", e))?;
 
     Ok(format!("Template exported successfully to {}", export_path))
@@ -147,7 +183,7 @@ pub async fn export_template(
 pub async fn import_template(
     import_path: String,
 ) -> Result<InstanceTemplate, String> {
-    let content = fs::read_to_string(&import_path)
+    let content = fs::read_to_string(long_path(std::path::Path::new(&import_path)))
         .map_err(|e| format!("Failed to read template file: {}", e))?;
 
     let export: TemplateExport = serde_json::from_str(&content)
@@ -157,13 +193,54 @@ pub async fn import_template(
         return Err(format!("Unsupported template version: {}. Expected 1.0.0", export.version));
     }
 
+    let mut template_data = export.template;
+    TemplateManager::localize(
+        &mut template_data.launcher_settings,
+        &mut template_data.minecraft_options,
+        template_data.source_os.as_deref(),
+    );
+
     let template = TemplateManager::create_template(
-        export.template.name,
-        export.template.description,
-        export.template.launcher_settings,
-        export.template.minecraft_options,
+        template_data.name,
+        template_data.description,
+        template_data.launcher_settings,
+        template_data.minecraft_options,
     )
     .map_err(|e| format!("Failed to create template: {}", e))?;
 
     Ok(template)
-}
\ No newline at end of file
+}
+/// Copy an options.txt from another computer into an instance, translating it when it was
+/// written on a different OS. `source_os` is "windows", "osx" or "linux", `None` means this OS.
+#[command]
+pub async fn import_options_file(
+    instance_name: String,
+    file_path: String,
+    source_os: Option<String>,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let current_os = get_current_os();
+    let source_os = source_os.unwrap_or_else(|| current_os.clone());
+    if !["windows", "osx", "linux"].contains(&source_os.as_str()) {
+        return Err(format!("Unknown operating system: {}", source_os));
+    }
+
+    let content = fs::read_to_string(long_path(std::path::Path::new(&file_path)))
+        .map_err(|e| format!("Failed to read options file: {}", e))?;
+    let content = if source_os == current_os {
+        content
+    } else {
+        translate_options(&content, &source_os, &current_os)
+    };
+
+    fs::write(long_path(&instance_dir.join("options.txt")), content)
+        .map_err(|e| format!("Failed to write options.txt: {}", e))?;
+
+    println!("✓ Imported options into '{}' (from {})", safe_name, source_os);
+    Ok(())
+}
@@ -47,6 +47,31 @@ pub fn sanitize_filename(filename: &str) -> Result<String, String> {
     Ok(filename.to_string())
 }
 
+/// Sanitize filenames of downloaded pack content (mods, resource packs, shader packs)
+pub fn sanitize_pack_filename(filename: &str) -> Result<String, String> {
+    if filename.is_empty() {
+        return Err("Filename cannot be empty".to_string());
+    }
+    
+    if filename.contains("..") || filename.contains('/') || filename.contains('\\') {
+        return Err("Filename contains invalid characters".to_string());
+    }
+    
+    if filename.starts_with('.') {
+        return Err("Filename cannot start with a dot".to_string());
+    }
+    
+    if filename.contains('\0') {
+        return Err("Filename contains null bytes".to_string());
+    }
+    
+    if !filename.ends_with(".jar") && !filename.ends_with(".zip") {
+        return Err("Only .jar and .zip files are allowed".to_string());
+    }
+    
+    Ok(filename.to_string())
+}
+
 /// Sanitize server names
 pub fn sanitize_server_name(name: &str) -> Result<String, String> {
     if name.is_empty() {
@@ -178,4 +203,159 @@ pub fn validate_memory_allocation(memory_mb: u64) -> Result<(), String> {
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Validate CurseForge download URLs point at the CurseForge CDN
+pub fn validate_curseforge_url(url: &str) -> Result<url::Url, String> {
+    let parsed_url = url::Url::parse(url)
+        .map_err(|_| "Invalid URL format".to_string())?;
+    
+    if parsed_url.scheme() != "https" {
+        return Err("Only HTTPS URLs are allowed".to_string());
+    }
+    
+    let host = parsed_url.host_str()
+        .ok_or("URL has no host")?;
+    
+    if host != "forgecdn.net" && !host.ends_with(".forgecdn.net") {
+        return Err("CurseForge downloads are only allowed from forgecdn.net".to_string());
+    }
+    
+    Ok(parsed_url)
+}
+
+/// Arguments the launcher always sets itself
+const RESERVED_GAME_ARGS: &[&str] = &[
+    "--username",
+    "--uuid",
+    "--accessToken",
+    "--version",
+    "--gameDir",
+    "--assetsDir",
+    "--assetIndex",
+];
+
+/// Validate user supplied game arguments, one token per entry
+pub fn validate_game_arguments(args: &[String]) -> Result<(), String> {
+    if args.len() > 64 {
+        return Err("Too many game arguments".to_string());
+    }
+
+    for arg in args {
+        if arg.is_empty() || arg.len() > 256 {
+            return Err("Game arguments must be between 1 and 256 characters".to_string());
+        }
+
+        if arg.chars().any(|c| c.is_control()) {
+            return Err(format!("Game argument '{}' contains invalid characters", arg.escape_debug()));
+        }
+
+        let flag = arg.split('=').next().unwrap_or(arg);
+        if RESERVED_GAME_ARGS.iter().any(|r| r.eq_ignore_ascii_case(flag)) {
+            return Err(format!("'{}' is managed by the launcher and cannot be overridden", flag));
+        }
+    }
+
+    Ok(())
+}
+
+/// JVM options the launcher always sets itself
+const RESERVED_JVM_ARGS: &[&str] = &["-cp", "-classpath", "--class-path", "-jar", "-Djava.library.path"];
+
+/// Validate user supplied JVM arguments, one token per entry
+pub fn validate_jvm_arguments(args: &[String]) -> Result<(), String> {
+    if args.len() > 64 {
+        return Err("Too many JVM arguments".to_string());
+    }
+
+    for arg in args {
+        if arg.is_empty() || arg.len() > 512 {
+            return Err("JVM arguments must be between 1 and 512 characters".to_string());
+        }
+
+        if arg.chars().any(|c| c.is_control()) {
+            return Err(format!("JVM argument '{}' contains invalid characters", arg.escape_debug()));
+        }
+
+        if !arg.starts_with('-') {
+            return Err(format!("JVM argument '{}' must start with '-'", arg));
+        }
+
+        if arg.starts_with("-Xmx") || arg.starts_with("-Xms") {
+            return Err("Memory is set with the memory setting, not JVM arguments".to_string());
+        }
+
+        let flag = arg.split('=').next().unwrap_or(arg);
+        if RESERVED_JVM_ARGS.contains(&flag) {
+            return Err(format!("'{}' is managed by the launcher and cannot be overridden", flag));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate and trim an instance group name
+pub fn validate_group_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err("Group name cannot be empty".to_string());
+    }
+
+    if name.chars().count() > 64 {
+        return Err("Group name is too long".to_string());
+    }
+
+    if name.chars().any(|c| c.is_control()) {
+        return Err("Group name contains invalid characters".to_string());
+    }
+
+    Ok(name.to_string())
+}
+
+/// Validate the pre-launch/post-exit hook commands of a settings object
+pub fn validate_hook_commands(settings: &crate::models::LauncherSettings) -> Result<(), String> {
+    for command in [&settings.pre_launch_command, &settings.post_exit_command].into_iter().flatten() {
+        if command.len() > 4096 {
+            return Err("Hook command is too long".to_string());
+        }
+
+        if command.contains('\0') {
+            return Err("Hook command contains null bytes".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate an accent color, returning it as lowercase #rrggbb
+pub fn validate_accent_color(color: &str) -> Result<String, String> {
+    let color = color.trim();
+    let hex = color.strip_prefix('#').unwrap_or(color);
+
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Accent color must be a hex color like #3b82f6".to_string());
+    }
+
+    Ok(format!("#{}", hex.to_lowercase()))
+}
+
+/// Validate an instance emoji. Emoji can span several code points (skin tones, ZWJ sequences),
+/// so this only bounds the length and rejects plain text.
+pub fn validate_emoji(emoji: &str) -> Result<String, String> {
+    let emoji = emoji.trim();
+
+    if emoji.is_empty() {
+        return Err("Emoji cannot be empty".to_string());
+    }
+
+    if emoji.chars().count() > 10 {
+        return Err("Emoji is too long".to_string());
+    }
+
+    if emoji.chars().any(|c| c.is_ascii() || c.is_control() || c.is_whitespace()) {
+        return Err("Emoji contains invalid characters".to_string());
+    }
+
+    Ok(emoji.to_string())
+}
@@ -1,14 +1,26 @@
 use crate::services::installer::MinecraftInstaller;
 use crate::services::fabric::FabricInstaller;
-use crate::models::FabricLoaderVersion;
+use crate::services::forge::{ForgeFlavor, ForgeInstaller};
+use crate::services::liteloader::LiteLoaderInstaller;
+use crate::services::settings::SettingsManager;
+use crate::models::{FabricLoaderVersion, ForgeVersion, LiteLoaderVersion, VersionChannel};
 use crate::utils::get_meta_dir;
 
+/// Version ids, newest first. Without channels this is the latest 500 of every kind.
 #[tauri::command]
-pub async fn get_minecraft_versions() -> Result<Vec<String>, String> {
+pub async fn get_minecraft_versions(channels: Option<Vec<VersionChannel>>) -> Result<Vec<String>, String> {
     let installer = MinecraftInstaller::new(get_meta_dir());
+    let Some(channels) = channels else {
+        return installer
+            .get_versions()
+            .await
+            .map_err(|e| format!("Failed to fetch versions: {}", e));
+    };
+
     installer
-        .get_versions()
+        .get_versions_in_channels(&channels)
         .await
+        .map(|versions| versions.into_iter().map(|v| v.id).collect())
         .map_err(|e| format!("Failed to fetch versions: {}", e))
 }
 
@@ -78,6 +90,22 @@ pub async fn get_fabric_versions() -> Result<Vec<FabricLoaderVersion>, String> {
     installer
         .get_loader_versions()
         .await
+        .map(|versions| offered_versions(versions, |v| v.stable))
+        .map_err(|e| format!("Failed to fetch Fabric versions: {}", e))
+}
+
+/// Fabric loader versions available for a Minecraft version, newest first
+#[tauri::command]
+pub async fn get_fabric_loader_versions(minecraft_version: String) -> Result<Vec<FabricLoaderVersion>, String> {
+    if !minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version format".to_string());
+    }
+
+    let installer = FabricInstaller::new(get_meta_dir());
+    installer
+        .get_loader_versions_for_game(&minecraft_version)
+        .await
+        .map(|versions| offered_versions(versions, |v| v.stable))
         .map_err(|e| format!("Failed to fetch Fabric versions: {}", e))
 }
 
@@ -97,4 +125,81 @@ pub async fn install_fabric(minecraft_version: String, loader_version: String) -
         .install_fabric(&minecraft_version, &loader_version)
         .await
         .map_err(|e| format!("Fabric installation failed: {}", e))
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_liteloader_versions(minecraft_version: String) -> Result<Vec<LiteLoaderVersion>, String> {
+    if !minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version format".to_string());
+    }
+
+    let installer = LiteLoaderInstaller::new(get_meta_dir());
+    installer
+        .get_loader_versions(&minecraft_version)
+        .await
+        .map(|versions| offered_versions(versions, |v| !v.snapshot))
+        .map_err(|e| format!("Failed to fetch LiteLoader versions: {}", e))
+}
+
+#[tauri::command]
+pub async fn install_liteloader(minecraft_version: String, loader_version: String) -> Result<String, String> {
+    if !minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version format".to_string());
+    }
+    if !loader_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid loader version format".to_string());
+    }
+
+    let installer = LiteLoaderInstaller::new(get_meta_dir());
+    installer
+        .install_liteloader(&minecraft_version, &loader_version)
+        .await
+        .map_err(|e| format!("LiteLoader installation failed: {}", e))
+}
+
+/// Forge or NeoForge builds for a Minecraft version, newest first
+#[tauri::command]
+pub async fn get_forge_versions(loader: String, minecraft_version: String) -> Result<Vec<ForgeVersion>, String> {
+    let flavor = ForgeFlavor::from_loader(&loader).ok_or_else(|| format!("Unknown loader '{}'", loader))?;
+    if !minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version format".to_string());
+    }
+
+    let installer = ForgeInstaller::new(get_meta_dir());
+    installer
+        .get_loader_versions(flavor, &minecraft_version)
+        .await
+        .map(|versions| offered_versions(versions, |v| v.stable))
+        .map_err(|e| format!("Failed to fetch {} versions: {}", loader, e))
+}
+
+#[tauri::command]
+pub async fn install_forge(
+    loader: String,
+    minecraft_version: String,
+    loader_version: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let flavor = ForgeFlavor::from_loader(&loader).ok_or_else(|| format!("Unknown loader '{}'", loader))?;
+    if !minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid Minecraft version format".to_string());
+    }
+    if !loader_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+        return Err("Invalid loader version format".to_string());
+    }
+
+    let installer = ForgeInstaller::new(get_meta_dir()).with_progress(app_handle, None);
+    installer
+        .install_forge(flavor, &minecraft_version, &loader_version)
+        .await
+        .map_err(|e| format!("{} installation failed: {}", loader, e))
+}
+
+/// Drop unstable loader builds unless settings ask for them. Versions that only have unstable
+/// builds keep them, there would be nothing to pick otherwise.
+fn offered_versions<T>(versions: Vec<T>, is_stable: impl Fn(&T) -> bool) -> Vec<T> {
+    if SettingsManager::unstable_loaders_enabled() || !versions.iter().any(&is_stable) {
+        return versions;
+    }
+    versions.into_iter().filter(|v| is_stable(v)).collect()
+}
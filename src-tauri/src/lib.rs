@@ -36,26 +36,102 @@ use commands::{
     
     // Instance commands
     create_instance,
+    create_ephemeral_instance,
     get_instances,
+    get_big_picture_model,
+    big_picture_activate,
+    queue_launches,
+    prewarm_instance,
+    launch_instances_synchronized,
+    get_launch_queue,
+    cancel_queued_launch,
     delete_instance,
+    delete_instances,
+    get_orphaned_instances,
+    repair_orphaned_instance,
+    remove_orphaned_instance,
+    update_instances_loader,
+    apply_settings_to_instances,
     rename_instance,
     duplicate_instance,
+    clone_instance,
     launch_instance,
     kill_instance,
     launch_instance_with_active_account,
     get_launch_token,
     refresh_account_token,
+    begin_device_login,
+    poll_device_login,
+    cancel_device_login,
     set_instance_icon,
     remove_instance_icon,
     get_instance_icon,
+    get_bundled_icons,
+    set_instance_icon_preset,
+    set_instance_appearance,
+    set_instance_banner,
+    remove_instance_banner,
+    get_instance_banner,
     get_launcher_directory,
     open_instance_folder,
+    reveal_instance_path,
+    open_instance_file,
+    create_desktop_shortcut,
+    add_instance_to_steam,
     open_worlds_folder,
     open_world_folder,
+    create_world,
     get_instance_worlds,
+    list_world_datapacks,
+    install_datapack,
+    remove_datapack,
+    set_archive_mode,
+    set_instance_frozen,
+    set_speedrun_attestation,
+    attest_instance,
+    verify_attestation,
     delete_world,
+    get_practice_maps,
+    install_practice_map,
     update_instance_fabric_loader,
+    install_optifine,
     update_instance_minecraft_version,
+    check_version_compatibility,
+    change_instance_version,
+    get_instance_recordings,
+    rename_recording,
+    move_recordings_to_media,
+    get_instance_notes,
+    save_instance_notes,
+    get_recent_launches,
+    get_instance_directory_layout,
+    set_instance_directory_layout,
+    set_instance_isolation,
+    repair_instance_links,
+    set_instance_language,
+    get_audio_profiles,
+    save_audio_profile,
+    delete_audio_profile,
+    set_instance_audio_profile,
+    set_instance_game_arguments,
+    set_instance_group,
+    pin_instance,
+    unpin_instance,
+    reorder_instances,
+    rename_group,
+    get_groups,
+    export_instance,
+    archive_instance,
+    restore_instance,
+    get_archived_instances,
+    create_instance_snapshot,
+    list_instance_snapshots,
+    rollback_instance_snapshot,
+    compare_instances,
+    get_instance_disk_usage,
+    import_curseforge_pack,
+    export_instance_code,
+    import_instance_code,
     
     // Version commands
     get_minecraft_versions,
@@ -65,26 +141,90 @@ use commands::{
     install_minecraft,
     check_version_installed,
     get_fabric_versions,
+    get_fabric_loader_versions,
     install_fabric,
+    get_liteloader_versions,
+    install_liteloader,
+    get_forge_versions,
+    install_forge,
     
     // Mod commands
     get_installed_mods,
+    list_mods,
     delete_mod,
     open_mods_folder,
     toggle_mod,
+    get_mod_config_files,
+    list_config_files,
+    read_config_file,
+    write_config_file,
+    get_cached_mod_details,
+    get_installed_mod_details,
+    export_mod_list,
+    get_instance_credits,
+    export_instance_credits,
+    get_jar_mods,
+    add_jar_mod,
+    remove_jar_mod,
+    reorder_jar_mods,
+    rebuild_jar_mods,
+    revert_jar_mods,
+    optimize_instance,
+    remove_performance_preset,
     search_mods,
     get_mod_details,
     get_mod_versions,
     download_mod,
+    install_modrinth_mod,
+    search_curseforge_mods,
+    install_curseforge_mod,
+    watch_curseforge_downloads,
+    get_pending_curseforge_downloads,
+    open_curseforge_download,
+    list_resource_packs,
+    install_resource_pack,
+    toggle_resource_pack,
+    reorder_resource_packs,
+    list_library_packs,
+    import_library_pack,
+    move_pack_to_library,
+    set_instance_library_packs,
+    remove_library_pack,
+    link_modrinth_account,
+    unlink_modrinth_account,
+    get_modrinth_account,
+    get_followed_projects,
+    get_modrinth_collections,
+    get_followed_updates,
+    dismiss_followed_updates,
+    install_modrinth_collection,
+    check_mod_updates,
+    update_mods,
+    set_mod_update_preference,
+    sync_from_lockfile,
+    set_auto_update_policy,
+    run_mod_auto_update,
+    analyze_mod_conflicts,
+    add_local_mods,
     get_project_details,
     
     // Modpack commands
     get_modpack_versions,
     install_modpack,
     get_modpack_manifest,
+    preview_pack_update,
+    get_interrupted_tasks,
+    resolve_interrupted_task,
     get_modpack_game_versions,
     install_modpack_from_file,
     get_modpack_name_from_file,
+    validate_pack_file,
+    get_instance_setup_steps,
+    reset_instance_setup,
+    set_managed_instance_source,
+    check_managed_instance_update,
+    apply_managed_instance_update,
+    import_modrinth_pack,
     
     // Server commands
     get_servers,
@@ -99,9 +239,37 @@ use commands::{
     get_instance_settings,
     save_instance_settings,
     detect_java_installations,
+    recommend_java_for_version,
     set_sidebar_background,
     get_sidebar_background,
     remove_sidebar_background,
+    uninstall_cleanup,
+    get_cache_stats,
+    prune_cache,
+    
+    // Diagnostics commands
+    get_antivirus_report,
+    clear_antivirus_report,
+    get_defender_exclusion_info,
+    add_defender_exclusion,
+    check_launcher_path,
+    run_network_diagnostics,
+    check_instance_case_conflicts,
+    check_pack_case_conflicts,
+    get_gc_report,
+    analyze_gc_log,
+    install_spark_profiler,
+    get_spark_profiles,
+    verify_instance,
+    repair_instance,
+
+    // Migration commands
+    detect_launcher_installs,
+    list_importable_instances,
+    import_launcher_instance,
+    inspect_game_dir,
+    adopt_existing_game_dir,
+    import_global_game_files,
     
     // Template commands
     create_template,
@@ -114,6 +282,7 @@ use commands::{
     create_instance_from_template,
     export_template,
     import_template,
+    import_options_file,
     
     // Skin commands
     upload_skin,
@@ -247,6 +416,79 @@ async fn update_discord_rpc_mode(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Headless entry points for scripts and CI. Returns the exit code if a CLI command was handled.
+///
+/// `atomic-launcher --validate-pack <file> [--verify-hashes]` prints a JSON report
+/// and exits with 0 when the pack is valid, 1 otherwise.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let position = args.iter().position(|a| a == "--validate-pack")?;
+
+    // Release builds use the windows subsystem, so output only shows up once attached to the caller's console
+    #[cfg(windows)]
+    attach_console();
+
+    let Some(file) = args.get(position + 1) else {
+        eprintln!("Usage: --validate-pack <file> [--verify-hashes]");
+        return Some(2);
+    };
+    let verify_hashes = args.iter().any(|a| a == "--verify-hashes");
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let report = runtime.block_on(services::pack_validator::PackValidator::validate(
+        std::path::Path::new(file),
+        verify_hashes,
+    ));
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize report: {}", e);
+            return Some(2);
+        }
+    }
+
+    Some(if report.valid { 0 } else { 1 })
+}
+
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+        fn AllocConsole() -> i32;
+    }
+
+    unsafe {
+        if AttachConsole(ATTACH_PARENT_PROCESS) == 0 {
+            AllocConsole();
+        }
+    }
+}
+
+/// `atomic-launcher --launch <instance>`, used by desktop shortcuts, starts the instance
+/// with the active account once the app is up. Failures are sent to the UI as `launch-error`.
+fn launch_from_args(app_handle: tauri::AppHandle) {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(position) = args.iter().position(|a| a == services::shortcuts::LAUNCH_ARG) else {
+        return;
+    };
+    let Some(instance_name) = args.get(position + 1).cloned() else {
+        eprintln!("Usage: {} <instance>", services::shortcuts::LAUNCH_ARG);
+        return;
+    };
+
+    tauri::async_runtime::spawn(async move {
+        use tauri::Emitter;
+
+        println!("Launching '{}' from the command line", instance_name);
+        if let Err(e) = launch_instance_with_active_account(instance_name, app_handle.clone()).await {
+            eprintln!("✗ {}", e);
+            let _ = app_handle.emit("launch-error", e);
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     if let Err(e) = dotenvy::dotenv() {
@@ -261,6 +503,27 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .manage(discord_rpc.clone())
         .setup(move |app| {
+            for warning in services::preflight::PathPreflight::check().warnings {
+                println!("⚠ {}", warning);
+            }
+
+            services::instance::InstanceManager::remove_leftover_ephemeral();
+            for task in services::journal::TaskJournal::interrupted() {
+                println!("⚠ Install of '{}' was interrupted at: {}", task.instance_name, task.step);
+            }
+            for orphan in services::orphans::OrphanScanner::scan() {
+                println!("⚠ Instance folder '{}' needs repair or removal ({:?})", orphan.folder, orphan.reason);
+            }
+
+            if let Ok(settings) = services::settings::SettingsManager::load() {
+                if let Err(e) = services::trust_store::TrustStoreManager::apply(&settings.ca_certificates) {
+                    println!("⚠ {}", e);
+                }
+            }
+            launch_from_args(app.handle().clone());
+            services::auto_update::ModAutoUpdater::start_scheduler(app.handle().clone());
+            services::installer::MinecraftInstaller::start_manifest_refresh(utils::get_meta_dir());
+
             // Initialize Discord RPC based on settings
             use crate::services::settings::SettingsManager;
             let should_enable_rpc = match SettingsManager::load() {
@@ -302,7 +565,7 @@ pub fn run() {
                 _ => {}
             }
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(commands::freeze::guard(tauri::generate_handler![
             // App info
             get_app_version,
             check_for_updates,
@@ -318,6 +581,9 @@ pub fn run() {
             launch_instance_with_active_account,
             get_launch_token,
             refresh_account_token,
+            begin_device_login,
+            poll_device_login,
+            cancel_device_login,
             
             // Friends System
             send_friend_request,
@@ -350,25 +616,99 @@ pub fn run() {
             
             // Fabric loader
             get_fabric_versions,
+            get_fabric_loader_versions,
             install_fabric,
+            get_liteloader_versions,
+            install_liteloader,
+            get_forge_versions,
+            install_forge,
             
             // Instance management
             create_instance,
+            create_ephemeral_instance,
             get_instances,
+            get_big_picture_model,
+            big_picture_activate,
+            queue_launches,
+            prewarm_instance,
+            launch_instances_synchronized,
+            get_launch_queue,
+            cancel_queued_launch,
             delete_instance,
+            delete_instances,
+            get_orphaned_instances,
+            repair_orphaned_instance,
+            remove_orphaned_instance,
+            update_instances_loader,
+            apply_settings_to_instances,
             rename_instance,
             duplicate_instance,
+            clone_instance,
             open_worlds_folder,
             open_world_folder,
+            create_world,
             get_instance_worlds,
+            list_world_datapacks,
+            install_datapack,
+            remove_datapack,
+            set_archive_mode,
+            set_instance_frozen,
+            set_speedrun_attestation,
+            attest_instance,
+            verify_attestation,
             delete_world,
+            get_practice_maps,
+            install_practice_map,
             update_instance_fabric_loader,
+            install_optifine,
             update_instance_minecraft_version,
+            check_version_compatibility,
+            change_instance_version,
+            get_instance_recordings,
+            rename_recording,
+            move_recordings_to_media,
+            get_instance_notes,
+            save_instance_notes,
+            get_recent_launches,
+            get_instance_directory_layout,
+            set_instance_directory_layout,
+            set_instance_isolation,
+            repair_instance_links,
+            set_instance_language,
+            get_audio_profiles,
+            save_audio_profile,
+            delete_audio_profile,
+            set_instance_audio_profile,
+            set_instance_game_arguments,
+            set_instance_group,
+            pin_instance,
+            unpin_instance,
+            reorder_instances,
+            rename_group,
+            get_groups,
+            export_instance,
+            archive_instance,
+            restore_instance,
+            get_archived_instances,
+            create_instance_snapshot,
+            list_instance_snapshots,
+            rollback_instance_snapshot,
+            compare_instances,
+            get_instance_disk_usage,
+            import_curseforge_pack,
+            export_instance_code,
+            import_instance_code,
             
             // Instance icons
             set_instance_icon,
             remove_instance_icon,
             get_instance_icon,
+            get_bundled_icons,
+            set_instance_icon_preset,
+            set_instance_appearance,
+            set_instance_banner,
+            remove_instance_banner,
+            get_instance_banner,
             
             // Launch
             launch_instance,
@@ -377,12 +717,47 @@ pub fn run() {
             // Launcher directory
             get_launcher_directory,
             open_instance_folder,
+            reveal_instance_path,
+            open_instance_file,
+            create_desktop_shortcut,
+            add_instance_to_steam,
             
             // Modrinth API
             search_mods,
             get_mod_details,
             get_mod_versions,
             download_mod,
+            install_modrinth_mod,
+            search_curseforge_mods,
+            install_curseforge_mod,
+            watch_curseforge_downloads,
+            get_pending_curseforge_downloads,
+            open_curseforge_download,
+            list_resource_packs,
+            install_resource_pack,
+            toggle_resource_pack,
+            reorder_resource_packs,
+            list_library_packs,
+            import_library_pack,
+            move_pack_to_library,
+            set_instance_library_packs,
+            remove_library_pack,
+            link_modrinth_account,
+            unlink_modrinth_account,
+            get_modrinth_account,
+            get_followed_projects,
+            get_modrinth_collections,
+            get_followed_updates,
+            dismiss_followed_updates,
+            install_modrinth_collection,
+            check_mod_updates,
+            update_mods,
+            set_mod_update_preference,
+            sync_from_lockfile,
+            set_auto_update_policy,
+            run_mod_auto_update,
+            analyze_mod_conflicts,
+            add_local_mods,
             get_project_details,
             
             // Settings
@@ -391,24 +766,80 @@ pub fn run() {
             get_instance_settings,
             save_instance_settings,
             detect_java_installations,
+            recommend_java_for_version,
             set_sidebar_background,
             get_sidebar_background,
             remove_sidebar_background,
+            uninstall_cleanup,
+            get_cache_stats,
+            prune_cache,
             update_discord_rpc_mode,
 
+            // Diagnostics
+            get_antivirus_report,
+            clear_antivirus_report,
+            get_defender_exclusion_info,
+            add_defender_exclusion,
+            check_launcher_path,
+            run_network_diagnostics,
+            check_instance_case_conflicts,
+            check_pack_case_conflicts,
+            get_gc_report,
+            analyze_gc_log,
+            install_spark_profiler,
+            get_spark_profiles,
+            verify_instance,
+            repair_instance,
+
+            // Migration
+            detect_launcher_installs,
+            list_importable_instances,
+            import_launcher_instance,
+            inspect_game_dir,
+            adopt_existing_game_dir,
+            import_global_game_files,
+
             // Mod Management
             get_installed_mods,
+            list_mods,
             delete_mod,
             open_mods_folder,
             toggle_mod,
+            get_mod_config_files,
+            list_config_files,
+            read_config_file,
+            write_config_file,
+            get_cached_mod_details,
+            get_installed_mod_details,
+            export_mod_list,
+            get_instance_credits,
+            export_instance_credits,
+            get_jar_mods,
+            add_jar_mod,
+            remove_jar_mod,
+            reorder_jar_mods,
+            rebuild_jar_mods,
+            revert_jar_mods,
+            optimize_instance,
+            remove_performance_preset,
 
             // Modpacks
             get_modpack_versions,
             install_modpack,
             get_modpack_manifest,
+            preview_pack_update,
+            get_interrupted_tasks,
+            resolve_interrupted_task,
             get_modpack_game_versions,
             install_modpack_from_file,
             get_modpack_name_from_file,
+            validate_pack_file,
+            get_instance_setup_steps,
+            reset_instance_setup,
+            set_managed_instance_source,
+            check_managed_instance_update,
+            apply_managed_instance_update,
+            import_modrinth_pack,
 
             // Servers
             get_servers,
@@ -428,6 +859,7 @@ pub fn run() {
             create_instance_from_template,
             export_template,
             import_template,
+            import_options_file,
 
             // Open links
             open_url,
@@ -438,7 +870,7 @@ pub fn run() {
 
             // System Info
             get_system_info,
-        ])
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
\ No newline at end of file
@@ -2,5 +2,11 @@
 
 fn main() {
     dotenvy::dotenv().ok();
+
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(exit_code) = atomic_launcher_lib::run_cli(&args) {
+        std::process::exit(exit_code);
+    }
+
     atomic_launcher_lib::run()
 }
\ No newline at end of file
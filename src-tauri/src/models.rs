@@ -28,6 +28,140 @@ pub struct Instance {
     pub icon_path: Option<String>,
     #[serde(default)]
     pub total_playtime_seconds: u64,
+    #[serde(default)]
+    pub shared_directories: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Jar mods in patch order, the last one overrides earlier ones
+    #[serde(default)]
+    pub jar_mods: Vec<String>,
+    /// Passed to the game after the launcher's own arguments
+    #[serde(default)]
+    pub extra_game_args: Vec<String>,
+    #[serde(default)]
+    pub demo_mode: bool,
+    /// Folder the instance is listed under, e.g. "1.20 packs"
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Favorites are listed before everything else
+    #[serde(default)]
+    pub pinned: bool,
+    /// Manual position from drag ordering, lower comes first
+    #[serde(default)]
+    pub sort_index: u32,
+    /// Card accent as #RRGGBB
+    #[serde(default)]
+    pub accent_color: Option<String>,
+    /// Banner image relative to the instance folder
+    #[serde(default)]
+    pub banner_path: Option<String>,
+    #[serde(default)]
+    pub emoji: Option<String>,
+    /// Throwaway test instance, hidden from the list and deleted when the game exits
+    #[serde(default)]
+    pub ephemeral: bool,
+    /// Id of the audio profile written into options.txt at launch
+    #[serde(default)]
+    pub audio_profile: Option<String>,
+    /// Ids of the pack's run-once setup steps that already ran
+    #[serde(default)]
+    pub completed_setup_steps: Vec<String>,
+    /// modrinth.index.json the instance follows, set for server-managed packs
+    #[serde(default)]
+    pub managed_manifest_url: Option<String>,
+    /// `versionId` of the managed pack last applied
+    #[serde(default)]
+    pub managed_version: Option<String>,
+    /// World folder to open through Quick Play on the next launch, cleared once used
+    #[serde(default)]
+    pub quick_play_world: Option<String>,
+    /// Java matching the version's requirement, picked or downloaded when the instance was created.
+    /// Settings still win when they name a Java path.
+    #[serde(default)]
+    pub java_path: Option<String>,
+    /// Version files, libraries and assets are kept in pinned/ and restored from there
+    #[serde(default)]
+    pub archive_mode: bool,
+    /// Opt-in policy for updating mods without asking, off when None
+    #[serde(default)]
+    pub auto_update: Option<AutoUpdatePolicy>,
+    /// Update checker preferences by project id, mods without an entry get the newest release
+    #[serde(default)]
+    pub mod_update_preferences: HashMap<String, ModUpdatePreference>,
+    /// Blocks every command that would change the instance, for setups that must stay exactly as they are
+    #[serde(default)]
+    pub frozen: bool,
+    /// Sign a manifest of the game jar, mods and options when the game starts and after it exits
+    #[serde(default)]
+    pub speedrun_attestation: bool,
+    /// Existing game folder the instance was adopted from. Its folders are linked in, not copied.
+    #[serde(default)]
+    pub linked_game_dir: Option<String>,
+    /// Resource packs from the shared library linked into resourcepacks/
+    #[serde(default)]
+    pub library_resource_packs: Vec<String>,
+    /// Shader packs from the shared library linked into shaderpacks/
+    #[serde(default)]
+    pub library_shader_packs: Vec<String>,
+}
+
+/// Least stable kind of build a mod may update to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseChannel {
+    Release,
+    Beta,
+    Alpha,
+}
+
+impl ReleaseChannel {
+    /// Modrinth's `version_type`
+    pub fn from_modrinth(version_type: &str) -> Option<Self> {
+        match version_type {
+            "release" => Some(Self::Release),
+            "beta" => Some(Self::Beta),
+            "alpha" => Some(Self::Alpha),
+            _ => None,
+        }
+    }
+
+    /// CurseForge's `releaseType`
+    pub fn from_curseforge(release_type: u32) -> Option<Self> {
+        match release_type {
+            1 => Some(Self::Release),
+            2 => Some(Self::Beta),
+            3 => Some(Self::Alpha),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModUpdatePreference {
+    /// Never offer updates for this mod
+    #[serde(default)]
+    pub ignore: bool,
+    /// None keeps the default: the newest release, or any build when there's no release
+    #[serde(default)]
+    pub channel: Option<ReleaseChannel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoUpdateSchedule {
+    OnLaunch,
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutoUpdatePolicy {
+    pub schedule: AutoUpdateSchedule,
+    /// Projects to keep updated, every mod with a known origin when empty
+    #[serde(default)]
+    pub project_ids: Vec<String>,
+    #[serde(default)]
+    pub last_run: Option<DateTime<Utc>>,
 }
 
 // ===== FRIENDS SYSTEM MODELS =====
@@ -89,6 +223,9 @@ pub struct InstanceTemplate {
     pub created_at: String,
     pub launcher_settings: Option<LauncherSettings>,
     pub minecraft_options: Option<MinecraftOptions>,
+    /// OS the template was captured on, `None` for templates made before this was recorded
+    #[serde(default)]
+    pub source_os: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -149,6 +286,52 @@ pub struct LauncherSettings {
     pub memory_mb: u32,
     #[serde(default = "default_discord_rpc_enabled")]
     pub discord_rpc_enabled: bool,
+    /// Language new instances start with
+    #[serde(default)]
+    pub default_language: Option<String>,
+    /// Write a GC log during play and analyze it when the game exits
+    #[serde(default)]
+    pub gc_logging: bool,
+    /// Shell command run before the game starts, a failure cancels the launch
+    #[serde(default)]
+    pub pre_launch_command: Option<String>,
+    /// Shell command run after the game exits
+    #[serde(default)]
+    pub post_exit_command: Option<String>,
+    /// Copy new screenshots to the clipboard while the game runs
+    #[serde(default)]
+    pub screenshot_to_clipboard: bool,
+    /// Move new screenshots into screenshots/YYYY-MM-DD
+    #[serde(default)]
+    pub organize_screenshots: bool,
+    /// Where replays and rendered videos are moved to, usually on a bigger drive
+    #[serde(default)]
+    pub media_directory: Option<String>,
+    /// Games the launch queue lets run at once, `None` for no limit
+    #[serde(default)]
+    pub max_concurrent_instances: Option<u32>,
+    /// Total memory the launch queue hands out to running games, `None` for no limit
+    #[serde(default)]
+    pub memory_budget_mb: Option<u32>,
+    /// Key for the CurseForge API, from console.curseforge.com
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+    /// Modrinth personal access token, for followed projects and private collections
+    #[serde(default)]
+    pub modrinth_token: Option<String>,
+    /// Extra JVM flags, one per entry. Flags the selected Java doesn't support are dropped at launch.
+    #[serde(default)]
+    pub jvm_args: Vec<String>,
+    /// PEM or DER files with CA certificates to trust on top of the system ones, for networks
+    /// behind a TLS-intercepting proxy. Used by the launcher and passed to the game.
+    #[serde(default)]
+    pub ca_certificates: Vec<String>,
+    /// Offer beta and snapshot loader builds when creating and upgrading instances
+    #[serde(default)]
+    pub unstable_loaders: bool,
+    /// Catalog URLs to offer practice maps from, see `PracticeMapCatalog`
+    #[serde(default)]
+    pub practice_map_catalogs: Vec<String>,
 }
 
 fn default_memory() -> u32 {
@@ -165,6 +348,21 @@ impl Default for LauncherSettings {
             java_path: None,
             memory_mb: 2048,
             discord_rpc_enabled: true,
+            default_language: None,
+            gc_logging: false,
+            pre_launch_command: None,
+            post_exit_command: None,
+            screenshot_to_clipboard: false,
+            organize_screenshots: false,
+            media_directory: None,
+            max_concurrent_instances: None,
+            memory_budget_mb: None,
+            curseforge_api_key: None,
+            modrinth_token: None,
+            jvm_args: Vec::new(),
+            ca_certificates: Vec::new(),
+            unstable_loaders: false,
+            practice_map_catalogs: Vec::new(),
         }
     }
 }
@@ -257,7 +455,10 @@ pub struct MinecraftProfile {
 pub struct StoredAccount {
     pub uuid: String,
     pub username: String,
+    /// Kept in the credential store, empty in accounts.json
+    #[serde(default)]
     pub access_token: String,
+    #[serde(default)]
     pub refresh_token: String,
     pub token_expiry: DateTime<Utc>,
     pub added_at: String,
@@ -300,6 +501,36 @@ pub struct MinecraftVersion {
     pub release_time: String,
 }
 
+/// Filter for version lists. Mojang files experimental snapshots as plain snapshots, they're
+/// told apart by their id.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionChannel {
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+    Experimental,
+}
+
+impl VersionChannel {
+    pub fn of(version: &MinecraftVersion) -> Self {
+        match version.r#type.as_str() {
+            "release" => Self::Release,
+            "old_beta" => Self::OldBeta,
+            "old_alpha" => Self::OldAlpha,
+            _ if is_experimental_id(&version.id) => Self::Experimental,
+            _ => Self::Snapshot,
+        }
+    }
+}
+
+/// e.g. "1.18_experimental-snapshot-1" or "1.16_combat-6"
+fn is_experimental_id(id: &str) -> bool {
+    let id = id.to_lowercase();
+    id.contains("experimental") || id.contains("combat")
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VersionManifest {
     pub latest: Latest,
@@ -354,15 +585,18 @@ pub struct Library {
     pub downloads: Option<LibraryDownloads>,
     pub name: String,
     pub rules: Option<Vec<Rule>>,
+    /// Before 1.19 natives are classifiers of the library, keyed by OS, e.g. `natives-windows-${arch}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub natives: Option<std::collections::HashMap<String, String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LibraryDownloads {
     pub artifact: Option<Artifact>,
     pub classifiers: Option<std::collections::HashMap<String, Artifact>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Artifact {
     pub path: String,
     pub sha1: String,
@@ -390,12 +624,17 @@ pub struct Arguments {
 #[derive(Debug, Deserialize)]
 pub struct AssetIndexData {
     pub objects: std::collections::HashMap<String, AssetObject>,
+    /// `legacy` index: the game reads assets by name from assets/virtual/legacy
+    #[serde(rename = "virtual", default)]
+    pub is_virtual: bool,
+    /// `pre-1.6` index: the game reads assets from resources/ in its game directory
+    #[serde(default)]
+    pub map_to_resources: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct AssetObject {
     pub hash: String,
-    #[allow(dead_code)]
     pub size: u64,
 }
 
@@ -427,7 +666,12 @@ pub struct FabricArguments {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FabricProfileLibrary {
     pub name: String,
+    /// Maven repository, empty for Forge libraries that carry their own download
+    #[serde(default)]
     pub url: String,
+    /// Set by Forge and NeoForge profiles, whose library names can have classifiers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downloads: Option<LibraryDownloads>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -437,4 +681,150 @@ pub struct FabricLoaderVersion {
     pub maven: String,
     pub version: String,
     pub stable: bool,
-}
\ No newline at end of file
+}
+
+// ===== FORGE MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForgeVersion {
+    /// Loader version without the Minecraft prefix, e.g. `47.2.0` or `21.1.77`
+    pub version: String,
+    pub minecraft_version: String,
+    /// NeoForge marks early builds for a new Minecraft version as beta
+    pub stable: bool,
+}
+
+// ===== LITELOADER MODELS =====
+
+/// dl.liteloader.com/versions/versions.json
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderManifest {
+    pub versions: std::collections::HashMap<String, LiteLoaderGameVersion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderGameVersion {
+    pub repo: Option<LiteLoaderRepo>,
+    pub artefacts: Option<LiteLoaderArtefacts>,
+    pub snapshots: Option<LiteLoaderArtefacts>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderRepo {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderArtefacts {
+    /// Snapshot builds are served from their own repository
+    pub repo: Option<LiteLoaderRepo>,
+    #[serde(default)]
+    pub libraries: Vec<LiteLoaderLibrary>,
+    #[serde(rename = "com.mumfrey:liteloader", default)]
+    pub builds: std::collections::HashMap<String, LiteLoaderBuild>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderBuild {
+    pub version: String,
+    pub file: String,
+    #[serde(rename = "tweakClass")]
+    pub tweak_class: String,
+    #[serde(default)]
+    pub libraries: Vec<LiteLoaderLibrary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderLibrary {
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiteLoaderVersion {
+    pub version: String,
+    pub minecraft_version: String,
+    pub snapshot: bool,
+}
+
+// ===== MODPACK MANIFEST MODELS =====
+
+/// modrinth.index.json inside a .mrpack
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MrpackIndex {
+    #[serde(rename = "formatVersion")]
+    pub format_version: u32,
+    pub game: String,
+    #[serde(rename = "versionId")]
+    pub version_id: String,
+    pub name: String,
+    pub summary: Option<String>,
+    pub files: Vec<MrpackFile>,
+    pub dependencies: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MrpackFile {
+    pub path: String,
+    pub hashes: HashMap<String, String>,
+    pub env: Option<MrpackEnv>,
+    pub downloads: Vec<String>,
+    #[serde(rename = "fileSize")]
+    pub file_size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MrpackEnv {
+    pub client: String,
+    pub server: String,
+}
+
+/// manifest.json inside a CurseForge modpack zip
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    #[serde(rename = "manifestType")]
+    pub manifest_type: String,
+    #[serde(rename = "manifestVersion")]
+    pub manifest_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub author: String,
+    pub files: Vec<CurseForgeManifestFile>,
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(rename = "modLoaders", default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurseForgeModLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u32,
+    #[serde(rename = "fileID")]
+    pub file_id: u32,
+    #[serde(default = "default_true")]
+    pub required: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -1,12 +1,17 @@
-use crate::models::{AccountInfo, AccountsData, StoredAccount};
+use crate::models::{AccountInfo, AccountsData, AuthResponse, StoredAccount};
+use crate::services::credentials::CredentialStore;
 use chrono::{DateTime, Utc};
 use std::fs;
 use std::path::PathBuf;
 
+lazy_static::lazy_static! {
+    static ref REFRESH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::new(());
+}
+
 pub struct AccountManager;
 
 impl AccountManager {
-    fn get_accounts_file() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    pub(crate) fn get_accounts_file() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let data_dir = dirs::data_dir()
             .ok_or("Could not find data directory")?
             .join("atomic-launcher");
@@ -23,17 +28,57 @@ impl AccountManager {
         }
 
         let contents = fs::read_to_string(path)?;
-        let data: AccountsData = serde_json::from_str(&contents)?;
+        let mut data: AccountsData = serde_json::from_str(&contents)?;
+
+        let mut plain_text_tokens = false;
+        for account in data.accounts.values_mut() {
+            // Written before tokens moved to the credential store
+            if !account.access_token.is_empty() || !account.refresh_token.is_empty() {
+                plain_text_tokens = true;
+                continue;
+            }
+            let (access_key, refresh_key) = token_keys(&account.uuid);
+            account.access_token = CredentialStore::get(&access_key)?.unwrap_or_default();
+            account.refresh_token = CredentialStore::get(&refresh_key)?.unwrap_or_default();
+        }
+
+        if plain_text_tokens {
+            match Self::save_accounts(&data) {
+                Ok(()) => println!("✓ Moved account tokens to the {}", CredentialStore::backend_name()),
+                Err(e) => println!("⚠ Failed to move account tokens out of accounts.json: {}", e),
+            }
+        }
+
         Ok(data)
     }
 
+    /// Tokens go to the credential store, accounts.json only keeps the profile
     fn save_accounts(data: &AccountsData) -> Result<(), Box<dyn std::error::Error>> {
+        let mut stripped = data.clone();
+        for account in stripped.accounts.values_mut() {
+            let (access_key, refresh_key) = token_keys(&account.uuid);
+            CredentialStore::set(&access_key, &account.access_token)?;
+            CredentialStore::set(&refresh_key, &account.refresh_token)?;
+            account.access_token.clear();
+            account.refresh_token.clear();
+        }
+
         let path = Self::get_accounts_file()?;
-        let json = serde_json::to_string_pretty(data)?;
+        let json = serde_json::to_string_pretty(&stripped)?;
         fs::write(path, json)?;
         Ok(())
     }
 
+    /// Remove every account's tokens from the credential store, before accounts.json goes
+    pub fn forget_credentials() -> Result<(), Box<dyn std::error::Error>> {
+        for uuid in Self::load_accounts()?.accounts.keys() {
+            let (access_key, refresh_key) = token_keys(uuid);
+            CredentialStore::delete(&access_key)?;
+            CredentialStore::delete(&refresh_key)?;
+        }
+        Ok(())
+    }
+
     pub fn add_account(
         uuid: String,
         username: String,
@@ -64,6 +109,34 @@ impl AccountManager {
         Ok(())
     }
 
+    /// Add the account of a finished login, or update its tokens when it's already known, and
+    /// make it the active account
+    pub fn store_login(auth: &AuthResponse) -> Result<AccountInfo, Box<dyn std::error::Error>> {
+        if Self::account_exists(&auth.uuid)? {
+            Self::update_account_tokens(
+                &auth.uuid,
+                auth.access_token.clone(),
+                auth.refresh_token.clone(),
+                auth.token_expiry,
+            )?;
+            Self::set_username(&auth.uuid, &auth.username)?;
+            Self::set_active_account(&auth.uuid)?;
+        } else {
+            Self::add_account(
+                auth.uuid.clone(),
+                auth.username.clone(),
+                auth.access_token.clone(),
+                auth.refresh_token.clone(),
+                auth.token_expiry,
+            )?;
+        }
+
+        Self::get_all_accounts()?
+            .into_iter()
+            .find(|acc| acc.uuid == auth.uuid)
+            .ok_or_else(|| "Failed to retrieve account info".into())
+    }
+
     pub fn account_exists(uuid: &str) -> Result<bool, Box<dyn std::error::Error>> {
         let data = Self::load_accounts()?;
         Ok(data.accounts.contains_key(uuid))
@@ -119,6 +192,9 @@ impl AccountManager {
         let mut data = Self::load_accounts()?;
         
         data.accounts.remove(uuid);
+        let (access_key, refresh_key) = token_keys(uuid);
+        CredentialStore::delete(&access_key)?;
+        CredentialStore::delete(&refresh_key)?;
         
         // If removed account was active, clear active account
         if data.active_account_uuid.as_ref() == Some(&uuid.to_string()) {
@@ -151,8 +227,13 @@ impl AccountManager {
         Ok(())
     }
 
-    pub async fn get_valid_token(uuid: &str) -> Result<String, Box<dyn std::error::Error>> {
-        // Load fresh data to check token status
+    /// A Minecraft access token for the account that is good for at least five more minutes,
+    /// refreshed through the stored Microsoft refresh token when it isn't
+    pub async fn ensure_fresh_token(uuid: &str) -> Result<String, Box<dyn std::error::Error>> {
+        // Microsoft rotates refresh tokens, two refreshes at once would leave one of them stale
+        let _guard = REFRESH_LOCK.lock().await;
+
+        // Loaded after the lock, a refresh that just finished has already written new tokens
         let data = Self::load_accounts()?;
         let account = data
             .accounts
@@ -164,7 +245,8 @@ impl AccountManager {
         let buffer = chrono::Duration::minutes(5);
         
         // Check if token is still valid (expires more than 5 minutes from now)
-        if account.token_expiry > now + buffer {
+        // A missing token means the credential store lost it, refreshing gets a new one
+        if !account.access_token.is_empty() && account.token_expiry > now + buffer {
             let minutes_until_expiry = (account.token_expiry - now).num_minutes();
             println!("Token still valid (expires in {} minutes)", minutes_until_expiry);
             return Ok(account.access_token);
@@ -174,7 +256,10 @@ impl AccountManager {
         println!("Token expired or expiring soon, refreshing...");
         
         let authenticator = crate::auth::Authenticator::new()?;
-        let refreshed = authenticator.refresh_tokens(&account.refresh_token).await?;
+        let refreshed = authenticator
+            .refresh_tokens(&account.refresh_token)
+            .await
+            .map_err(|e| format!("Could not refresh the session of {}, please sign in again: {}", account.username, e))?;
         
         // Update the account with new tokens
         Self::update_account_tokens(
@@ -183,8 +268,28 @@ impl AccountManager {
             refreshed.refresh_token,
             refreshed.token_expiry,
         )?;
+
+        if refreshed.username != account.username {
+            Self::set_username(uuid, &refreshed.username)?;
+        }
         
         println!("✓ Token refreshed successfully");
         Ok(refreshed.access_token)
     }
-}
\ No newline at end of file
+
+    /// Picks up a name change made on minecraft.net
+    fn set_username(uuid: &str, username: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut data = Self::load_accounts()?;
+        if let Some(account) = data.accounts.get_mut(uuid) {
+            println!("Account {} is now named {}", account.username, username);
+            account.username = username.to_string();
+        }
+        Self::save_accounts(&data)
+    }
+}
+
+/// Separate entries, Windows Credential Manager limits each secret to 2560 bytes even when
+/// stored as bytes
+fn token_keys(uuid: &str) -> (String, String) {
+    (format!("{}-access-token", uuid), format!("{}-refresh-token", uuid))
+}
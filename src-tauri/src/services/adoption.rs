@@ -0,0 +1,257 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::orphans::detect_setup;
+use crate::utils::links::create_dir_link;
+use crate::utils::{get_instance_dir, get_launcher_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Owned by the official launcher, or replaced by the launcher's own copies
+const NOT_LINKED: &[&str] = &[
+    "versions",
+    "libraries",
+    "assets",
+    "runtime",
+    "bin",
+    "natives",
+    "webcache",
+    "webcache2",
+    "logs",
+    "instance.json",
+    "clientId.txt",
+];
+
+/// A version folder of the game directory, as the official launcher installed it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameDirVersion {
+    pub id: String,
+    pub minecraft_version: String,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameDirInspection {
+    pub path: String,
+    pub versions: Vec<GameDirVersion>,
+    /// What the folder would be adopted as, the version last played where it's known
+    pub detected: Option<GameDirVersion>,
+    pub mod_count: usize,
+    pub world_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdoptedGameDir {
+    pub instance: Instance,
+    /// Folders linked into the instance
+    pub linked: Vec<String>,
+    /// Loose files copied, such as options.txt, so the original keeps its own
+    pub copied: Vec<String>,
+}
+
+pub struct GameDirAdopter;
+
+impl GameDirAdopter {
+    /// Check that the path is a game directory and work out what it runs
+    pub fn inspect(path: &Path) -> Result<GameDirInspection, Box<dyn std::error::Error>> {
+        let game_dir = validate_game_dir(path)?;
+        let versions = installed_versions(&game_dir);
+
+        let detected = last_played_version(&game_dir)
+            .and_then(|id| versions.iter().find(|v| v.id == id).cloned())
+            .or_else(|| newest_version(&game_dir, &versions))
+            .or_else(|| {
+                // Folders copied without versions/ still have logs and mods to go by
+                let setup = detect_setup(&game_dir);
+                setup.minecraft_version.map(|minecraft_version| GameDirVersion {
+                    id: minecraft_version.clone(),
+                    minecraft_version,
+                    loader: setup.loader,
+                    loader_version: setup.loader_version,
+                })
+            });
+
+        Ok(GameDirInspection {
+            path: game_dir.to_string_lossy().to_string(),
+            versions,
+            detected,
+            mod_count: count_entries(&game_dir.join("mods"), |p| p.extension().is_some_and(|e| e == "jar")),
+            world_count: count_entries(&game_dir.join("saves"), |p| p.join("level.dat").exists()),
+        })
+    }
+
+    /// Link the game directory's folders into a freshly created instance and copy its loose
+    /// files. The instance remembers the folder so its layout is left alone from then on.
+    pub fn link_into(instance: &mut Instance, game_dir: &Path) -> Result<AdoptedGameDir, Box<dyn std::error::Error>> {
+        let game_dir = validate_game_dir(game_dir)?;
+        let instance_dir = get_instance_dir(&instance.name);
+        let mut linked = Vec::new();
+        let mut copied = Vec::new();
+
+        for entry in fs::read_dir(long_path(&game_dir))?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if NOT_LINKED.contains(&name.as_str()) || name.starts_with("launcher_") {
+                continue;
+            }
+
+            let source = entry.path();
+            let target = instance_dir.join(&name);
+            if source.is_dir() {
+                // The empty folders a new instance starts with make way for the real ones
+                if target.is_dir() {
+                    fs::remove_dir(&target).map_err(|_| format!("{} is not empty", target.display()))?;
+                }
+                create_dir_link(&source, &target)?;
+                linked.push(name);
+            } else if source.is_file() {
+                fs::copy(long_path(&source), long_path(&target))?;
+                copied.push(name);
+            }
+        }
+
+        instance.linked_game_dir = Some(game_dir.to_string_lossy().to_string());
+        InstanceManager::save(instance)?;
+
+        println!(
+            "✓ Adopted {} as '{}': {} folders linked, {} files copied",
+            game_dir.display(),
+            instance.name,
+            linked.len(),
+            copied.len()
+        );
+        Ok(AdoptedGameDir {
+            instance: instance.clone(),
+            linked,
+            copied,
+        })
+    }
+}
+
+/// Canonical path of a folder that looks like a game directory and isn't the launcher's own
+fn validate_game_dir(path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if !path.is_dir() {
+        return Err(format!("{} is not a folder", path.display()).into());
+    }
+    let game_dir = fs::canonicalize(path)?;
+
+    let markers = ["saves", "options.txt", "versions", "mods", "resourcepacks"];
+    if !markers.iter().any(|m| game_dir.join(m).exists()) {
+        return Err(format!("{} doesn't look like a Minecraft folder", game_dir.display()).into());
+    }
+
+    if let Ok(launcher_dir) = fs::canonicalize(get_launcher_dir()) {
+        if game_dir.starts_with(&launcher_dir) || launcher_dir.starts_with(&game_dir) {
+            return Err("The launcher's own folders can't be adopted".into());
+        }
+    }
+
+    Ok(game_dir)
+}
+
+fn installed_versions(game_dir: &Path) -> Vec<GameDirVersion> {
+    let Ok(entries) = fs::read_dir(game_dir.join("versions")) else {
+        return Vec::new();
+    };
+
+    let mut versions: Vec<GameDirVersion> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let content = fs::read_to_string(entry.path().join(format!("{}.json", id))).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            Some(describe_version(&id, &json))
+        })
+        .collect();
+    versions.sort_by(|a, b| a.id.cmp(&b.id));
+    versions
+}
+
+/// Loaders are recognised by the library they add, ids vary between installers
+fn describe_version(id: &str, json: &serde_json::Value) -> GameDirVersion {
+    let minecraft_version = json
+        .get("inheritsFrom")
+        .and_then(|v| v.as_str())
+        .unwrap_or(id)
+        .to_string();
+    let libraries: Vec<&str> = json
+        .get("libraries")
+        .and_then(|l| l.as_array())
+        .map(|l| l.iter().filter_map(|lib| lib.get("name").and_then(|n| n.as_str())).collect())
+        .unwrap_or_default();
+
+    let mut loader = None;
+    for name in libraries {
+        let mut parts = name.split(':');
+        let (Some(group), Some(artifact), Some(version)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let found = match (group, artifact) {
+            ("net.fabricmc", "fabric-loader") => Some(("fabric", version.to_string())),
+            ("org.quiltmc", "quilt-loader") => Some(("quilt", version.to_string())),
+            ("com.mumfrey", "liteloader") => Some(("liteloader", version.to_string())),
+            ("net.neoforged", "neoforge") => Some(("neoforge", version.to_string())),
+            ("net.minecraftforge", "forge" | "fmlloader") => {
+                // "1.20.1-47.2.0" for Forge itself, plain "47.2.0" in some profiles
+                let version = version.strip_prefix(&format!("{}-", minecraft_version)).unwrap_or(version);
+                Some(("forge", version.to_string()))
+            }
+            _ => None,
+        };
+        if found.is_some() {
+            loader = found;
+            break;
+        }
+    }
+
+    GameDirVersion {
+        id: id.to_string(),
+        minecraft_version,
+        loader: loader.as_ref().map(|(name, _)| name.to_string()),
+        loader_version: loader.map(|(_, version)| version),
+    }
+}
+
+/// The most recently used profile of the official launcher that plays in this folder
+fn last_played_version(game_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(game_dir.join("launcher_profiles.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    json.get("profiles")?
+        .as_object()?
+        .values()
+        .filter(|profile| {
+            profile
+                .get("gameDir")
+                .and_then(|d| d.as_str())
+                .map_or(true, |d| fs::canonicalize(d).is_ok_and(|d| d == game_dir))
+        })
+        .filter_map(|profile| {
+            let version = profile.get("lastVersionId")?.as_str()?;
+            let last_used = profile.get("lastUsed").and_then(|u| u.as_str()).unwrap_or_default();
+            // "latest-release" and "latest-snapshot" don't name a version
+            (!version.starts_with("latest-")).then(|| (last_used.to_string(), version.to_string()))
+        })
+        .max()
+        .map(|(_, version)| version)
+}
+
+fn newest_version(game_dir: &Path, versions: &[GameDirVersion]) -> Option<GameDirVersion> {
+    versions
+        .iter()
+        .max_by_key(|v| {
+            fs::metadata(game_dir.join("versions").join(&v.id).join(format!("{}.json", v.id)))
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH)
+        })
+        .cloned()
+}
+
+fn count_entries(dir: &Path, matches: impl Fn(&Path) -> bool) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().filter(|e| matches(&e.path())).count())
+        .unwrap_or(0)
+}
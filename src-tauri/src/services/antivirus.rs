@@ -0,0 +1,184 @@
+use crate::utils::{get_launcher_dir, get_logs_dir};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DefenderExclusionInfo {
+    pub supported: bool,
+    pub exclusion_path: String,
+    pub consent_steps: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AntivirusReport {
+    pub detected_at: String,
+    pub affected_paths: Vec<String>,
+    pub suggested_exclusions: Vec<String>,
+}
+
+/// Returned when files vanish or change right after we wrote them
+#[derive(Debug)]
+pub struct AntivirusInterference {
+    pub affected_paths: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for AntivirusInterference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} downloaded file(s) were removed or modified immediately after download. \
+             This is usually caused by antivirus software quarantining game files. \
+             Add {} to your antivirus exclusions and try again.",
+            self.affected_paths.len(),
+            get_launcher_dir().display()
+        )
+    }
+}
+
+impl std::error::Error for AntivirusInterference {}
+
+pub struct AntivirusDetector;
+
+impl AntivirusDetector {
+    fn get_report_path() -> PathBuf {
+        get_logs_dir().join("antivirus_report.json")
+    }
+
+    /// Whether a real-time scanner here can quarantine files behind our back. Only Windows
+    /// ships one by default (Defender), elsewhere downloads aren't re-checked.
+    pub fn can_quarantine() -> bool {
+        cfg!(target_os = "windows")
+    }
+
+    /// Re-check freshly downloaded files. Anything missing or with a changed hash is suspicious.
+    pub fn find_tampered(files: &[(PathBuf, String)]) -> Vec<PathBuf> {
+        files
+            .iter()
+            .filter(|(path, expected_sha1)| {
+                match fs::read(path) {
+                    Ok(contents) => {
+                        let mut hasher = Sha1::new();
+                        hasher.update(&contents);
+                        format!("{:x}", hasher.finalize()) != *expected_sha1
+                    }
+                    Err(_) => true,
+                }
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    pub fn record(affected: &[PathBuf]) -> Result<AntivirusReport, Box<dyn std::error::Error + Send + Sync>> {
+        let mut suggested_exclusions = vec![get_launcher_dir().to_string_lossy().to_string()];
+
+        // Also list the exact folders, for AV products that don't accept recursive exclusions
+        for path in affected {
+            if let Some(parent) = path.parent() {
+                let parent = parent.to_string_lossy().to_string();
+                if !suggested_exclusions.contains(&parent) {
+                    suggested_exclusions.push(parent);
+                }
+            }
+        }
+
+        let report = AntivirusReport {
+            detected_at: Utc::now().to_rfc3339(),
+            affected_paths: affected.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+            suggested_exclusions,
+        };
+
+        let report_path = Self::get_report_path();
+        if let Some(parent) = report_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+
+        Ok(report)
+    }
+
+    pub fn load_report() -> Result<Option<AntivirusReport>, Box<dyn std::error::Error>> {
+        let report_path = Self::get_report_path();
+
+        if !report_path.exists() {
+            return Ok(None);
+        }
+
+        let report: AntivirusReport = serde_json::from_str(&fs::read_to_string(&report_path)?)?;
+        Ok(Some(report))
+    }
+
+    pub fn clear_report() -> Result<(), Box<dyn std::error::Error>> {
+        let report_path = Self::get_report_path();
+
+        if report_path.exists() {
+            fs::remove_file(&report_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Everything the user needs to know before agreeing to the exclusion
+    pub fn defender_exclusion_info() -> DefenderExclusionInfo {
+        let exclusion_path = get_launcher_dir().to_string_lossy().to_string();
+
+        DefenderExclusionInfo {
+            supported: cfg!(target_os = "windows"),
+            consent_steps: vec![
+                format!(
+                    "Windows Defender will stop scanning {} and everything inside it.",
+                    exclusion_path
+                ),
+                "Windows will show an administrator prompt (UAC). Nothing changes unless you accept it.".to_string(),
+                "You can remove the exclusion at any time in Windows Security > Virus & threat protection > Exclusions.".to_string(),
+            ],
+            exclusion_path,
+        }
+    }
+
+    /// Add the launcher directory to Defender exclusions through an elevated PowerShell
+    #[cfg(target_os = "windows")]
+    pub fn add_defender_exclusion() -> Result<(), Box<dyn std::error::Error>> {
+        use base64::{engine::general_purpose, Engine as _};
+        use std::process::Command;
+
+        let exclusion_path = get_launcher_dir().to_string_lossy().to_string();
+        let script = format!(
+            "Add-MpPreference -ExclusionPath '{}'",
+            exclusion_path.replace('\'', "''")
+        );
+
+        // -EncodedCommand expects UTF-16LE, which also keeps non-ASCII user names intact
+        let utf16: Vec<u8> = script.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+        let encoded = general_purpose::STANDARD.encode(utf16);
+
+        let output = Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                &format!(
+                    "$p = Start-Process powershell -Verb RunAs -Wait -PassThru -WindowStyle Hidden \
+                     -ArgumentList '-NoProfile','-EncodedCommand','{}'; exit $p.ExitCode",
+                    encoded
+                ),
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Exclusion was not added (elevation declined or PowerShell failed): {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+
+        println!("✓ Added {} to Windows Defender exclusions", exclusion_path);
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn add_defender_exclusion() -> Result<(), Box<dyn std::error::Error>> {
+        Err("Windows Defender exclusions are only available on Windows".into())
+    }
+}
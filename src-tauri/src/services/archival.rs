@@ -0,0 +1,191 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::layout::LayoutManager;
+use crate::utils::archive::{extract_prefixed, read_entry};
+use crate::utils::{get_instance_dir, get_launcher_dir, long_path};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+// Rebuilt on launch, not worth keeping in cold storage
+const SKIPPED: &[&str] = &["natives", ".fabric", ".cache"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedInstance {
+    pub name: String,
+    pub version: String,
+    pub loader: Option<String>,
+    pub group: Option<String>,
+    pub archived_at: String,
+    pub size_bytes: u64,
+}
+
+pub struct ArchiveManager;
+
+impl ArchiveManager {
+    pub fn get_archives_dir() -> PathBuf {
+        get_launcher_dir().join("archives")
+    }
+
+    fn get_archive_path(instance_name: &str) -> PathBuf {
+        Self::get_archives_dir().join(format!("{}.zip", instance_name))
+    }
+
+    /// Compress the instance into launcher_dir/archives and remove it from the instances folder
+    pub fn archive(instance_name: &str) -> Result<ArchivedInstance, Box<dyn std::error::Error>> {
+        let instance = InstanceManager::load(instance_name)?;
+        let instance_dir = get_instance_dir(instance_name);
+        let archive_path = Self::get_archive_path(instance_name);
+
+        if archive_path.exists() {
+            return Err(format!("An archive named '{}' already exists", instance_name).into());
+        }
+
+        if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(instance_name) {
+            return Err(format!("Instance '{}' is running", instance_name).into());
+        }
+
+        fs::create_dir_all(Self::get_archives_dir())?;
+
+        // Written under a temporary name so a failed archive never looks complete
+        let temp_path = archive_path.with_extension("zip.tmp");
+        let result = write_archive(&instance_dir, &temp_path);
+        if let Err(e) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+        fs::rename(&temp_path, &archive_path)?;
+
+        fs::remove_dir_all(&instance_dir)?;
+
+        let size_bytes = fs::metadata(&archive_path)?.len();
+        println!("✓ Archived '{}' ({} bytes)", instance_name, size_bytes);
+
+        Ok(ArchivedInstance {
+            name: instance.name,
+            version: instance.version,
+            loader: instance.loader,
+            group: instance.group,
+            archived_at: Utc::now().to_rfc3339(),
+            size_bytes,
+        })
+    }
+
+    /// Unpack an archived instance back into the instances folder and drop the archive
+    pub fn restore(instance_name: &str) -> Result<Instance, Box<dyn std::error::Error>> {
+        let archive_path = Self::get_archive_path(instance_name);
+        let instance_dir = get_instance_dir(instance_name);
+
+        if !archive_path.exists() {
+            return Err(format!("No archive named '{}'", instance_name).into());
+        }
+
+        if instance_dir.exists() {
+            return Err(format!("Instance '{}' already exists", instance_name).into());
+        }
+
+        if let Err(e) = extract_prefixed(&archive_path, &[""], &instance_dir) {
+            let _ = fs::remove_dir_all(&instance_dir);
+            return Err(e);
+        }
+
+        let instance = InstanceManager::load(instance_name)?;
+
+        // Shared directories were archived as links only
+        LayoutManager::apply(&instance)?;
+
+        fs::remove_file(&archive_path)?;
+        println!("✓ Restored '{}' from archive", instance_name);
+
+        Ok(instance)
+    }
+
+    pub fn list() -> Result<Vec<ArchivedInstance>, Box<dyn std::error::Error>> {
+        let archives_dir = Self::get_archives_dir();
+        let mut archived = Vec::new();
+
+        if !archives_dir.exists() {
+            return Ok(archived);
+        }
+
+        for entry in fs::read_dir(&archives_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("zip") {
+                continue;
+            }
+
+            let Some(instance) = read_entry(&path, "instance.json")
+                .and_then(|bytes| serde_json::from_slice::<Instance>(&bytes).ok())
+            else {
+                println!("⚠ Skipping unreadable archive {}", path.display());
+                continue;
+            };
+
+            let metadata = fs::metadata(&path)?;
+            let archived_at = metadata
+                .modified()
+                .map(|t| chrono::DateTime::<Utc>::from(t).to_rfc3339())
+                .unwrap_or_default();
+
+            archived.push(ArchivedInstance {
+                name: instance.name,
+                version: instance.version,
+                loader: instance.loader,
+                group: instance.group,
+                archived_at,
+                size_bytes: metadata.len(),
+            });
+        }
+
+        archived.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(archived)
+    }
+}
+
+fn write_archive(instance_dir: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut zip = ZipWriter::new(fs::File::create(long_path(destination))?);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+
+    for entry in fs::read_dir(instance_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if SKIPPED.contains(&name.as_str()) {
+            continue;
+        }
+        add_path(&mut zip, &entry.path(), &name, options)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Links (shared directories) are skipped, the layout recreates them on restore
+fn add_path(
+    zip: &mut ZipWriter<fs::File>,
+    path: &Path,
+    archive_name: &str,
+    options: SimpleFileOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = fs::symlink_metadata(path)?;
+
+    if metadata.is_dir() {
+        zip.add_directory(format!("{}/", archive_name), options)?;
+        for entry in fs::read_dir(long_path(path))? {
+            let entry = entry?;
+            let child_name = format!("{}/{}", archive_name, entry.file_name().to_string_lossy());
+            add_path(zip, &entry.path(), &child_name, options)?;
+        }
+    } else if metadata.is_file() {
+        zip.start_file(archive_name, options)?;
+        let mut source = fs::File::open(long_path(path))?;
+        std::io::copy(&mut source, zip)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::{get_instance_dir, get_launcher_dir, get_meta_dir, long_path};
+use base64::Engine;
+use chrono::{Local, Utc};
+use ring::signature::{self, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const ATTESTATIONS_DIR: &str = "attestations";
+/// Launcher signing key, PKCS#8. Created on first use and never leaves this machine.
+const KEY_FILE: &str = "attestation.key";
+/// Loose files in the instance folder that hold game options
+const OPTIONS_FILES: &[&str] = &["options.txt", "optionsof.txt", "optionsshaders.txt"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationStage {
+    Launch,
+    Exit,
+    Manual,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttestedFile {
+    /// Relative to the instance folder
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+    pub mod_id: Option<String>,
+    pub mod_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttestationManifest {
+    pub instance_name: String,
+    pub stage: AttestationStage,
+    pub created_at: String,
+    pub launcher_build: String,
+    pub minecraft_version: String,
+    /// Launch profile id, includes the loader
+    pub version_id: String,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    /// None when the vanilla jar is missing
+    pub client_jar_sha256: Option<String>,
+    /// Enabled jars in mods/
+    pub mods: Vec<AttestedFile>,
+    /// Options files and everything under config/
+    pub options: Vec<AttestedFile>,
+}
+
+/// A manifest with an Ed25519 signature over its JSON
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignedAttestation {
+    pub manifest: AttestationManifest,
+    /// Base64 Ed25519 public key of the launcher that signed it
+    pub public_key: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttestationCheck {
+    pub valid: bool,
+    /// First 16 hex characters of the public key's SHA256, to compare across a runner's submissions
+    pub key_fingerprint: String,
+    pub manifest: AttestationManifest,
+}
+
+pub struct RunAttestor;
+
+impl RunAttestor {
+    /// Hash the instance's game jar, mods and options, sign the result and store it in
+    /// attestations/. Returns the signed manifest.
+    pub fn attest(instance: &Instance, stage: AttestationStage) -> Result<SignedAttestation, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+        let client_jar = get_meta_dir()
+            .join("versions")
+            .join(&minecraft_version)
+            .join(format!("{}.jar", minecraft_version));
+
+        let manifest = AttestationManifest {
+            instance_name: instance.name.clone(),
+            stage,
+            created_at: Utc::now().to_rfc3339(),
+            launcher_build: include_str!("../../commit_hash.txt").trim().to_string(),
+            client_jar_sha256: fs::read(long_path(&client_jar)).ok().map(|bytes| sha256_hex(&bytes)),
+            minecraft_version,
+            version_id: instance.version.clone(),
+            loader: instance.loader.clone(),
+            loader_version: instance.loader_version.clone(),
+            mods: attested_mods(&instance_dir)?,
+            options: attested_options(&instance_dir)?,
+        };
+
+        let key_pair = signing_key()?;
+        let signature = key_pair.sign(&serde_json::to_vec(&manifest)?);
+        let engine = base64::engine::general_purpose::STANDARD;
+        let signed = SignedAttestation {
+            manifest,
+            public_key: engine.encode(key_pair.public_key().as_ref()),
+            signature: engine.encode(signature.as_ref()),
+        };
+
+        let dir = instance_dir.join(ATTESTATIONS_DIR);
+        fs::create_dir_all(&dir)?;
+        let stage_name = serde_json::to_value(stage)?.as_str().unwrap_or("manual").to_string();
+        let path = dir.join(format!("{}-{}.json", Local::now().format("%Y%m%d-%H%M%S"), stage_name));
+        fs::write(&path, serde_json::to_string_pretty(&signed)?)?;
+
+        println!(
+            "✓ Attested '{}' ({} mods, {} option files): {}",
+            instance.name,
+            signed.manifest.mods.len(),
+            signed.manifest.options.len(),
+            path.display()
+        );
+        Ok(signed)
+    }
+
+    /// Check an attestation file's signature. A valid signature shows the manifest wasn't edited
+    /// after signing, the fingerprint shows which launcher install signed it.
+    pub fn verify(path: &Path) -> Result<AttestationCheck, Box<dyn std::error::Error>> {
+        let signed: SignedAttestation = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let engine = base64::engine::general_purpose::STANDARD;
+        let public_key = engine.decode(&signed.public_key)?;
+        let signature = engine.decode(&signed.signature)?;
+
+        let valid = UnparsedPublicKey::new(&signature::ED25519, &public_key)
+            .verify(&serde_json::to_vec(&signed.manifest)?, &signature)
+            .is_ok();
+
+        Ok(AttestationCheck {
+            valid,
+            key_fingerprint: sha256_hex(&public_key)[..16].to_string(),
+            manifest: signed.manifest,
+        })
+    }
+}
+
+fn signing_key() -> Result<Ed25519KeyPair, Box<dyn std::error::Error>> {
+    let key_path = get_launcher_dir().join(KEY_FILE);
+    if !key_path.exists() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new())
+            .map_err(|_| "Failed to generate the attestation key")?;
+        fs::write(&key_path, pkcs8.as_ref())?;
+        println!("✓ Created attestation key at {}", key_path.display());
+    }
+
+    let pkcs8 = fs::read(&key_path)?;
+    Ed25519KeyPair::from_pkcs8(&pkcs8)
+        .map_err(|e| format!("Attestation key at {} is unreadable: {}", key_path.display(), e).into())
+}
+
+fn attested_mods(instance_dir: &Path) -> Result<Vec<AttestedFile>, Box<dyn std::error::Error>> {
+    let mods_dir = instance_dir.join("mods");
+    let Ok(entries) = fs::read_dir(&mods_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut mods = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !file_name.ends_with(".jar") {
+            continue;
+        }
+
+        let metadata = ModMetadataReader::read(&entry.path());
+        let mut file = attested_file(instance_dir, &entry.path())?;
+        file.mod_id = metadata.as_ref().map(|m| m.id.clone());
+        file.mod_version = metadata.map(|m| m.version);
+        mods.push(file);
+    }
+
+    mods.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(mods)
+}
+
+fn attested_options(instance_dir: &Path) -> Result<Vec<AttestedFile>, Box<dyn std::error::Error>> {
+    let mut paths: Vec<PathBuf> = OPTIONS_FILES
+        .iter()
+        .map(|name| instance_dir.join(name))
+        .filter(|path| path.is_file())
+        .collect();
+    collect_files(&instance_dir.join("config"), &mut paths);
+
+    let mut options = paths
+        .iter()
+        .map(|path| attested_file(instance_dir, path))
+        .collect::<Result<Vec<_>, _>>()?;
+    options.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(options)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+fn attested_file(instance_dir: &Path, path: &Path) -> Result<AttestedFile, Box<dyn std::error::Error>> {
+    let bytes = fs::read(long_path(path))?;
+    let relative = path.strip_prefix(instance_dir).unwrap_or(path);
+    Ok(AttestedFile {
+        path: relative.to_string_lossy().replace('\\', "/"),
+        sha256: sha256_hex(&bytes),
+        size: bytes.len() as u64,
+        mod_id: None,
+        mod_version: None,
+    })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
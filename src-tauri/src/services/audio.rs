@@ -0,0 +1,152 @@
+use crate::models::Instance;
+use crate::utils::game_options::set_options;
+use crate::utils::{get_instance_dir, get_launcher_dir};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// Sound categories as named in `soundCategory_<name>` entries of options.txt
+pub const SOUND_CATEGORIES: &[&str] = &[
+    "master", "music", "record", "weather", "block", "hostile", "neutral", "player", "ambient", "voice",
+];
+
+/// (id, name, volumes) of the profiles that ship with the launcher
+const BUILT_IN_PROFILES: &[(&str, &str, &[(&str, f32)])] = &[
+    ("no_music", "No music", &[("music", 0.0), ("record", 0.0)]),
+    ("farming", "Quiet farm", &[("music", 0.0), ("neutral", 0.2), ("block", 0.3), ("hostile", 0.5)]),
+    ("muted", "Muted", &[("master", 0.0)]),
+];
+
+/// Volumes for some sound categories, the ones left out keep whatever the player set in game
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioProfile {
+    pub id: String,
+    pub name: String,
+    /// Category -> volume from 0.0 to 1.0
+    pub volumes: BTreeMap<String, f32>,
+    #[serde(default)]
+    pub built_in: bool,
+}
+
+pub struct AudioProfileManager;
+
+impl AudioProfileManager {
+    fn get_profiles_path() -> PathBuf {
+        get_launcher_dir().join("audio_profiles.json")
+    }
+
+    /// Built-in profiles first, then the user's own
+    pub fn get_all() -> Result<Vec<AudioProfile>, Box<dyn std::error::Error>> {
+        let mut profiles: Vec<AudioProfile> = BUILT_IN_PROFILES
+            .iter()
+            .map(|(id, name, volumes)| AudioProfile {
+                id: id.to_string(),
+                name: name.to_string(),
+                volumes: volumes.iter().map(|(c, v)| (c.to_string(), *v)).collect(),
+                built_in: true,
+            })
+            .collect();
+
+        profiles.extend(Self::load_custom()?);
+        Ok(profiles)
+    }
+
+    pub fn get(profile_id: &str) -> Result<AudioProfile, Box<dyn std::error::Error>> {
+        Self::get_all()?
+            .into_iter()
+            .find(|p| p.id == profile_id)
+            .ok_or_else(|| format!("Audio profile '{}' not found", profile_id).into())
+    }
+
+    /// Create or replace a custom profile
+    pub fn save(mut profile: AudioProfile) -> Result<AudioProfile, Box<dyn std::error::Error>> {
+        Self::validate(&profile)?;
+        if BUILT_IN_PROFILES.iter().any(|(id, _, _)| *id == profile.id) {
+            return Err(format!("'{}' is a built-in profile", profile.id).into());
+        }
+
+        profile.built_in = false;
+        let mut profiles = Self::load_custom()?;
+        match profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile.clone(),
+            None => profiles.push(profile.clone()),
+        }
+
+        Self::save_custom(&profiles)?;
+        Ok(profile)
+    }
+
+    pub fn delete(profile_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut profiles = Self::load_custom()?;
+        let before = profiles.len();
+        profiles.retain(|p| p.id != profile_id);
+
+        if profiles.len() == before {
+            return Err(format!("Audio profile '{}' not found", profile_id).into());
+        }
+        Self::save_custom(&profiles)
+    }
+
+    /// Write the instance's profile into options.txt, a missing profile is skipped with a warning
+    pub fn apply(instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(profile_id) = &instance.audio_profile else {
+            return Ok(());
+        };
+
+        let profile = match Self::get(profile_id) {
+            Ok(profile) => profile,
+            Err(e) => {
+                println!("⚠ {}, leaving sound settings of '{}' alone", e, instance.name);
+                return Ok(());
+            }
+        };
+
+        let values: Vec<(String, String)> = profile
+            .volumes
+            .iter()
+            .map(|(category, volume)| (format!("soundCategory_{}", category), volume.to_string()))
+            .collect();
+        let values: Vec<(&str, String)> = values.iter().map(|(k, v)| (k.as_str(), v.clone())).collect();
+
+        let options_path = get_instance_dir(&instance.name).join("options.txt");
+        set_options(&options_path, &values)?;
+        Ok(())
+    }
+
+    fn validate(profile: &AudioProfile) -> Result<(), String> {
+        let valid_id = !profile.id.is_empty()
+            && profile.id.len() <= 64
+            && profile.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_id {
+            return Err(format!("Invalid audio profile id '{}'", profile.id));
+        }
+        if profile.name.trim().is_empty() {
+            return Err("Audio profile needs a name".to_string());
+        }
+
+        for (category, volume) in &profile.volumes {
+            if !SOUND_CATEGORIES.contains(&category.as_str()) {
+                return Err(format!("Unknown sound category '{}'", category));
+            }
+            if !(0.0..=1.0).contains(volume) {
+                return Err(format!("Volume for '{}' must be between 0 and 1", category));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_custom() -> Result<Vec<AudioProfile>, Box<dyn std::error::Error>> {
+        let path = Self::get_profiles_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save_custom(profiles: &[AudioProfile]) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(get_launcher_dir())?;
+        fs::write(Self::get_profiles_path(), serde_json::to_string_pretty(profiles)?)?;
+        Ok(())
+    }
+}
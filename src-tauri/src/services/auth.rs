@@ -0,0 +1,241 @@
+use crate::auth::Authenticator;
+use crate::models::{AccountInfo, AuthResponse};
+use crate::services::accounts::AccountManager;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
+const TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const SCOPE: &str = "XboxLive.signin offline_access";
+
+lazy_static::lazy_static! {
+    /// Logins waiting for the user, keyed by the code they enter. The device code itself never
+    /// leaves the backend.
+    static ref PENDING: Mutex<HashMap<String, PendingLogin>> = Mutex::new(HashMap::new());
+}
+
+struct PendingLogin {
+    device_code: String,
+    expires_at: DateTime<Utc>,
+    interval: u64,
+}
+
+/// What the user needs to sign in on another device or in the browser
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceLoginStart {
+    /// Pass back to `poll`, the same as the code shown to the user
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_at: DateTime<Utc>,
+    /// Seconds to wait between polls
+    pub interval: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeviceLoginPoll {
+    /// The user hasn't finished signing in yet
+    Pending { interval: u64 },
+    Expired,
+    Declined,
+    /// Signed in, the account is stored and active
+    Complete { account: AccountInfo },
+}
+
+#[derive(Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: u64,
+    interval: Option<u64>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DeviceTokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EntitlementsResponse {
+    #[serde(default)]
+    items: Vec<Entitlement>,
+}
+
+#[derive(Deserialize)]
+struct Entitlement {
+    name: String,
+}
+
+/// Microsoft sign-in without a local callback server, for machines where the browser can't
+/// reach the launcher or there is no browser at all
+pub struct DeviceLogin;
+
+impl DeviceLogin {
+    pub async fn begin() -> Result<DeviceLoginStart, Box<dyn std::error::Error>> {
+        let response = http_client()?
+            .post(DEVICE_CODE_URL)
+            .form(&[("client_id", env!("MICROSOFT_CLIENT_ID")), ("scope", SCOPE)])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Device code request failed: {}", error_text).into());
+        }
+
+        let code: DeviceCodeResponse = response.json().await?;
+        let expires_at = Utc::now() + chrono::Duration::seconds(code.expires_in as i64);
+        let interval = code.interval.unwrap_or(5);
+
+        let mut pending = PENDING.lock().unwrap();
+        pending.retain(|_, login| login.expires_at > Utc::now());
+        pending.insert(
+            code.user_code.clone(),
+            PendingLogin {
+                device_code: code.device_code,
+                expires_at,
+                interval,
+            },
+        );
+
+        println!("Waiting for device login with code {}", code.user_code);
+        Ok(DeviceLoginStart {
+            message: code.message.unwrap_or_else(|| {
+                format!("Go to {} and enter the code {}", code.verification_uri, code.user_code)
+            }),
+            user_code: code.user_code,
+            verification_uri: code.verification_uri,
+            expires_at,
+            interval,
+        })
+    }
+
+    /// Ask once whether the user has signed in, finishing the login when they have
+    pub async fn poll(user_code: &str) -> Result<DeviceLoginPoll, Box<dyn std::error::Error>> {
+        let (device_code, interval) = {
+            let pending = PENDING.lock().unwrap();
+            let login = pending.get(user_code).ok_or("No login is waiting for this code")?;
+            if login.expires_at <= Utc::now() {
+                drop(pending);
+                PENDING.lock().unwrap().remove(user_code);
+                return Ok(DeviceLoginPoll::Expired);
+            }
+            (login.device_code.clone(), login.interval)
+        };
+
+        let token: DeviceTokenResponse = http_client()?
+            .post(TOKEN_URL)
+            .form(&[
+                ("grant_type", DEVICE_CODE_GRANT),
+                ("client_id", env!("MICROSOFT_CLIENT_ID")),
+                ("device_code", device_code.as_str()),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match token.error.as_deref() {
+            None => {}
+            Some("authorization_pending") => return Ok(DeviceLoginPoll::Pending { interval }),
+            Some("slow_down") => {
+                let interval = interval + 5;
+                if let Some(login) = PENDING.lock().unwrap().get_mut(user_code) {
+                    login.interval = interval;
+                }
+                return Ok(DeviceLoginPoll::Pending { interval });
+            }
+            Some(error) => {
+                PENDING.lock().unwrap().remove(user_code);
+                return match error {
+                    "expired_token" => Ok(DeviceLoginPoll::Expired),
+                    "authorization_declined" => Ok(DeviceLoginPoll::Declined),
+                    _ => Err(format!(
+                        "Device login failed: {}",
+                        token.error_description.as_deref().unwrap_or(error)
+                    )
+                    .into()),
+                };
+            }
+        }
+
+        // The code is spent either way, a failure below means starting over
+        PENDING.lock().unwrap().remove(user_code);
+
+        let msa_token = token.access_token.ok_or("No access token received")?;
+        let refresh_token = token.refresh_token.ok_or("No refresh token received")?;
+        println!("✓ Microsoft access token obtained");
+
+        let auth = complete_login(&msa_token, refresh_token).await?;
+        let account = AccountManager::store_login(&auth)?;
+        Ok(DeviceLoginPoll::Complete { account })
+    }
+
+    /// Stop waiting for a login the user abandoned
+    pub fn cancel(user_code: &str) {
+        PENDING.lock().unwrap().remove(user_code);
+    }
+}
+
+/// Xbox Live, XSTS and Minecraft tokens, then the ownership check and profile
+async fn complete_login(msa_token: &str, refresh_token: String) -> Result<AuthResponse, Box<dyn std::error::Error>> {
+    let authenticator = Authenticator::new()?;
+
+    let xbl_token = authenticator.authenticate_xbox(msa_token).await?;
+    println!("✓ Xbox Live token obtained");
+
+    let (xsts_token, userhash) = authenticator.obtain_xsts(&xbl_token.token).await?;
+    println!("✓ XSTS token obtained");
+
+    let mc_token = authenticator.authenticate_minecraft(&xsts_token.token, &userhash).await?;
+    println!("✓ Minecraft access token obtained");
+
+    if !owns_minecraft(&mc_token.token).await? {
+        return Err("Account does not own Minecraft".into());
+    }
+
+    let profile = authenticator.get_minecraft_profile(&mc_token.token).await?;
+    println!("✓ Device login complete for {}", profile.name);
+
+    Ok(AuthResponse {
+        access_token: mc_token.token.to_string(),
+        refresh_token,
+        token_expiry: mc_token.expiry,
+        username: profile.name.to_string(),
+        uuid: profile.id.to_string(),
+    })
+}
+
+/// Game Pass accounts hold `product_minecraft` instead of `game_minecraft`
+async fn owns_minecraft(access_token: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let response = http_client()?
+        .get("https://api.minecraftservices.com/entitlements/mcstore")
+        .bearer_auth(access_token)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await?;
+        return Err(format!("Failed to check entitlements: {}", error_text).into());
+    }
+
+    let entitlements: EntitlementsResponse = response.json().await?;
+    Ok(entitlements
+        .items
+        .iter()
+        .any(|item| item.name == "game_minecraft" || item.name == "product_minecraft"))
+}
+
+fn http_client() -> Result<reqwest::Client, reqwest::Error> {
+    crate::utils::http::client_builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+}
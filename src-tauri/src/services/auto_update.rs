@@ -0,0 +1,135 @@
+use crate::models::{AutoUpdateSchedule, Instance};
+use crate::services::instance::InstanceManager;
+use crate::services::mod_updates::{ModUpdate, ModUpdater};
+use crate::services::snapshot::SnapshotManager;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+/// How often the scheduler looks for instances that are due
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+/// Wait after startup so the first check doesn't compete with the UI loading
+const STARTUP_DELAY: std::time::Duration = std::time::Duration::from_secs(2 * 60);
+
+/// Sent as `mod-auto-update` after a run that found updates
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutoUpdateSummary {
+    pub instance_name: String,
+    /// Snapshot taken before anything changed, None when there was nothing to update
+    pub snapshot_id: Option<String>,
+    pub updated: Vec<ModUpdate>,
+    pub failed: Vec<String>,
+    pub backup_dir: Option<String>,
+}
+
+pub struct ModAutoUpdater;
+
+impl ModAutoUpdater {
+    /// Apply the updates the instance's policy selects, snapshotting mods and config first.
+    /// Updates only come from the instance's own Minecraft version and loader.
+    pub async fn run(instance_name: &str) -> Result<AutoUpdateSummary, Box<dyn std::error::Error>> {
+        let mut instance = InstanceManager::load(instance_name)?;
+        let policy = instance
+            .auto_update
+            .clone()
+            .ok_or_else(|| format!("Instance '{}' has no auto-update policy", instance_name))?;
+
+        let updates: Vec<ModUpdate> = ModUpdater::check(&instance)
+            .await?
+            .into_iter()
+            .filter(|u| policy.project_ids.is_empty() || policy.project_ids.contains(&u.project_id))
+            .collect();
+
+        let mut summary = AutoUpdateSummary {
+            instance_name: instance.name.clone(),
+            snapshot_id: None,
+            updated: Vec::new(),
+            failed: Vec::new(),
+            backup_dir: None,
+        };
+
+        if !updates.is_empty() {
+            let snapshot = SnapshotManager::create(&instance.name, Some("Before automatic mod update".to_string()))?;
+            summary.snapshot_id = Some(snapshot.id);
+
+            let project_ids: Vec<String> = updates.iter().map(|u| u.project_id.clone()).collect();
+            let result = ModUpdater::apply(&instance, &project_ids).await?;
+            summary.updated = updates
+                .into_iter()
+                .filter(|u| result.updated.contains(&u.new_filename))
+                .collect();
+            summary.failed = result.failed;
+            summary.backup_dir = result.backup_dir;
+        }
+
+        if let Some(policy) = instance.auto_update.as_mut() {
+            policy.last_run = Some(Utc::now());
+        }
+        InstanceManager::save(&instance)?;
+
+        println!(
+            "✓ Auto-update of '{}': {} updated, {} failed",
+            instance.name,
+            summary.updated.len(),
+            summary.failed.len()
+        );
+        Ok(summary)
+    }
+
+    /// Run the policy of an instance set to update on launch. Failures are logged, the game
+    /// still starts with the mods it has.
+    pub async fn run_on_launch(instance_name: &str, app_handle: &tauri::AppHandle) {
+        let due = InstanceManager::load(instance_name)
+            .ok()
+            .filter(|i| !i.frozen)
+            .and_then(|i| i.auto_update)
+            .is_some_and(|p| p.schedule == AutoUpdateSchedule::OnLaunch);
+        if due {
+            Self::run_and_notify(instance_name, app_handle).await;
+        }
+    }
+
+    /// Check every hour for instances whose daily or weekly update is due
+    pub fn start_scheduler(app_handle: tauri::AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(STARTUP_DELAY).await;
+            loop {
+                let instances = InstanceManager::get_all().unwrap_or_default();
+                for instance in instances.iter().filter(|i| is_due(i, Utc::now())) {
+                    // Jars can't be swapped under a running game, it's picked up next hour
+                    if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&instance.name) {
+                        continue;
+                    }
+                    Self::run_and_notify(&instance.name, &app_handle).await;
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    async fn run_and_notify(instance_name: &str, app_handle: &tauri::AppHandle) {
+        match Self::run(instance_name).await {
+            Ok(summary) => {
+                if !summary.updated.is_empty() || !summary.failed.is_empty() {
+                    let _ = app_handle.emit("mod-auto-update", summary);
+                }
+            }
+            Err(e) => println!("Warning: Auto-update of '{}' failed: {}", instance_name, e),
+        }
+    }
+}
+
+fn is_due(instance: &Instance, now: DateTime<Utc>) -> bool {
+    let Some(policy) = &instance.auto_update else {
+        return false;
+    };
+    if instance.frozen {
+        return false;
+    }
+    let interval = match policy.schedule {
+        AutoUpdateSchedule::OnLaunch => return false,
+        AutoUpdateSchedule::Daily => Duration::days(1),
+        AutoUpdateSchedule::Weekly => Duration::weeks(1),
+    };
+    policy.last_run.map_or(true, |last| now - last >= interval)
+}
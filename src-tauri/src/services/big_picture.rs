@@ -0,0 +1,157 @@
+use crate::models::Instance;
+use crate::services::accounts::AccountManager;
+use crate::services::instance::InstanceManager;
+use crate::services::recent::RecentLaunches;
+use crate::utils::get_instance_dir;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::Emitter;
+
+/// How many instances the "Continue playing" row shows
+const RECENT_TILES: usize = 6;
+
+/// What pressing A on a tile does
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayAction {
+    Play,
+    Stop,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BigPictureTile {
+    pub instance: String,
+    /// e.g. "Fabric 1.20.1"
+    pub subtitle: String,
+    /// `data:image/png;base64,...`
+    pub icon: Option<String>,
+    pub accent_color: Option<String>,
+    pub emoji: Option<String>,
+    pub running: bool,
+    pub last_played: Option<String>,
+    pub total_playtime_seconds: u64,
+    pub action: PlayAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BigPictureSection {
+    pub id: String,
+    pub title: String,
+    pub tiles: Vec<BigPictureTile>,
+}
+
+/// Everything a controller UI needs in display order, rows top to bottom and tiles left to right
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BigPictureModel {
+    pub sections: Vec<BigPictureSection>,
+    /// Username of the account Play launches with, `None` means sign in first
+    pub account: Option<String>,
+}
+
+pub struct BigPicture;
+
+impl BigPicture {
+    pub fn build() -> Result<BigPictureModel, Box<dyn std::error::Error>> {
+        let instances = InstanceManager::get_all()?;
+        let running: Vec<String> = crate::commands::instances::RUNNING_PROCESSES
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+
+        let tiles: HashMap<&str, BigPictureTile> = instances
+            .iter()
+            .map(|i| (i.name.as_str(), tile(i, running.contains(&i.name))))
+            .collect();
+        let tiles_for = |names: Vec<&str>| -> Vec<BigPictureTile> {
+            names.into_iter().filter_map(|n| tiles.get(n).cloned()).collect()
+        };
+
+        let mut sections = Vec::new();
+        let mut push = |id: String, title: String, tiles: Vec<BigPictureTile>| {
+            if !tiles.is_empty() {
+                sections.push(BigPictureSection { id, title, tiles });
+            }
+        };
+
+        let recent = RecentLaunches::get(RECENT_TILES, true);
+        push(
+            "recent".to_string(),
+            "Continue playing".to_string(),
+            tiles_for(recent.iter().map(|r| r.instance.as_str()).collect()),
+        );
+
+        push(
+            "pinned".to_string(),
+            "Favorites".to_string(),
+            tiles_for(instances.iter().filter(|i| i.pinned).map(|i| i.name.as_str()).collect()),
+        );
+
+        let mut groups: Vec<&str> = instances.iter().filter_map(|i| i.group.as_deref()).collect();
+        groups.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()).then(a.cmp(b)));
+        groups.dedup();
+        for group in groups {
+            push(
+                format!("group:{}", group),
+                group.to_string(),
+                tiles_for(
+                    instances
+                        .iter()
+                        .filter(|i| i.group.as_deref() == Some(group))
+                        .map(|i| i.name.as_str())
+                        .collect(),
+                ),
+            );
+        }
+
+        push(
+            "all".to_string(),
+            "All instances".to_string(),
+            tiles_for(instances.iter().map(|i| i.name.as_str()).collect()),
+        );
+
+        let account = AccountManager::get_active_account().ok().flatten().map(|a| a.username);
+
+        Ok(BigPictureModel { sections, account })
+    }
+
+    /// Send a fresh model as `big-picture-updated`, called whenever a game starts or stops
+    pub fn notify(app_handle: &tauri::AppHandle) {
+        match Self::build() {
+            Ok(model) => {
+                let _ = app_handle.emit("big-picture-updated", model);
+            }
+            Err(e) => println!("⚠ Failed to refresh big picture data: {}", e),
+        }
+    }
+}
+
+fn tile(instance: &Instance, running: bool) -> BigPictureTile {
+    let loader = match instance.loader.as_deref() {
+        Some("fabric") => "Fabric ",
+        Some("quilt") => "Quilt ",
+        Some("liteloader") => "LiteLoader ",
+        Some("optifine") => "OptiFine ",
+        _ => "",
+    };
+
+    let icon = instance.icon_path.as_ref().and_then(|_| {
+        std::fs::read(get_instance_dir(&instance.name).join("icon.png"))
+            .ok()
+            .map(|bytes| format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(bytes)))
+    });
+
+    BigPictureTile {
+        instance: instance.name.clone(),
+        subtitle: format!("{}{}", loader, InstanceManager::base_minecraft_version(&instance.version)),
+        icon,
+        accent_color: instance.accent_color.clone(),
+        emoji: instance.emoji.clone(),
+        running,
+        last_played: instance.last_played.clone(),
+        total_playtime_seconds: instance.total_playtime_seconds,
+        action: if running { PlayAction::Stop } else { PlayAction::Play },
+    }
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+use zip::ZipArchive;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CaseConflictReport {
+    /// Groups of paths that only differ by case and would collide on Windows/macOS
+    pub collisions: Vec<Vec<String>>,
+    /// Resource files whose path Minecraft can't address (resource locations must be lowercase)
+    pub invalid_resource_locations: Vec<String>,
+}
+
+impl CaseConflictReport {
+    pub fn is_empty(&self) -> bool {
+        self.collisions.is_empty() && self.invalid_resource_locations.is_empty()
+    }
+
+    fn extend(&mut self, other: CaseConflictReport) {
+        self.collisions.extend(other.collisions);
+        self.invalid_resource_locations.extend(other.invalid_resource_locations);
+    }
+}
+
+pub struct CaseChecker;
+
+impl CaseChecker {
+    /// Find case-only collisions among relative paths ("a/B.txt" and "A/b.txt" collide on "a")
+    pub fn find_collisions<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<Vec<String>> {
+        // Every parent directory can collide too, not just the files themselves
+        let mut all_paths = BTreeSet::new();
+        for path in paths {
+            let path = path.trim_end_matches('/');
+            let mut prefix = String::new();
+            for part in path.split('/').filter(|p| !p.is_empty()) {
+                if !prefix.is_empty() {
+                    prefix.push('/');
+                }
+                prefix.push_str(part);
+                all_paths.insert(prefix.clone());
+            }
+        }
+
+        let mut groups: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for path in all_paths {
+            groups.entry(path.to_lowercase()).or_default().insert(path);
+        }
+
+        // Only report the outermost colliding level, everything below it follows from that
+        let mut reported: Vec<String> = Vec::new();
+        let mut collisions = Vec::new();
+        for (key, variants) in groups {
+            if variants.len() < 2 || reported.iter().any(|r| key.starts_with(&format!("{}/", r))) {
+                continue;
+            }
+            reported.push(key);
+            collisions.push(variants.into_iter().collect());
+        }
+
+        collisions
+    }
+
+    /// Paths inside a jar or resource pack that break resource location rules
+    pub fn find_invalid_resource_locations<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        paths
+            .into_iter()
+            .filter(|path| !path.ends_with('/'))
+            .filter(|path| {
+                let rest = match path.strip_prefix("assets/").or_else(|| path.strip_prefix("data/")) {
+                    Some(rest) => rest,
+                    None => return false,
+                };
+
+                // Legacy (pre-1.13) lang files are named en_US.lang and are loaded by name
+                if rest.ends_with(".lang") || !rest.contains('/') {
+                    return false;
+                }
+
+                !rest
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "/._-".contains(c))
+            })
+            .map(|path| path.to_string())
+            .collect()
+    }
+
+    fn archive_entries(archive_path: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let file = fs::File::open(archive_path)?;
+        let mut archive = ZipArchive::new(file)?;
+
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            entries.push(archive.by_index(i)?.name().replace('\\', "/"));
+        }
+
+        Ok(entries)
+    }
+
+    /// Check a modpack archive (.mrpack / CurseForge zip) before extracting it
+    pub fn check_pack_archive(archive_path: &Path) -> Result<CaseConflictReport, Box<dyn std::error::Error>> {
+        let entries = Self::archive_entries(archive_path)?;
+
+        Ok(CaseConflictReport {
+            collisions: Self::find_collisions(entries.iter().map(|e| e.as_str())),
+            invalid_resource_locations: Vec::new(),
+        })
+    }
+
+    /// Check a jar or resource pack zip, reporting paths as "archive!/entry"
+    pub fn check_resource_archive(archive_path: &Path) -> Result<CaseConflictReport, Box<dyn std::error::Error>> {
+        let entries = Self::archive_entries(archive_path)?;
+        let archive_name = archive_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let qualify = |path: String| format!("{}!/{}", archive_name, path);
+
+        Ok(CaseConflictReport {
+            collisions: Self::find_collisions(entries.iter().map(|e| e.as_str()))
+                .into_iter()
+                .map(|group| group.into_iter().map(qualify).collect())
+                .collect(),
+            invalid_resource_locations: Self::find_invalid_resource_locations(entries.iter().map(|e| e.as_str()))
+                .into_iter()
+                .map(qualify)
+                .collect(),
+        })
+    }
+
+    /// Scan an instance directory plus its mods and resource packs
+    pub fn check_instance(instance_dir: &Path) -> Result<CaseConflictReport, Box<dyn std::error::Error>> {
+        let mut files = Vec::new();
+        collect_relative_paths(instance_dir, instance_dir, &mut files)?;
+
+        let mut report = CaseConflictReport {
+            collisions: Self::find_collisions(files.iter().map(|f| f.as_str())),
+            invalid_resource_locations: Vec::new(),
+        };
+
+        for (folder, extension) in [("mods", "jar"), ("resourcepacks", "zip")] {
+            let dir = instance_dir.join(folder);
+            if !dir.is_dir() {
+                continue;
+            }
+
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.extension().and_then(|e| e.to_str()) != Some(extension) {
+                    continue;
+                }
+
+                match Self::check_resource_archive(&path) {
+                    Ok(archive_report) => report.extend(archive_report),
+                    Err(e) => println!("✗ Could not inspect {}: {}", path.display(), e),
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Collect paths relative to `root`, using '/' separators and without following links
+fn collect_relative_paths(root: &Path, dir: &Path, out: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.push(relative);
+        }
+
+        if file_type.is_dir() {
+            collect_relative_paths(root, &path, out)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::services::accounts::AccountManager;
+use crate::utils::trash::move_to_trash;
+use crate::utils::{get_instances_dir, get_launcher_dir, get_logs_dir, get_meta_dir, get_shared_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CleanupCategory {
+    pub id: String,
+    pub label: String,
+    pub paths: Vec<String>,
+    pub size_bytes: u64,
+    /// Removing it signs the user out or loses their own content
+    pub personal_data: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CleanupReport {
+    pub categories: Vec<CleanupCategory>,
+    pub removed: Vec<String>,
+    /// Paths that could not be removed, with the reason
+    pub failed: Vec<String>,
+}
+
+pub struct CleanupManager;
+
+impl CleanupManager {
+    /// Everything the launcher writes, grouped the way a user would choose what to keep
+    pub fn categories() -> Vec<CleanupCategory> {
+        let launcher_dir = get_launcher_dir();
+        let meta_dir = get_meta_dir();
+        let mod_details_dir = meta_dir.join("mod-details");
+
+        // Everything in meta except the API cache is downloaded game data
+        let game_files: Vec<PathBuf> = fs::read_dir(&meta_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| *p != mod_details_dir)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut accounts: Vec<PathBuf> = AccountManager::get_accounts_file().into_iter().collect();
+        accounts.extend(crate::services::credentials::CredentialStore::files());
+
+        let definitions: Vec<(&str, &str, Vec<PathBuf>, bool)> = vec![
+            ("instances", "Instances (worlds, mods, settings)", vec![get_instances_dir()], true),
+            ("archives", "Archived instances", vec![launcher_dir.join("archives")], true),
+            ("shared", "Shared worlds and resource packs", vec![get_shared_dir()], true),
+            ("templates", "Instance templates", vec![launcher_dir.join("templates")], true),
+            ("game_files", "Game versions, libraries and assets", game_files, false),
+            (
+                "cache",
+                "Cached mods and mod information",
+                vec![mod_details_dir, crate::services::mod_cache::ModCache::dir()],
+                false,
+            ),
+            ("logs", "Launcher logs", vec![get_logs_dir()], false),
+            (
+                "settings",
+                "Launcher settings and server list",
+                vec![
+                    launcher_dir.join("settings.json"),
+                    launcher_dir.join("servers.json"),
+                    launcher_dir.join("sidebar_bg.png"),
+                ],
+                true,
+            ),
+            ("accounts", "Signed-in accounts", accounts, true),
+        ];
+
+        definitions
+            .into_iter()
+            .map(|(id, label, paths, personal_data)| {
+                let paths: Vec<PathBuf> = paths.into_iter().filter(|p| p.exists()).collect();
+                CleanupCategory {
+                    id: id.to_string(),
+                    label: label.to_string(),
+                    size_bytes: paths.iter().map(|p| path_size(p)).sum(),
+                    paths: paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+                    personal_data,
+                }
+            })
+            .collect()
+    }
+
+    /// Remove the selected categories, to the OS trash when `use_trash` is set
+    pub fn remove(ids: &[String], use_trash: bool) -> Result<CleanupReport, Box<dyn std::error::Error>> {
+        let categories = Self::categories();
+
+        if let Some(unknown) = ids.iter().find(|id| !categories.iter().any(|c| &c.id == *id)) {
+            return Err(format!("Unknown cleanup category '{}'", unknown).into());
+        }
+
+        let touches_game = ids.iter().any(|id| matches!(id.as_str(), "instances" | "shared" | "game_files"));
+        if touches_game && !crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().is_empty() {
+            return Err("Close all running instances first".into());
+        }
+
+        let mut removed = Vec::new();
+        let mut failed = Vec::new();
+
+        for category in categories.iter().filter(|c| ids.contains(&c.id)) {
+            // Keychain entries aren't files, they go before the accounts they belong to
+            if category.id == "accounts" {
+                if let Err(e) = AccountManager::forget_credentials() {
+                    failed.push(format!("Account tokens: {}", e));
+                }
+            }
+            for path in &category.paths {
+                let path = Path::new(path);
+                let result = if use_trash {
+                    move_to_trash(path)
+                } else if path.is_dir() {
+                    fs::remove_dir_all(long_path(path))
+                } else {
+                    fs::remove_file(long_path(path))
+                };
+
+                match result {
+                    Ok(()) => {
+                        println!("✓ Removed {}", path.display());
+                        removed.push(path.to_string_lossy().to_string());
+                    }
+                    Err(e) => {
+                        println!("✗ Failed to remove {}: {}", path.display(), e);
+                        failed.push(format!("{}: {}", path.display(), e));
+                    }
+                }
+            }
+        }
+
+        Ok(CleanupReport {
+            categories: Self::categories(),
+            removed,
+            failed,
+        })
+    }
+}
+
+/// Shared folders are linked into instances, links are not followed so nothing is counted twice
+pub(crate) fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(long_path(path)) else {
+        return 0;
+    };
+
+    if metadata.is_file() {
+        return metadata.len();
+    }
+    if !metadata.is_dir() {
+        return 0;
+    }
+
+    fs::read_dir(long_path(path))
+        .map(|entries| entries.flatten().map(|e| path_size(&e.path())).sum())
+        .unwrap_or(0)
+}
@@ -0,0 +1,237 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FieldDiff {
+    pub field: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModEntry {
+    /// Mod id from the jar metadata, or the file name if the jar has none
+    pub id: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub filename: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModVersionDiff {
+    pub id: String,
+    pub name: String,
+    pub a: ModEntry,
+    pub b: ModEntry,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModListDiff {
+    pub only_in_a: Vec<ModEntry>,
+    pub only_in_b: Vec<ModEntry>,
+    /// Present in both with a different version or enabled state
+    pub changed: Vec<ModVersionDiff>,
+    pub identical: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FileSetDiff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub different: Vec<String>,
+    pub identical: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstanceComparison {
+    pub a: String,
+    pub b: String,
+    pub fields: Vec<FieldDiff>,
+    pub mods: ModListDiff,
+    pub configs: FileSetDiff,
+    pub options: Vec<FieldDiff>,
+}
+
+pub struct InstanceComparer;
+
+impl InstanceComparer {
+    pub fn compare(a_name: &str, b_name: &str) -> Result<InstanceComparison, Box<dyn std::error::Error>> {
+        let a = InstanceManager::load(a_name)?;
+        let b = InstanceManager::load(b_name)?;
+        let a_dir = get_instance_dir(a_name);
+        let b_dir = get_instance_dir(b_name);
+
+        Ok(InstanceComparison {
+            a: a.name.clone(),
+            b: b.name.clone(),
+            fields: compare_fields(&a, &b),
+            mods: compare_mods(&a_dir.join("mods"), &b_dir.join("mods")),
+            configs: compare_file_sets(&a_dir.join("config"), &b_dir.join("config"))?,
+            options: compare_options(&a_dir.join("options.txt"), &b_dir.join("options.txt")),
+        })
+    }
+}
+
+fn compare_fields(a: &Instance, b: &Instance) -> Vec<FieldDiff> {
+    fn values(instance: &Instance) -> Vec<(&'static str, Option<String>)> {
+        let settings = instance.settings_override.as_ref();
+        vec![
+            ("version", Some(instance.version.clone())),
+            ("loader", instance.loader.clone()),
+            ("loader_version", instance.loader_version.clone()),
+            ("memory_mb", settings.map(|s| s.memory_mb.to_string())),
+            ("java_path", settings.and_then(|s| s.java_path.clone())),
+            ("gc_logging", settings.map(|s| s.gc_logging.to_string())),
+            ("language", instance.language.clone()),
+            ("jar_mods", Some(instance.jar_mods.join(", ")).filter(|s| !s.is_empty())),
+            ("extra_game_args", Some(instance.extra_game_args.join(" ")).filter(|s| !s.is_empty())),
+            ("demo_mode", Some(instance.demo_mode.to_string())),
+            ("shared_directories", Some(instance.shared_directories.join(", ")).filter(|s| !s.is_empty())),
+        ]
+    }
+
+    values(a)
+        .into_iter()
+        .zip(values(b))
+        .filter(|((_, a), (_, b))| a != b)
+        .map(|((field, a), (_, b))| FieldDiff {
+            field: field.to_string(),
+            a,
+            b,
+        })
+        .collect()
+}
+
+fn list_mods(mods_dir: &Path) -> BTreeMap<String, ModEntry> {
+    let mut mods = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(mods_dir) else {
+        return mods;
+    };
+
+    for entry in entries.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        let enabled = filename.ends_with(".jar");
+        if !enabled && !filename.ends_with(".jar.disabled") {
+            continue;
+        }
+
+        let metadata = ModMetadataReader::read(&entry.path());
+        let id = metadata.as_ref().map(|m| m.id.clone()).unwrap_or_else(|| filename.trim_end_matches(".disabled").to_string());
+
+        mods.insert(
+            id.clone(),
+            ModEntry {
+                name: metadata.as_ref().map(|m| m.name.clone()).unwrap_or_else(|| id.clone()),
+                version: metadata.map(|m| m.version).filter(|v| !v.is_empty()),
+                id,
+                filename,
+                enabled,
+            },
+        );
+    }
+
+    mods
+}
+
+fn compare_mods(a_dir: &Path, b_dir: &Path) -> ModListDiff {
+    let a = list_mods(a_dir);
+    let mut b = list_mods(b_dir);
+    let mut diff = ModListDiff::default();
+
+    for (id, a_mod) in a {
+        match b.remove(&id) {
+            None => diff.only_in_a.push(a_mod),
+            Some(b_mod) if b_mod.version != a_mod.version || b_mod.enabled != a_mod.enabled => {
+                diff.changed.push(ModVersionDiff {
+                    name: a_mod.name.clone(),
+                    id,
+                    a: a_mod,
+                    b: b_mod,
+                });
+            }
+            Some(_) => diff.identical += 1,
+        }
+    }
+
+    diff.only_in_b = b.into_values().collect();
+    diff
+}
+
+fn hash_files(root: &Path) -> Result<BTreeMap<String, String>, Box<dyn std::error::Error>> {
+    fn walk(root: &Path, dir: &Path, out: &mut BTreeMap<String, String>) -> Result<(), Box<dyn std::error::Error>> {
+        for entry in fs::read_dir(long_path(dir))? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+
+            if file_type.is_dir() {
+                walk(root, &path, out)?;
+            } else if file_type.is_file() {
+                let mut hasher = Sha1::new();
+                hasher.update(fs::read(long_path(&path))?);
+                let relative = path.strip_prefix(root)?.to_string_lossy().replace('\\', "/");
+                out.insert(relative, format!("{:x}", hasher.finalize()));
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    if root.is_dir() {
+        walk(root, root, &mut files)?;
+    }
+    Ok(files)
+}
+
+fn compare_file_sets(a_dir: &Path, b_dir: &Path) -> Result<FileSetDiff, Box<dyn std::error::Error>> {
+    let a = hash_files(a_dir)?;
+    let mut b = hash_files(b_dir)?;
+    let mut diff = FileSetDiff::default();
+
+    for (path, a_hash) in a {
+        match b.remove(&path) {
+            None => diff.only_in_a.push(path),
+            Some(b_hash) if b_hash != a_hash => diff.different.push(path),
+            Some(_) => diff.identical += 1,
+        }
+    }
+
+    diff.only_in_b = b.into_keys().collect();
+    Ok(diff)
+}
+
+fn compare_options(a_path: &Path, b_path: &Path) -> Vec<FieldDiff> {
+    fn read(path: &Path) -> BTreeMap<String, String> {
+        fs::read_to_string(path)
+            .map(|c| {
+                c.lines()
+                    .filter_map(|l| l.split_once(':'))
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    let a = read(a_path);
+    let b = read(b_path);
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+
+    keys.into_iter()
+        .filter(|key| a.get(*key) != b.get(*key))
+        .map(|key| FieldDiff {
+            field: key.clone(),
+            a: a.get(key).cloned(),
+            b: b.get(key).cloned(),
+        })
+        .collect()
+}
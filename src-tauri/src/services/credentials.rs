@@ -0,0 +1,218 @@
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::{collections::HashMap, fs, path::PathBuf, sync::Mutex};
+
+const SERVICE: &str = "Atomic Launcher";
+const KEY_FILE: &str = "credentials.key";
+const STORE_FILE: &str = "credentials.enc";
+/// Left once the keychain holds an entry, it stays the backend from then on
+const KEYCHAIN_MARKER: &str = "credentials.keychain";
+
+lazy_static::lazy_static! {
+    /// Decided on first use, a keychain that fails once is likely missing for the whole session
+    static ref BACKEND: Mutex<Option<Backend>> = Mutex::new(None);
+    /// Secrets already read or written this session, keychains can be slow or prompt on each read
+    static ref CACHE: Mutex<HashMap<String, Option<String>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Backend {
+    /// Windows Credential Manager, macOS Keychain or the Secret Service on Linux
+    Keychain,
+    /// For headless Linux without a Secret Service: a file encrypted with a key next to it.
+    /// Keeps tokens out of plain text and backups of accounts.json, not safe from local access.
+    EncryptedFile,
+}
+
+/// Secrets such as account tokens, kept out of the launcher's JSON files
+pub struct CredentialStore;
+
+impl CredentialStore {
+    pub fn get(key: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if let Some(value) = CACHE.lock().unwrap().get(key) {
+            return Ok(value.clone());
+        }
+
+        let value = match backend() {
+            // Raw bytes, Windows would store text as UTF-16 and halve the 2560 byte limit
+            Backend::Keychain => match keyring::Entry::new(SERVICE, key)?.get_secret() {
+                Ok(value) => Some(String::from_utf8(value)?),
+                Err(keyring::Error::NoEntry) => None,
+                Err(e) => return Err(e.into()),
+            },
+            Backend::EncryptedFile => read_file_store()?.remove(key),
+        };
+
+        CACHE.lock().unwrap().insert(key.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// Skips the write when the stored value is the same, keychains may ask the user each time
+    pub fn set(key: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if Self::get(key)?.as_deref() == Some(value) {
+            return Ok(());
+        }
+
+        match backend() {
+            Backend::Keychain => {
+                keyring::Entry::new(SERVICE, key)?.set_secret(value.as_bytes())?;
+                let marker = data_dir().join(KEYCHAIN_MARKER);
+                if !marker.exists() {
+                    fs::create_dir_all(data_dir())?;
+                    fs::write(marker, "")?;
+                }
+            }
+            Backend::EncryptedFile => {
+                let mut store = read_file_store()?;
+                store.insert(key.to_string(), value.to_string());
+                write_file_store(&store)?;
+            }
+        }
+
+        CACHE.lock().unwrap().insert(key.to_string(), Some(value.to_string()));
+        Ok(())
+    }
+
+    pub fn delete(key: &str) -> Result<(), Box<dyn std::error::Error>> {
+        match backend() {
+            Backend::Keychain => match keyring::Entry::new(SERVICE, key)?.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(e.into()),
+            },
+            Backend::EncryptedFile => {
+                let mut store = read_file_store()?;
+                if store.remove(key).is_some() {
+                    write_file_store(&store)?;
+                }
+            }
+        }
+
+        CACHE.lock().unwrap().insert(key.to_string(), None);
+        Ok(())
+    }
+
+    /// The encrypted file, its key and the keychain marker, for cleanup
+    pub fn files() -> Vec<PathBuf> {
+        vec![
+            data_dir().join(STORE_FILE),
+            data_dir().join(KEY_FILE),
+            data_dir().join(KEYCHAIN_MARKER),
+        ]
+    }
+
+    /// Where secrets end up, for log messages
+    pub fn backend_name() -> &'static str {
+        match backend() {
+            Backend::Keychain => "system keychain",
+            Backend::EncryptedFile => "encrypted credentials file",
+        }
+    }
+}
+
+fn backend() -> Backend {
+    let mut backend = BACKEND.lock().unwrap();
+    *backend.get_or_insert_with(|| {
+        // An encrypted file from an earlier session means the keychain wasn't usable then
+        // either, switching now would lose the tokens in it
+        if data_dir().join(STORE_FILE).exists() {
+            return Backend::EncryptedFile;
+        }
+        match probe_keychain() {
+            Ok(()) => Backend::Keychain,
+            // Tokens are already in the keychain, an empty file would look like a logout.
+            // Calls fail until the keychain is back instead.
+            Err(e) if data_dir().join(KEYCHAIN_MARKER).exists() => {
+                println!("⚠ The keychain is not responding ({}), account tokens can't be read", e);
+                Backend::Keychain
+            }
+            Err(e) => {
+                println!("⚠ No usable keychain ({}), storing credentials in an encrypted file", e);
+                Backend::EncryptedFile
+            }
+        }
+    })
+}
+
+/// Write, read back and remove an entry, some Secret Service setups accept writes and lose them
+fn probe_keychain() -> Result<(), Box<dyn std::error::Error>> {
+    let entry = keyring::Entry::new(SERVICE, "probe")?;
+    entry.set_secret(b"probe")?;
+    let read = entry.get_secret()?;
+    let _ = entry.delete_credential();
+    if read != b"probe" {
+        return Err("the keychain returned a different value".into());
+    }
+    Ok(())
+}
+
+/// Beside accounts.json
+fn data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_default().join("atomic-launcher")
+}
+
+fn file_key() -> Result<LessSafeKey, Box<dyn std::error::Error>> {
+    let key_path = data_dir().join(KEY_FILE);
+    if !key_path.exists() {
+        let mut key = [0u8; 32];
+        SystemRandom::new().fill(&mut key).map_err(|_| "Failed to generate the credentials key")?;
+        fs::create_dir_all(data_dir())?;
+        fs::write(&key_path, key)?;
+        restrict_permissions(&key_path)?;
+    }
+
+    let key = fs::read(&key_path)?;
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &key)
+        .map_err(|_| format!("Credentials key at {} is unreadable", key_path.display()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn read_file_store() -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let path = data_dir().join(STORE_FILE);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let mut sealed = base64::engine::general_purpose::STANDARD.decode(fs::read_to_string(&path)?.trim())?;
+    if sealed.len() < NONCE_LEN {
+        return Err("The credentials file is damaged".into());
+    }
+    let mut contents = sealed.split_off(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(&sealed).map_err(|_| "The credentials file is damaged")?;
+
+    let plain = file_key()?
+        .open_in_place(nonce, Aad::empty(), &mut contents)
+        .map_err(|_| "The credentials file can't be decrypted with its key")?;
+    Ok(serde_json::from_slice(plain)?)
+}
+
+fn write_file_store(store: &HashMap<String, String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new().fill(&mut nonce).map_err(|_| "Failed to generate a nonce")?;
+
+    let mut contents = serde_json::to_vec(store)?;
+    file_key()?
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut contents)
+        .map_err(|_| "Failed to encrypt credentials")?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(contents);
+
+    let path = data_dir().join(STORE_FILE);
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, base64::engine::general_purpose::STANDARD.encode(sealed))?;
+    restrict_permissions(&temp)?;
+    fs::rename(&temp, &path)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &std::path::Path) -> std::io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::ModrinthClient;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreditEntry {
+    pub name: String,
+    pub version: Option<String>,
+    pub authors: Vec<String>,
+    pub license: Option<String>,
+    pub url: Option<String>,
+    pub filename: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreditsReport {
+    pub instance: String,
+    pub mods: Vec<CreditEntry>,
+    /// License -> names of the mods using it
+    pub licenses: BTreeMap<String, Vec<String>>,
+    /// Mods that declare no license anywhere, worth checking before redistributing
+    pub unknown_license: Vec<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+pub struct CreditsCollector;
+
+impl CreditsCollector {
+    /// Licenses and authors of every enabled mod, from the jar metadata with Modrinth as a fallback
+    pub async fn collect(instance_name: &str) -> Result<CreditsReport, Box<dyn std::error::Error>> {
+        let mods_dir = get_instance_dir(instance_name).join("mods");
+        let mut mods = Vec::new();
+        let mut hashes = Vec::new();
+
+        if mods_dir.exists() {
+            for entry in fs::read_dir(&mods_dir)? {
+                let entry = entry?;
+                let filename = entry.file_name().to_string_lossy().to_string();
+                // Disabled mods don't ship with the pack
+                if !filename.ends_with(".jar") {
+                    continue;
+                }
+
+                let metadata = ModMetadataReader::read(&entry.path());
+                let mut hasher = Sha1::new();
+                hasher.update(fs::read(entry.path())?);
+                hashes.push(format!("{:x}", hasher.finalize()));
+
+                mods.push(CreditEntry {
+                    name: metadata.as_ref().map(|m| m.name.clone()).unwrap_or_else(|| filename.clone()),
+                    version: metadata.as_ref().map(|m| m.version.clone()).filter(|v| !v.is_empty()),
+                    authors: metadata.as_ref().map(|m| m.authors.clone()).unwrap_or_default(),
+                    license: metadata.and_then(|m| m.license),
+                    url: None,
+                    filename,
+                });
+            }
+        }
+
+        if let Err(e) = Self::add_modrinth_details(&mut mods, &hashes).await {
+            println!("⚠ Could not look up mod licenses on Modrinth: {}", e);
+        }
+
+        mods.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+        let mut licenses: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut unknown_license = Vec::new();
+        for entry in &mods {
+            match &entry.license {
+                Some(license) => licenses.entry(license.clone()).or_default().push(entry.name.clone()),
+                None => unknown_license.push(entry.name.clone()),
+            }
+        }
+
+        Ok(CreditsReport {
+            instance: instance_name.to_string(),
+            mods,
+            licenses,
+            unknown_license,
+            generated_at: Utc::now(),
+        })
+    }
+
+    async fn add_modrinth_details(
+        mods: &mut [CreditEntry],
+        hashes: &[String],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let client = ModrinthClient::new();
+        let versions = client.get_versions_from_hashes(hashes).await?;
+
+        let mut project_ids: Vec<String> = versions.values().map(|v| v.project_id.clone()).collect();
+        project_ids.sort();
+        project_ids.dedup();
+        if project_ids.is_empty() {
+            return Ok(());
+        }
+
+        let projects: HashMap<String, _> = client
+            .get_projects(&project_ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect();
+
+        for (entry, hash) in mods.iter_mut().zip(hashes) {
+            let Some(project) = versions.get(hash).and_then(|v| projects.get(&v.project_id)) else {
+                continue;
+            };
+
+            entry.url = Some(format!("https://modrinth.com/{}/{}", project.project_type, project.slug));
+            if entry.name == entry.filename {
+                entry.name = project.title.clone();
+            }
+            // The jar is what actually ships, so its declaration wins over the listing
+            if entry.license.is_none() && !project.license.id.is_empty() {
+                entry.license = Some(project.license.id.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn render_markdown(report: &CreditsReport) -> String {
+        let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+
+        let mut out = format!("# {} - Credits\n\n", cell(&report.instance));
+        out.push_str("| Mod | Authors | License |\n|---|---|---|\n");
+
+        for entry in &report.mods {
+            let name = match &entry.url {
+                Some(url) => format!("[{}]({})", cell(&entry.name), url),
+                None => cell(&entry.name),
+            };
+            let authors = if entry.authors.is_empty() {
+                "-".to_string()
+            } else {
+                cell(&entry.authors.join(", "))
+            };
+
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                name,
+                authors,
+                cell(entry.license.as_deref().unwrap_or("Unknown"))
+            ));
+        }
+
+        out.push_str("\n## Licenses\n\n");
+        for (license, names) in &report.licenses {
+            out.push_str(&format!("- **{}**: {}\n", cell(license), cell(&names.join(", "))));
+        }
+        if !report.unknown_license.is_empty() {
+            out.push_str(&format!(
+                "- **Unknown**: {}\n",
+                cell(&report.unknown_license.join(", "))
+            ));
+        }
+
+        out
+    }
+}
@@ -0,0 +1,478 @@
+use crate::models::{Instance, ReleaseChannel};
+use crate::services::instance::InstanceManager;
+use crate::services::mod_lock::{LockedFile, ModLockfile};
+use crate::services::settings::SettingsManager;
+use crate::utils::curseforge::{
+    self, folder_for_class, mod_loader_type, CurseForgeClient, CurseForgeFile, CurseForgeMod,
+    RELATION_INCOMPATIBLE, RELATION_REQUIRED,
+};
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashSet, fs, path::Path, sync::Mutex, time::Duration};
+use tauri::{AppHandle, Emitter};
+
+/// Manual downloads an instance still waits for, kept until the files turn up
+const MANUAL_DOWNLOADS_FILE: &str = ".manual-downloads.json";
+
+lazy_static::lazy_static! {
+    /// Instances with a watcher running. Also held while claiming, so files added to the
+    /// pending list meanwhile aren't lost.
+    static ref WATCHED: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledCurseForgeFile {
+    pub mod_id: u32,
+    pub file_id: u32,
+    pub file_name: String,
+    /// Pulled in as a required dependency rather than picked by the player
+    pub dependency: bool,
+}
+
+/// A file whose author disabled third-party downloads. The player fetches it from the website
+/// and the launcher picks it up from their downloads folder.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManualDownload {
+    pub mod_id: u32,
+    pub file_id: u32,
+    pub file_name: String,
+    pub file_length: u64,
+    pub fingerprint: u32,
+    pub sha1: Option<String>,
+    /// Instance subfolder the file belongs in
+    pub folder: String,
+    /// Download page on curseforge.com
+    pub url: String,
+}
+
+impl ManualDownload {
+    /// For a file that has no download URL because its author disabled third-party downloads
+    pub fn for_file(file: &CurseForgeFile, project: Option<&CurseForgeMod>) -> Self {
+        let page = project
+            .and_then(|p| p.links.as_ref())
+            .and_then(|l| l.website_url.clone())
+            .unwrap_or_else(|| format!("https://www.curseforge.com/projects/{}", file.mod_id));
+
+        Self {
+            mod_id: file.mod_id,
+            file_id: file.id,
+            file_name: file.file_name.clone(),
+            file_length: file.file_length,
+            fingerprint: file.file_fingerprint,
+            sha1: file.sha1().map(|s| s.to_string()),
+            folder: folder_for_class(project.and_then(|p| p.class_id)).to_string(),
+            url: format!("{}/download/{}", page.trim_end_matches('/'), file.id),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CurseForgeInstallResult {
+    pub installed: Vec<InstalledCurseForgeFile>,
+    pub manual_downloads: Vec<ManualDownload>,
+    /// Dependencies that were already installed
+    pub already_installed: Vec<u32>,
+    /// Required dependencies left out because dependency installation was turned off
+    pub skipped_dependencies: Vec<u32>,
+    /// Required dependencies with no build for this instance, or mods marked incompatible
+    pub warnings: Vec<String>,
+}
+
+pub struct CurseForgeInstaller;
+
+impl CurseForgeInstaller {
+    /// Client using the API key from settings
+    pub fn client() -> Result<CurseForgeClient, String> {
+        let api_key = SettingsManager::load().ok().and_then(|s| s.curseforge_api_key);
+        CurseForgeClient::with_key(api_key)
+    }
+
+    /// ModLoaderType ids whose mods run on the instance. Quilt also loads Fabric mods.
+    pub fn loader_types(instance: &Instance) -> Vec<u32> {
+        match instance.loader.as_deref() {
+            Some("quilt") => vec![5, 4],
+            Some(loader) => mod_loader_type(loader).into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Install a CurseForge project plus its required dependencies, unless `with_dependencies`
+    /// is off. Without `file_id` the newest file for the instance's game version and loader is used.
+    pub async fn install(
+        instance: &Instance,
+        mod_id: u32,
+        file_id: Option<u32>,
+        with_dependencies: bool,
+    ) -> Result<CurseForgeInstallResult, Box<dyn std::error::Error>> {
+        let client = Self::client()?;
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let loader_types = Self::loader_types(instance);
+        let instance_dir = get_instance_dir(&instance.name);
+
+        let mut lockfile = ModLockfile::load(&instance_dir)?;
+        let mut result = CurseForgeInstallResult::default();
+
+        let requested = match file_id {
+            Some(id) => {
+                let file = client
+                    .get_files(&[id])
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| format!("CurseForge file {} not found", id))?;
+                if file.mod_id != mod_id {
+                    return Err(format!("File {} doesn't belong to project {}", id, mod_id).into());
+                }
+                file
+            }
+            None => Self::resolve(&client, mod_id, &game_version, &loader_types)
+                .await?
+                .ok_or_else(|| format!("Project {} has no file for Minecraft {}", mod_id, game_version))?,
+        };
+
+        let mut installed_projects: HashSet<String> = lockfile
+            .files
+            .iter()
+            .filter(|f| f.source.as_deref() == Some("curseforge"))
+            .filter_map(|f| f.project_id.clone())
+            .collect();
+        installed_projects.extend(untracked_projects(&client, &instance_dir, &lockfile).await);
+        let mut seen: HashSet<u32> = HashSet::new();
+        let mut queue = vec![(requested, false)];
+
+        while let Some((file, dependency)) = queue.pop() {
+            if !seen.insert(file.mod_id) {
+                continue;
+            }
+            // The requested project is reinstalled on purpose, e.g. to switch versions
+            if dependency && installed_projects.contains(&file.mod_id.to_string()) {
+                result.already_installed.push(file.mod_id);
+                continue;
+            }
+
+            for dep in &file.dependencies {
+                match dep.relation_type {
+                    RELATION_REQUIRED if !with_dependencies => {
+                        if !installed_projects.contains(&dep.mod_id.to_string()) {
+                            result.skipped_dependencies.push(dep.mod_id);
+                        }
+                    }
+                    RELATION_REQUIRED if !seen.contains(&dep.mod_id) => {
+                        match Self::resolve(&client, dep.mod_id, &game_version, &loader_types).await? {
+                            Some(dep_file) => queue.push((dep_file, true)),
+                            None => result.warnings.push(format!(
+                                "{} requires project {}, which has no file for Minecraft {}",
+                                file.file_name, dep.mod_id, game_version
+                            )),
+                        }
+                    }
+                    RELATION_INCOMPATIBLE if installed_projects.contains(&dep.mod_id.to_string()) => {
+                        result
+                            .warnings
+                            .push(format!("{} is incompatible with installed project {}", file.file_name, dep.mod_id));
+                    }
+                    _ => {}
+                }
+            }
+
+            let project = client.get_mods(&[file.mod_id]).await?.into_iter().next();
+            let folder = folder_for_class(project.as_ref().and_then(|p| p.class_id));
+
+            let Some(url) = file.download_url.clone() else {
+                result.manual_downloads.push(ManualDownload::for_file(&file, project.as_ref()));
+                continue;
+            };
+
+            crate::commands::validation::validate_curseforge_url(&url)?;
+            let bytes = client.download_file(&url).await?;
+            if file.sha1().is_some_and(|expected| sha1_hex(&bytes) != expected) {
+                return Err(format!("Hash mismatch for {}", file.file_name).into());
+            }
+            let file_name = place_file(&file.file_name, &bytes, &instance_dir, folder)?;
+
+            lockfile.record(locked_file(file.mod_id, file.id, folder, &file_name, &bytes, vec![url]));
+            // Saved per file so a failed dependency still leaves the others tracked
+            lockfile.save(&instance_dir)?;
+
+            println!("  ✓ Installed {}", file_name);
+            result.installed.push(InstalledCurseForgeFile {
+                mod_id: file.mod_id,
+                file_id: file.id,
+                file_name,
+                dependency,
+            });
+        }
+
+        println!(
+            "✓ Installed CurseForge project {} into '{}': {} files, {} manual downloads",
+            mod_id,
+            instance.name,
+            result.installed.len(),
+            result.manual_downloads.len()
+        );
+        Ok(result)
+    }
+
+    /// Move manually downloaded files from the downloads folder into the instance. Returns the
+    /// ones found, `pending` keeps the rest. Files are matched by hash, so a browser renaming
+    /// `mod.jar` to `mod (1).jar` doesn't matter.
+    pub fn claim_manual_downloads(
+        instance_name: &str,
+        pending: &mut Vec<ManualDownload>,
+    ) -> Result<Vec<ManualDownload>, Box<dyn std::error::Error>> {
+        let downloads_dir = dirs::download_dir().ok_or("Could not find the downloads folder")?;
+        let instance_dir = get_instance_dir(instance_name);
+        let mut claimed = Vec::new();
+
+        for entry in fs::read_dir(&downloads_dir)?.flatten() {
+            let path = entry.path();
+            // Still being written by the browser
+            if path.extension().is_some_and(|e| e == "crdownload" || e == "part") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() || !pending.iter().any(|d| d.file_length == 0 || d.file_length == metadata.len()) {
+                continue;
+            }
+
+            let bytes = fs::read(&path)?;
+            let Some(pos) = pending.iter().position(|d| matches_download(d, &bytes)) else {
+                continue;
+            };
+
+            let download = pending.remove(pos);
+            let file_name = place_file(&download.file_name, &bytes, &instance_dir, &download.folder)?;
+            let _ = fs::remove_file(&path);
+
+            let mut lockfile = ModLockfile::load(&instance_dir)?;
+            lockfile.record(locked_file(
+                download.mod_id,
+                download.file_id,
+                &download.folder,
+                &file_name,
+                &bytes,
+                Vec::new(),
+            ));
+            lockfile.save(&instance_dir)?;
+
+            println!("  ✓ Picked up {} from the downloads folder", file_name);
+            claimed.push(download);
+            if pending.is_empty() {
+                break;
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    pub fn pending_manual_downloads(instance_name: &str) -> Vec<ManualDownload> {
+        fs::read_to_string(get_instance_dir(instance_name).join(MANUAL_DOWNLOADS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_manual_downloads(instance_name: &str, pending: &[ManualDownload]) -> Result<(), Box<dyn std::error::Error>> {
+        let path = get_instance_dir(instance_name).join(MANUAL_DOWNLOADS_FILE);
+        if pending.is_empty() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        fs::write(path, serde_json::to_string_pretty(pending)?)?;
+        Ok(())
+    }
+
+    /// Add files to the instance's pending list and watch the downloads folder until they all
+    /// turn up, emitting `curseforge-download-found` per file. Gives up after 30 minutes, the
+    /// list stays so watching can be resumed.
+    pub fn watch_manual_downloads(
+        app_handle: AppHandle,
+        instance_name: &str,
+        downloads: Vec<ManualDownload>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let instance_name = instance_name.to_string();
+        {
+            let mut watched = WATCHED.lock().unwrap();
+            let mut pending = Self::pending_manual_downloads(&instance_name);
+            for download in downloads {
+                if !pending.iter().any(|d| d.file_id == download.file_id) {
+                    pending.push(download);
+                }
+            }
+            Self::save_manual_downloads(&instance_name, &pending)?;
+
+            // The running watcher reads the list again on its next pass
+            if pending.is_empty() || !watched.insert(instance_name.clone()) {
+                return Ok(());
+            }
+        }
+
+        tauri::async_runtime::spawn(async move {
+            let deadline = std::time::Instant::now() + Duration::from_secs(30 * 60);
+
+            while std::time::Instant::now() < deadline {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+
+                let result = {
+                    let _watched = WATCHED.lock().unwrap();
+                    let mut pending = Self::pending_manual_downloads(&instance_name);
+                    Self::claim_manual_downloads(&instance_name, &mut pending)
+                        .and_then(|claimed| {
+                            Self::save_manual_downloads(&instance_name, &pending)?;
+                            Ok((claimed, pending.len()))
+                        })
+                        .map_err(|e| e.to_string())
+                };
+
+                let (claimed, remaining) = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        println!("⚠ Stopped watching downloads for '{}': {}", instance_name, e);
+                        break;
+                    }
+                };
+
+                for download in claimed {
+                    let _ = app_handle.emit("curseforge-download-found", serde_json::json!({
+                        "instance": instance_name,
+                        "file_name": download.file_name,
+                        "remaining": remaining
+                    }));
+                }
+                if remaining == 0 {
+                    println!("✓ All manual downloads of '{}' are in place", instance_name);
+                    break;
+                }
+            }
+
+            WATCHED.lock().unwrap().remove(&instance_name);
+        });
+
+        Ok(())
+    }
+
+    /// Open the file's page on curseforge.com, where the player can download it
+    pub fn open_download_page(download: &ManualDownload) -> Result<(), Box<dyn std::error::Error>> {
+        if !download.url.starts_with("https://www.curseforge.com/") {
+            return Err(format!("Refusing to open {}", download.url).into());
+        }
+        webbrowser::open(&download.url)?;
+        Ok(())
+    }
+
+    /// Newest release for the instance, or the newest file of any type if there is no release
+    pub async fn resolve(
+        client: &CurseForgeClient,
+        mod_id: u32,
+        game_version: &str,
+        loader_types: &[u32],
+    ) -> Result<Option<CurseForgeFile>, Box<dyn std::error::Error>> {
+        Self::resolve_in_channel(client, mod_id, game_version, loader_types, None).await
+    }
+
+    /// Like `resolve`, but with a channel only files at least as stable as it, with no fallback
+    pub async fn resolve_in_channel(
+        client: &CurseForgeClient,
+        mod_id: u32,
+        game_version: &str,
+        loader_types: &[u32],
+        channel: Option<ReleaseChannel>,
+    ) -> Result<Option<CurseForgeFile>, Box<dyn std::error::Error>> {
+        // Resource packs and shaders aren't tagged with a loader
+        let mut attempts: Vec<Option<u32>> = loader_types.iter().map(|t| Some(*t)).collect();
+        attempts.push(None);
+
+        for loader_type in attempts {
+            let mut files = client.get_mod_files(mod_id, Some(game_version), loader_type).await?;
+            if loader_type.is_none() {
+                files.retain(|f| !f.game_versions.iter().any(|v| is_loader_tag(v)));
+            }
+            let picked = match channel {
+                Some(channel) => files
+                    .iter()
+                    .find(|f| ReleaseChannel::from_curseforge(f.release_type).is_some_and(|c| c <= channel)),
+                None => files.iter().find(|f| f.release_type == 1).or_else(|| files.first()),
+            }
+            .cloned();
+            if picked.is_some() {
+                return Ok(picked);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Loader names CurseForge lists among a file's game versions
+/// Projects of the jars in mods/ the lockfile doesn't track, so a dependency the player
+/// already has isn't downloaded a second time
+async fn untracked_projects(client: &CurseForgeClient, instance_dir: &Path, lockfile: &ModLockfile) -> HashSet<String> {
+    let tracked: HashSet<&str> = lockfile.files.iter().map(|f| f.path.as_str()).collect();
+    let fingerprints: Vec<u32> = fs::read_dir(instance_dir.join("mods"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().ends_with(".jar"))
+                .filter(|e| !tracked.contains(format!("mods/{}", e.file_name().to_string_lossy()).as_str()))
+                .filter_map(|e| fs::read(long_path(&e.path())).ok())
+                .map(|bytes| curseforge::fingerprint(&bytes))
+                .collect()
+        })
+        .unwrap_or_default();
+    if fingerprints.is_empty() {
+        return HashSet::new();
+    }
+
+    match client.get_fingerprint_matches(&fingerprints).await {
+        Ok(matches) => matches.into_values().map(|f| f.mod_id.to_string()).collect(),
+        Err(e) => {
+            println!("Warning: Could not identify untracked mods: {}", e);
+            HashSet::new()
+        }
+    }
+}
+
+fn is_loader_tag(tag: &str) -> bool {
+    ["forge", "neoforge", "fabric", "quilt", "liteloader"].contains(&tag.to_lowercase().as_str())
+}
+
+fn matches_download(download: &ManualDownload, bytes: &[u8]) -> bool {
+    match &download.sha1 {
+        Some(expected) => sha1_hex(bytes) == *expected,
+        None => curseforge::fingerprint(bytes) == download.fingerprint,
+    }
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Write a verified file into the instance, returning the file name used
+fn place_file(file_name: &str, bytes: &[u8], instance_dir: &Path, folder: &str) -> Result<String, String> {
+    let file_name = crate::commands::validation::sanitize_pack_filename(file_name)?;
+    let target_dir = instance_dir.join(folder);
+    fs::create_dir_all(&target_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    fs::write(long_path(&target_dir.join(&file_name)), bytes)
+        .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+
+    Ok(file_name)
+}
+
+fn locked_file(mod_id: u32, file_id: u32, folder: &str, file_name: &str, bytes: &[u8], downloads: Vec<String>) -> LockedFile {
+    LockedFile {
+        path: format!("{}/{}", folder, file_name),
+        sha1: sha1_hex(bytes),
+        sha512: Some(crate::services::mod_lock::sha512_hex(bytes)),
+        downloads,
+        source: Some("curseforge".to_string()),
+        project_id: Some(mod_id.to_string()),
+        version_id: Some(file_id.to_string()),
+    }
+}
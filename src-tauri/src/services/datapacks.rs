@@ -0,0 +1,321 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::utils::{copy_dir_recursive, get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+/// (minor, first patch, data pack format) of 1.x releases, newest first
+const DATA_PACK_FORMATS: &[(u32, u32, u32)] = &[
+    (21, 7, 81),
+    (21, 6, 80),
+    (21, 5, 71),
+    (21, 4, 61),
+    (21, 2, 57),
+    (21, 0, 48),
+    (20, 5, 41),
+    (20, 3, 26),
+    (20, 2, 18),
+    (20, 0, 15),
+    (19, 4, 12),
+    (19, 0, 10),
+    (18, 2, 9),
+    (18, 0, 8),
+    (17, 0, 7),
+    (16, 2, 6),
+    (15, 0, 5),
+    (13, 0, 4),
+];
+
+/// Newest release in the table, later versions aren't checked
+const NEWEST_KNOWN: (u32, u32) = (21, 8);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DataPack {
+    /// File or folder name in the world's datapacks/
+    pub name: String,
+    pub is_folder: bool,
+    pub pack_format: Option<u32>,
+    /// Range from `supported_formats`, for packs that work on several versions
+    pub supported_formats: Option<(u32, u32)>,
+    pub description: Option<String>,
+    /// None when the instance's version isn't in the format table
+    pub compatible: Option<bool>,
+}
+
+pub struct DataPackManager;
+
+impl DataPackManager {
+    pub fn list(instance: &Instance, world: &str) -> Result<Vec<DataPack>, Box<dyn std::error::Error>> {
+        let datapacks_dir = world_dir(instance, world)?.join("datapacks");
+        let expected = data_pack_format(&instance.version);
+        let mut packs = Vec::new();
+
+        if datapacks_dir.exists() {
+            for entry in fs::read_dir(&datapacks_dir)?.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let path = entry.path();
+                let is_folder = path.is_dir();
+                if !is_folder && !name.ends_with(".zip") {
+                    continue;
+                }
+
+                let meta = read_pack(&path).ok();
+                packs.push(DataPack {
+                    name,
+                    is_folder,
+                    pack_format: meta.as_ref().map(|m| m.pack_format),
+                    supported_formats: meta.as_ref().and_then(|m| m.supported_formats),
+                    description: meta.as_ref().and_then(|m| m.description.clone()),
+                    compatible: expected.zip(meta.as_ref()).map(|(format, meta)| meta.supports(format)),
+                });
+            }
+        }
+
+        packs.sort_by_key(|p| p.name.to_lowercase());
+        Ok(packs)
+    }
+
+    /// Copy a data pack zip or folder into a world, replacing an older copy with the same name.
+    /// Packs made for another pack_format are refused unless `force` is set.
+    pub fn install(
+        instance: &Instance,
+        world: &str,
+        source_path: &Path,
+        force: bool,
+    ) -> Result<DataPack, Box<dyn std::error::Error>> {
+        let world_dir = world_dir(instance, world)?;
+        let expected = data_pack_format(&instance.version);
+        if expected.is_none() && is_before_data_packs(&instance.version) {
+            return Err("Data packs need Minecraft 1.13 or newer".into());
+        }
+
+        let is_folder = source_path.is_dir();
+        let name = source_path
+            .file_name()
+            .ok_or("Invalid data pack path")?
+            .to_string_lossy()
+            .to_string();
+        let name = if is_folder {
+            validate_pack_name(&name)?
+        } else {
+            let name = crate::commands::validation::sanitize_pack_filename(&name)?;
+            if !name.ends_with(".zip") {
+                return Err("Data packs must be .zip files or folders".into());
+            }
+            name
+        };
+
+        let meta = read_pack(source_path)?;
+        if let Some(format) = expected {
+            if !meta.supports(format) && !force {
+                return Err(format!(
+                    "{} is made for pack format {}, Minecraft {} uses {}",
+                    name,
+                    meta.format_label(),
+                    InstanceManager::base_minecraft_version(&instance.version),
+                    format
+                )
+                .into());
+            }
+        }
+
+        let datapacks_dir = world_dir.join("datapacks");
+        fs::create_dir_all(&datapacks_dir)?;
+        let destination = datapacks_dir.join(&name);
+        if destination.is_dir() {
+            fs::remove_dir_all(&destination)?;
+        }
+
+        if is_folder {
+            copy_dir_recursive(source_path, &destination)?;
+        } else {
+            fs::copy(source_path, long_path(&destination))?;
+        }
+
+        println!("✓ Installed data pack {} into world '{}' of '{}'", name, world, instance.name);
+        Ok(DataPack {
+            name,
+            is_folder,
+            pack_format: Some(meta.pack_format),
+            supported_formats: meta.supported_formats,
+            description: meta.description.clone(),
+            compatible: expected.map(|format| meta.supports(format)),
+        })
+    }
+
+    pub fn remove(instance: &Instance, world: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = world_dir(instance, world)?.join("datapacks").join(validate_pack_name(name)?);
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else if path.is_file() {
+            fs::remove_file(&path)?;
+        } else {
+            return Err(format!("Data pack '{}' not found", name).into());
+        }
+
+        println!("✓ Removed data pack {} from world '{}' of '{}'", name, world, instance.name);
+        Ok(())
+    }
+}
+
+struct PackMeta {
+    pack_format: u32,
+    supported_formats: Option<(u32, u32)>,
+    description: Option<String>,
+}
+
+impl PackMeta {
+    fn supports(&self, format: u32) -> bool {
+        match self.supported_formats {
+            Some((min, max)) => (min..=max).contains(&format),
+            None => self.pack_format == format,
+        }
+    }
+
+    fn format_label(&self) -> String {
+        match self.supported_formats {
+            Some((min, max)) if min != max => format!("{}-{}", min, max),
+            _ => self.pack_format.to_string(),
+        }
+    }
+}
+
+/// A world folder in saves/, refusing names that would leave it
+fn world_dir(instance: &Instance, world: &str) -> Result<PathBuf, String> {
+    if world.is_empty() || world.contains("..") || world.contains('/') || world.contains('\\') {
+        return Err("Invalid folder name".to_string());
+    }
+
+    let world_dir = get_instance_dir(&instance.name).join("saves").join(world);
+    if !world_dir.join("level.dat").exists() {
+        return Err(format!("World '{}' does not exist", world));
+    }
+    Ok(world_dir)
+}
+
+fn validate_pack_name(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.contains("..") || name.contains('/') || name.contains('\\') || name.contains('\0') {
+        return Err(format!("Invalid data pack name '{}'", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Data pack format of a release, None for snapshots and releases newer than the table
+pub fn data_pack_format(version: &str) -> Option<u32> {
+    let (minor, patch) = release_parts(version)?;
+    if (minor, patch) > NEWEST_KNOWN {
+        return None;
+    }
+    DATA_PACK_FORMATS
+        .iter()
+        .find(|(m, p, _)| (minor, patch) >= (*m, *p))
+        .map(|(_, _, format)| *format)
+}
+
+fn is_before_data_packs(version: &str) -> bool {
+    release_parts(version).is_some_and(|(minor, _)| minor < 13)
+}
+
+/// Minor and patch of a 1.x release
+fn release_parts(version: &str) -> Option<(u32, u32)> {
+    let version = InstanceManager::base_minecraft_version(version);
+    let mut parts = version.strip_prefix("1.")?.split('.');
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    Some((minor, patch))
+}
+
+/// Check the pack.mcmeta at the top of a zip or folder and that the pack has data to load
+fn read_pack(path: &Path) -> Result<PackMeta, String> {
+    let (content, entries) = if path.is_dir() {
+        let content = fs::read_to_string(path.join("pack.mcmeta")).ok();
+        let entries = ["data", "assets"]
+            .iter()
+            .filter(|d| path.join(d).is_dir())
+            .map(|d| format!("{}/", d))
+            .collect();
+        (content, entries)
+    } else {
+        let file = fs::File::open(long_path(path)).map_err(|e| format!("Failed to open data pack: {}", e))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a valid zip file: {}", e))?;
+        let content = archive.by_name("pack.mcmeta").ok().and_then(|mut entry| {
+            let mut content = String::new();
+            entry.read_to_string(&mut content).ok().map(|_| content)
+        });
+        let entries: Vec<String> = archive.file_names().map(String::from).collect();
+        (content, entries)
+    };
+
+    let Some(content) = content else {
+        // GitHub downloads wrap the pack in one more folder
+        let nested = entries.iter().any(|e| e.matches('/').count() == 1 && e.ends_with("/pack.mcmeta"));
+        return Err(if nested {
+            "pack.mcmeta is inside a subfolder, zip the folder's contents instead".to_string()
+        } else {
+            "Not a data pack, it has no pack.mcmeta".to_string()
+        });
+    };
+    if !entries.iter().any(|e| e.starts_with("data/")) {
+        return Err(if entries.iter().any(|e| e.starts_with("assets/")) {
+            "This is a resource pack, not a data pack".to_string()
+        } else {
+            "Not a data pack, it has no data folder".to_string()
+        });
+    }
+
+    let json: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+        .map_err(|e| format!("Invalid pack.mcmeta: {}", e))?;
+    let pack = json.get("pack").ok_or("pack.mcmeta has no pack section")?;
+    let pack_format = pack
+        .get("pack_format")
+        .or_else(|| pack.get("min_format"))
+        .and_then(format_number)
+        .ok_or("pack.mcmeta has no pack_format")?;
+
+    Ok(PackMeta {
+        pack_format,
+        supported_formats: pack.get("supported_formats").and_then(format_range),
+        description: pack.get("description").and_then(description_text),
+    })
+}
+
+/// A format is a number, or `[major, minor]` since 1.21.9
+fn format_number(value: &serde_json::Value) -> Option<u32> {
+    value
+        .as_u64()
+        .or_else(|| value.as_array()?.first()?.as_u64())
+        .map(|f| f as u32)
+}
+
+/// `supported_formats` is a number, `[min, max]` or `{ "min_inclusive", "max_inclusive" }`
+fn format_range(value: &serde_json::Value) -> Option<(u32, u32)> {
+    if let Some(format) = value.as_u64() {
+        return Some((format as u32, format as u32));
+    }
+    if let Some([min, max]) = value.as_array().map(|a| a.as_slice()) {
+        return Some((min.as_u64()? as u32, max.as_u64()? as u32));
+    }
+    Some((
+        value.get("min_inclusive")?.as_u64()? as u32,
+        value.get("max_inclusive")?.as_u64()? as u32,
+    ))
+}
+
+/// Plain text of a description, which is a string or a JSON text component
+fn description_text(value: &serde_json::Value) -> Option<String> {
+    let text = match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts.iter().filter_map(description_text).collect(),
+        serde_json::Value::Object(_) => value.get("text").and_then(|t| t.as_str()).unwrap_or_default().to_string(),
+        _ => return None,
+    };
+    Some(text).filter(|t| !t.is_empty())
+}
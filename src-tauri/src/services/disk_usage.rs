@@ -0,0 +1,141 @@
+use crate::utils::links::is_link;
+use crate::utils::{get_instance_dir, long_path};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path, sync::Mutex};
+
+/// Cached reports older than this are recomputed
+const CACHE_TTL_SECONDS: i64 = 300;
+
+/// Folders shown as their own category, everything else counts as "other"
+const CATEGORIES: &[(&str, &[&str])] = &[
+    ("mods", &["mods", "jarmods"]),
+    ("saves", &["saves"]),
+    ("resourcepacks", &["resourcepacks"]),
+    ("shaderpacks", &["shaderpacks"]),
+    ("logs", &["logs", "crash-reports"]),
+    ("screenshots", &["screenshots"]),
+    ("config", &["config", "options.txt"]),
+    ("snapshots", &["snapshots"]),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskUsageCategory {
+    pub name: String,
+    pub bytes: u64,
+    pub files: u64,
+    /// Backed by a shared directory, so the space isn't owned by this instance
+    pub shared: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskUsageReport {
+    pub instance: String,
+    pub total_bytes: u64,
+    pub categories: Vec<DiskUsageCategory>,
+    pub computed_at: DateTime<Utc>,
+}
+
+lazy_static::lazy_static! {
+    static ref USAGE_CACHE: Mutex<HashMap<String, DiskUsageReport>> = Mutex::new(HashMap::new());
+}
+
+pub struct DiskUsageAnalyzer;
+
+impl DiskUsageAnalyzer {
+    pub fn cached(instance_name: &str) -> Option<DiskUsageReport> {
+        let cache = USAGE_CACHE.lock().unwrap();
+        cache
+            .get(instance_name)
+            .filter(|r| (Utc::now() - r.computed_at).num_seconds() < CACHE_TTL_SECONDS)
+            .cloned()
+    }
+
+    pub fn invalidate(instance_name: &str) {
+        USAGE_CACHE.lock().unwrap().remove(instance_name);
+    }
+
+    /// Walk the instance directory and cache the result. Blocking, call from a worker thread.
+    pub fn compute(instance_name: &str) -> Result<DiskUsageReport, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        if !instance_dir.exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let mut categories: Vec<DiskUsageCategory> = CATEGORIES
+            .iter()
+            .map(|(name, _)| DiskUsageCategory {
+                name: name.to_string(),
+                bytes: 0,
+                files: 0,
+                shared: false,
+            })
+            .chain(std::iter::once(DiskUsageCategory {
+                name: "other".to_string(),
+                bytes: 0,
+                files: 0,
+                shared: false,
+            }))
+            .collect();
+
+        for entry in fs::read_dir(&instance_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let index = CATEGORIES
+                .iter()
+                .position(|(_, folders)| folders.contains(&name.as_str()))
+                .unwrap_or(categories.len() - 1);
+
+            let linked = is_link(&entry.path());
+            let (bytes, files) = measure(&entry.path(), true);
+
+            let category = &mut categories[index];
+            category.bytes += bytes;
+            category.files += files;
+            category.shared |= linked;
+        }
+
+        let report = DiskUsageReport {
+            instance: instance_name.to_string(),
+            // Shared space is listed but not charged to the instance
+            total_bytes: categories.iter().filter(|c| !c.shared).map(|c| c.bytes).sum(),
+            categories,
+            computed_at: Utc::now(),
+        };
+
+        USAGE_CACHE
+            .lock()
+            .unwrap()
+            .insert(instance_name.to_string(), report.clone());
+
+        Ok(report)
+    }
+}
+
+/// (bytes, files) below `path`. Only the top-level link is followed, nested links could loop.
+fn measure(path: &Path, follow_link: bool) -> (u64, u64) {
+    let metadata = if follow_link {
+        fs::metadata(long_path(path))
+    } else {
+        fs::symlink_metadata(long_path(path))
+    };
+    let Ok(metadata) = metadata else {
+        return (0, 0);
+    };
+
+    if metadata.is_file() {
+        return (metadata.len(), 1);
+    }
+    if !metadata.is_dir() {
+        return (0, 0);
+    }
+
+    let Ok(entries) = fs::read_dir(long_path(path)) else {
+        return (0, 0);
+    };
+
+    entries.flatten().fold((0, 0), |(bytes, files), entry| {
+        let (b, f) = measure(&entry.path(), false);
+        (bytes + b, files + f)
+    })
+}
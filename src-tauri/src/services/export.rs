@@ -0,0 +1,189 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::utils::{get_instance_dir, long_path};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+pub const EXPORT_MANIFEST_NAME: &str = "atomic-instance.json";
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+/// Game files live below this folder inside the archive
+pub const EXPORT_GAME_DIR: &str = "minecraft";
+
+// Regenerated by the launcher or only useful on this machine, never worth shipping
+const ALWAYS_EXCLUDED: &[&str] = &["natives", ".fabric", ".cache", "mod-backups", "pinned", "attestations"];
+
+/// Instance metadata and appearance, kept next to the manifest rather than with the game files
+pub const LAUNCHER_FILES: &[&str] = &["instance.json", "icon.png", "banner.png", "notes.md", "atomic.lock"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportOptions {
+    #[serde(default)]
+    pub include_saves: bool,
+    #[serde(default)]
+    pub include_logs: bool,
+}
+
+/// Launcher-agnostic description of an exported instance
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportManifest {
+    pub format_version: u32,
+    pub name: String,
+    pub minecraft_version: String,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    pub memory_mb: Option<u32>,
+    pub exported_at: String,
+    pub exported_by: String,
+    pub game_dir: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportSummary {
+    pub path: String,
+    pub files: usize,
+    pub size_bytes: u64,
+}
+
+pub struct ExportManager;
+
+impl ExportManager {
+    pub fn export(
+        instance_name: &str,
+        destination: &Path,
+        options: &ExportOptions,
+    ) -> Result<ExportSummary, Box<dyn std::error::Error>> {
+        let instance = InstanceManager::load(instance_name)?;
+        let instance_dir = get_instance_dir(instance_name);
+
+        let manifest = ExportManifest {
+            format_version: EXPORT_FORMAT_VERSION,
+            name: instance.name.clone(),
+            minecraft_version: InstanceManager::base_minecraft_version(&instance.version),
+            loader: instance.loader.clone(),
+            loader_version: instance.loader_version.clone(),
+            memory_mb: instance.settings_override.as_ref().map(|s| s.memory_mb),
+            exported_at: Utc::now().to_rfc3339(),
+            exported_by: format!("AtomicLauncher {}", env!("CARGO_PKG_VERSION")),
+            game_dir: EXPORT_GAME_DIR.to_string(),
+        };
+
+        let mut excluded: Vec<&str> = ALWAYS_EXCLUDED.to_vec();
+        if !options.include_saves {
+            excluded.push("saves");
+        }
+        if !options.include_logs {
+            excluded.extend(["logs", "crash-reports"]);
+        }
+
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = fs::File::create(long_path(destination))?;
+        let mut zip = ZipWriter::new(file);
+        let file_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file(EXPORT_MANIFEST_NAME, file_options)?;
+        zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+        let mut files = 0;
+        for entry in fs::read_dir(&instance_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if excluded.contains(&name.as_str()) {
+                continue;
+            }
+
+            // The launcher's own metadata stays at the archive root
+            let archive_name = if LAUNCHER_FILES.contains(&name.as_str()) {
+                name.clone()
+            } else {
+                format!("{}/{}", EXPORT_GAME_DIR, name)
+            };
+
+            if name == "instance.json" {
+                zip.start_file(archive_name, file_options)?;
+                zip.write_all(serde_json::to_string_pretty(&portable_instance(&instance))?.as_bytes())?;
+                files += 1;
+                continue;
+            }
+
+            // Shared directories are links; export what they point to
+            files += add_path(&mut zip, &entry.path(), &archive_name, file_options, true)?;
+        }
+
+        zip.finish()?;
+
+        let size_bytes = fs::metadata(long_path(destination))?.len();
+        println!(
+            "✓ Exported '{}' to {} ({} files, {} bytes)",
+            instance_name,
+            destination.display(),
+            files,
+            size_bytes
+        );
+
+        Ok(ExportSummary {
+            path: destination.to_string_lossy().to_string(),
+            files,
+            size_bytes,
+        })
+    }
+}
+
+/// Java paths, hooks and linked folders only make sense on this machine
+fn portable_instance(instance: &Instance) -> Instance {
+    let mut instance = instance.clone();
+    instance.java_path = None;
+    instance.linked_game_dir = None;
+    if let Some(settings) = instance.settings_override.as_mut() {
+        settings.java_path = None;
+        settings.pre_launch_command = None;
+        settings.post_exit_command = None;
+    }
+    instance
+}
+
+fn add_path(
+    zip: &mut ZipWriter<fs::File>,
+    path: &Path,
+    archive_name: &str,
+    options: SimpleFileOptions,
+    follow_links: bool,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let metadata = if follow_links {
+        fs::metadata(path)?
+    } else {
+        fs::symlink_metadata(path)?
+    };
+
+    if metadata.is_dir() {
+        zip.add_directory(format!("{}/", archive_name), options)?;
+
+        let mut count = 0;
+        for entry in fs::read_dir(long_path(path))? {
+            let entry = entry?;
+            let child_name = format!("{}/{}", archive_name, entry.file_name().to_string_lossy());
+            // Only top-level links are followed, deeper ones could loop
+            count += add_path(zip, &entry.path(), &child_name, options, false)?;
+        }
+        return Ok(count);
+    }
+
+    if !metadata.is_file() {
+        return Ok(0);
+    }
+
+    zip.start_file(archive_name, options)?;
+    let mut source = fs::File::open(long_path(path))?;
+    std::io::copy(&mut source, zip)?;
+
+    Ok(1)
+}
@@ -1,4 +1,5 @@
 use crate::models::*;
+use crate::utils::long_path;
 use std::{fs, path::PathBuf, time::Duration};
 
 const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
@@ -10,7 +11,7 @@ pub struct FabricInstaller {
 
 impl FabricInstaller {
     pub fn new(launcher_dir: PathBuf) -> Self {
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::utils::http::client_builder()
             .timeout(Duration::from_secs(30))
             .build()
             .unwrap();
@@ -33,6 +34,27 @@ impl FabricInstaller {
         Ok(versions)
     }
 
+    /// Loader versions that have intermediary mappings for `minecraft_version`, newest first
+    pub async fn get_loader_versions_for_game(
+        &self,
+        minecraft_version: &str,
+    ) -> Result<Vec<FabricLoaderVersion>, Box<dyn std::error::Error>> {
+        let url = format!("{}/versions/loader/{}", FABRIC_META_URL, minecraft_version);
+        let response = self.http_client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch Fabric loader versions: HTTP {}", response.status()).into());
+        }
+
+        #[derive(serde::Deserialize)]
+        struct LoaderEntry {
+            loader: FabricLoaderVersion,
+        }
+
+        let entries: Vec<LoaderEntry> = response.json().await?;
+        Ok(entries.into_iter().map(|e| e.loader).collect())
+    }
+
     pub async fn get_supported_game_versions(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
         let url = format!("{}/versions/game", FABRIC_META_URL);
         
@@ -72,6 +94,14 @@ impl FabricInstaller {
             return Err("No Fabric loaders available for this Minecraft version".into());
         }
 
+        // Newest build first, whatever its channel
+        if crate::services::settings::SettingsManager::unstable_loaders_enabled() {
+            if let Some(version) = loaders[0].get("loader").and_then(|l| l.get("version")).and_then(|v| v.as_str()) {
+                println!("Using latest Fabric loader: {}", version);
+                return Ok(version.to_string());
+            }
+        }
+
         for loader in &loaders {
             if let Some(loader_obj) = loader.get("loader") {
                 if let (Some(version), Some(stable)) = (
@@ -159,7 +189,7 @@ impl FabricInstaller {
             let (group, artifact, version) = (parts[0], parts[1], parts[2]);
             let group_path = group.replace('.', "/");
             let jar_name = format!("{}-{}.jar", artifact, version);
-            let lib_path = libraries_dir.join(&group_path).join(artifact).join(version).join(&jar_name);
+            let lib_path = long_path(&libraries_dir.join(&group_path).join(artifact).join(version).join(&jar_name));
 
             // Construct the full URL
             let base_url = if lib.url.ends_with('/') {
@@ -212,8 +242,9 @@ impl FabricInstaller {
         println!("✓ Fabric libraries: {} downloaded, {} failed, {} total", 
                  successful_downloads, failed_downloads, profile.libraries.len());
 
+        // Without the profile the next launch or install tries again instead of starting a broken game
         if failed_downloads > 0 {
-            println!("Warning: Some libraries failed to download. The instance may not work correctly.");
+            return Err(format!("{} Fabric libraries failed to download", failed_downloads).into());
         }
 
         // Save the profile JSON directly as received from Fabric
@@ -237,4 +268,30 @@ impl FabricInstaller {
         
         profile_path.exists()
     }
+
+    /// Install the Fabric profile a launch needs if it's missing, e.g. after the meta folder was
+    /// cleaned or for an instance imported from elsewhere
+    pub async fn ensure_installed(&self, instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        if instance.loader.as_deref() != Some("fabric") {
+            return Ok(());
+        }
+        let Some(loader_version) = instance.loader_version.as_deref() else {
+            return Ok(());
+        };
+
+        let profile_path = self
+            .launcher_dir
+            .join("versions")
+            .join(&instance.version)
+            .join(format!("{}.json", instance.version));
+        if profile_path.exists() {
+            return Ok(());
+        }
+
+        let minecraft_version = crate::services::instance::InstanceManager::base_minecraft_version(&instance.version);
+
+        println!("Fabric {} for '{}' is missing, installing it", loader_version, instance.name);
+        self.install_fabric(&minecraft_version, loader_version).await?;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,849 @@
+use crate::models::*;
+use crate::services::installer::MinecraftInstaller;
+use crate::services::java::JavaManager;
+use crate::services::liteloader::maven_path;
+use crate::utils::{get_logs_dir, long_path};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tauri::Emitter;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use zip::ZipArchive;
+
+const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net/";
+const NEOFORGE_MAVEN_URL: &str = "https://maven.neoforged.net/releases/";
+/// Legacy installers point at the old Forge file server, which no longer serves every library
+const LEGACY_LIBRARY_MIRRORS: &[&str] = &[
+    FORGE_MAVEN_URL,
+    "https://libraries.minecraft.net/",
+    "https://repo1.maven.org/maven2/",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForgeFlavor {
+    Forge,
+    NeoForge,
+}
+
+impl ForgeFlavor {
+    pub fn from_loader(loader: &str) -> Option<Self> {
+        match loader {
+            "forge" => Some(Self::Forge),
+            "neoforge" => Some(Self::NeoForge),
+            _ => None,
+        }
+    }
+
+    pub fn loader(self) -> &'static str {
+        match self {
+            Self::Forge => "forge",
+            Self::NeoForge => "neoforge",
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            Self::Forge => "Forge",
+            Self::NeoForge => "NeoForge",
+        }
+    }
+
+    fn maven_url(self) -> &'static str {
+        match self {
+            Self::Forge => FORGE_MAVEN_URL,
+            Self::NeoForge => NEOFORGE_MAVEN_URL,
+        }
+    }
+
+    fn metadata_url(self) -> String {
+        match self {
+            Self::Forge => format!("{}net/minecraftforge/forge/maven-metadata.xml", FORGE_MAVEN_URL),
+            Self::NeoForge => format!("{}net/neoforged/neoforge/maven-metadata.xml", NEOFORGE_MAVEN_URL),
+        }
+    }
+
+    /// Forge versions carry the Minecraft version as a prefix, NeoForge's don't
+    fn installer_name(self, minecraft_version: &str, loader_version: &str) -> String {
+        match self {
+            Self::Forge => format!("net.minecraftforge:forge:{}-{}:installer", minecraft_version, loader_version),
+            Self::NeoForge => format!("net.neoforged:neoforge:{}:installer", loader_version),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct InstallProfile {
+    #[serde(default)]
+    data: HashMap<String, SidedValue>,
+    #[serde(default)]
+    processors: Vec<Processor>,
+    #[serde(default)]
+    libraries: Vec<FabricProfileLibrary>,
+    /// Name of the version JSON inside the installer, modern installers only
+    json: Option<String>,
+    /// Installers for Minecraft 1.12.2 and older describe everything here instead
+    install: Option<LegacyInstall>,
+    #[serde(rename = "versionInfo")]
+    version_info: Option<LegacyVersionInfo>,
+}
+
+#[derive(Deserialize)]
+struct SidedValue {
+    #[serde(default)]
+    client: String,
+}
+
+#[derive(Deserialize)]
+struct Processor {
+    /// Runs on both sides when missing
+    sides: Option<Vec<String>>,
+    jar: String,
+    #[serde(default)]
+    classpath: Vec<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    /// Files the processor writes and their expected SHA1
+    #[serde(default)]
+    outputs: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct LegacyInstall {
+    /// Maven coordinate of the universal jar
+    path: String,
+    /// The universal jar's name inside the installer
+    #[serde(rename = "filePath")]
+    file_path: String,
+}
+
+#[derive(Deserialize)]
+struct LegacyVersionInfo {
+    #[serde(rename = "mainClass")]
+    main_class: String,
+    #[serde(rename = "minecraftArguments", default)]
+    minecraft_arguments: String,
+    #[serde(default)]
+    libraries: Vec<LegacyLibrary>,
+}
+
+#[derive(Deserialize)]
+struct LegacyLibrary {
+    name: String,
+    url: Option<String>,
+    #[serde(default)]
+    checksums: Vec<String>,
+    clientreq: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ForgeInstallStage {
+    Libraries { done: usize, total: usize },
+    /// A processor started, `index` counts from 1
+    Processor { index: usize, total: usize, name: String },
+    /// A line the running processor printed
+    Output { line: String },
+    Done,
+}
+
+/// Sent as `forge-install-progress` while a Forge or NeoForge profile is installed
+#[derive(Debug, Serialize, Clone)]
+pub struct ForgeInstallProgress {
+    pub profile_id: String,
+    pub instance: Option<String>,
+    /// Full processor output of this install
+    pub log_path: String,
+    #[serde(flatten)]
+    pub stage: ForgeInstallStage,
+}
+
+pub struct ForgeInstaller {
+    http_client: reqwest::Client,
+    launcher_dir: PathBuf,
+    progress: Option<(tauri::AppHandle, Option<String>)>,
+}
+
+impl ForgeInstaller {
+    pub fn new(launcher_dir: PathBuf) -> Self {
+        let http_client = crate::utils::http::client_builder()
+            .timeout(Duration::from_secs(300))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()
+            .unwrap();
+
+        Self {
+            http_client,
+            launcher_dir,
+            progress: None,
+        }
+    }
+
+    /// Report library downloads and each processor run as `forge-install-progress`, tagged with
+    /// the instance being set up
+    pub fn with_progress(mut self, app_handle: tauri::AppHandle, instance: Option<String>) -> Self {
+        self.progress = Some((app_handle, instance));
+        self
+    }
+
+    fn report(&self, profile_id: &str, stage: ForgeInstallStage) {
+        let Some((app_handle, instance)) = &self.progress else {
+            return;
+        };
+        let _ = app_handle.emit(
+            "forge-install-progress",
+            ForgeInstallProgress {
+                profile_id: profile_id.to_string(),
+                instance: instance.clone(),
+                log_path: path_string(&install_log_path(profile_id)),
+                stage,
+            },
+        );
+    }
+
+    /// e.g. `1.20.1-forge-47.2.0` or `1.21.1-neoforge-21.1.77`
+    pub fn profile_id(flavor: ForgeFlavor, minecraft_version: &str, loader_version: &str) -> String {
+        format!("{}-{}-{}", minecraft_version, flavor.loader(), loader_version)
+    }
+
+    /// Builds for `minecraft_version`, newest first
+    pub async fn get_loader_versions(
+        &self,
+        flavor: ForgeFlavor,
+        minecraft_version: &str,
+    ) -> Result<Vec<ForgeVersion>, Box<dyn std::error::Error>> {
+        let response = self.http_client.get(flavor.metadata_url()).send().await?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch {} versions: HTTP {}", flavor.display_name(), response.status()).into());
+        }
+        let metadata = response.text().await?;
+
+        // maven-metadata.xml lists oldest first
+        let mut versions: Vec<ForgeVersion> = metadata
+            .split("<version>")
+            .skip(1)
+            .filter_map(|s| s.split_once("</version>").map(|(v, _)| v.trim()))
+            .filter_map(|full| {
+                let version = match flavor {
+                    ForgeFlavor::Forge => full.strip_prefix(&format!("{}-", minecraft_version))?.to_string(),
+                    ForgeFlavor::NeoForge => {
+                        if neoforge_minecraft_version(full)? != minecraft_version {
+                            return None;
+                        }
+                        full.to_string()
+                    }
+                };
+                Some(ForgeVersion {
+                    stable: !full.contains("beta") && !full.contains("pre"),
+                    version,
+                    minecraft_version: minecraft_version.to_string(),
+                })
+            })
+            .collect();
+        versions.reverse();
+        Ok(versions)
+    }
+
+    /// Newest stable build for the Minecraft version, or the newest of any kind when unstable
+    /// loaders are allowed or there is no stable one
+    pub async fn get_compatible_loader_for_minecraft(
+        &self,
+        flavor: ForgeFlavor,
+        minecraft_version: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let versions = self.get_loader_versions(flavor, minecraft_version).await?;
+        let unstable = crate::services::settings::SettingsManager::unstable_loaders_enabled();
+        versions
+            .iter()
+            .find(|v| unstable || v.stable)
+            .or_else(|| versions.first())
+            .map(|v| v.version.clone())
+            .ok_or_else(|| format!("{} is not available for Minecraft {}", flavor.display_name(), minecraft_version).into())
+    }
+
+    /// Download the installer, fetch its libraries and run its client processors, which produce
+    /// the patched client jar. Processor outputs stay in libraries/, so reinstalling a version
+    /// that's already complete returns straight away.
+    pub async fn install_forge(
+        &self,
+        flavor: ForgeFlavor,
+        minecraft_version: &str,
+        loader_version: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if !loader_version.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_') {
+            return Err(format!("Invalid {} version '{}'", flavor.display_name(), loader_version).into());
+        }
+        let loader_version = &self.maven_version(flavor, minecraft_version, loader_version).await?;
+        let profile_id = Self::profile_id(flavor, minecraft_version, loader_version);
+        if self.is_installed(&profile_id) {
+            println!("✓ {} {} is already installed", flavor.display_name(), loader_version);
+            return Ok(profile_id);
+        }
+
+        println!(
+            "=== Installing {} {} for Minecraft {} ===",
+            flavor.display_name(),
+            loader_version,
+            minecraft_version
+        );
+
+        // Processors patch the vanilla jar, so it has to be there first
+        let minecraft_installer = MinecraftInstaller::new(self.launcher_dir.clone());
+        if !minecraft_installer.check_version_installed(minecraft_version) {
+            minecraft_installer
+                .install_version(minecraft_version)
+                .await
+                .map_err(|e| format!("Failed to install Minecraft {}: {}", minecraft_version, e))?;
+        }
+
+        let installer_name = flavor.installer_name(minecraft_version, loader_version);
+        let installer_path = self.libraries_dir().join(maven_path(&installer_name)?);
+        let installer_url = format!("{}{}", flavor.maven_url(), maven_path(&installer_name)?);
+        if !long_path(&installer_path).exists() {
+            let bytes = self.download(&installer_url).await?;
+            write_file(&installer_path, &bytes)?;
+            println!("  ✓ Downloaded installer: {}", installer_name);
+        }
+        let installer = fs::read(long_path(&installer_path))?;
+
+        let install_profile: InstallProfile = serde_json::from_slice(
+            &read_entry(&installer, "install_profile.json").ok_or("The installer has no install_profile.json")?,
+        )?;
+
+        let profile = if let (Some(install), Some(version_info)) = (&install_profile.install, &install_profile.version_info) {
+            self.install_legacy(&installer, install, version_info, &profile_id, minecraft_version).await?
+        } else {
+            self.install_modern(&installer, &installer_path, &install_profile, &profile_id, minecraft_version)
+                .await?
+        };
+
+        let versions_dir = self.launcher_dir.join("versions").join(&profile_id);
+        fs::create_dir_all(&versions_dir)?;
+        let profile_path = versions_dir.join(format!("{}.json", profile_id));
+        fs::write(&profile_path, serde_json::to_string_pretty(&profile)?)?;
+        println!("✓ Created profile at: {}", profile_path.display());
+
+        self.report(&profile_id, ForgeInstallStage::Done);
+        println!("=== {} Installation Complete ===", flavor.display_name());
+        Ok(profile_id)
+    }
+
+    /// Old Forge builds are published as `1.7.10-10.13.4.1614-1.7.10`, while modpacks and other
+    /// launchers name them `10.13.4.1614`. Returns the name the maven uses.
+    async fn maven_version(
+        &self,
+        flavor: ForgeFlavor,
+        minecraft_version: &str,
+        loader_version: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Some launchers store the full maven name
+        let loader_version = loader_version
+            .strip_prefix(&format!("{}-", minecraft_version))
+            .unwrap_or(loader_version);
+        let suffixed = format!("{}-{}", loader_version, minecraft_version);
+        if flavor == ForgeFlavor::NeoForge || self.is_installed(&Self::profile_id(flavor, minecraft_version, loader_version)) {
+            return Ok(loader_version.to_string());
+        }
+        if self.is_installed(&Self::profile_id(flavor, minecraft_version, &suffixed)) {
+            return Ok(suffixed);
+        }
+
+        let versions = self.get_loader_versions(flavor, minecraft_version).await?;
+        versions
+            .into_iter()
+            .map(|v| v.version)
+            .find(|v| *v == loader_version || *v == suffixed)
+            .ok_or_else(|| format!("Forge {} not found for Minecraft {}", loader_version, minecraft_version).into())
+    }
+
+    /// Install the profile a launch needs if it's missing, e.g. after the meta folder was cleaned
+    pub async fn ensure_installed(&self, instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(flavor) = instance.loader.as_deref().and_then(ForgeFlavor::from_loader) else {
+            return Ok(());
+        };
+        let Some(loader_version) = instance.loader_version.as_deref() else {
+            return Ok(());
+        };
+        if self.is_installed(&instance.version) {
+            return Ok(());
+        }
+
+        let minecraft_version = crate::services::instance::InstanceManager::base_minecraft_version(&instance.version);
+
+        println!("{} {} for '{}' is missing, installing it", flavor.display_name(), loader_version, instance.name);
+        self.install_forge(flavor, &minecraft_version, loader_version).await?;
+        Ok(())
+    }
+
+    /// The profile exists and every library it lists is on disk
+    fn is_installed(&self, profile_id: &str) -> bool {
+        let profile_path = self
+            .launcher_dir
+            .join("versions")
+            .join(profile_id)
+            .join(format!("{}.json", profile_id));
+        let Ok(content) = fs::read_to_string(&profile_path) else {
+            return false;
+        };
+        let Ok(profile) = serde_json::from_str::<FabricProfileJson>(&content) else {
+            return false;
+        };
+
+        profile.libraries.iter().all(|lib| {
+            library_path(lib).is_ok_and(|path| long_path(&self.libraries_dir().join(path)).exists())
+        })
+    }
+
+    /// Installers for Minecraft 1.13+ (and late 1.12.2 builds): libraries, then processors
+    async fn install_modern(
+        &self,
+        installer: &[u8],
+        installer_path: &Path,
+        install_profile: &InstallProfile,
+        profile_id: &str,
+        minecraft_version: &str,
+    ) -> Result<FabricProfileJson, Box<dyn std::error::Error>> {
+        let json_name = install_profile.json.as_deref().unwrap_or("/version.json").trim_start_matches('/');
+        let mut version_json: serde_json::Value =
+            serde_json::from_slice(&read_entry(installer, json_name).ok_or("The installer has no version JSON")?)?;
+
+        // Older profiles keep their tweaker in a single argument string
+        if version_json.get("arguments").is_none() {
+            if let Some(arguments) = version_json.get("minecraftArguments").and_then(|a| a.as_str()) {
+                let game = tweak_class_args(arguments);
+                version_json["arguments"] = serde_json::json!({ "game": game, "jvm": [] });
+            }
+        }
+        version_json["id"] = serde_json::Value::String(profile_id.to_string());
+        let profile: FabricProfileJson = serde_json::from_value(version_json)?;
+
+        println!(
+            "Downloading {} installer and {} game libraries...",
+            install_profile.libraries.len(),
+            profile.libraries.len()
+        );
+        let total = install_profile.libraries.len() + profile.libraries.len();
+        for (done, lib) in install_profile.libraries.iter().enumerate() {
+            if !self.install_library(installer, lib).await? {
+                return Err(format!("Installer library {} has no download", lib.name).into());
+            }
+            self.report(profile_id, ForgeInstallStage::Libraries { done: done + 1, total });
+        }
+        // Libraries without a download are produced by the processors below
+        let mut pending = Vec::new();
+        for (done, lib) in profile.libraries.iter().enumerate() {
+            if !self.install_library(installer, lib).await? {
+                pending.push(lib.name.clone());
+            }
+            self.report(
+                profile_id,
+                ForgeInstallStage::Libraries {
+                    done: install_profile.libraries.len() + done + 1,
+                    total,
+                },
+            );
+        }
+
+        self.run_processors(installer, installer_path, install_profile, profile_id, minecraft_version)
+            .await?;
+
+        let missing: Vec<&String> = profile
+            .libraries
+            .iter()
+            .filter(|lib| pending.contains(&lib.name))
+            .filter(|lib| !library_path(lib).is_ok_and(|p| long_path(&self.libraries_dir().join(p)).exists()))
+            .map(|lib| &lib.name)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("The installer did not produce {:?}", missing).into());
+        }
+
+        Ok(profile)
+    }
+
+    /// Installers for Minecraft 1.12.2 and older: the universal jar plus a LaunchWrapper profile
+    async fn install_legacy(
+        &self,
+        installer: &[u8],
+        install: &LegacyInstall,
+        version_info: &LegacyVersionInfo,
+        profile_id: &str,
+        minecraft_version: &str,
+    ) -> Result<FabricProfileJson, Box<dyn std::error::Error>> {
+        let universal = read_entry(installer, &install.file_path)
+            .ok_or_else(|| format!("The installer has no {}", install.file_path))?;
+        write_file(&self.libraries_dir().join(maven_path(&install.path)?), &universal)?;
+
+        let mut libraries = Vec::new();
+        for lib in version_info.libraries.iter().filter(|l| l.clientreq != Some(false)) {
+            let path = maven_path(&lib.name)?;
+            let target = self.libraries_dir().join(&path);
+
+            let mut mirrors: Vec<String> = LEGACY_LIBRARY_MIRRORS.iter().map(|m| m.to_string()).collect();
+            if let Some(url) = lib.url.as_deref().filter(|u| u.starts_with("https://")) {
+                mirrors.insert(0, url.to_string());
+            }
+
+            let mut found = None;
+            if long_path(&target).exists() {
+                found = Some(mirrors[0].clone());
+            } else {
+                for mirror in &mirrors {
+                    let url = format!("{}/{}", mirror.trim_end_matches('/'), path);
+                    let Ok(bytes) = self.download(&url).await else {
+                        continue;
+                    };
+                    if !lib.checksums.is_empty() && !lib.checksums.contains(&sha1_hex(&bytes)) {
+                        println!("  ✗ Checksum mismatch for {} from {}", lib.name, mirror);
+                        continue;
+                    }
+                    write_file(&target, &bytes)?;
+                    println!("  ✓ Downloaded: {}", lib.name);
+                    found = Some(mirror.clone());
+                    break;
+                }
+            }
+
+            let url = found.ok_or_else(|| format!("Failed to download library {}", lib.name))?;
+            libraries.push(FabricProfileLibrary {
+                name: lib.name.clone(),
+                url,
+                downloads: None,
+            });
+        }
+
+        let now = Utc::now().to_rfc3339();
+        Ok(FabricProfileJson {
+            id: profile_id.to_string(),
+            inherits_from: minecraft_version.to_string(),
+            release_time: now.clone(),
+            time: now,
+            profile_type: "release".to_string(),
+            main_class: version_info.main_class.clone(),
+            arguments: Some(FabricArguments {
+                game: tweak_class_args(&version_info.minecraft_arguments),
+                jvm: Vec::new(),
+            }),
+            libraries,
+        })
+    }
+
+    /// Make sure a library is in libraries/ with the right hash, from the installer's bundled
+    /// maven folder or its download URL. Returns false when it has neither.
+    async fn install_library(
+        &self,
+        installer: &[u8],
+        lib: &FabricProfileLibrary,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let artifact = lib.downloads.as_ref().and_then(|d| d.artifact.as_ref());
+        let path = library_path(lib)?;
+        let sha1 = artifact.map(|a| a.sha1.as_str()).unwrap_or_default();
+        let target = self.libraries_dir().join(&path);
+
+        if matches_sha1(&target, sha1) {
+            return Ok(true);
+        }
+
+        let bytes = if let Some(bytes) = read_entry(installer, &format!("maven/{}", path)) {
+            bytes
+        } else {
+            let url = match artifact {
+                Some(a) if !a.url.is_empty() => a.url.clone(),
+                _ if !lib.url.is_empty() => format!("{}/{}", lib.url.trim_end_matches('/'), path),
+                _ => return Ok(false),
+            };
+            self.download(&url).await?
+        };
+
+        if !sha1.is_empty() && sha1_hex(&bytes) != sha1 {
+            return Err(format!("SHA1 mismatch for {}", lib.name).into());
+        }
+        write_file(&target, &bytes)?;
+        println!("  ✓ Installed: {}", lib.name);
+        Ok(true)
+    }
+
+    async fn run_processors(
+        &self,
+        installer: &[u8],
+        installer_path: &Path,
+        install_profile: &InstallProfile,
+        profile_id: &str,
+        minecraft_version: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let processors: Vec<&Processor> = install_profile
+            .processors
+            .iter()
+            .filter(|p| p.sides.as_ref().map_or(true, |s| s.iter().any(|side| side == "client")))
+            .collect();
+        if processors.is_empty() {
+            return Ok(());
+        }
+
+        let libraries_dir = self.libraries_dir();
+        let work_dir = std::env::temp_dir().join(format!("atomic-{}-install", profile_id));
+        fs::create_dir_all(&work_dir)?;
+
+        let minecraft_jar = self
+            .launcher_dir
+            .join("versions")
+            .join(minecraft_version)
+            .join(format!("{}.jar", minecraft_version));
+        let mut vars = HashMap::from([
+            ("SIDE".to_string(), "client".to_string()),
+            ("MINECRAFT_JAR".to_string(), path_string(&minecraft_jar)),
+            ("MINECRAFT_VERSION".to_string(), minecraft_version.to_string()),
+            ("ROOT".to_string(), path_string(&self.launcher_dir)),
+            ("INSTALLER".to_string(), path_string(installer_path)),
+            ("LIBRARY_DIR".to_string(), path_string(&libraries_dir)),
+        ]);
+        for (key, value) in &install_profile.data {
+            let resolved = if let Some(coordinate) = value.client.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                path_string(&libraries_dir.join(maven_path(coordinate)?))
+            } else if let Some(entry) = value.client.strip_prefix('/') {
+                // Files bundled in the installer, e.g. the binary patches
+                let bytes = read_entry(installer, entry).ok_or_else(|| format!("The installer has no {}", entry))?;
+                let target = work_dir.join(entry);
+                write_file(&target, &bytes)?;
+                path_string(&target)
+            } else {
+                value.client.clone()
+            };
+            vars.insert(key.clone(), resolved);
+        }
+
+        let java_path = JavaManager::ensure_for_version(minecraft_version).await?;
+        let classpath_separator = if cfg!(windows) { ";" } else { ":" };
+
+        let log_path = install_log_path(profile_id);
+        fs::create_dir_all(get_logs_dir())?;
+        let mut log = tokio::fs::File::create(&log_path).await?;
+
+        for (index, processor) in processors.iter().enumerate() {
+            let outputs = processor
+                .outputs
+                .iter()
+                .map(|(file, sha1)| Ok((resolve_arg(file, &vars, &libraries_dir)?, resolve_arg(sha1, &vars, &libraries_dir)?)))
+                .collect::<Result<Vec<(String, String)>, String>>()?;
+            if !outputs.is_empty() && outputs.iter().all(|(file, sha1)| matches_sha1(Path::new(file), sha1)) {
+                println!("  → Processor {} already done: {}", index + 1, processor.jar);
+                continue;
+            }
+
+            let jar_path = libraries_dir.join(maven_path(&processor.jar)?);
+            let main_class = jar_main_class(&jar_path)?;
+            let mut classpath = vec![path_string(&jar_path)];
+            for entry in &processor.classpath {
+                classpath.push(path_string(&libraries_dir.join(maven_path(entry)?)));
+            }
+            let args = processor
+                .args
+                .iter()
+                .map(|a| resolve_arg(a, &vars, &libraries_dir))
+                .collect::<Result<Vec<String>, String>>()?;
+
+            println!("  Running processor {}/{}: {}", index + 1, processors.len(), processor.jar);
+            self.report(
+                profile_id,
+                ForgeInstallStage::Processor {
+                    index: index + 1,
+                    total: processors.len(),
+                    name: processor.jar.clone(),
+                },
+            );
+            log.write_all(format!("=== Processor {}/{}: {}\n", index + 1, processors.len(), processor.jar).as_bytes())
+                .await?;
+
+            let mut child = tokio::process::Command::new(&java_path)
+                .arg("-cp")
+                .arg(classpath.join(classpath_separator))
+                .arg(&main_class)
+                .args(&args)
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()?;
+
+            // Both streams feed one channel so the log keeps the order the lines came in
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<String>();
+            if let Some(stdout) = child.stdout.take() {
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let mut lines = BufReader::new(stdout).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let _ = sender.send(line);
+                    }
+                });
+            }
+            if let Some(stderr) = child.stderr.take() {
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let mut lines = BufReader::new(stderr).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let _ = sender.send(line);
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut tail: Vec<String> = Vec::new();
+            while let Some(line) = receiver.recv().await {
+                log.write_all(format!("{}\n", line).as_bytes()).await?;
+                if tail.len() == 10 {
+                    tail.remove(0);
+                }
+                tail.push(line.clone());
+                self.report(profile_id, ForgeInstallStage::Output { line });
+            }
+
+            let status = child.wait().await?;
+            log.flush().await?;
+            if !status.success() {
+                return Err(format!(
+                    "Processor {} failed ({}), full output in {}: {}",
+                    processor.jar,
+                    status,
+                    log_path.display(),
+                    tail.join("\n")
+                )
+                .into());
+            }
+
+            for (file, sha1) in &outputs {
+                if !matches_sha1(Path::new(file), sha1) {
+                    return Err(format!("Processor {} produced a bad {}", processor.jar, file).into());
+                }
+            }
+        }
+
+        let _ = fs::remove_dir_all(&work_dir);
+        println!("✓ Ran {} installer processors", processors.len());
+        Ok(())
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let response = self.http_client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to download {}: HTTP {}", url, response.status()).into());
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    fn libraries_dir(&self) -> PathBuf {
+        self.launcher_dir.join("libraries")
+    }
+}
+
+/// Where a profile library lives under libraries/
+pub fn library_path(lib: &FabricProfileLibrary) -> Result<String, String> {
+    match lib.downloads.as_ref().and_then(|d| d.artifact.as_ref()) {
+        Some(artifact) if !artifact.path.is_empty() => Ok(artifact.path.clone()),
+        _ => maven_path(&lib.name),
+    }
+}
+
+/// Processor output of the last install of a profile
+pub fn install_log_path(profile_id: &str) -> PathBuf {
+    get_logs_dir().join(format!("install-{}.log", profile_id))
+}
+
+/// NeoForge 20.4.x is for Minecraft 1.20.4, 21.0.x for 1.21
+fn neoforge_minecraft_version(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major = parts.next()?;
+    let minor = parts.next()?;
+    if !major.chars().all(|c| c.is_ascii_digit()) || !minor.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(if minor == "0" {
+        format!("1.{}", major)
+    } else {
+        format!("1.{}.{}", major, minor)
+    })
+}
+
+/// The `--tweakClass` pairs of a legacy argument string, the rest are vanilla's
+fn tweak_class_args(arguments: &str) -> Vec<serde_json::Value> {
+    let words: Vec<&str> = arguments.split_whitespace().collect();
+    words
+        .windows(2)
+        .filter(|pair| pair[0] == "--tweakClass")
+        .flat_map(|pair| pair.iter().map(|w| serde_json::Value::String(w.to_string())))
+        .collect()
+}
+
+/// Processor arguments are `[maven:coordinate]`, `{DATA_KEY}` or `'literal'`
+fn resolve_arg(arg: &str, vars: &HashMap<String, String>, libraries_dir: &Path) -> Result<String, String> {
+    if let Some(coordinate) = arg.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+        return Ok(path_string(&libraries_dir.join(maven_path(coordinate)?)));
+    }
+    if let Some(literal) = arg.strip_prefix('\'').and_then(|a| a.strip_suffix('\'')) {
+        return Ok(literal.to_string());
+    }
+
+    let mut resolved = arg.to_string();
+    let mut position = 0;
+    while let Some(start) = resolved[position..].find('{').map(|s| position + s) {
+        let Some(end) = resolved[start..].find('}').map(|e| start + e) else {
+            break;
+        };
+        let key = &resolved[start + 1..end];
+        let value = vars.get(key).ok_or_else(|| format!("Unknown installer variable {{{}}}", key))?;
+        let value = value.trim_matches('\'').to_string();
+        resolved.replace_range(start..=end, &value);
+        position = start + value.len();
+    }
+    Ok(resolved)
+}
+
+/// Main-Class from a jar's manifest, whose long lines continue on lines starting with a space
+fn jar_main_class(jar_path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let bytes = fs::read(long_path(jar_path))?;
+    let manifest = read_entry(&bytes, "META-INF/MANIFEST.MF")
+        .ok_or_else(|| format!("{} has no manifest", jar_path.display()))?;
+    let manifest = String::from_utf8_lossy(&manifest).replace("\r\n", "\n").replace("\n ", "");
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Main-Class:"))
+        .map(|class| class.trim().to_string())
+        .ok_or_else(|| format!("{} has no Main-Class", jar_path.display()).into())
+}
+
+fn read_entry(archive: &[u8], name: &str) -> Option<Vec<u8>> {
+    let mut archive = ZipArchive::new(Cursor::new(archive)).ok()?;
+    let mut entry = archive.by_name(name).ok()?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// The file exists and, when a hash is given, has it
+fn matches_sha1(path: &Path, sha1: &str) -> bool {
+    match fs::read(long_path(path)) {
+        Ok(bytes) => sha1.is_empty() || sha1_hex(&bytes) == sha1,
+        Err(_) => false,
+    }
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(long_path(parent))?;
+    }
+    fs::write(long_path(path), bytes)?;
+    Ok(())
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
@@ -14,7 +14,7 @@ impl FriendsService {
         let supabase_key = env!("SUPABASE_ANON_KEY").to_string();
 
         Ok(Self {
-            client: reqwest::Client::new(),
+            client: crate::utils::http::client(),
             supabase_url,
             supabase_key,
         })
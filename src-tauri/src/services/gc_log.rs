@@ -0,0 +1,244 @@
+use crate::utils::get_instance_dir;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const GC_LOG_NAME: &str = "gc.log";
+const GC_REPORT_NAME: &str = "gc-report.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GcReport {
+    pub instance: String,
+    pub analyzed_at: String,
+    pub collections: usize,
+    pub full_collections: usize,
+    pub runtime_seconds: f64,
+    pub total_pause_ms: f64,
+    /// Share of the runtime spent in GC pauses
+    pub pause_time_percent: f64,
+    pub pause_p50_ms: f64,
+    pub pause_p95_ms: f64,
+    pub pause_p99_ms: f64,
+    pub pause_max_ms: f64,
+    pub allocation_rate_mb_per_sec: f64,
+    pub peak_heap_after_gc_mb: f64,
+    pub heap_capacity_mb: f64,
+    pub recommendations: Vec<String>,
+}
+
+/// One stop-the-world collection
+#[derive(Debug, Clone)]
+struct GcPause {
+    uptime_seconds: f64,
+    before_mb: f64,
+    after_mb: f64,
+    capacity_mb: f64,
+    pause_ms: f64,
+    full: bool,
+}
+
+pub struct GcLogAnalyzer;
+
+impl GcLogAnalyzer {
+    pub fn get_log_path(instance_name: &str) -> PathBuf {
+        get_instance_dir(instance_name).join("logs").join(GC_LOG_NAME)
+    }
+
+    fn get_report_path(instance_name: &str) -> PathBuf {
+        get_instance_dir(instance_name).join("logs").join(GC_REPORT_NAME)
+    }
+
+    /// JVM flags that write a GC log the analyzer understands
+    pub fn jvm_flags(java_version: u32, log_path: &Path) -> Vec<String> {
+        if java_version >= 9 {
+            // Quoted so Windows drive letters don't end the file option
+            vec![format!("-Xlog:gc:file=\"{}\":uptime", log_path.display())]
+        } else {
+            vec![
+                "-verbose:gc".to_string(),
+                "-XX:+PrintGCTimeStamps".to_string(),
+                format!("-Xloggc:{}", log_path.display()),
+            ]
+        }
+    }
+
+    /// Analyze the instance's last GC log and store the report next to it
+    pub fn analyze(instance_name: &str, memory_mb: u32) -> Result<GcReport, Box<dyn std::error::Error>> {
+        let log_path = Self::get_log_path(instance_name);
+        if !log_path.exists() {
+            return Err("No GC log found. Enable GC logging and launch the instance first.".into());
+        }
+
+        let content = fs::read_to_string(&log_path)?;
+        let pauses: Vec<GcPause> = content.lines().filter_map(parse_line).collect();
+
+        if pauses.is_empty() {
+            return Err("GC log contains no collections".into());
+        }
+
+        let report = build_report(instance_name, &pauses, memory_mb);
+        fs::write(Self::get_report_path(instance_name), serde_json::to_string_pretty(&report)?)?;
+
+        println!(
+            "✓ GC report for '{}': {} collections, p99 {:.1}ms, {:.1}% paused",
+            instance_name, report.collections, report.pause_p99_ms, report.pause_time_percent
+        );
+        Ok(report)
+    }
+
+    pub fn load_report(instance_name: &str) -> Result<Option<GcReport>, Box<dyn std::error::Error>> {
+        let path = Self::get_report_path(instance_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+}
+
+fn build_report(instance_name: &str, pauses: &[GcPause], memory_mb: u32) -> GcReport {
+    let mut durations: Vec<f64> = pauses.iter().map(|p| p.pause_ms).collect();
+    durations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let total_pause_ms: f64 = durations.iter().sum();
+    let runtime_seconds = pauses.iter().map(|p| p.uptime_seconds).fold(0.0, f64::max);
+
+    // Whatever was freed by one collection and is back by the next was allocated in between
+    let mut allocated_mb = 0.0;
+    let mut previous_after = 0.0;
+    for pause in pauses {
+        allocated_mb += (pause.before_mb - previous_after).max(0.0);
+        previous_after = pause.after_mb;
+    }
+
+    let pause_time_percent = if runtime_seconds > 0.0 {
+        total_pause_ms / (runtime_seconds * 1000.0) * 100.0
+    } else {
+        0.0
+    };
+
+    let mut report = GcReport {
+        instance: instance_name.to_string(),
+        analyzed_at: Utc::now().to_rfc3339(),
+        collections: pauses.len(),
+        full_collections: pauses.iter().filter(|p| p.full).count(),
+        runtime_seconds,
+        total_pause_ms,
+        pause_time_percent,
+        pause_p50_ms: percentile(&durations, 50.0),
+        pause_p95_ms: percentile(&durations, 95.0),
+        pause_p99_ms: percentile(&durations, 99.0),
+        pause_max_ms: durations.last().copied().unwrap_or(0.0),
+        allocation_rate_mb_per_sec: if runtime_seconds > 0.0 { allocated_mb / runtime_seconds } else { 0.0 },
+        peak_heap_after_gc_mb: pauses.iter().map(|p| p.after_mb).fold(0.0, f64::max),
+        heap_capacity_mb: pauses.iter().map(|p| p.capacity_mb).fold(0.0, f64::max),
+        recommendations: Vec::new(),
+    };
+
+    let live_ratio = report.peak_heap_after_gc_mb / memory_mb.max(1) as f64;
+
+    if report.full_collections > 0 || live_ratio > 0.85 {
+        report.recommendations.push(format!(
+            "The heap stays {:.0}% full after collections. Allocating more memory should reduce stutter.",
+            live_ratio * 100.0
+        ));
+    } else if live_ratio < 0.3 && memory_mb > 4096 {
+        report.recommendations.push(format!(
+            "Only {:.0}MB stays live after collections. {}MB is more than this instance needs; a smaller heap gives shorter pauses.",
+            report.peak_heap_after_gc_mb, memory_mb
+        ));
+    }
+
+    if report.pause_time_percent > 5.0 {
+        report.recommendations.push(format!(
+            "{:.1}% of the session was spent in GC pauses.",
+            report.pause_time_percent
+        ));
+    }
+
+    if report.pause_p99_ms > 100.0 {
+        report.recommendations.push(format!(
+            "The slowest 1% of pauses take over {:.0}ms, which shows up as visible stutter.",
+            report.pause_p99_ms
+        ));
+    }
+
+    report
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let rank = ((p / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+/// Parses both unified logging (Java 9+) and -Xloggc (Java 8) lines:
+/// `[12.345s][info][gc] GC(3) Pause Young (Normal) (G1 Evacuation Pause) 24M->3M(256M) 5.123ms`
+/// `12.345: [GC (Allocation Failure)  33280K->5112K(125952K), 0.0043210 secs]`
+fn parse_line(line: &str) -> Option<GcPause> {
+    let (uptime_seconds, pause_ms, full) = if line.starts_with('[') {
+        if !line.contains(" Pause ") {
+            return None;
+        }
+
+        let uptime = line[1..].split("s]").next()?.parse::<f64>().ok()?;
+        let pause = line.trim_end().strip_suffix("ms")?.rsplit(' ').next()?.parse::<f64>().ok()?;
+        (uptime, pause, line.contains("Pause Full"))
+    } else {
+        let (timestamp, rest) = line.split_once(": [")?;
+        if !rest.starts_with("GC") && !rest.starts_with("Full GC") {
+            return None;
+        }
+
+        let uptime = timestamp.trim().parse::<f64>().ok()?;
+        let secs = rest.split(" secs]").next()?.rsplit(' ').next()?.parse::<f64>().ok()?;
+        (uptime, secs * 1000.0, rest.starts_with("Full GC"))
+    };
+
+    let (before_mb, after_mb, capacity_mb) = parse_heap_change(line)?;
+
+    Some(GcPause {
+        uptime_seconds,
+        before_mb,
+        after_mb,
+        capacity_mb,
+        pause_ms,
+        full,
+    })
+}
+
+/// The last `before->after(capacity)` group on the line, in MB
+fn parse_heap_change(line: &str) -> Option<(f64, f64, f64)> {
+    let arrow = line.rfind("->")?;
+
+    let before_start = line[..arrow]
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let before = parse_size(&line[before_start..arrow])?;
+
+    let rest = &line[arrow + 2..];
+    let (after, rest) = rest.split_once('(')?;
+    let capacity = rest.split(')').next()?;
+
+    Some((before, parse_size(after)?, parse_size(capacity)?))
+}
+
+fn parse_size(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (number, unit) = value.split_at(value.len().checked_sub(1)?);
+    let number = number.parse::<f64>().ok()?;
+
+    match unit {
+        "K" => Some(number / 1024.0),
+        "M" => Some(number),
+        "G" => Some(number * 1024.0),
+        _ => None,
+    }
+}
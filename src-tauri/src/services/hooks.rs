@@ -0,0 +1,118 @@
+use crate::models::Instance;
+use crate::utils::{get_instance_dir, get_launcher_dir};
+use std::{
+    io::Read,
+    process::{Child, Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A hook still running after this is killed and counts as failed
+const HOOK_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub struct LaunchHooks;
+
+impl LaunchHooks {
+    /// Run a user hook through the system shell from the instance folder and wait for it,
+    /// for at most `HOOK_TIMEOUT`.
+    /// The instance is described by `$INSTANCE_NAME`, `$INSTANCE_DIR`, `$INSTANCE_MC_VERSION`,
+    /// `$INSTANCE_LOADER` and `$LAUNCHER_DIR`, plus any `extra_env`.
+    pub fn run(
+        command: &str,
+        instance: &Instance,
+        extra_env: &[(&str, String)],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+
+        #[cfg(target_os = "windows")]
+        let mut cmd = {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        };
+
+        #[cfg(not(target_os = "windows"))]
+        let mut cmd = {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
+        };
+
+        cmd.current_dir(&instance_dir)
+            .env("INSTANCE_NAME", &instance.name)
+            .env("INSTANCE_DIR", &instance_dir)
+            .env("INSTANCE_MC_VERSION", &instance.version)
+            .env("INSTANCE_LOADER", instance.loader.as_deref().unwrap_or("vanilla"))
+            .env("LAUNCHER_DIR", get_launcher_dir());
+        for (key, value) in extra_env {
+            cmd.env(key, value);
+        }
+
+        println!("Running hook: {}", command);
+        let child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Launches run on the async runtime's workers, don't stall the other tasks on them
+        let output = match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| wait_with_timeout(child, HOOK_TIMEOUT))
+            }
+            _ => wait_with_timeout(child, HOOK_TIMEOUT),
+        }?;
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            println!("[hook] {}", line);
+        }
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            eprintln!("[hook] {}", line);
+        }
+
+        if !output.status.success() {
+            return Err(format!("Hook exited with {}", output.status).into());
+        }
+
+        println!("✓ Hook finished");
+        Ok(())
+    }
+}
+
+/// Like `Child::wait_with_output`, but kills the child once the timeout passes
+fn wait_with_timeout(mut child: Child, timeout: Duration) -> Result<Output, Box<dyn std::error::Error>> {
+    // Drained on their own threads so a chatty hook can't fill a pipe and stall
+    let stdout = child.stdout.take().map(|mut pipe| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
+    });
+    let stderr = child.stderr.take().map(|mut pipe| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("Hook timed out after {} seconds", timeout.as_secs()).into());
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.and_then(|t| t.join().ok()).unwrap_or_default(),
+        stderr: stderr.and_then(|t| t.join().ok()).unwrap_or_default(),
+    })
+}
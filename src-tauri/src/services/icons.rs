@@ -0,0 +1,67 @@
+use crate::services::instance::InstanceManager;
+use crate::utils::get_instance_dir;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+
+/// Icons shipped with the launcher, picked by id instead of uploading an image
+const BUNDLED_ICONS: &[(&str, &str, &[u8])] = &[
+    ("grass", "Grass Block", include_bytes!("../../resources/instance-icons/grass.png")),
+    ("dirt", "Dirt", include_bytes!("../../resources/instance-icons/dirt.png")),
+    ("stone", "Stone", include_bytes!("../../resources/instance-icons/stone.png")),
+    ("cobblestone", "Cobblestone", include_bytes!("../../resources/instance-icons/cobblestone.png")),
+    ("planks", "Oak Planks", include_bytes!("../../resources/instance-icons/planks.png")),
+    ("sand", "Sand", include_bytes!("../../resources/instance-icons/sand.png")),
+    ("diamond_ore", "Diamond Ore", include_bytes!("../../resources/instance-icons/diamond_ore.png")),
+    ("obsidian", "Obsidian", include_bytes!("../../resources/instance-icons/obsidian.png")),
+    ("netherrack", "Netherrack", include_bytes!("../../resources/instance-icons/netherrack.png")),
+    ("end_stone", "End Stone", include_bytes!("../../resources/instance-icons/end_stone.png")),
+];
+
+/// Same size as uploaded icons
+const ICON_SIZE: u32 = 256;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BundledIcon {
+    pub id: String,
+    pub name: String,
+    /// `data:image/png;base64,...` of the original pixel art
+    pub data_url: String,
+}
+
+pub struct InstanceIcons;
+
+impl InstanceIcons {
+    pub fn list() -> Vec<BundledIcon> {
+        BUNDLED_ICONS
+            .iter()
+            .map(|(id, name, bytes)| BundledIcon {
+                id: id.to_string(),
+                name: name.to_string(),
+                data_url: format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(bytes)),
+            })
+            .collect()
+    }
+
+    /// Raw PNG of a bundled icon
+    pub fn bundled(icon_id: &str) -> Option<&'static [u8]> {
+        BUNDLED_ICONS.iter().find(|(id, _, _)| *id == icon_id).map(|(_, _, bytes)| *bytes)
+    }
+
+    /// Write a bundled icon to the instance's icon.png, so it's exported and cloned like an uploaded one
+    pub fn apply(instance_name: &str, icon_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = Self::bundled(icon_id).ok_or_else(|| format!("Unknown icon '{}'", icon_id))?;
+
+        let mut instance = InstanceManager::load(instance_name)?;
+
+        // Nearest keeps the pixel art sharp
+        let img = image::load_from_memory(bytes)?;
+        let resized = img.resize_exact(ICON_SIZE, ICON_SIZE, image::imageops::FilterType::Nearest);
+        resized.save(get_instance_dir(instance_name).join("icon.png"))?;
+
+        instance.icon_path = Some("icon.png".to_string());
+        InstanceManager::save(&instance)?;
+
+        println!("✓ Set icon '{}' for instance '{}'", icon_id, instance_name);
+        Ok(())
+    }
+}
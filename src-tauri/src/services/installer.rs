@@ -1,11 +1,20 @@
 use crate::models::*;
-use crate::utils::get_current_os;
+use crate::services::antivirus::{AntivirusDetector, AntivirusInterference};
+use crate::utils::{get_current_os, long_path};
 use sha1::{Digest, Sha1};
 use std::{fs, path::PathBuf, sync::Arc, time::Duration};
 use tokio::sync::Semaphore;
 
 const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
+/// Cached copy of the manifest in the meta folder
+const MANIFEST_CACHE_FILE: &str = "version_manifest.json";
+const MANIFEST_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+const MANIFEST_REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// Short enough that a slow Mojang falls back to the cache instead of blocking instance creation
+const MANIFEST_TIMEOUT: Duration = Duration::from_secs(10);
 const MAX_CONCURRENT_DOWNLOADS: usize = 32;
+// Real-time scanners usually settle within a second or two of the write, suspicious files get that long to reappear
+const AV_RECHECK_DELAY: Duration = Duration::from_secs(2);
 
 type DownloadError = Box<dyn std::error::Error + Send + Sync>;
 
@@ -16,7 +25,7 @@ pub struct MinecraftInstaller {
 
 impl MinecraftInstaller {
     pub fn new(launcher_dir: PathBuf) -> Self {
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::utils::http::client_builder()
             .timeout(Duration::from_secs(300))
             .pool_max_idle_per_host(MAX_CONCURRENT_DOWNLOADS * 2)
             .pool_idle_timeout(Duration::from_secs(90))
@@ -37,6 +46,7 @@ impl MinecraftInstaller {
         url: &str,
         path: &PathBuf,
     ) -> Result<(), DownloadError> {
+        let path = &long_path(path);
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -84,10 +94,88 @@ impl MinecraftInstaller {
         Ok(true) // File was downloaded
     }
 
+    /// The version manifest, from the copy in the meta folder while it's fresh. When Mojang is
+    /// slow or offline an older copy is used instead, so versions can still be listed.
+    pub async fn version_manifest(&self) -> Result<VersionManifest, DownloadError> {
+        let cache_path = self.launcher_dir.join(MANIFEST_CACHE_FILE);
+        let cache_age = fs::metadata(&cache_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+
+        if cache_age.is_some_and(|age| age < MANIFEST_MAX_AGE) {
+            if let Some(manifest) = Self::cached_version_manifest(&cache_path) {
+                return Ok(manifest);
+            }
+        }
+
+        match self.refresh_version_manifest().await {
+            Ok(manifest) => Ok(manifest),
+            Err(e) => match Self::cached_version_manifest(&cache_path) {
+                Some(manifest) => {
+                    println!("Warning: Using cached version manifest, Mojang is unreachable: {}", e);
+                    Ok(manifest)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Download the version manifest and replace the cached copy
+    pub async fn refresh_version_manifest(&self) -> Result<VersionManifest, DownloadError> {
+        let response = self
+            .http_client
+            .get(VERSION_MANIFEST_URL)
+            .timeout(MANIFEST_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+        let content = response.text().await?;
+        let manifest: VersionManifest = serde_json::from_str(&content)?;
+
+        fs::create_dir_all(&self.launcher_dir)?;
+        let cache_path = self.launcher_dir.join(MANIFEST_CACHE_FILE);
+        let temp_path = cache_path.with_extension("json.tmp");
+        fs::write(&temp_path, &content)?;
+        fs::rename(&temp_path, &cache_path)?;
+
+        Ok(manifest)
+    }
+
+    fn cached_version_manifest(cache_path: &std::path::Path) -> Option<VersionManifest> {
+        serde_json::from_str(&fs::read_to_string(cache_path).ok()?).ok()
+    }
+
+    /// Keep the cached manifest current in the background, so listing versions rarely waits on Mojang
+    pub fn start_manifest_refresh(launcher_dir: PathBuf) {
+        tauri::async_runtime::spawn(async move {
+            let installer = Self::new(launcher_dir);
+            loop {
+                if let Err(e) = installer.refresh_version_manifest().await {
+                    println!("Warning: Could not refresh the version manifest: {}", e);
+                }
+                tokio::time::sleep(MANIFEST_REFRESH_INTERVAL).await;
+            }
+        });
+    }
+
+    /// Versions in any of the given channels, newest first
+    pub async fn get_versions_in_channels(
+        &self,
+        channels: &[VersionChannel],
+    ) -> Result<Vec<MinecraftVersion>, DownloadError> {
+        let manifest = self.version_manifest().await?;
+
+        Ok(manifest
+            .versions
+            .into_iter()
+            .filter(|v| channels.contains(&VersionChannel::of(v)))
+            .collect())
+    }
+
     /// Get all versions (releases, snapshots, and pre-releases)
     pub async fn get_versions(&self) -> Result<Vec<String>, DownloadError> {
-        let response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
-        let manifest: VersionManifest = response.json().await?;
+        let manifest = self.version_manifest().await?;
 
         let versions: Vec<String> = manifest
             .versions
@@ -101,8 +189,7 @@ impl MinecraftInstaller {
 
     /// Get versions with metadata (includes version type)
     pub async fn get_versions_with_metadata(&self) -> Result<Vec<MinecraftVersion>, DownloadError> {
-        let response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
-        let manifest: VersionManifest = response.json().await?;
+        let manifest = self.version_manifest().await?;
 
         let versions: Vec<MinecraftVersion> = manifest
             .versions
@@ -115,8 +202,7 @@ impl MinecraftInstaller {
 
     /// Get versions by type (release, snapshot, old_beta, old_alpha)
     pub async fn get_versions_by_type(&self, version_type: &str) -> Result<Vec<String>, DownloadError> {
-        let response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
-        let manifest: VersionManifest = response.json().await?;
+        let manifest = self.version_manifest().await?;
 
         let versions: Vec<String> = manifest
             .versions
@@ -135,8 +221,13 @@ impl MinecraftInstaller {
     ) -> Result<(), DownloadError> {
         println!("=== Installing Minecraft {} ===", version_id);
 
-        let manifest_response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
-        let manifest: VersionManifest = manifest_response.json().await?;
+        let mut manifest = self.version_manifest().await?;
+        if !manifest.versions.iter().any(|v| v.id == version_id) {
+            // Released since the cached copy was taken
+            if let Ok(fresh) = self.refresh_version_manifest().await {
+                manifest = fresh;
+            }
+        }
 
         let version_info = manifest
             .versions
@@ -168,12 +259,17 @@ impl MinecraftInstaller {
 
         println!("Downloading client JAR...");
         let jar_path = versions_dir.join(format!("{}.jar", version_id));
-        self.download_file_with_sha1(
-            &version_details.downloads.client.url,
-            &jar_path,
-            &version_details.downloads.client.sha1,
-        )
-        .await?;
+        let mut fresh_jars = Vec::new();
+        if self
+            .download_file_with_sha1(
+                &version_details.downloads.client.url,
+                &jar_path,
+                &version_details.downloads.client.sha1,
+            )
+            .await?
+        {
+            fresh_jars.push((jar_path.clone(), version_details.downloads.client.sha1.clone()));
+        }
         println!("✓ Client JAR downloaded");
 
         let json_path = versions_dir.join(format!("{}.json", version_id));
@@ -189,68 +285,21 @@ impl MinecraftInstaller {
         let mut native_count = 0;
         let mut regular_count = 0;
         
-        for library in &version_details.libraries {
-            // Check if this is a native library
-            let is_native = library.name.contains(":natives-");
-            
-            if is_native {
-                // Extract the platform from the library name
-                let platform_suffix = if library.name.contains(":natives-windows") {
-                    "windows"
-                } else if library.name.contains(":natives-linux") {
-                    "linux"
-                } else if library.name.contains(":natives-macos") || library.name.contains(":natives-osx") {
-                    "osx"
-                } else {
-                    ""
-                };
-                
-                // Only download natives for current OS
-                if platform_suffix == current_os {
-                    if let Some(downloads) = &library.downloads {
-                        if let Some(artifact) = &downloads.artifact {
-                            // Check OS rules if they exist
-                            let should_include = if let Some(rules) = &library.rules {
-                                should_include_library(rules, &current_os)
-                            } else {
-                                true
-                            };
-                            
-                            if should_include {
-                                native_count += 1;
-                                library_tasks.push((
-                                    artifact.url.clone(),
-                                    libraries_dir.join(&artifact.path),
-                                    artifact.sha1.clone(),
-                                    format!("NATIVE: {}", library.name),
-                                ));
-                            }
-                        }
-                    }
-                }
+        for (is_native, library, artifact) in library_artifacts(&version_details, &current_os) {
+            let label = if is_native {
+                native_count += 1;
+                format!("NATIVE: {}", library.name)
             } else {
-                // Regular library (not a native)
-                if let Some(downloads) = &library.downloads {
-                    if let Some(artifact) = &downloads.artifact {
-                        // Check OS rules if they exist
-                        let should_include = if let Some(rules) = &library.rules {
-                            should_include_library(rules, &current_os)
-                        } else {
-                            true
-                        };
-
-                        if should_include {
-                            regular_count += 1;
-                            library_tasks.push((
-                                artifact.url.clone(),
-                                libraries_dir.join(&artifact.path),
-                                artifact.sha1.clone(),
-                                format!("regular: {}", library.name),
-                            ));
-                        }
-                    }
-                }
-            }
+                regular_count += 1;
+                format!("regular: {}", library.name)
+            };
+
+            library_tasks.push((
+                artifact.url.clone(),
+                libraries_dir.join(&artifact.path),
+                artifact.sha1.clone(),
+                label,
+            ));
         }
 
         println!("Total downloads queued: {} regular libraries + {} natives = {}", 
@@ -262,7 +311,10 @@ impl MinecraftInstaller {
         }
 
         let downloaded = self.download_parallel_with_types(library_tasks).await?;
-        println!("✓ Downloaded {} files", downloaded);
+        println!("✓ Downloaded {} files", downloaded.len());
+        fresh_jars.extend(downloaded);
+
+        Self::check_antivirus_interference(&fresh_jars).await?;
 
         // Download asset index
         println!("Downloading assets...");
@@ -303,39 +355,73 @@ impl MinecraftInstaller {
         Ok(())
     }
 
+    /// Files that vanish or change right after a successful download point to an antivirus
+    async fn check_antivirus_interference(fresh_files: &[(PathBuf, String)]) -> Result<(), DownloadError> {
+        if fresh_files.is_empty() || !AntivirusDetector::can_quarantine() {
+            return Ok(());
+        }
+
+        let suspicious = AntivirusDetector::find_tampered(fresh_files);
+        if suspicious.is_empty() {
+            return Ok(());
+        }
+
+        // Only the files that already look wrong are checked again, in case a scanner was still holding them
+        tokio::time::sleep(AV_RECHECK_DELAY).await;
+
+        let recheck: Vec<(PathBuf, String)> = fresh_files
+            .iter()
+            .filter(|(path, _)| suspicious.contains(path))
+            .cloned()
+            .collect();
+        let affected = AntivirusDetector::find_tampered(&recheck);
+        if affected.is_empty() {
+            return Ok(());
+        }
+
+        println!("✗ {} freshly downloaded file(s) disappeared or changed:", affected.len());
+        for path in &affected {
+            println!("  ✗ {}", path.display());
+        }
+
+        if let Err(e) = AntivirusDetector::record(&affected) {
+            eprintln!("Failed to save antivirus report: {}", e);
+        }
+
+        Err(Box::new(AntivirusInterference { affected_paths: affected }))
+    }
+
+    /// Returns the files that were actually downloaded, with their expected SHA1
     async fn download_parallel_with_types(
         &self,
         tasks: Vec<(String, PathBuf, String, String)>,
-    ) -> Result<usize, DownloadError> {
+    ) -> Result<Vec<(PathBuf, String)>, DownloadError> {
         let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
         let client = Arc::new(self.http_client.clone());
         let mut handles = Vec::new();
-        let downloaded_count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
 
         for (url, path, sha1, label) in tasks {
             let permit = semaphore.clone().acquire_owned().await.unwrap();
             let client = client.clone();
-            let downloaded_count = downloaded_count.clone();
 
             let handle = tokio::spawn(async move {
                 let result = Self::download_with_client_labeled(&client, &url, &path, &sha1, &label).await;
                 drop(permit);
-                
-                if let Ok(true) = result {
-                    downloaded_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                }
-                
-                result
+
+                result.map(|downloaded| downloaded.then_some((path, sha1)))
             });
 
             handles.push(handle);
         }
 
+        let mut downloaded = Vec::new();
         for handle in handles {
-            handle.await??;
+            if let Some(file) = handle.await?? {
+                downloaded.push(file);
+            }
         }
 
-        Ok(downloaded_count.load(std::sync::atomic::Ordering::Relaxed))
+        Ok(downloaded)
     }
 
     async fn download_parallel_fast(
@@ -491,6 +577,7 @@ impl MinecraftInstaller {
         expected_sha1: &str,
         label: &str,
     ) -> Result<bool, DownloadError> {
+        let path = &long_path(path);
         // Fast check without SHA1 validation
         if !Self::file_needs_download(path, Some(expected_sha1)) {
             if label.starts_with("NATIVE:") {
@@ -531,6 +618,7 @@ impl MinecraftInstaller {
         path: &PathBuf,
         expected_sha1: &str,
     ) -> Result<bool, DownloadError> {
+        let path = &long_path(path);
         // Fast existence check
         if !Self::file_needs_download(path, Some(expected_sha1)) {
             return Ok(false);
@@ -560,6 +648,7 @@ impl MinecraftInstaller {
         path: &PathBuf,
         expected_sha1: &str,
     ) -> Result<bool, DownloadError> {
+        let path = &long_path(path);
         if path.exists() {
             if let Ok(contents) = fs::read(path) {
                 let mut hasher = Sha1::new();
@@ -594,6 +683,61 @@ impl MinecraftInstaller {
     }
 }
 
+/// Library jars a version needs on `current_os` as (is_native, library, artifact). Natives for
+/// other platforms and libraries excluded by their rules are left out.
+pub fn library_artifacts<'a>(details: &'a VersionDetails, current_os: &str) -> Vec<(bool, &'a Library, &'a Artifact)> {
+    let mut artifacts = Vec::new();
+
+    for library in &details.libraries {
+        let included = match &library.rules {
+            Some(rules) => should_include_library(rules, current_os),
+            None => true,
+        };
+        if !included {
+            continue;
+        }
+
+        // Older versions name the native jar through the `natives` map instead of a separate library
+        if let Some(classifier) = library.natives.as_ref().and_then(|n| n.get(current_os)) {
+            let arch = if cfg!(target_pointer_width = "64") { "64" } else { "32" };
+            let classifier = classifier.replace("${arch}", arch);
+            let native = library
+                .downloads
+                .as_ref()
+                .and_then(|d| d.classifiers.as_ref())
+                .and_then(|c| c.get(&classifier));
+            if let Some(artifact) = native {
+                artifacts.push((true, library, artifact));
+            }
+        }
+
+        let is_native = library.name.contains(":natives-");
+
+        if is_native {
+            let platform = if library.name.contains(":natives-windows") {
+                "windows"
+            } else if library.name.contains(":natives-linux") {
+                "linux"
+            } else if library.name.contains(":natives-macos") || library.name.contains(":natives-osx") {
+                "osx"
+            } else {
+                ""
+            };
+
+            // Only natives for the current OS
+            if platform != current_os {
+                continue;
+            }
+        }
+
+        if let Some(artifact) = library.downloads.as_ref().and_then(|d| d.artifact.as_ref()) {
+            artifacts.push((is_native, library, artifact));
+        }
+    }
+
+    artifacts
+}
+
 pub fn should_include_library(rules: &[Rule], current_os: &str) -> bool {
     let mut allowed = false;
 
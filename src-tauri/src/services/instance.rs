@@ -1,4 +1,9 @@
-use crate::services::installer::should_include_library;
+use crate::services::attestation::{AttestationStage, RunAttestor};
+use crate::services::installer::{library_artifacts, should_include_library};
+use crate::services::liteloader::maven_path;
+use crate::services::java::JavaManager;
+use crate::services::legacy_assets::LegacyAssets;
+use crate::services::sync_launch::{StartGate, CALLED_OFF};
 use crate::models::{FabricProfileJson, Instance, VersionDetails};
 use crate::utils::*;
 use chrono::Utc;
@@ -41,14 +46,64 @@ impl InstanceManager {
             settings_override: None,
             icon_path: None,
             total_playtime_seconds: 0,
+            shared_directories: Vec::new(),
+            language: crate::services::settings::SettingsManager::load()
+                .ok()
+                .and_then(|s| s.default_language),
+            jar_mods: Vec::new(),
+            extra_game_args: Vec::new(),
+            demo_mode: false,
+            group: None,
+            pinned: false,
+            sort_index: 0,
+            accent_color: None,
+            banner_path: None,
+            emoji: None,
+            ephemeral: false,
+            audio_profile: None,
+            completed_setup_steps: Vec::new(),
+            managed_manifest_url: None,
+            managed_version: None,
+            quick_play_world: None,
+            java_path: None,
+            archive_mode: false,
+            auto_update: None,
+            mod_update_preferences: std::collections::HashMap::new(),
+            frozen: false,
+            speedrun_attestation: false,
+            linked_game_dir: None,
+            library_resource_packs: Vec::new(),
+            library_shader_packs: Vec::new(),
         };
 
         let instance_json = serde_json::to_string_pretty(&instance)?;
         fs::write(instance_dir.join("instance.json"), instance_json)?;
 
+        if let Err(e) = crate::services::language::LanguageManager::apply(&instance) {
+            println!("Warning: Failed to apply default language: {}", e);
+        }
+
         Ok(instance)
     }
 
+    pub fn load(instance_name: &str) -> Result<Instance, Box<dyn std::error::Error>> {
+        let instance_json = get_instance_dir(instance_name).join("instance.json");
+
+        if !instance_json.exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let instance: Instance = serde_json::from_str(&fs::read_to_string(&instance_json)?)?;
+        Ok(instance)
+    }
+
+    pub fn save(instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        let instance_json = get_instance_dir(&instance.name).join("instance.json");
+        let json = serde_json::to_string_pretty(instance)?;
+        fs::write(instance_json, json)?;
+        Ok(())
+    }
+
     pub fn get_all() -> Result<Vec<Instance>, Box<dyn std::error::Error>> {
         let instances_dir = get_instances_dir();
 
@@ -68,7 +123,9 @@ impl InstanceManager {
                     if instance_json.exists() {
                         if let Ok(content) = fs::read_to_string(instance_json) {
                             if let Ok(instance) = serde_json::from_str::<Instance>(&content) {
-                                instances.push(instance);
+                                if !instance.ephemeral {
+                                    instances.push(instance);
+                                }
                             }
                         }
                     }
@@ -76,6 +133,13 @@ impl InstanceManager {
             }
         }
 
+        instances.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then(a.sort_index.cmp(&b.sort_index))
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
         Ok(instances)
     }
 
@@ -87,11 +151,101 @@ impl InstanceManager {
         }
 
         fs::remove_dir_all(&instance_dir)?;
+        crate::services::disk_usage::DiskUsageAnalyzer::invalidate(instance_name);
+        if let Err(e) = crate::commands::servers::rebind_instance_servers(instance_name, None) {
+            println!("⚠ Failed to unbind servers from '{}': {}", instance_name, e);
+        }
 
         Ok(())
     }
 
-    #[allow(dead_code)]
+    /// Remove test instances left behind when the launcher was closed while they were running
+    pub fn remove_leftover_ephemeral() {
+        let Ok(entries) = fs::read_dir(get_instances_dir()) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_ephemeral = Self::load(&name).map(|i| i.ephemeral).unwrap_or(false);
+
+            if is_ephemeral && !crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(&name) {
+                match Self::delete(&name) {
+                    Ok(()) => println!("✓ Removed leftover test instance '{}'", name),
+                    Err(e) => println!("⚠ Failed to remove leftover test instance '{}': {}", name, e),
+                }
+            }
+        }
+    }
+
+    /// Copy an instance's configuration and content into a new instance.
+    /// Worlds are only copied when asked for; logs, natives and the patched jar are rebuilt on launch.
+    pub fn clone_instance(
+        source_name: &str,
+        new_name: &str,
+        include_saves: bool,
+    ) -> Result<Instance, Box<dyn std::error::Error>> {
+        let source_dir = get_instance_dir(source_name);
+        let dest_dir = get_instance_dir(new_name);
+
+        if !source_dir.exists() {
+            return Err(format!("Instance '{}' does not exist", source_name).into());
+        }
+
+        if dest_dir.exists() {
+            return Err(format!("Instance '{}' already exists", new_name).into());
+        }
+
+        let mut instance = Self::load(source_name)?;
+        let mut skipped = vec!["natives", "logs", "crash-reports", "bin", "snapshots", "instance.json"];
+        if !include_saves {
+            skipped.push("saves");
+        }
+
+        fs::create_dir_all(&dest_dir)?;
+
+        let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+            for entry in fs::read_dir(&source_dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().to_string();
+
+                // Shared directories are links and get recreated by the layout below
+                if skipped.contains(&name.as_str())
+                    || instance.shared_directories.iter().any(|d| d == &name)
+                {
+                    continue;
+                }
+
+                let file_type = entry.file_type()?;
+                // An adopted game directory's folders are links, the copy gets their contents
+                let linked_dir = instance.linked_game_dir.is_some() && entry.path().is_dir();
+                if file_type.is_dir() || linked_dir {
+                    copy_dir_recursive(&entry.path(), &dest_dir.join(&name))?;
+                } else if file_type.is_file() {
+                    fs::copy(long_path(&entry.path()), long_path(&dest_dir.join(&name)))?;
+                }
+            }
+
+            instance.name = new_name.to_string();
+            instance.linked_game_dir = None;
+            instance.created_at = Utc::now().to_rfc3339();
+            instance.last_played = None;
+            instance.total_playtime_seconds = 0;
+            Self::save(&instance)?;
+
+            crate::services::layout::LayoutManager::apply(&instance)?;
+            crate::services::pack_library::PackLibrary::materialize(&instance)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            let _ = fs::remove_dir_all(&dest_dir);
+            return Err(e);
+        }
+
+        Ok(instance)
+    }
+
     pub fn rename(old_name: &str, new_name: &str) -> Result<(), Box<dyn std::error::Error>> {
         let old_dir = get_instance_dir(old_name);
         let new_dir = get_instance_dir(new_name);
@@ -100,12 +254,22 @@ impl InstanceManager {
             return Err(format!("Instance '{}' does not exist", old_name).into());
         }
 
-        if new_dir.exists() {
+        // Only a case change on a case-insensitive filesystem would hit the same directory
+        if new_dir.exists() && !old_name.eq_ignore_ascii_case(new_name) {
             return Err(format!("Instance '{}' already exists", new_name).into());
         }
 
+        if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(old_name) {
+            return Err(format!("Instance '{}' is running", old_name).into());
+        }
+
         // Rename directory
         fs::rename(&old_dir, &new_dir)?;
+        crate::services::disk_usage::DiskUsageAnalyzer::invalidate(old_name);
+        crate::services::recent::RecentLaunches::rename_instance(old_name, new_name);
+        if let Err(e) = crate::commands::servers::rebind_instance_servers(old_name, Some(new_name)) {
+            println!("⚠ Failed to update servers bound to '{}': {}", old_name, e);
+        }
 
         // Update metadata
         let instance_json = new_dir.join("instance.json");
@@ -113,6 +277,22 @@ impl InstanceManager {
 
         instance.name = new_name.to_string();
 
+        // Absolute paths that pointed into the old directory
+        let rebase = |path: &mut String| {
+            if let Ok(relative) = std::path::Path::new(path.as_str()).strip_prefix(&old_dir) {
+                *path = new_dir.join(relative).to_string_lossy().to_string();
+            }
+        };
+        if let Some(icon_path) = instance.icon_path.as_mut() {
+            rebase(icon_path);
+        }
+        if let Some(java_path) = instance.settings_override.as_mut().and_then(|s| s.java_path.as_mut()) {
+            rebase(java_path);
+        }
+        if let Some(java_path) = instance.java_path.as_mut() {
+            rebase(java_path);
+        }
+
         let updated_json = serde_json::to_string_pretty(&instance)?;
         fs::write(instance_json, updated_json)?;
 
@@ -127,93 +307,18 @@ impl InstanceManager {
         }));
     }
 
-    fn get_java_version(java_path: &str) -> Result<u32, Box<dyn std::error::Error>> {
-        let output = Command::new(java_path)
-            .arg("-version")
-            .output()?;
-        
-        let version_text = String::from_utf8_lossy(&output.stderr);
-        
-        // Try multiple parsing strategies
-        for line in version_text.lines() {
-            // Strategy 1: Modern format - openjdk 17.0.8 or java version "17.0.8"
-            if let Some(captures) = line.split('"').nth(1) {
-                if let Some(major) = Self::parse_major_version(captures) {
-                    return Ok(major);
-                }
-            }
-            
-            // Strategy 2: Simple format - openjdk 21
-            if line.starts_with("openjdk") || line.starts_with("java") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    if let Some(major) = Self::parse_major_version(parts[1]) {
-                        return Ok(major);
-                    }
-                }
-            }
-        }
-        
-        Err("Could not parse Java version from output".into())
-    }
-
-    fn parse_major_version(version_str: &str) -> Option<u32> {
-        let parts: Vec<&str> = version_str.split('.').collect();
-        
-        if parts.is_empty() {
-            return None;
-        }
-        
-        // Handle old format: 1.8.0_xxx -> 8
-        if parts[0] == "1" && parts.len() > 1 {
-            parts[1].parse::<u32>().ok()
+    /// Vanilla version behind a loader version id (fabric-loader-X.X.X-1.XX.X -> 1.XX.X, 1.XX.X-LiteLoaderX.X.X -> 1.XX.X,
+    /// 1.XX.X-forge-X.X.X -> 1.XX.X, 1.XX.X-neoforge-X.X.X -> 1.XX.X, 1.XX.X-OptiFine_HD_U_X -> 1.XX.X)
+    pub fn base_minecraft_version(version: &str) -> String {
+        if let Some((base, _)) = version.split_once("-LiteLoader").or_else(|| version.split_once("-OptiFine_")) {
+            base.to_string()
+        } else if let Some((base, _)) = version.split_once("-forge-").or_else(|| version.split_once("-neoforge-")) {
+            base.to_string()
+        } else if version.contains("fabric-loader") {
+            version.split('-').last().unwrap_or(version).to_string()
         } else {
-            // Handle modern format: 17.0.8 -> 17
-            parts[0].parse::<u32>().ok()
-        }
-    }
-
-    fn get_required_java_version(minecraft_version: &str) -> u32 {
-        let base_version = if let Some(pos) = minecraft_version.find('-') {
-            &minecraft_version[..pos]
-        } else {
-            minecraft_version
-        };
-
-        let parts: Vec<&str> = base_version.split('.').collect();
-        
-        // New format: 26.x (snapshots after version naming change)
-        if parts.len() >= 1 {
-            if let Ok(major) = parts[0].parse::<u32>() {
-                if major >= 26 {
-                    return 25; // Minecraft 26+ requires Java 25
-                }
-            }
-        }
-        
-        // Old format: 1.x.y
-        if parts.len() >= 2 {
-            if let (Ok(major), Ok(minor)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
-                if major == 1 {
-                    // Check patch version for 1.20.5+
-                    if minor == 20 && parts.len() >= 3 {
-                        if let Ok(patch) = parts[2].parse::<u32>() {
-                            if patch >= 5 {
-                                return 21; // 1.20.5+ requires Java 21
-                            }
-                        }
-                    }
-                    
-                    // Version-specific requirements
-                    if minor >= 20 { return 17; } // 1.20-1.20.4 requires Java 17
-                    if minor >= 18 { return 17; } // 1.18+ requires Java 17
-                    if minor >= 17 { return 16; } // 1.17+ requires Java 16
-                    if minor >= 16 { return 8; }  // 1.16 works with Java 8
-                }
-            }
+            version.to_string()
         }
-
-        8
     }
 
     // Regular launch
@@ -224,7 +329,19 @@ impl InstanceManager {
         access_token: &str,
         app_handle: tauri::AppHandle,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        Self::launch_internal(instance_name, username, uuid, access_token, None, app_handle)
+        Self::launch_internal(instance_name, username, uuid, access_token, None, None, app_handle)
+    }
+
+    // Launch once every instance sharing the gate is prepared
+    pub fn launch_synchronized(
+        instance_name: &str,
+        username: &str,
+        uuid: &str,
+        access_token: &str,
+        start_gate: &StartGate,
+        app_handle: tauri::AppHandle,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Self::launch_internal(instance_name, username, uuid, access_token, None, Some(start_gate), app_handle)
     }
 
     // Launch with server connection
@@ -236,7 +353,7 @@ impl InstanceManager {
         server_address: &str,
         app_handle: tauri::AppHandle,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        Self::launch_internal(instance_name, username, uuid, access_token, Some(server_address), app_handle)
+        Self::launch_internal(instance_name, username, uuid, access_token, Some(server_address), None, app_handle)
     }
 
     // Internal launch method with optional server connection
@@ -246,6 +363,7 @@ impl InstanceManager {
         uuid: &str,
         access_token: &str,
         server_address: Option<&str>,
+        start_gate: Option<&StartGate>,
         app_handle: tauri::AppHandle,
     ) -> Result<(), Box<dyn std::error::Error>> {
         println!("=== Launching Instance: {} ===", instance_name);
@@ -264,7 +382,7 @@ impl InstanceManager {
 
         // Load instance metadata
         let instance_json = instance_dir.join("instance.json");
-        let instance: Instance = match fs::read_to_string(&instance_json) {
+        let mut instance: Instance = match fs::read_to_string(&instance_json) {
             Ok(content) => match serde_json::from_str(&content) {
                 Ok(inst) => inst,
                 Err(e) => {
@@ -280,6 +398,34 @@ impl InstanceManager {
             }
         };
 
+        // Make sure shared directories are linked before the game touches them
+        if let Err(e) = crate::services::layout::LayoutManager::apply(&instance) {
+            let err_msg = format!("Failed to prepare game directories: {}", e);
+            Self::emit_error_log(&app_handle, instance_name, &err_msg);
+            return Err(err_msg.into());
+        }
+
+        // Frozen instances start with options.txt and the pack exactly as they were left
+        if !instance.frozen {
+            if let Err(e) = crate::services::language::LanguageManager::apply(&instance) {
+                println!("Warning: Failed to apply instance language: {}", e);
+            }
+
+            if let Err(e) = crate::services::audio::AudioProfileManager::apply(&instance) {
+                println!("Warning: Failed to apply audio profile: {}", e);
+            }
+
+            // A broken step is retried next launch instead of keeping the player out of the game
+            if let Err(e) = crate::services::pack_setup::PackSetupRunner::run_pending(&mut instance) {
+                println!("Warning: Pack setup did not finish: {}", e);
+                let _ = app_handle.emit("console-log", serde_json::json!({
+                    "instance": instance_name,
+                    "message": format!("Pack setup did not finish: {}", e),
+                    "type": "stderr"
+                }));
+            }
+        }
+
         let version = instance.version.clone();
         println!("Version: {}", version);
         println!("Username: {}", username);
@@ -294,9 +440,15 @@ impl InstanceManager {
             global_settings
         };
 
-        // Use the settings for Java path
+        // Use the settings for Java path, then the one picked for this instance's version
+        let instance_java = instance
+            .java_path
+            .clone()
+            .filter(|path| std::path::Path::new(path).is_file());
         let java_path = if let Some(custom_java) = &effective_settings.java_path {
             custom_java.clone()
+        } else if let Some(instance_java) = instance_java {
+            instance_java
         } else {
             match find_java() {
                 Some(path) => path,
@@ -311,12 +463,18 @@ impl InstanceManager {
         println!("Java found: {}", java_path);
 
         // Check Java version
-        let required_java = Self::get_required_java_version(&version);
+        let required_java = JavaManager::required_major_version(&version);
         println!("Required Java version: {}", required_java);
 
-        match Self::get_java_version(&java_path) {
-            Ok(java_version) => {
+        let detected_java = JavaManager::probe(&java_path);
+        match detected_java.as_ref().ok_or("Could not parse Java version from output") {
+            Ok(java) => {
+                let java_version = java.major_version;
                 println!("Detected Java version: {}", java_version);
+                if let Some(warning) = JavaManager::memory_warning(java, effective_settings.memory_mb) {
+                    println!("Warning: {}", warning);
+                    Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", warning));
+                }
                 if java_version < required_java {
                     let err_msg = format!(
                         "Java {} detected, but Minecraft {} requires Java {} or higher. Please update Java in Settings.",
@@ -346,9 +504,34 @@ impl InstanceManager {
 
         println!("RAM allocation: {}MB", effective_settings.memory_mb);
 
-        // Check if this is a Fabric instance
+        // The UI checks before launching too, this covers launches from shortcuts and the CLI
+        match crate::services::mod_conflicts::ModConflictAnalyzer::analyze(&instance) {
+            Ok(conflicts) => {
+                for conflict in conflicts {
+                    println!("Warning: {}", conflict.message);
+                    Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", conflict.message));
+                }
+            }
+            Err(e) => println!("Warning: Could not analyze mods: {}", e),
+        }
+
+        if instance.archive_mode {
+            if let Err(e) = crate::services::pinning::VersionPinner::restore(&instance) {
+                let warning = format!("Could not restore pinned files: {}", e);
+                println!("Warning: {}", warning);
+                Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", warning));
+            }
+        }
+
+        // Fabric, LiteLoader and Forge profiles inherit from a vanilla version
         let is_fabric = version.contains("fabric-loader");
-        println!("Is Fabric: {}", is_fabric);
+        let is_liteloader = version.contains("-LiteLoader");
+        let is_forge = version.contains("-forge-") || version.contains("-neoforge-");
+        let is_optifine = version.contains("-OptiFine_");
+        println!(
+            "Is Fabric: {}, Is LiteLoader: {}, Is Forge: {}, Is OptiFine: {}",
+            is_fabric, is_liteloader, is_forge, is_optifine
+        );
 
         let versions_dir = meta_dir.join("versions").join(&version);
         let json_path = versions_dir.join(format!("{}.json", version));
@@ -372,19 +555,23 @@ impl InstanceManager {
         println!("Current OS: {}", current_os);
 
         // Parse the profile based on type
-        let (main_class, base_version_id, all_libraries, assets_id) = if is_fabric {
-            println!("Parsing as Fabric profile...");
+        let (main_class, base_version_id, all_libraries, assets_id, profile_game_args, profile_jvm_args) = if is_fabric
+            || is_liteloader
+            || is_forge
+            || is_optifine
+        {
+            println!("Parsing as loader profile...");
             
             let fabric_profile: FabricProfileJson = match serde_json::from_str(&json_content) {
                 Ok(profile) => profile,
                 Err(e) => {
-                    let err_msg = format!("Failed to parse Fabric profile: {}", e);
+                    let err_msg = format!("Failed to parse loader profile: {}", e);
                     Self::emit_error_log(&app_handle, instance_name, &err_msg);
                     return Err(err_msg.into());
                 }
             };
             
-            println!("Fabric main class: {}", fabric_profile.main_class);
+            println!("Loader main class: {}", fabric_profile.main_class);
             println!("Inherits from: {}", fabric_profile.inherits_from);
             
             let base_version_dir = meta_dir.join("versions").join(&fabric_profile.inherits_from);
@@ -447,12 +634,14 @@ impl InstanceManager {
                     let lib_key = format!("{}:{}", parts[0], parts[1]);
 
                     if base_lib_names.contains(&lib_key) {
-                        println!("Skipping Fabric library {} (Minecraft provides {})", lib.name, lib_key);
+                        println!("Skipping loader library {} (Minecraft provides {})", lib.name, lib_key);
                         continue;
                     }
                 }
                 
-                combined_libs.push((lib.name.clone(), lib.url.clone(), None));
+                // Forge names libraries with classifiers and gives their paths
+                let artifact_path = lib.downloads.as_ref().and_then(|d| d.artifact.as_ref()).map(|a| a.path.clone());
+                combined_libs.push((lib.name.clone(), lib.url.clone(), artifact_path));
             }
 
             for lib in &base_version.libraries {
@@ -482,11 +671,39 @@ impl InstanceManager {
                 }
             }
             
+            // e.g. LiteLoader's --tweakClass
+            let game_args: Vec<String> = fabric_profile
+                .arguments
+                .as_ref()
+                .map(|args| args.game.iter().filter_map(|a| a.as_str().map(String::from)).collect())
+                .unwrap_or_default();
+
+            // e.g. Forge's module path, which points into the libraries folder
+            let library_directory = meta_dir.join("libraries").to_string_lossy().to_string();
+            let classpath_separator = if cfg!(windows) { ";" } else { ":" };
+            let jvm_args: Vec<String> = fabric_profile
+                .arguments
+                .as_ref()
+                .map(|args| {
+                    args.jvm
+                        .iter()
+                        .filter_map(|a| a.as_str())
+                        .map(|a| {
+                            a.replace("${library_directory}", &library_directory)
+                                .replace("${classpath_separator}", classpath_separator)
+                                .replace("${version_name}", &fabric_profile.inherits_from)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
             (
                 fabric_profile.main_class,
                 fabric_profile.inherits_from,
                 combined_libs,
                 base_version.assets,
+                game_args,
+                jvm_args,
             )
         } else {
             println!("Parsing as vanilla Minecraft profile...");
@@ -533,6 +750,8 @@ impl InstanceManager {
                 version_details.id.clone(),
                 libs,
                 version_details.assets,
+                Vec::new(),
+                Vec::new(),
             )
         };
 
@@ -571,94 +790,65 @@ impl InstanceManager {
         let mut natives_extracted = 0;
         let mut natives_attempted = 0;
         
-        for library in &base_version.libraries {
-            let is_native = library.name.contains(":natives-");
-            
-            if !is_native {
-                continue;
-            }
-            
-            let platform_suffix = if library.name.contains(":natives-windows") {
-                "windows"
-            } else if library.name.contains(":natives-linux") {
-                "linux"
-            } else if library.name.contains(":natives-macos") || library.name.contains(":natives-osx") {
-                "osx"
-            } else {
-                ""
-            };
+        let natives = library_artifacts(&base_version, &current_os).into_iter().filter(|(is_native, _, _)| *is_native);
+        for (_, library, artifact) in natives {
+            natives_attempted += 1;
+            let native_path = libraries_dir.join(&artifact.path);
             
-            if platform_suffix != current_os {
-                continue;
-            }
+            println!("  → Processing native: {} ({})", library.name, artifact.path);
             
-            if let Some(rules) = &library.rules {
-                if !should_include_library(rules, &current_os) {
-                    continue;
-                }
-            }
-            
-            if let Some(downloads) = &library.downloads {
-                if let Some(artifact) = &downloads.artifact {
-                    natives_attempted += 1;
-                    let native_path = libraries_dir.join(&artifact.path);
-                    
-                    println!("  → Processing native: {} ({})", library.name, artifact.path);
-                    
-                    if native_path.exists() {
-                        match fs::File::open(&native_path) {
-                            Ok(file) => {
-                                match ZipArchive::new(file) {
-                                    Ok(mut archive) => {
-                                        for i in 0..archive.len() {
-                                            if let Ok(mut file) = archive.by_index(i) {
-                                                let file_name = file.name().to_string();
-                                                
-                                                if file_name.ends_with('/') || file_name.starts_with("META-INF") {
-                                                    continue;
-                                                }
-                                                
-                                                let outpath = natives_dir.join(&file_name);
-                                                
-                                                if let Some(parent) = outpath.parent() {
-                                                    let _ = fs::create_dir_all(parent);
-                                                }
-                                                
-                                                if let Ok(mut outfile) = fs::File::create(&outpath) {
-                                                    if std::io::copy(&mut file, &mut outfile).is_ok() {
-                                                        natives_extracted += 1;
-                                                    }
-                                                }
+            if native_path.exists() {
+                match fs::File::open(&native_path) {
+                    Ok(file) => {
+                        match ZipArchive::new(file) {
+                            Ok(mut archive) => {
+                                for i in 0..archive.len() {
+                                    if let Ok(mut file) = archive.by_index(i) {
+                                        let file_name = file.name().to_string();
+                                        
+                                        if file_name.ends_with('/') || file_name.starts_with("META-INF") {
+                                            continue;
+                                        }
+                                        
+                                        let outpath = long_path(&natives_dir.join(&file_name));
+                                        
+                                        if let Some(parent) = outpath.parent() {
+                                            let _ = fs::create_dir_all(parent);
+                                        }
+                                        
+                                        if let Ok(mut outfile) = fs::File::create(&outpath) {
+                                            if std::io::copy(&mut file, &mut outfile).is_ok() {
+                                                natives_extracted += 1;
                                             }
                                         }
-                                        println!("    ✓ Extracted native library");
-                                    }
-                                    Err(e) => {
-                                        let err_msg = format!("Failed to open native archive: {}", e);
-                                        println!("    ✗ {}", err_msg);
-                                        Self::emit_error_log(&app_handle, instance_name, &err_msg);
                                     }
                                 }
+                                println!("    ✓ Extracted native library");
                             }
                             Err(e) => {
-                                let err_msg = format!("Failed to open native file: {}", e);
+                                let err_msg = format!("Failed to open native archive: {}", e);
                                 println!("    ✗ {}", err_msg);
                                 Self::emit_error_log(&app_handle, instance_name, &err_msg);
                             }
                         }
-                    } else {
-                        let err_msg = format!(
-                            "Native library not found: {}. This will cause LWJGL to fail!",
-                            artifact.path
-                        );
+                    }
+                    Err(e) => {
+                        let err_msg = format!("Failed to open native file: {}", e);
                         println!("    ✗ {}", err_msg);
                         Self::emit_error_log(&app_handle, instance_name, &err_msg);
-                        return Err(format!(
-                            "Native library missing: {}. Please reinstall Minecraft {}",
-                            artifact.path, base_version_id
-                        ).into());
                     }
                 }
+            } else {
+                let err_msg = format!(
+                    "Native library not found: {}. This will cause LWJGL to fail!",
+                    artifact.path
+                );
+                println!("    ✗ {}", err_msg);
+                Self::emit_error_log(&app_handle, instance_name, &err_msg);
+                return Err(format!(
+                    "Native library missing: {}. Please reinstall Minecraft {}",
+                    artifact.path, base_version_id
+                ).into());
             }
         }
         
@@ -690,23 +880,9 @@ impl InstanceManager {
         println!("Building classpath from {} libraries...", all_libraries.len());
         
         for (lib_name, _lib_url, artifact_path) in all_libraries {
-            let parts: Vec<&str> = lib_name.split(':').collect();
-            if parts.len() != 3 {
-                continue;
-            }
-            
-            let (group, artifact, lib_version) = (parts[0], parts[1], parts[2]);
-            
-            let lib_path = if let Some(path) = artifact_path {
-                libraries_dir.join(path)
-            } else {
-                let group_path = group.replace('.', "/");
-                let jar_name = format!("{}-{}.jar", artifact, lib_version);
-                libraries_dir
-                    .join(&group_path)
-                    .join(artifact)
-                    .join(lib_version)
-                    .join(&jar_name)
+            let lib_path = match artifact_path.map(Ok).unwrap_or_else(|| maven_path(&lib_name)) {
+                Ok(path) => libraries_dir.join(path),
+                Err(_) => continue,
             };
             
             if lib_path.exists() {
@@ -733,6 +909,15 @@ impl InstanceManager {
             return Err(err_msg.into());
         }
 
+        let client_jar = match crate::services::jarmod::JarModManager::resolve_client_jar(&instance, &client_jar) {
+            Ok(jar) => jar,
+            Err(e) => {
+                let err_msg = format!("Failed to apply jar mods: {}", e);
+                Self::emit_error_log(&app_handle, instance_name, &err_msg);
+                return Err(err_msg.into());
+            }
+        };
+
         classpath.push(client_jar.to_string_lossy().to_string());
         
         println!("Total classpath entries: {}", classpath.len());
@@ -740,6 +925,18 @@ impl InstanceManager {
         let classpath_separator = if cfg!(windows) { ";" } else { ":" };
         let classpath_str = classpath.join(classpath_separator);
 
+        // Versions before 1.7.3 read assets by name instead of from the hashed objects
+        let assets_root = meta_dir.join("assets");
+        let game_assets = match LegacyAssets::prepare(&assets_id, &instance_dir) {
+            Ok(dir) => dir.unwrap_or_else(|| assets_root.clone()),
+            Err(e) => {
+                let warning = format!("Could not lay out legacy assets: {}", e);
+                println!("Warning: {}", warning);
+                Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", warning));
+                assets_root.clone()
+            }
+        };
+
         println!("Main class: {}", main_class);
         println!("Assets ID: {}", assets_id);
         println!("Natives directory: {}", natives_dir.display());
@@ -752,21 +949,115 @@ impl InstanceManager {
             .arg("-cp")
             .arg(&classpath_str);
 
-        cmd.arg(&main_class)
-            .arg("--username")
-            .arg(username)
-            .arg("--uuid")
-            .arg(uuid)
-            .arg("--accessToken")
-            .arg(access_token)
-            .arg("--version")
-            .arg(&version)
-            .arg("--gameDir")
-            .arg(&instance_dir)
-            .arg("--assetsDir")
-            .arg(meta_dir.join("assets"))
-            .arg("--assetIndex")
-            .arg(&assets_id);
+        let java_version = detected_java.as_ref().map(|j| j.major_version).unwrap_or(required_java);
+        let gc_logging = effective_settings.gc_logging;
+        if gc_logging {
+            let log_path = crate::services::gc_log::GcLogAnalyzer::get_log_path(instance_name);
+            let _ = fs::create_dir_all(instance_dir.join("logs"));
+            let _ = fs::remove_file(&log_path);
+
+            println!("GC logging enabled: {}", log_path.display());
+            cmd.args(crate::services::gc_log::GcLogAnalyzer::jvm_flags(java_version, &log_path));
+        }
+
+        match crate::services::trust_store::TrustStoreManager::jvm_args(&java_path) {
+            Ok(args) => {
+                cmd.args(args);
+            }
+            Err(e) => {
+                let warning = format!("Could not pass the extra CA certificates to Java: {}", e);
+                println!("Warning: {}", warning);
+                Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", warning));
+            }
+        }
+
+        if !effective_settings.jvm_args.is_empty() {
+            let filtered = crate::services::jvm_flags::JvmFlagFilter::filter(&effective_settings.jvm_args, java_version);
+            for dropped in &filtered.dropped {
+                let warning = format!("Dropped JVM flag {} for Java {}: {}", dropped.flag, java_version, dropped.reason);
+                println!("Warning: {}", warning);
+                Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", warning));
+            }
+            for added in &filtered.added {
+                println!("Added JVM flag {} for Java {}", added, java_version);
+            }
+            println!("JVM arguments: {:?}", filtered.args);
+            cmd.args(&filtered.args);
+        }
+
+        if !profile_jvm_args.is_empty() {
+            println!("Profile JVM arguments: {:?}", profile_jvm_args);
+            cmd.args(&profile_jvm_args);
+        }
+
+        cmd.arg(&main_class);
+        match base_version.minecraft_arguments.as_deref().filter(|_| base_version.arguments.is_none()) {
+            // 1.12.2 and older give their arguments as a template. Before 1.6 they're positional and
+            // Alpha/Beta go through LaunchWrapper's applet tweakers, so only the template gets them right.
+            Some(template) => {
+                let values = [
+                    ("${auth_player_name}", username.to_string()),
+                    ("${auth_uuid}", uuid.to_string()),
+                    ("${auth_access_token}", access_token.to_string()),
+                    ("${auth_session}", format!("token:{}:{}", access_token, uuid)),
+                    ("${user_properties}", "{}".to_string()),
+                    ("${user_type}", "msa".to_string()),
+                    ("${version_name}", version.clone()),
+                    ("${version_type}", "release".to_string()),
+                    ("${game_directory}", instance_dir.to_string_lossy().to_string()),
+                    ("${assets_root}", assets_root.to_string_lossy().to_string()),
+                    ("${game_assets}", game_assets.to_string_lossy().to_string()),
+                    ("${assets_index_name}", assets_id.clone()),
+                ];
+                let args: Vec<String> = template
+                    .split_whitespace()
+                    .map(|arg| values.iter().fold(arg.to_string(), |arg, (key, value)| arg.replace(key, value)))
+                    .collect();
+                cmd.args(&args);
+            }
+            None => {
+                cmd.arg("--username")
+                    .arg(username)
+                    .arg("--uuid")
+                    .arg(uuid)
+                    .arg("--accessToken")
+                    .arg(access_token)
+                    .arg("--version")
+                    .arg(&version)
+                    .arg("--gameDir")
+                    .arg(&instance_dir)
+                    .arg("--assetsDir")
+                    .arg(&game_assets)
+                    .arg("--assetIndex")
+                    .arg(&assets_id);
+            }
+        }
+
+        if !profile_game_args.is_empty() {
+            println!("Profile game arguments: {:?}", profile_game_args);
+            cmd.args(&profile_game_args);
+        }
+
+        if instance.demo_mode {
+            println!("Launching in demo mode");
+            cmd.arg("--demo");
+        }
+
+        if !instance.extra_game_args.is_empty() {
+            println!("Extra game arguments: {:?}", instance.extra_game_args);
+            cmd.args(&instance.extra_game_args);
+        }
+
+        // Open a world the launcher just created, only once
+        if let Some(world) = instance.quick_play_world.take() {
+            if let Err(e) = Self::save(&instance) {
+                println!("⚠ Failed to clear the world to open: {}", e);
+            }
+            if server_address.is_none() && should_use_quickplay(&base_version_id) {
+                println!("Opening world: --quickPlaySingleplayer {}", world);
+                cmd.arg("--quickPlaySingleplayer").arg(world);
+            }
+        }
 
         // Add server connection arguments if provided
         if let Some(server) = server_address {
@@ -837,6 +1128,28 @@ impl InstanceManager {
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
+        if let Some(hook) = effective_settings.pre_launch_command.as_deref().filter(|c| !c.trim().is_empty()) {
+            if let Err(e) = crate::services::hooks::LaunchHooks::run(hook, &instance, &[]) {
+                let err_msg = format!("Pre-launch command failed: {}", e);
+                Self::emit_error_log(&app_handle, instance_name, &err_msg);
+                return Err(err_msg.into());
+            }
+        }
+
+        if instance.speedrun_attestation {
+            if let Err(e) = RunAttestor::attest(&instance, AttestationStage::Launch) {
+                let warning = format!("Could not attest the run: {}", e);
+                println!("Warning: {}", warning);
+                Self::emit_error_log(&app_handle, instance_name, &format!("WARNING: {}", warning));
+            }
+        }
+
+        if let Some(gate) = start_gate {
+            if !gate.arrive(instance_name) {
+                return Err(CALLED_OFF.into());
+            }
+        }
+
         let mut child = match cmd.spawn() {
             Ok(child) => child,
             Err(e) => {
@@ -854,6 +1167,27 @@ impl InstanceManager {
             let mut processes = crate::commands::instances::RUNNING_PROCESSES.lock().unwrap();
             processes.insert(instance_name.to_string(), child_pid);
         }
+        crate::services::big_picture::BigPicture::notify(&app_handle);
+        crate::services::prewarm::InstancePrewarmer::invalidate(instance_name);
+
+        let recent_launch_id = match crate::services::recent::RecentLaunches::record_start(
+            instance_name,
+            username,
+            &Self::base_minecraft_version(&version),
+        ) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                println!("⚠ Failed to record launch: {}", e);
+                None
+            }
+        };
+
+        crate::services::screenshots::ScreenshotWatcher::start(
+            instance_name,
+            effective_settings.screenshot_to_clipboard,
+            effective_settings.organize_screenshots,
+            app_handle.clone(),
+        );
 
         // Update user status to in-game for the launching account
         let instance_name_for_status = instance_name.to_string();
@@ -973,11 +1307,20 @@ impl InstanceManager {
         let app_handle_clone = app_handle.clone();
         let launching_uuid = uuid.to_string();
         let launch_time = std::time::Instant::now();
+        let memory_mb = effective_settings.memory_mb;
+        let post_exit_command = effective_settings.post_exit_command.clone().filter(|c| !c.trim().is_empty());
+        let ephemeral = instance.ephemeral;
         std::thread::spawn(move || {
-            let _ = child.wait();
+            let exit_status = child.wait();
             let play_duration = launch_time.elapsed().as_secs();
             
             println!("Instance '{}' has exited after {} seconds", instance_name_clone, play_duration);
+
+            if let Some(id) = recent_launch_id {
+                if let Err(e) = crate::services::recent::RecentLaunches::record_exit(&id, play_duration) {
+                    println!("⚠ Failed to record launch duration: {}", e);
+                }
+            }
             
             // Update playtime
             let instance_dir = get_instance_dir(&instance_name_clone);
@@ -991,14 +1334,55 @@ impl InstanceManager {
                         let _ = fs::write(&instance_json_path, updated_json);
                         println!("Updated total playtime: {} seconds", instance.total_playtime_seconds);
                     }
+
+                    if instance.speedrun_attestation {
+                        if let Err(e) = RunAttestor::attest(&instance, AttestationStage::Exit) {
+                            println!("⚠ Failed to attest the finished run: {}", e);
+                        }
+                    }
                 }
             }
             
+            if gc_logging {
+                match crate::services::gc_log::GcLogAnalyzer::analyze(&instance_name_clone, memory_mb) {
+                    Ok(report) => {
+                        let _ = app_handle_clone.emit("gc-report-ready", serde_json::json!({
+                            "instance": instance_name_clone,
+                            "report": report
+                        }));
+                    }
+                    Err(e) => println!("⚠ GC log analysis failed: {}", e),
+                }
+            }
+
             // Remove from running processes
             {
                 let mut processes = crate::commands::instances::RUNNING_PROCESSES.lock().unwrap();
                 processes.remove(&instance_name_clone);
             }
+            crate::services::big_picture::BigPicture::notify(&app_handle_clone);
+            // A slot is free, queued launches may fit now
+            crate::services::launch_queue::LaunchQueue::process(&app_handle_clone);
+
+            if let Some(hook) = post_exit_command {
+                let exit_code = exit_status
+                    .ok()
+                    .and_then(|s| s.code())
+                    .map(|c| c.to_string())
+                    .unwrap_or_default();
+                if let Ok(instance) = Self::load(&instance_name_clone) {
+                    if let Err(e) = crate::services::hooks::LaunchHooks::run(&hook, &instance, &[("INSTANCE_EXIT_CODE", exit_code)]) {
+                        println!("⚠ Post-exit command failed: {}", e);
+                    }
+                }
+            }
+
+            if ephemeral {
+                match Self::delete(&instance_name_clone) {
+                    Ok(()) => println!("✓ Deleted test instance '{}'", instance_name_clone),
+                    Err(e) => println!("⚠ Failed to delete test instance '{}': {}", instance_name_clone, e),
+                }
+            }
 
             // Update user status back to online for the account that launched it
             tauri::async_runtime::spawn(async move {
@@ -0,0 +1,268 @@
+use crate::models::{Instance, MrpackFile, VersionDetails};
+use crate::services::installer::{library_artifacts, MinecraftInstaller};
+use crate::services::instance::InstanceManager;
+use crate::services::mod_lock::ModLockfile;
+use crate::services::mrpack::MrpackReader;
+use crate::utils::{get_current_os, get_instance_dir, get_meta_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashMap, fs, path::Path};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityProblemKind {
+    /// instance.json is missing, unreadable or doesn't match the schema
+    InvalidInstanceJson,
+    MissingFile,
+    HashMismatch,
+}
+
+/// Which download a repair has to redo for a problem
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityArea {
+    Instance,
+    /// Version JSON, client jar or libraries
+    Game,
+    Mods,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntegrityProblem {
+    pub kind: IntegrityProblemKind,
+    pub area: IntegrityArea,
+    pub path: String,
+    pub message: String,
+    /// `repair_instance` can fix it by downloading the file again
+    pub repairable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntegrityReport {
+    pub instance: String,
+    pub checked_files: usize,
+    pub problems: Vec<IntegrityProblem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepairReport {
+    pub repaired: usize,
+    pub failed: Vec<String>,
+    /// A fresh check after repairing
+    pub report: IntegrityReport,
+}
+
+pub struct InstanceVerifier;
+
+impl InstanceVerifier {
+    /// Check instance.json, the game files against the version JSON's SHA1s and mods against the
+    /// lockfile. Loader libraries have no hashes to check against and are skipped.
+    pub fn verify(instance_name: &str) -> Result<IntegrityReport, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        if !instance_dir.exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let mut report = IntegrityReport {
+            instance: instance_name.to_string(),
+            checked_files: 0,
+            problems: Vec::new(),
+        };
+
+        let instance = match read_instance_json(&instance_dir) {
+            Ok(instance) => instance,
+            Err(message) => {
+                report.problems.push(IntegrityProblem {
+                    kind: IntegrityProblemKind::InvalidInstanceJson,
+                    area: IntegrityArea::Instance,
+                    path: "instance.json".to_string(),
+                    message,
+                    repairable: false,
+                });
+                return Ok(report);
+            }
+        };
+
+        check_game_files(&instance, &mut report);
+        check_mods(&instance_dir, &mut report)?;
+
+        println!(
+            "✓ Verified '{}': {} files, {} problems",
+            instance_name,
+            report.checked_files,
+            report.problems.len()
+        );
+        Ok(report)
+    }
+
+    /// Download everything `verify` flagged as repairable again, then check once more
+    pub async fn repair(instance_name: &str) -> Result<RepairReport, Box<dyn std::error::Error>> {
+        let report = Self::verify(instance_name)?;
+        let instance = InstanceManager::load(instance_name)?;
+        let instance_dir = get_instance_dir(instance_name);
+
+        let mut repaired = 0;
+        let mut failed = Vec::new();
+
+        let broken_game_files = report
+            .problems
+            .iter()
+            .filter(|p| p.repairable && p.area == IntegrityArea::Game)
+            .count();
+        if broken_game_files > 0 && instance.archive_mode {
+            // Archived instances come back from their own copies, the remote ones may be gone
+            match crate::services::pinning::VersionPinner::restore(&instance) {
+                Ok(_) => repaired += broken_game_files,
+                Err(e) => failed.push(format!("Pinned files: {}", e)),
+            }
+        } else if broken_game_files > 0 {
+            // The installer re-downloads every file whose SHA1 doesn't match
+            let version = InstanceManager::base_minecraft_version(&instance.version);
+            match MinecraftInstaller::new(get_meta_dir()).install_version(&version).await {
+                Ok(()) => repaired += broken_game_files,
+                Err(e) => failed.push(format!("Minecraft {}: {}", version, e)),
+            }
+        }
+
+        let broken_mods: Vec<&str> = report
+            .problems
+            .iter()
+            .filter(|p| p.repairable && p.area == IntegrityArea::Mods)
+            .map(|p| p.path.as_str())
+            .collect();
+        if !broken_mods.is_empty() {
+            let lockfile = ModLockfile::load(&instance_dir)?;
+            let client = crate::utils::http::client_builder()
+                .timeout(std::time::Duration::from_secs(300))
+                .user_agent("AtomicLauncher/2.4.0")
+                .build()?;
+
+            for locked in lockfile.files.iter().filter(|f| broken_mods.contains(&f.path.as_str())) {
+                // Same host, path and hash checks as a pack download
+                let file = MrpackFile {
+                    path: locked.path.clone(),
+                    hashes: HashMap::from([("sha1".to_string(), locked.sha1.clone())]),
+                    env: None,
+                    downloads: locked.downloads.clone(),
+                    file_size: 0,
+                };
+                match MrpackReader::download_file(&client, &file, &instance_dir).await {
+                    Ok(()) => repaired += 1,
+                    Err(e) => failed.push(e),
+                }
+            }
+        }
+
+        println!("✓ Repaired {} files in '{}' ({} failed)", repaired, instance_name, failed.len());
+
+        Ok(RepairReport {
+            repaired,
+            failed,
+            report: Self::verify(instance_name)?,
+        })
+    }
+}
+
+fn read_instance_json(instance_dir: &Path) -> Result<Instance, String> {
+    let content = fs::read_to_string(instance_dir.join("instance.json"))
+        .map_err(|e| format!("Failed to read instance.json: {}", e))?;
+    let instance: Instance =
+        serde_json::from_str(&content).map_err(|e| format!("instance.json doesn't match the schema: {}", e))?;
+
+    if instance.version.trim().is_empty() {
+        return Err("instance.json has no Minecraft version".to_string());
+    }
+
+    Ok(instance)
+}
+
+fn check_game_files(instance: &Instance, report: &mut IntegrityReport) {
+    let version = InstanceManager::base_minecraft_version(&instance.version);
+    let meta_dir = get_meta_dir();
+    let version_dir = meta_dir.join("versions").join(&version);
+    let json_path = version_dir.join(format!("{}.json", version));
+
+    let details: VersionDetails = match fs::read_to_string(&json_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+    {
+        Ok(details) => details,
+        Err(e) => {
+            report.problems.push(IntegrityProblem {
+                kind: IntegrityProblemKind::MissingFile,
+                area: IntegrityArea::Game,
+                path: format!("versions/{}/{}.json", version, version),
+                message: format!("Version JSON for {} is missing or unreadable: {}", version, e),
+                repairable: true,
+            });
+            return;
+        }
+    };
+
+    let mut expected = vec![(
+        format!("versions/{}/{}.jar", version, version),
+        details.downloads.client.sha1.clone(),
+    )];
+    for (_, _, artifact) in library_artifacts(&details, &get_current_os()) {
+        expected.push((format!("libraries/{}", artifact.path), artifact.sha1.clone()));
+    }
+
+    for (path, sha1) in expected {
+        check_file(&meta_dir.join(&path), &path, &sha1, IntegrityArea::Game, true, report);
+    }
+}
+
+fn check_mods(instance_dir: &Path, report: &mut IntegrityReport) -> Result<(), Box<dyn std::error::Error>> {
+    let lockfile = ModLockfile::load(instance_dir)?;
+
+    for locked in &lockfile.files {
+        let repairable = !locked.downloads.is_empty();
+        check_file(
+            &instance_dir.join(&locked.path),
+            &locked.path,
+            &locked.sha1,
+            IntegrityArea::Mods,
+            repairable,
+            report,
+        );
+    }
+
+    Ok(())
+}
+
+fn check_file(
+    full_path: &Path,
+    path: &str,
+    expected_sha1: &str,
+    area: IntegrityArea,
+    repairable: bool,
+    report: &mut IntegrityReport,
+) {
+    report.checked_files += 1;
+
+    let problem = match fs::read(long_path(full_path)) {
+        Err(_) => Some((IntegrityProblemKind::MissingFile, format!("{} is missing", path))),
+        Ok(contents) => {
+            let mut hasher = Sha1::new();
+            hasher.update(&contents);
+            let hash = format!("{:x}", hasher.finalize());
+
+            (hash != expected_sha1).then(|| {
+                (
+                    IntegrityProblemKind::HashMismatch,
+                    format!("{} is corrupt (expected SHA1 {}, got {})", path, expected_sha1, hash),
+                )
+            })
+        }
+    };
+
+    if let Some((kind, message)) = problem {
+        report.problems.push(IntegrityProblem {
+            kind,
+            area,
+            path: path.to_string(),
+            message,
+            repairable,
+        });
+    }
+}
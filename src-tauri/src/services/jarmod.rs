@@ -0,0 +1,182 @@
+use crate::models::Instance;
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+use zip::{ZipArchive, ZipWriter};
+
+const PATCHED_JAR_NAME: &str = "minecraft-patched.jar";
+const PATCH_STATE_NAME: &str = "patch.json";
+
+/// What the current patched jar was built from, so we know when to rebuild
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct PatchState {
+    base_jar_sha1: String,
+    jar_mods: Vec<(String, String)>,
+}
+
+pub struct JarModManager;
+
+impl JarModManager {
+    pub fn get_jarmods_dir(instance_name: &str) -> PathBuf {
+        get_instance_dir(instance_name).join("jarmods")
+    }
+
+    fn get_patch_dir(instance_name: &str) -> PathBuf {
+        get_instance_dir(instance_name).join("bin")
+    }
+
+    /// Copy a jar mod into the instance and append it to the patch order (last one wins)
+    pub fn add(instance: &mut Instance, source: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let file_name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or("Invalid jar mod path")?;
+
+        let lower = file_name.to_lowercase();
+        if !lower.ends_with(".jar") && !lower.ends_with(".zip") {
+            return Err("Jar mods must be .jar or .zip files".into());
+        }
+
+        // Must at least be a readable archive
+        ZipArchive::new(fs::File::open(source)?)?;
+
+        let jarmods_dir = Self::get_jarmods_dir(&instance.name);
+        fs::create_dir_all(&jarmods_dir)?;
+        let target = jarmods_dir.join(&file_name);
+        // Copying a file onto itself truncates it before reading, re-adding one already in place
+        // only moves it to the end of the order
+        let same_file = target.exists() && fs::canonicalize(source)? == fs::canonicalize(&target)?;
+        if !same_file {
+            fs::copy(source, &target)?;
+        }
+
+        instance.jar_mods.retain(|m| m != &file_name);
+        instance.jar_mods.push(file_name.clone());
+        Self::invalidate(&instance.name)?;
+
+        Ok(file_name)
+    }
+
+    pub fn remove(instance: &mut Instance, file_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if !instance.jar_mods.iter().any(|m| m == file_name) {
+            return Err(format!("Jar mod '{}' is not installed", file_name).into());
+        }
+
+        instance.jar_mods.retain(|m| m != file_name);
+
+        let path = Self::get_jarmods_dir(&instance.name).join(file_name);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+
+        Self::invalidate(&instance.name)
+    }
+
+    pub fn reorder(instance: &mut Instance, order: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut current = instance.jar_mods.clone();
+        let mut requested = order.clone();
+        current.sort();
+        requested.sort();
+
+        if current != requested {
+            return Err("New order must contain exactly the installed jar mods".into());
+        }
+
+        instance.jar_mods = order;
+        Self::invalidate(&instance.name)
+    }
+
+    /// Drop the patched jar. The next launch rebuilds it, or uses the vanilla jar if no jar mods remain.
+    pub fn invalidate(instance_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let patch_dir = Self::get_patch_dir(instance_name);
+
+        for name in [PATCHED_JAR_NAME, PATCH_STATE_NAME] {
+            let path = patch_dir.join(name);
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The jar to launch: vanilla if there are no jar mods, otherwise an up-to-date patched copy
+    pub fn resolve_client_jar(instance: &Instance, base_jar: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        if instance.jar_mods.is_empty() {
+            return Ok(base_jar.to_path_buf());
+        }
+
+        let jarmods_dir = Self::get_jarmods_dir(&instance.name);
+        let state = PatchState {
+            base_jar_sha1: file_sha1(base_jar)?,
+            jar_mods: instance
+                .jar_mods
+                .iter()
+                .map(|name| Ok((name.clone(), file_sha1(&jarmods_dir.join(name))?)))
+                .collect::<Result<_, Box<dyn std::error::Error>>>()?,
+        };
+
+        let patch_dir = Self::get_patch_dir(&instance.name);
+        let patched_jar = patch_dir.join(PATCHED_JAR_NAME);
+        let state_path = patch_dir.join(PATCH_STATE_NAME);
+
+        let existing_state: Option<PatchState> = fs::read_to_string(&state_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok());
+
+        if patched_jar.exists() && existing_state.as_ref() == Some(&state) {
+            return Ok(patched_jar);
+        }
+
+        println!("Building patched client jar with {} jar mod(s)...", instance.jar_mods.len());
+        fs::create_dir_all(&patch_dir)?;
+
+        // Highest priority first: the last jar mod wins, vanilla fills in the rest
+        let mut sources: Vec<PathBuf> = instance.jar_mods.iter().rev().map(|m| jarmods_dir.join(m)).collect();
+        sources.push(base_jar.to_path_buf());
+
+        let temp_jar = patch_dir.join(format!("{}.tmp", PATCHED_JAR_NAME));
+        build_patched_jar(&sources, &temp_jar)?;
+        fs::rename(&temp_jar, &patched_jar)?;
+        fs::write(&state_path, serde_json::to_string_pretty(&state)?)?;
+
+        println!("✓ Patched client jar ready");
+        Ok(patched_jar)
+    }
+}
+
+fn build_patched_jar(sources: &[PathBuf], output: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = ZipWriter::new(fs::File::create(long_path(output))?);
+    let mut written = HashSet::new();
+
+    for source in sources {
+        let mut archive = ZipArchive::new(fs::File::open(long_path(source))?)?;
+
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            let name = entry.name().to_string();
+
+            // Signatures of the vanilla jar are invalid once anything is patched in
+            if name.starts_with("META-INF/") || !written.insert(name) {
+                continue;
+            }
+
+            writer.raw_copy_file(entry)?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+fn file_sha1(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let contents = fs::read(long_path(path))?;
+    let mut hasher = Sha1::new();
+    hasher.update(&contents);
+    Ok(format!("{:x}", hasher.finalize()))
+}
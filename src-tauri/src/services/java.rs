@@ -0,0 +1,527 @@
+use crate::services::instance::InstanceManager;
+use crate::utils::{get_launcher_dir, get_meta_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Temurin builds, offered when no installed JVM can run a version
+const JAVA_DOWNLOAD_URL: &str = "https://adoptium.net/temurin/releases/?version=";
+/// Mojang's Java runtimes, the same ones the official launcher ships
+const RUNTIME_INDEX_URL: &str =
+    "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+const MAX_CONCURRENT_DOWNLOADS: usize = 16;
+/// Roughly the largest heap a 32-bit JVM can reserve, more fails with "Could not reserve enough space"
+pub const MAX_32BIT_MEMORY_MB: u32 = 1536;
+
+/// `javaVersion` of a version JSON
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaVersionRequirement {
+    /// Mojang runtime name, e.g. "java-runtime-gamma"
+    pub component: String,
+    pub major_version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeEntry {
+    manifest: RuntimeDownload,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct RuntimeDownload {
+    sha1: String,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeManifest {
+    files: HashMap<String, RuntimeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RuntimeFile {
+    File {
+        #[serde(default)]
+        executable: bool,
+        downloads: RuntimeFileDownloads,
+    },
+    Directory,
+    Link {
+        target: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeFileDownloads {
+    raw: RuntimeDownload,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JavaInstallation {
+    pub path: String,
+    pub major_version: u32,
+    /// Full `java.version`, e.g. "17.0.8" or "1.8.0_382"
+    pub version: String,
+    pub vendor: Option<String>,
+    /// 32-bit JVMs can't use more than about 1.5GB of memory
+    pub is_64bit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JavaRecommendation {
+    pub minecraft_version: String,
+    pub required_major: u32,
+    /// Best installed JVM, None when nothing installed can run this version
+    pub installation: Option<JavaInstallation>,
+    /// The recommended JVM is the exact major version the game was built for
+    pub exact_match: bool,
+    /// Where to get a suitable JVM when none is installed
+    pub download_url: Option<String>,
+}
+
+pub struct JavaManager;
+
+impl JavaManager {
+    /// Every JVM found in the usual install locations, newest major version first
+    pub fn detect() -> Vec<JavaInstallation> {
+        let mut installations: Vec<JavaInstallation> = Vec::new();
+
+        for path in Self::candidate_paths() {
+            if installations.iter().any(|i| same_file(&i.path, &path)) {
+                continue;
+            }
+            if let Some(installation) = Self::probe(&path) {
+                installations.push(installation);
+            }
+        }
+
+        installations.sort_by(|a, b| {
+            b.major_version
+                .cmp(&a.major_version)
+                .then_with(|| b.is_64bit.cmp(&a.is_64bit))
+                .then_with(|| version_key(&b.version).cmp(&version_key(&a.version)))
+                .then_with(|| a.vendor.cmp(&b.vendor))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        installations
+    }
+
+    /// The installed JVM that suits `minecraft_version` best: the required major version if
+    /// there is one, otherwise the closest newer one
+    pub fn recommend(minecraft_version: &str) -> JavaRecommendation {
+        let required_major = Self::required_major_version(minecraft_version);
+        let installations = Self::usable(Self::detect());
+
+        // detect() sorts newest first, so the last compatible entry is the closest one
+        let installation = installations
+            .iter()
+            .find(|i| i.major_version == required_major)
+            .or_else(|| installations.iter().rev().find(|i| i.major_version > required_major))
+            .cloned();
+
+        // There are no long-term builds of 16, 17 runs those versions fine
+        let download_major = if required_major == 16 { 17 } else { required_major };
+
+        JavaRecommendation {
+            minecraft_version: minecraft_version.to_string(),
+            required_major,
+            exact_match: installation.as_ref().is_some_and(|i| i.major_version == required_major),
+            download_url: installation
+                .is_none()
+                .then(|| format!("{}{}", JAVA_DOWNLOAD_URL, download_major)),
+            installation,
+        }
+    }
+
+    /// What an installed version asks for, from its version JSON or the one it inherits from.
+    /// Old versions have no `javaVersion` and get Java 8.
+    pub fn requirement_for_version(version_id: &str) -> JavaVersionRequirement {
+        let base_version = Self::installed_base_version(version_id);
+        let json_path = get_meta_dir()
+            .join("versions")
+            .join(&base_version)
+            .join(format!("{}.json", base_version));
+
+        std::fs::read_to_string(json_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| serde_json::from_value(json.get("javaVersion")?.clone()).ok())
+            .unwrap_or_else(|| JavaVersionRequirement {
+                component: "jre-legacy".to_string(),
+                major_version: Self::required_major_version(version_id),
+            })
+    }
+
+    /// Loader profiles name their vanilla parent in `inheritsFrom`
+    fn installed_base_version(version_id: &str) -> String {
+        let json_path = get_meta_dir()
+            .join("versions")
+            .join(version_id)
+            .join(format!("{}.json", version_id));
+
+        std::fs::read_to_string(json_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| json.get("inheritsFrom")?.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| InstanceManager::base_minecraft_version(version_id))
+    }
+
+    /// Java for a new instance: an installed JVM of the exact major version, otherwise Mojang's
+    /// runtime for it, downloaded into the launcher folder
+    pub async fn ensure_for_version(version_id: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let requirement = Self::requirement_for_version(version_id);
+
+        let installed = Self::usable(tokio::task::spawn_blocking(Self::detect).await?);
+        if let Some(java) = installed.iter().find(|i| i.major_version == requirement.major_version) {
+            return Ok(java.path.clone());
+        }
+
+        println!(
+            "No Java {} installed, downloading runtime {}",
+            requirement.major_version, requirement.component
+        );
+        Self::install_runtime(&requirement.component).await
+    }
+
+    /// Download a Mojang runtime, skipping files that are already there. Returns its java executable.
+    pub async fn install_runtime(component: &str) -> Result<String, Box<dyn std::error::Error>> {
+        if !component.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("Invalid Java runtime '{}'", component).into());
+        }
+
+        let client = crate::utils::http::client_builder()
+            .timeout(std::time::Duration::from_secs(300))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+
+        let index: HashMap<String, HashMap<String, Vec<RuntimeEntry>>> =
+            client.get(RUNTIME_INDEX_URL).send().await?.json().await?;
+        let platform = runtime_platform();
+        let entry = index
+            .get(platform)
+            .and_then(|components| components.get(component))
+            .and_then(|entries| entries.first())
+            .ok_or_else(|| format!("Java runtime '{}' is not available for {}", component, platform))?;
+
+        let runtime_dir = Self::runtimes_dir().join(component);
+        let marker = runtime_dir.join(".manifest-sha1");
+        let java_path = runtime_dir.join(runtime_java_executable());
+        if std::fs::read_to_string(&marker).ok().as_deref() == Some(entry.manifest.sha1.as_str()) && java_path.is_file() {
+            return Ok(java_path.to_string_lossy().to_string());
+        }
+
+        let manifest: RuntimeManifest = client.get(&entry.manifest.url).send().await?.json().await?;
+        let mut downloads = Vec::new();
+        let mut links = Vec::new();
+
+        for (path, file) in &manifest.files {
+            if path.split('/').any(|part| part == ".." || part.is_empty()) {
+                return Err(format!("Runtime manifest contains unsafe path '{}'", path).into());
+            }
+            let target = runtime_dir.join(path);
+            match file {
+                RuntimeFile::Directory => std::fs::create_dir_all(long_path(&target))?,
+                RuntimeFile::File { executable, downloads: files } => {
+                    if file_sha1(&target).as_deref() != Some(files.raw.sha1.as_str()) {
+                        downloads.push((target, files.raw.clone(), *executable));
+                    }
+                }
+                RuntimeFile::Link { target: link } => links.push((target, link.clone())),
+            }
+        }
+
+        println!("Downloading {} files of Java runtime {}", downloads.len(), component);
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
+        let mut tasks = tokio::task::JoinSet::new();
+        for (target, download, executable) in downloads {
+            let client = client.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire().await.map_err(|e| e.to_string())?;
+                download_runtime_file(&client, &download, &target, executable).await
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result??;
+        }
+
+        #[cfg(unix)]
+        for (path, link) in links {
+            let _ = std::fs::remove_file(&path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::os::unix::fs::symlink(link, &path)?;
+        }
+        #[cfg(not(unix))]
+        let _ = links;
+
+        if !java_path.is_file() {
+            return Err(format!("Java runtime '{}' has no java executable", component).into());
+        }
+        std::fs::write(&marker, &entry.manifest.sha1)?;
+
+        println!("✓ Installed Java runtime {}", component);
+        Ok(java_path.to_string_lossy().to_string())
+    }
+
+    fn runtimes_dir() -> PathBuf {
+        get_launcher_dir().join("runtimes")
+    }
+
+    /// 32-bit JVMs are never recommended on a 64-bit system, the memory limit isn't worth it
+    fn usable(installations: Vec<JavaInstallation>) -> Vec<JavaInstallation> {
+        if !cfg!(target_pointer_width = "64") {
+            return installations;
+        }
+        installations.into_iter().filter(|i| i.is_64bit).collect()
+    }
+
+    /// Why `memory_mb` won't work with this JVM, if it won't
+    pub fn memory_warning(installation: &JavaInstallation, memory_mb: u32) -> Option<String> {
+        (!installation.is_64bit && memory_mb > MAX_32BIT_MEMORY_MB).then(|| {
+            format!(
+                "{} is a 32-bit Java, which can't use more than about {}MB of memory. Install a 64-bit Java or lower the memory to avoid crashes and OutOfMemoryErrors.",
+                installation.path, MAX_32BIT_MEMORY_MB
+            )
+        })
+    }
+
+    /// Run the JVM once and read its version and vendor from the system properties
+    pub fn probe(java_path: &str) -> Option<JavaInstallation> {
+        let output = Command::new(java_path)
+            .arg("-XshowSettings:properties")
+            .arg("-version")
+            .output()
+            .ok()?;
+        let text = String::from_utf8_lossy(&output.stderr);
+
+        let property = |name: &str| {
+            text.lines()
+                .filter_map(|l| l.trim().split_once(" = "))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.trim().to_string())
+        };
+
+        let version = property("java.version").or_else(|| version_from_banner(&text))?;
+        let major_version = Self::parse_major_version(&version)?;
+
+        Some(JavaInstallation {
+            path: java_path.to_string(),
+            major_version,
+            version,
+            vendor: property("java.vendor"),
+            // os.arch is "x86" or "i386" on 32-bit JVMs, even on a 64-bit OS
+            is_64bit: match property("sun.arch.data.model").as_deref() {
+                Some(model) => model == "64",
+                None => !matches!(property("os.arch").as_deref(), Some("x86" | "i386" | "i686" | "arm")),
+            },
+        })
+    }
+
+    fn parse_major_version(version_str: &str) -> Option<u32> {
+        let parts: Vec<&str> = version_str.split(|c: char| c == '.' || c == '_' || c == '-' || c == '+').collect();
+
+        if parts.is_empty() {
+            return None;
+        }
+
+        // Handle old format: 1.8.0_xxx -> 8
+        if parts[0] == "1" && parts.len() > 1 {
+            parts[1].parse::<u32>().ok()
+        } else {
+            // Handle modern format: 17.0.8 -> 17
+            parts[0].parse::<u32>().ok()
+        }
+    }
+
+    pub fn required_major_version(minecraft_version: &str) -> u32 {
+        let minecraft_version = InstanceManager::base_minecraft_version(minecraft_version);
+        let base_version = match minecraft_version.find('-') {
+            Some(pos) => &minecraft_version[..pos],
+            None => minecraft_version.as_str(),
+        };
+
+        let parts: Vec<&str> = base_version.split('.').collect();
+
+        // New format: 26.x (snapshots after version naming change)
+        if let Some(Ok(major)) = parts.first().map(|p| p.parse::<u32>()) {
+            if major >= 26 {
+                return 25; // Minecraft 26+ requires Java 25
+            }
+        }
+
+        // Old format: 1.x.y
+        if parts.len() >= 2 {
+            if let (Ok(major), Ok(minor)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
+                if major == 1 {
+                    // Check patch version for 1.20.5+
+                    if minor == 20 && parts.len() >= 3 {
+                        if let Ok(patch) = parts[2].parse::<u32>() {
+                            if patch >= 5 {
+                                return 21; // 1.20.5+ requires Java 21
+                            }
+                        }
+                    }
+
+                    // Version-specific requirements
+                    if minor >= 21 { return 21; } // 1.21+ requires Java 21
+                    if minor >= 18 { return 17; } // 1.18-1.20.4 requires Java 17
+                    if minor >= 17 { return 16; } // 1.17 requires Java 16
+                }
+            }
+        }
+
+        8
+    }
+
+    fn candidate_paths() -> Vec<String> {
+        let executable = if cfg!(windows) { "javaw.exe" } else { "java" };
+        let mut homes: Vec<PathBuf> = Vec::new();
+
+        #[cfg(target_os = "windows")]
+        let roots = [
+            "C:\\Program Files\\Java",
+            "C:\\Program Files (x86)\\Java",
+            "C:\\Program Files\\Eclipse Adoptium",
+            "C:\\Program Files\\Microsoft",
+            "C:\\Program Files\\Zulu",
+            "C:\\Program Files\\Amazon Corretto",
+        ];
+        #[cfg(target_os = "macos")]
+        let roots = [
+            "/Library/Java/JavaVirtualMachines",
+            "/System/Library/Java/JavaVirtualMachines",
+        ];
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let roots = ["/usr/lib/jvm", "/usr/java", "/opt/java"];
+
+        for root in roots {
+            if let Ok(entries) = std::fs::read_dir(root) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if cfg!(target_os = "macos") {
+                        homes.push(path.join("Contents").join("Home"));
+                    } else {
+                        homes.push(path);
+                    }
+                }
+            }
+        }
+
+        if let Ok(java_home) = std::env::var("JAVA_HOME") {
+            homes.push(PathBuf::from(java_home));
+        }
+
+        let mut paths: Vec<PathBuf> = homes.iter().map(|h| h.join("bin").join(executable)).collect();
+        if let Ok(entries) = std::fs::read_dir(Self::runtimes_dir()) {
+            paths.extend(entries.flatten().map(|e| e.path().join(runtime_java_executable())));
+        }
+        if let Some(path_var) = std::env::var_os("PATH") {
+            paths.extend(std::env::split_paths(&path_var).map(|p| p.join(executable)));
+        }
+
+        paths
+            .into_iter()
+            .filter(|p| p.is_file())
+            .filter_map(|p| p.to_str().map(|s| s.to_string()))
+            .collect()
+    }
+}
+
+/// `java -version` without system properties, e.g. `openjdk version "17.0.8" 2023-07-18`
+fn version_from_banner(text: &str) -> Option<String> {
+    let line = text.lines().find(|l| l.contains("version"))?;
+    line.split('"').nth(1).map(|v| v.to_string())
+}
+
+/// Numeric parts of a version for ordering, "17.0.10" after "17.0.8"
+fn version_key(version: &str) -> Vec<u32> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|p| p.parse().ok())
+        .collect()
+}
+
+/// PATH entries are often symlinks into a JVM that was already found, e.g. /usr/bin/java
+fn same_file(a: &str, b: &str) -> bool {
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Platform key of Mojang's runtime index
+fn runtime_platform() -> &'static str {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("windows", "x86") => "windows-x86",
+        ("windows", "aarch64") => "windows-arm64",
+        ("windows", _) => "windows-x64",
+        ("macos", "aarch64") => "mac-os-arm64",
+        ("macos", _) => "mac-os",
+        (_, "x86") => "linux-i386",
+        _ => "linux",
+    }
+}
+
+/// Where a runtime keeps its java executable, relative to the runtime folder
+fn runtime_java_executable() -> PathBuf {
+    if cfg!(windows) {
+        Path::new("bin").join("javaw.exe")
+    } else if cfg!(target_os = "macos") {
+        Path::new("jre.bundle").join("Contents").join("Home").join("bin").join("java")
+    } else {
+        Path::new("bin").join("java")
+    }
+}
+
+async fn download_runtime_file(
+    client: &reqwest::Client,
+    download: &RuntimeDownload,
+    target: &Path,
+    executable: bool,
+) -> Result<(), String> {
+    let response = client
+        .get(&download.url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", target.display(), e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", target.display(), response.status()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", target.display(), e))?;
+
+    let mut hasher = Sha1::new();
+    hasher.update(&bytes);
+    if format!("{:x}", hasher.finalize()) != download.sha1 {
+        return Err(format!("Hash mismatch for {}", target.display()));
+    }
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(long_path(parent)).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(long_path(target), &bytes).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+
+    #[cfg(unix)]
+    if executable {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(target, std::fs::Permissions::from_mode(0o755));
+    }
+    #[cfg(not(unix))]
+    let _ = executable;
+
+    Ok(())
+}
+
+fn file_sha1(path: &Path) -> Option<String> {
+    let contents = std::fs::read(long_path(path)).ok()?;
+    let mut hasher = Sha1::new();
+    hasher.update(&contents);
+    Some(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,199 @@
+use crate::services::instance::InstanceManager;
+use crate::utils::{get_instance_dir, get_launcher_dir};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, sync::Mutex};
+
+const JOURNAL_FILE: &str = "journal.json";
+
+lazy_static::lazy_static! {
+    /// Entries written by another session were cut off by the launcher closing or crashing
+    static ref SESSION_ID: String = format!("{}-{}", std::process::id(), Utc::now().timestamp_millis());
+    static ref JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A long operation that creates an instance, with what's needed to run it again
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalTask {
+    ModpackInstall {
+        modpack_slug: String,
+        version_id: String,
+        preferred_game_version: Option<String>,
+    },
+    ModpackFileInstall {
+        file_path: String,
+        preferred_game_version: Option<String>,
+    },
+    ModrinthPackImport {
+        file_path: String,
+    },
+    CurseforgePackImport {
+        file_path: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalEntry {
+    pub id: String,
+    pub session: String,
+    #[serde(flatten)]
+    pub task: JournalTask,
+    pub instance_name: String,
+    pub started_at: String,
+    /// Last stage reached, as shown in the progress bar
+    pub step: String,
+    pub downloads_total: usize,
+    pub downloads_remaining: usize,
+    /// The instance folder exists and belongs to this task, so cleaning up removes it
+    pub instance_created: bool,
+    /// Temporary downloads and extraction folders
+    #[serde(default)]
+    pub temp_paths: Vec<String>,
+}
+
+/// Record of a running task. Dropping it marks the task as finished, so only a launcher that
+/// was killed mid-task leaves its entry behind.
+pub struct JournalHandle {
+    id: String,
+}
+
+impl JournalHandle {
+    pub fn step(&self, step: &str) {
+        update(&self.id, |entry| entry.step = step.to_string());
+    }
+
+    pub fn downloads(&self, remaining: usize, total: usize) {
+        update(&self.id, |entry| {
+            entry.downloads_remaining = remaining;
+            entry.downloads_total = total;
+        });
+    }
+
+    pub fn instance_created(&self) {
+        update(&self.id, |entry| entry.instance_created = true);
+    }
+
+    pub fn temp_path(&self, path: &Path) {
+        let path = path.to_string_lossy().to_string();
+        update(&self.id, |entry| {
+            if !entry.temp_paths.contains(&path) {
+                entry.temp_paths.push(path);
+            }
+        });
+    }
+}
+
+impl Drop for JournalHandle {
+    fn drop(&mut self) {
+        let _guard = JOURNAL_LOCK.lock().unwrap();
+        let mut entries = load();
+        entries.retain(|e| e.id != self.id);
+        if let Err(e) = save(&entries) {
+            println!("Warning: Failed to update the task journal: {}", e);
+        }
+    }
+}
+
+pub struct TaskJournal;
+
+impl TaskJournal {
+    /// Start recording a task for the instance
+    pub fn begin(instance_name: &str, task: JournalTask) -> JournalHandle {
+        let entry = JournalEntry {
+            id: format!("{}-{}", instance_name, Utc::now().timestamp_millis()),
+            session: SESSION_ID.clone(),
+            task,
+            instance_name: instance_name.to_string(),
+            started_at: Utc::now().to_rfc3339(),
+            step: "Starting".to_string(),
+            downloads_total: 0,
+            downloads_remaining: 0,
+            instance_created: false,
+            temp_paths: Vec::new(),
+        };
+        let id = entry.id.clone();
+
+        let _guard = JOURNAL_LOCK.lock().unwrap();
+        let mut entries = load();
+        entries.push(entry);
+        if let Err(e) = save(&entries) {
+            println!("Warning: Failed to update the task journal: {}", e);
+        }
+        JournalHandle { id }
+    }
+
+    /// Tasks an earlier session didn't get to finish
+    pub fn interrupted() -> Vec<JournalEntry> {
+        let _guard = JOURNAL_LOCK.lock().unwrap();
+        load().into_iter().filter(|e| e.session != *SESSION_ID).collect()
+    }
+
+    /// Drop an interrupted task's entry and keep what it left behind
+    pub fn forget(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let _guard = JOURNAL_LOCK.lock().unwrap();
+        let mut entries = load();
+        entries.retain(|e| e.id != id);
+        save(&entries)
+    }
+
+    /// Remove an interrupted task's half-created instance and temporary files, then forget it.
+    /// Returns the entry so the task can be started again.
+    pub fn clean_up(id: &str) -> Result<JournalEntry, Box<dyn std::error::Error>> {
+        let entry = Self::interrupted()
+            .into_iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| format!("No interrupted task '{}'", id))?;
+
+        if entry.instance_created && get_instance_dir(&entry.instance_name).exists() {
+            InstanceManager::delete(&entry.instance_name)?;
+        }
+        for path in &entry.temp_paths {
+            let path = Path::new(path);
+            let _ = if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+        }
+
+        let _guard = JOURNAL_LOCK.lock().unwrap();
+        let mut entries = load();
+        entries.retain(|e| e.id != id);
+        save(&entries)?;
+
+        println!("✓ Cleaned up interrupted task for '{}' ({})", entry.instance_name, entry.step);
+        Ok(entry)
+    }
+}
+
+fn update(id: &str, change: impl FnOnce(&mut JournalEntry)) {
+    let _guard = JOURNAL_LOCK.lock().unwrap();
+    let mut entries = load();
+    let Some(entry) = entries.iter_mut().find(|e| e.id == id) else {
+        return;
+    };
+    change(entry);
+    if let Err(e) = save(&entries) {
+        println!("Warning: Failed to update the task journal: {}", e);
+    }
+}
+
+fn load() -> Vec<JournalEntry> {
+    fs::read_to_string(get_launcher_dir().join(JOURNAL_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(entries: &[JournalEntry]) -> Result<(), Box<dyn std::error::Error>> {
+    let path = get_launcher_dir().join(JOURNAL_FILE);
+    if entries.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+
+    // Written whole and swapped in, a kill mid-write mustn't lose the other entries
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, serde_json::to_string_pretty(entries)?)?;
+    fs::rename(&temp_path, &path)?;
+    Ok(())
+}
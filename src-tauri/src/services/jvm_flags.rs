@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+/// A flag that only exists on some Java versions. `since` is the first major version that
+/// accepts it, `until` the first one that refuses to start with it.
+struct FlagRule {
+    /// `-XX` option name, or the start of any other argument
+    name: &'static str,
+    since: Option<u32>,
+    until: Option<u32>,
+    reason: &'static str,
+}
+
+const FLAG_RULES: &[FlagRule] = &[
+    FlagRule { name: "UseConcMarkSweepGC", since: None, until: Some(14), reason: "CMS was removed in Java 14" },
+    FlagRule { name: "CMSIncrementalMode", since: None, until: Some(14), reason: "CMS was removed in Java 14" },
+    FlagRule { name: "CMSClassUnloadingEnabled", since: None, until: Some(14), reason: "CMS was removed in Java 14" },
+    FlagRule { name: "CMSParallelRemarkEnabled", since: None, until: Some(14), reason: "CMS was removed in Java 14" },
+    FlagRule { name: "CMSInitiatingOccupancyFraction", since: None, until: Some(14), reason: "CMS was removed in Java 14" },
+    FlagRule { name: "UseCMSInitiatingOccupancyOnly", since: None, until: Some(14), reason: "CMS was removed in Java 14" },
+    FlagRule { name: "UseParNewGC", since: None, until: Some(10), reason: "ParNew was removed in Java 10" },
+    FlagRule { name: "PermSize", since: None, until: Some(8), reason: "PermGen was removed in Java 8" },
+    FlagRule { name: "MaxPermSize", since: None, until: Some(8), reason: "PermGen was removed in Java 8" },
+    FlagRule { name: "UseFastAccessorMethods", since: None, until: Some(9), reason: "removed in Java 9" },
+    FlagRule { name: "-Xincgc", since: None, until: Some(9), reason: "removed in Java 9" },
+    FlagRule { name: "PrintGCDateStamps", since: None, until: Some(9), reason: "replaced by -Xlog in Java 9" },
+    FlagRule { name: "PrintGCTimeStamps", since: None, until: Some(9), reason: "replaced by -Xlog in Java 9" },
+    FlagRule { name: "UseGCLogFileRotation", since: None, until: Some(9), reason: "replaced by -Xlog in Java 9" },
+    FlagRule { name: "NumberOfGCLogFiles", since: None, until: Some(9), reason: "replaced by -Xlog in Java 9" },
+    FlagRule { name: "GCLogFileSize", since: None, until: Some(9), reason: "replaced by -Xlog in Java 9" },
+    FlagRule { name: "AggressiveOpts", since: None, until: Some(12), reason: "removed in Java 12" },
+    FlagRule { name: "UseLargePagesInMetaspace", since: None, until: Some(16), reason: "removed in Java 16" },
+    FlagRule { name: "-Xlog", since: Some(9), until: None, reason: "unified logging needs Java 9" },
+    FlagRule { name: "--add-opens", since: Some(9), until: None, reason: "modules need Java 9" },
+    FlagRule { name: "--add-exports", since: Some(9), until: None, reason: "modules need Java 9" },
+    FlagRule { name: "--add-modules", since: Some(9), until: None, reason: "modules need Java 9" },
+    FlagRule { name: "EnableJVMCI", since: Some(10), until: None, reason: "JVMCI needs Java 10" },
+    FlagRule { name: "UseJVMCICompiler", since: Some(10), until: None, reason: "JVMCI needs Java 10" },
+    FlagRule { name: "UseZGC", since: Some(11), until: None, reason: "ZGC needs Java 11" },
+    FlagRule { name: "ZGenerational", since: Some(21), until: None, reason: "generational ZGC needs Java 21" },
+    FlagRule { name: "UseShenandoahGC", since: Some(11), until: None, reason: "Shenandoah needs Java 11" },
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DroppedFlag {
+    pub flag: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FilteredJvmArgs {
+    pub args: Vec<String>,
+    pub dropped: Vec<DroppedFlag>,
+    /// Flags the launcher added so the rest work, e.g. unlocking experimental ZGC on Java 11
+    pub added: Vec<String>,
+}
+
+pub struct JvmFlagFilter;
+
+impl JvmFlagFilter {
+    /// Drop the flags `java_version` would refuse to start with, so flag lists copied from guides
+    /// for another Java don't break the launch
+    pub fn filter(args: &[String], java_version: u32) -> FilteredJvmArgs {
+        let mut result = FilteredJvmArgs::default();
+
+        for arg in args {
+            let rule = FLAG_RULES.iter().find(|rule| matches_rule(arg, rule));
+            let unsupported = rule.filter(|rule| {
+                rule.since.is_some_and(|since| java_version < since) || rule.until.is_some_and(|until| java_version >= until)
+            });
+
+            match unsupported {
+                Some(rule) => result.dropped.push(DroppedFlag {
+                    flag: arg.clone(),
+                    reason: rule.reason.to_string(),
+                }),
+                None => result.args.push(arg.clone()),
+            }
+        }
+
+        // ZGC was experimental before Java 15
+        let unlock = "-XX:+UnlockExperimentalVMOptions".to_string();
+        if (11..15).contains(&java_version)
+            && result.args.iter().any(|a| a == "-XX:+UseZGC")
+            && !result.args.contains(&unlock)
+        {
+            result.args.insert(0, unlock.clone());
+            result.added.push(unlock);
+        }
+
+        result
+    }
+}
+
+/// `-XX:+Name`, `-XX:-Name` and `-XX:Name=value` match by option name, anything else by prefix
+fn matches_rule(arg: &str, rule: &FlagRule) -> bool {
+    match arg.strip_prefix("-XX:") {
+        Some(option) => {
+            let name = option.trim_start_matches(['+', '-']);
+            let name = name.split('=').next().unwrap_or(name);
+            name == rule.name
+        }
+        None => rule.name.starts_with('-') && arg.starts_with(rule.name),
+    }
+}
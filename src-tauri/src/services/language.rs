@@ -0,0 +1,125 @@
+use crate::models::{AssetIndexData, Instance, VersionDetails};
+use crate::services::instance::InstanceManager;
+use crate::utils::game_options::set_options;
+use crate::utils::{get_instance_dir, get_meta_dir};
+use sha1::{Digest, Sha1};
+use std::fs;
+
+pub struct LanguageManager;
+
+impl LanguageManager {
+    /// Language codes look like "de_de", "pt_br" or "lzh"
+    pub fn validate(language: &str) -> Result<(), String> {
+        let valid = !language.is_empty()
+            && language.len() <= 10
+            && language.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Invalid language code '{}'", language))
+        }
+    }
+
+    /// Before 1.11 options.txt and the lang assets used "de_DE" instead of "de_de"
+    fn is_legacy(minecraft_version: &str) -> bool {
+        let parts: Vec<u32> = minecraft_version
+            .split('.')
+            .filter_map(|p| p.parse().ok())
+            .collect();
+
+        matches!(parts.as_slice(), [1, minor, ..] if *minor < 11)
+    }
+
+    fn game_code(language: &str, minecraft_version: &str) -> String {
+        if !Self::is_legacy(minecraft_version) {
+            return language.to_string();
+        }
+
+        match language.split_once('_') {
+            Some((lang, region)) => format!("{}_{}", lang, region.to_uppercase()),
+            None => language.to_string(),
+        }
+    }
+
+    /// Write the instance language into options.txt
+    pub fn apply(instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(language) = &instance.language else {
+            return Ok(());
+        };
+
+        let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+        let options_path = get_instance_dir(&instance.name).join("options.txt");
+
+        set_options(&options_path, &[("lang", Self::game_code(language, &minecraft_version))])?;
+        Ok(())
+    }
+
+    /// Make sure the language file is in the asset store so the first launch is already translated
+    pub async fn ensure_language_asset(
+        instance: &Instance,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(language) = &instance.language else {
+            return Ok(());
+        };
+
+        let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+        let meta_dir = get_meta_dir();
+        let version_json = meta_dir
+            .join("versions")
+            .join(&minecraft_version)
+            .join(format!("{}.json", minecraft_version));
+
+        if !version_json.exists() {
+            return Err(format!("Minecraft {} is not installed", minecraft_version).into());
+        }
+
+        let details: VersionDetails = serde_json::from_str(&fs::read_to_string(&version_json)?)?;
+        let index_path = meta_dir
+            .join("assets")
+            .join("indexes")
+            .join(format!("{}.json", details.asset_index.id));
+        let index: AssetIndexData = serde_json::from_str(&fs::read_to_string(&index_path)?)?;
+
+        let code = Self::game_code(language, &minecraft_version);
+        let asset = ["json", "lang"]
+            .iter()
+            .find_map(|ext| index.objects.get(&format!("minecraft/lang/{}.{}", code, ext)));
+
+        // en_us ships inside the client jar and has no asset entry
+        let Some(asset) = asset else {
+            return Ok(());
+        };
+
+        let hash_prefix = &asset.hash[0..2];
+        let object_path = meta_dir
+            .join("assets")
+            .join("objects")
+            .join(hash_prefix)
+            .join(&asset.hash);
+
+        if object_path.exists() {
+            return Ok(());
+        }
+
+        let url = format!(
+            "https://resources.download.minecraft.net/{}/{}",
+            hash_prefix, asset.hash
+        );
+        let bytes = crate::utils::http::client().get(&url).send().await?.error_for_status()?.bytes().await?;
+
+        let mut hasher = Sha1::new();
+        hasher.update(&bytes);
+        if format!("{:x}", hasher.finalize()) != asset.hash {
+            return Err(format!("Language asset for '{}' failed verification", code).into());
+        }
+
+        if let Some(parent) = object_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&object_path, &bytes)?;
+
+        println!("✓ Downloaded language asset {}", code);
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use crate::services::instance::InstanceManager;
+use crate::services::settings::SettingsManager;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Mutex};
+use tauri::Emitter;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueuedLaunch {
+    pub instance: String,
+    pub memory_mb: u32,
+    pub queued_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActiveLaunch {
+    pub instance: String,
+    pub memory_mb: u32,
+    /// Still signing in or starting Java, not in the running list yet
+    pub starting: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaunchQueueState {
+    pub active: Vec<ActiveLaunch>,
+    pub queued: Vec<QueuedLaunch>,
+    pub max_concurrent: Option<u32>,
+    pub memory_budget_mb: Option<u32>,
+    pub memory_in_use_mb: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LaunchRequestOutcome {
+    Started,
+    /// Waiting for a running game to exit, `position` is 1-based
+    Queued { position: usize },
+    /// Could never fit, e.g. the instance wants more memory than the whole budget
+    Rejected { reason: String },
+    AlreadyRunning,
+}
+
+lazy_static::lazy_static! {
+    static ref QUEUE: Mutex<VecDeque<QueuedLaunch>> = Mutex::new(VecDeque::new());
+    /// Launches handed off but not yet in RUNNING_PROCESSES, so they count against the limits
+    static ref STARTING: Mutex<Vec<(String, u32)>> = Mutex::new(Vec::new());
+}
+
+pub struct LaunchQueue;
+
+impl LaunchQueue {
+    /// Start the instance now if the limits allow it, otherwise queue it until a game exits
+    pub fn request(instance_name: &str, app_handle: &tauri::AppHandle) -> Result<LaunchRequestOutcome, Box<dyn std::error::Error>> {
+        let settings = SettingsManager::load()?;
+        let memory_mb = instance_memory(instance_name)?;
+
+        if is_active(instance_name) {
+            return Ok(LaunchRequestOutcome::AlreadyRunning);
+        }
+
+        if let Some(budget) = settings.memory_budget_mb {
+            if memory_mb > budget {
+                return Ok(LaunchRequestOutcome::Rejected {
+                    reason: format!(
+                        "'{}' needs {} MB but the memory budget is {} MB",
+                        instance_name, memory_mb, budget
+                    ),
+                });
+            }
+        }
+
+        let outcome = {
+            let mut queue = QUEUE.lock().unwrap();
+            if let Some(position) = queue.iter().position(|q| q.instance == instance_name) {
+                LaunchRequestOutcome::Queued { position: position + 1 }
+            } else if queue.is_empty() && fits(memory_mb) {
+                STARTING.lock().unwrap().push((instance_name.to_string(), memory_mb));
+                LaunchRequestOutcome::Started
+            } else {
+                queue.push_back(QueuedLaunch {
+                    instance: instance_name.to_string(),
+                    memory_mb,
+                    queued_at: Utc::now().to_rfc3339(),
+                });
+                LaunchRequestOutcome::Queued { position: queue.len() }
+            }
+        };
+
+        match outcome {
+            LaunchRequestOutcome::Started => start(instance_name.to_string(), app_handle.clone()),
+            LaunchRequestOutcome::Queued { position } => {
+                println!("Queued launch of '{}' (position {})", instance_name, position)
+            }
+            _ => {}
+        }
+
+        Self::notify(app_handle);
+        Ok(outcome)
+    }
+
+    pub fn cancel(instance_name: &str, app_handle: &tauri::AppHandle) -> bool {
+        let removed = {
+            let mut queue = QUEUE.lock().unwrap();
+            let before = queue.len();
+            queue.retain(|q| q.instance != instance_name);
+            queue.len() != before
+        };
+
+        if removed {
+            Self::notify(app_handle);
+        }
+        removed
+    }
+
+    /// Start queued launches, in order, for as long as they fit. Called whenever a game exits.
+    pub fn process(app_handle: &tauri::AppHandle) {
+        loop {
+            let next = {
+                let mut queue = QUEUE.lock().unwrap();
+                match queue.front() {
+                    // Strict order, a big instance at the front isn't skipped for smaller ones behind it
+                    Some(front) if fits(front.memory_mb) => {
+                        let next = queue.pop_front().unwrap();
+                        STARTING.lock().unwrap().push((next.instance.clone(), next.memory_mb));
+                        next
+                    }
+                    _ => break,
+                }
+            };
+
+            println!("Starting queued launch of '{}'", next.instance);
+            start(next.instance, app_handle.clone());
+        }
+
+        Self::notify(app_handle);
+    }
+
+    pub fn state() -> LaunchQueueState {
+        let settings = SettingsManager::load().unwrap_or_default();
+        let active = active_launches();
+
+        LaunchQueueState {
+            memory_in_use_mb: active.iter().map(|a| a.memory_mb).sum(),
+            active,
+            queued: QUEUE.lock().unwrap().iter().cloned().collect(),
+            max_concurrent: settings.max_concurrent_instances,
+            memory_budget_mb: settings.memory_budget_mb,
+        }
+    }
+
+    /// Send the current state as `launch-queue-updated`
+    pub fn notify(app_handle: &tauri::AppHandle) {
+        let _ = app_handle.emit("launch-queue-updated", Self::state());
+    }
+}
+
+/// Launch with the active account, freeing the slot again if it fails
+fn start(instance_name: String, app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let result =
+            crate::commands::instances::launch_instance_with_active_account(instance_name.clone(), app_handle.clone())
+                .await;
+
+        STARTING.lock().unwrap().retain(|(name, _)| name != &instance_name);
+
+        if let Err(e) = result {
+            println!("✗ Queued launch of '{}' failed: {}", instance_name, e);
+            let _ = app_handle.emit("launch-error", e);
+            // The slot is free again, let the next one have it
+            LaunchQueue::process(&app_handle);
+        } else {
+            LaunchQueue::notify(&app_handle);
+        }
+    });
+}
+
+fn is_active(instance_name: &str) -> bool {
+    active_launches().iter().any(|a| a.instance == instance_name)
+}
+
+/// Running games plus the ones still starting
+fn active_launches() -> Vec<ActiveLaunch> {
+    let running: Vec<String> = crate::commands::instances::RUNNING_PROCESSES
+        .lock()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect();
+
+    let mut active: Vec<ActiveLaunch> = running
+        .into_iter()
+        .map(|instance| ActiveLaunch {
+            memory_mb: instance_memory(&instance).unwrap_or(0),
+            instance,
+            starting: false,
+        })
+        .collect();
+
+    for (instance, memory_mb) in STARTING.lock().unwrap().iter() {
+        if !active.iter().any(|a| &a.instance == instance) {
+            active.push(ActiveLaunch {
+                instance: instance.clone(),
+                memory_mb: *memory_mb,
+                starting: true,
+            });
+        }
+    }
+
+    active
+}
+
+fn fits(memory_mb: u32) -> bool {
+    let settings = SettingsManager::load().unwrap_or_default();
+    let active = active_launches();
+
+    let under_count = settings
+        .max_concurrent_instances
+        .is_none_or(|max| (active.len() as u32) < max);
+    let under_budget = settings
+        .memory_budget_mb
+        .is_none_or(|budget| active.iter().map(|a| a.memory_mb).sum::<u32>() + memory_mb <= budget);
+
+    under_count && under_budget
+}
+
+/// Max heap the instance launches with, the same settings the launch itself uses
+fn instance_memory(instance_name: &str) -> Result<u32, Box<dyn std::error::Error>> {
+    let instance = InstanceManager::load(instance_name)?;
+    Ok(match instance.settings_override {
+        Some(settings) => settings.memory_mb,
+        None => SettingsManager::load()?.memory_mb,
+    })
+}
@@ -0,0 +1,112 @@
+use crate::models::Instance;
+use crate::utils::links::{create_dir_link, is_link, relink_dir, remove_link};
+use crate::utils::{copy_dir_recursive, get_instance_dir, get_shared_dir};
+use std::{fs, path::Path};
+
+/// Game directories that can be shared between instances
+pub const SHAREABLE_DIRECTORIES: &[&str] = &["saves", "resourcepacks", "screenshots"];
+
+/// What "shared" means for the one-click isolation toggle
+pub const DEFAULT_SHARED_DIRECTORIES: &[&str] = &["resourcepacks", "saves"];
+
+pub struct LayoutManager;
+
+impl LayoutManager {
+    pub fn validate(directories: &[String]) -> Result<(), String> {
+        for dir in directories {
+            if !SHAREABLE_DIRECTORIES.contains(&dir.as_str()) {
+                return Err(format!(
+                    "'{}' cannot be shared. Shareable directories: {}",
+                    dir,
+                    SHAREABLE_DIRECTORIES.join(", ")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Bring the instance directory in line with its shared/isolated configuration
+    pub fn apply(instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        // Adopted instances keep their folders linked to the original game directory
+        if instance.linked_game_dir.is_some() {
+            return Ok(());
+        }
+
+        let instance_dir = get_instance_dir(&instance.name);
+        let shared_root = get_shared_dir();
+
+        for dir_name in SHAREABLE_DIRECTORIES {
+            let local_path = instance_dir.join(dir_name);
+            let shared_path = shared_root.join(dir_name);
+
+            if instance.shared_directories.iter().any(|d| d == dir_name) {
+                Self::share(&local_path, &shared_path, &instance.name)?;
+            } else {
+                Self::isolate(&local_path, &shared_path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn share(local: &Path, shared: &Path, instance_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(shared)?;
+
+        if is_link(local) {
+            relink_dir(shared, local)?;
+            return Ok(());
+        }
+
+        if local.exists() {
+            // Move the instance's own content into the shared folder before linking
+            merge_into(local, shared, instance_name)?;
+            fs::remove_dir_all(local)?;
+        }
+
+        println!("Linking {} -> {}", local.display(), shared.display());
+        create_dir_link(shared, local)?;
+        Ok(())
+    }
+
+    fn isolate(local: &Path, shared: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if is_link(local) {
+            remove_link(local)?;
+            fs::create_dir_all(local)?;
+
+            // Start the isolated folder with a private copy of the shared content
+            if shared.exists() {
+                copy_dir_recursive(shared, local)?;
+            }
+            println!("Isolated {}", local.display());
+        } else if !local.exists() {
+            fs::create_dir_all(local)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn merge_into(src: &Path, dst: &Path, conflict_suffix: &str) -> std::io::Result<()> {
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let mut dst_path = dst.join(&name);
+
+        // Never overwrite shared content, keep both copies instead
+        let mut attempt = 1;
+        while fs::symlink_metadata(&dst_path).is_ok() {
+            let renamed = if attempt == 1 {
+                format!("{} ({})", name, conflict_suffix)
+            } else {
+                format!("{} ({} {})", name, conflict_suffix, attempt)
+            };
+            dst_path = dst.join(renamed);
+            attempt += 1;
+        }
+
+        fs::rename(entry.path(), &dst_path)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::models::AssetIndexData;
+use crate::utils::{get_meta_dir, long_path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub struct LegacyAssets;
+
+impl LegacyAssets {
+    /// Lay out the assets of a `legacy` or `pre-1.6` index by name where those versions look for
+    /// them. Returns the folder to pass as the assets directory, None for modern indexes, which
+    /// read straight from assets/objects.
+    pub fn prepare(index_id: &str, instance_dir: &Path) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+        let assets_dir = get_meta_dir().join("assets");
+        let index_path = assets_dir.join("indexes").join(format!("{}.json", index_id));
+        let index: AssetIndexData = serde_json::from_str(&fs::read_to_string(&index_path)?)?;
+
+        let target_dir = if index.map_to_resources {
+            instance_dir.join("resources")
+        } else if index.is_virtual {
+            assets_dir.join("virtual").join(index_id)
+        } else {
+            return Ok(None);
+        };
+
+        let mut copied = 0;
+        for (name, object) in &index.objects {
+            let target = target_dir.join(name);
+            // The same size is enough, objects never change under a name within one index
+            if fs::metadata(long_path(&target)).is_ok_and(|m| m.len() == object.size) {
+                continue;
+            }
+
+            let source = assets_dir.join("objects").join(&object.hash[..2]).join(&object.hash);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(long_path(parent))?;
+            }
+            fs::copy(long_path(&source), long_path(&target))
+                .map_err(|e| format!("Asset {} is missing ({}), reinstall the version", name, e))?;
+            copied += 1;
+        }
+
+        if copied > 0 {
+            println!("✓ Copied {} {} assets into {}", copied, index_id, target_dir.display());
+        }
+        Ok(Some(target_dir))
+    }
+}
@@ -0,0 +1,239 @@
+use crate::models::*;
+use crate::utils::long_path;
+use chrono::Utc;
+use std::{fs, path::PathBuf, time::Duration};
+
+const LITELOADER_MANIFEST_URL: &str = "https://dl.liteloader.com/versions/versions.json";
+const LITELOADER_REPO_URL: &str = "https://dl.liteloader.com/versions/";
+const MOJANG_LIBRARIES_URL: &str = "https://libraries.minecraft.net/";
+/// LiteLoader is injected through LaunchWrapper's tweaker system
+pub const LAUNCHWRAPPER_MAIN_CLASS: &str = "net.minecraft.launchwrapper.Launch";
+
+pub struct LiteLoaderInstaller {
+    http_client: reqwest::Client,
+    launcher_dir: PathBuf,
+}
+
+impl LiteLoaderInstaller {
+    pub fn new(launcher_dir: PathBuf) -> Self {
+        let http_client = crate::utils::http::client_builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self {
+            http_client,
+            launcher_dir,
+        }
+    }
+
+    /// Version id of the installed profile, following the vanilla launcher's naming with the
+    /// LiteLoader build in place of the second Minecraft version, so builds don't overwrite each other
+    pub fn profile_id(minecraft_version: &str, loader_version: &str) -> String {
+        format!("{}-LiteLoader{}", minecraft_version, loader_version)
+    }
+
+    async fn get_manifest(&self) -> Result<LiteLoaderManifest, Box<dyn std::error::Error>> {
+        let response = self.http_client.get(LITELOADER_MANIFEST_URL).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch LiteLoader versions: HTTP {}", response.status()).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_loader_versions(
+        &self,
+        minecraft_version: &str,
+    ) -> Result<Vec<LiteLoaderVersion>, Box<dyn std::error::Error>> {
+        let manifest = self.get_manifest().await?;
+        let game_version = match manifest.versions.get(minecraft_version) {
+            Some(v) => v,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut versions = Vec::new();
+        for (artefacts, snapshot) in [(&game_version.artefacts, false), (&game_version.snapshots, true)] {
+            if let Some(artefacts) = artefacts {
+                for (key, build) in &artefacts.builds {
+                    // "latest" is an alias of one of the other entries
+                    if key == "latest" {
+                        continue;
+                    }
+                    versions.push(LiteLoaderVersion {
+                        version: build.version.clone(),
+                        minecraft_version: minecraft_version.to_string(),
+                        snapshot,
+                    });
+                }
+            }
+        }
+
+        // Releases first
+        versions.sort_by(|a, b| a.snapshot.cmp(&b.snapshot).then(b.version.cmp(&a.version)));
+        Ok(versions)
+    }
+
+    /// Newest release build for the Minecraft version, falling back to snapshots
+    pub async fn get_compatible_loader_for_minecraft(
+        &self,
+        minecraft_version: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let versions = self.get_loader_versions(minecraft_version).await?;
+        let unstable = crate::services::settings::SettingsManager::unstable_loaders_enabled();
+        versions
+            .iter()
+            .find(|v| unstable || !v.snapshot)
+            .or_else(|| versions.first())
+            .map(|v| v.version.clone())
+            .ok_or_else(|| format!("LiteLoader is not available for Minecraft {}", minecraft_version).into())
+    }
+
+    pub async fn install_liteloader(
+        &self,
+        minecraft_version: &str,
+        loader_version: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        println!("=== Installing LiteLoader {} for Minecraft {} ===", loader_version, minecraft_version);
+
+        let manifest = self.get_manifest().await?;
+        let game_version = manifest
+            .versions
+            .get(minecraft_version)
+            .ok_or_else(|| format!("LiteLoader is not available for Minecraft {}", minecraft_version))?;
+
+        let (artefacts, build) = [&game_version.artefacts, &game_version.snapshots]
+            .into_iter()
+            .flatten()
+            .find_map(|a| {
+                a.builds
+                    .iter()
+                    .find(|(key, b)| key.as_str() != "latest" && b.version == loader_version)
+                    .map(|(_, b)| (a, b))
+            })
+            .ok_or_else(|| format!("LiteLoader {} not found for Minecraft {}", loader_version, minecraft_version))?;
+
+        let repo_url = artefacts
+            .repo
+            .as_ref()
+            .or(game_version.repo.as_ref())
+            .map(|r| r.url.clone())
+            .unwrap_or_else(|| LITELOADER_REPO_URL.to_string());
+
+        let libraries_dir = self.launcher_dir.join("libraries");
+        fs::create_dir_all(&libraries_dir)?;
+
+        // The LiteLoader jar itself, stored under its maven coordinate so the launcher finds it
+        let liteloader_name = format!("com.mumfrey:liteloader:{}", build.version);
+        let liteloader_url = format!(
+            "{}/com/mumfrey/liteloader/{}/{}",
+            repo_url.trim_end_matches('/'),
+            build.version,
+            build.file
+        );
+        self.download_library(&libraries_dir, &liteloader_name, &liteloader_url).await?;
+
+        let mut profile_libraries = vec![FabricProfileLibrary {
+            name: liteloader_name,
+            url: repo_url.clone(),
+            downloads: None,
+        }];
+
+        // Build libraries take precedence over the stream-wide list
+        let mut seen = std::collections::HashSet::new();
+        for lib in build.libraries.iter().chain(artefacts.libraries.iter()) {
+            if !seen.insert(lib.name.clone()) {
+                continue;
+            }
+
+            let base_url = lib.url.clone().unwrap_or_else(|| MOJANG_LIBRARIES_URL.to_string());
+            let url = format!("{}/{}", base_url.trim_end_matches('/'), maven_path(&lib.name)?);
+            self.download_library(&libraries_dir, &lib.name, &url).await?;
+
+            profile_libraries.push(FabricProfileLibrary {
+                name: lib.name.clone(),
+                url: base_url,
+                downloads: None,
+            });
+        }
+
+        let profile_id = Self::profile_id(minecraft_version, &build.version);
+        let now = Utc::now().to_rfc3339();
+        let profile = FabricProfileJson {
+            id: profile_id.clone(),
+            inherits_from: minecraft_version.to_string(),
+            release_time: now.clone(),
+            time: now,
+            profile_type: "release".to_string(),
+            main_class: LAUNCHWRAPPER_MAIN_CLASS.to_string(),
+            arguments: Some(FabricArguments {
+                game: vec![
+                    serde_json::Value::String("--tweakClass".to_string()),
+                    serde_json::Value::String(build.tweak_class.clone()),
+                ],
+                jvm: Vec::new(),
+            }),
+            libraries: profile_libraries,
+        };
+
+        let versions_dir = self.launcher_dir.join("versions").join(&profile_id);
+        fs::create_dir_all(&versions_dir)?;
+
+        let profile_path = versions_dir.join(format!("{}.json", profile_id));
+        fs::write(&profile_path, serde_json::to_string_pretty(&profile)?)?;
+        println!("✓ Created profile at: {}", profile_path.display());
+
+        println!("=== LiteLoader Installation Complete ===");
+        Ok(profile_id)
+    }
+
+    async fn download_library(
+        &self,
+        libraries_dir: &std::path::Path,
+        name: &str,
+        url: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let lib_path = long_path(&libraries_dir.join(maven_path(name)?));
+
+        if lib_path.exists() {
+            println!("  → Already exists: {}", name);
+            return Ok(());
+        }
+
+        let response = self.http_client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to download {}: HTTP {}", url, response.status()).into());
+        }
+
+        if let Some(parent) = lib_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&lib_path, response.bytes().await?)?;
+
+        println!("  ✓ Downloaded: {}", name);
+        Ok(())
+    }
+}
+
+/// group:artifact:version[:classifier][@extension] -> group/path/artifact/version/artifact-version[-classifier].jar
+pub fn maven_path(name: &str) -> Result<String, String> {
+    let (coordinate, extension) = name.split_once('@').unwrap_or((name, "jar"));
+    let parts: Vec<&str> = coordinate.split(':').collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return Err(format!("Invalid library name: {}", name));
+    }
+
+    let (group, artifact, version) = (parts[0], parts[1], parts[2]);
+    let classifier = parts.get(3).map(|c| format!("-{}", c)).unwrap_or_default();
+    Ok(format!(
+        "{}/{}/{}/{}-{}{}.{}",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        artifact,
+        version,
+        classifier,
+        extension
+    ))
+}
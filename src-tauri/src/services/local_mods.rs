@@ -0,0 +1,200 @@
+use crate::commands::validation::sanitize_filename;
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_conflicts::{runs_on, version_matches};
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{fs, path::Path};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalModStatus {
+    Added,
+    /// The same file, or another version of the same mod, is already in mods/
+    AlreadyInstalled,
+    /// Not a jar, or a jar without mod metadata
+    Invalid,
+    /// Made for another loader or Minecraft version
+    Incompatible,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalModResult {
+    /// Path as it was dropped
+    pub source_path: String,
+    /// Name in mods/, None when nothing was copied
+    pub file_name: Option<String>,
+    pub mod_id: Option<String>,
+    pub status: LocalModStatus,
+    pub message: String,
+}
+
+/// A jar that's already in mods/, enabled or not
+struct ExistingMod {
+    file_name: String,
+    sha1: String,
+    mod_id: Option<String>,
+}
+
+pub struct LocalModImporter;
+
+impl LocalModImporter {
+    /// Copy dropped jars into mods/ after checking their metadata against the instance. Each path
+    /// gets its own result, one bad file doesn't stop the rest. `force` skips the Minecraft
+    /// version check, which is often stricter than the mod needs.
+    pub fn add(instance: &Instance, paths: &[String], force: bool) -> Result<Vec<LocalModResult>, Box<dyn std::error::Error>> {
+        let loader = instance
+            .loader
+            .as_deref()
+            .filter(|l| *l != "vanilla")
+            .ok_or_else(|| format!("'{}' has no mod loader, install one before adding mods", instance.name))?;
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+
+        let mods_dir = get_instance_dir(&instance.name).join("mods");
+        fs::create_dir_all(&mods_dir)?;
+        let mut existing = existing_mods(&mods_dir)?;
+
+        let mut results = Vec::new();
+        for source_path in paths {
+            let result = |file_name: Option<String>, mod_id: Option<String>, status, message: String| LocalModResult {
+                source_path: source_path.clone(),
+                file_name,
+                mod_id,
+                status,
+                message,
+            };
+
+            let path = Path::new(source_path);
+            let file_name = match path.file_name().map(|n| sanitize_filename(&n.to_string_lossy())) {
+                Some(Ok(name)) => name,
+                Some(Err(e)) => {
+                    results.push(result(None, None, LocalModStatus::Invalid, e));
+                    continue;
+                }
+                None => {
+                    results.push(result(None, None, LocalModStatus::Invalid, "Not a file".to_string()));
+                    continue;
+                }
+            };
+            let bytes = match fs::read(long_path(path)) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    results.push(result(None, None, LocalModStatus::Invalid, format!("Failed to read {}: {}", file_name, e)));
+                    continue;
+                }
+            };
+
+            let Some(metadata) = ModMetadataReader::read(path) else {
+                results.push(result(
+                    None,
+                    None,
+                    LocalModStatus::Invalid,
+                    format!("{} is not a mod, it has no fabric.mod.json, mods.toml or mcmod.info", file_name),
+                ));
+                continue;
+            };
+            let mod_id = Some(metadata.id.clone());
+
+            if !runs_on(&metadata.loader, loader, &game_version) {
+                results.push(result(
+                    None,
+                    mod_id,
+                    LocalModStatus::Incompatible,
+                    format!("{} is a {} mod, this instance uses {}", metadata.name, metadata.loader, loader),
+                ));
+                continue;
+            }
+            let minecraft_range = metadata
+                .depends
+                .iter()
+                .find(|d| d.id == "minecraft")
+                .and_then(|d| d.versions.as_deref());
+            if let Some(range) = minecraft_range {
+                if !force && version_matches(&metadata.loader, range, &game_version) == Some(false) {
+                    results.push(result(
+                        None,
+                        mod_id,
+                        LocalModStatus::Incompatible,
+                        format!("{} needs Minecraft {}, this instance has {}", metadata.name, range, game_version),
+                    ));
+                    continue;
+                }
+            }
+
+            let sha1 = sha1_hex(&bytes);
+            let duplicate = existing
+                .iter()
+                .find(|e| e.sha1 == sha1 || e.mod_id.as_deref() == Some(metadata.id.as_str()));
+            if let Some(duplicate) = duplicate {
+                let message = if duplicate.sha1 == sha1 {
+                    format!("{} is already installed as {}", metadata.name, duplicate.file_name)
+                } else {
+                    format!(
+                        "{} is already installed as {}, remove it first to use this version",
+                        metadata.name, duplicate.file_name
+                    )
+                };
+                results.push(result(None, mod_id, LocalModStatus::AlreadyInstalled, message));
+                continue;
+            }
+            if existing.iter().any(|e| e.file_name == file_name || e.file_name == format!("{}.disabled", file_name)) {
+                results.push(result(
+                    None,
+                    mod_id,
+                    LocalModStatus::AlreadyInstalled,
+                    format!("A different {} is already in the mods folder", file_name),
+                ));
+                continue;
+            }
+
+            if let Err(e) = fs::write(long_path(&mods_dir.join(&file_name)), &bytes) {
+                results.push(result(None, mod_id, LocalModStatus::Invalid, format!("Failed to copy {}: {}", file_name, e)));
+                continue;
+            }
+
+            existing.push(ExistingMod {
+                file_name: file_name.clone(),
+                sha1,
+                mod_id: mod_id.clone(),
+            });
+            let message = format!("Added {} {}", metadata.name, metadata.version);
+            results.push(result(Some(file_name), mod_id, LocalModStatus::Added, message));
+        }
+
+        println!(
+            "✓ Added {} of {} dropped mods to '{}'",
+            results.iter().filter(|r| r.status == LocalModStatus::Added).count(),
+            results.len(),
+            instance.name
+        );
+        Ok(results)
+    }
+}
+
+fn existing_mods(mods_dir: &Path) -> Result<Vec<ExistingMod>, Box<dyn std::error::Error>> {
+    let mut existing = Vec::new();
+    for entry in fs::read_dir(mods_dir)?.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !file_name.ends_with(".jar") && !file_name.ends_with(".jar.disabled") {
+            continue;
+        }
+
+        let Ok(bytes) = fs::read(long_path(&entry.path())) else {
+            continue;
+        };
+        existing.push(ExistingMod {
+            sha1: sha1_hex(&bytes),
+            mod_id: ModMetadataReader::read(&entry.path()).map(|m| m.id),
+            file_name,
+        });
+    }
+    Ok(existing)
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
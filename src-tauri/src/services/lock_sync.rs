@@ -0,0 +1,167 @@
+use crate::commands::validation::validate_curseforge_url;
+use crate::models::{Instance, MrpackFile};
+use crate::services::managed::MANAGED_SOURCE;
+use crate::services::mod_lock::{sha512_hex, LockedFile, ModLockfile};
+use crate::services::mod_updates::MOD_BACKUPS_DIR;
+use crate::services::mrpack::MrpackReader;
+use crate::services::pack_validator::is_safe_pack_path;
+use crate::utils::{get_instance_dir, long_path};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashMap, fs, path::Path};
+
+/// Folders the lockfile describes completely
+const CONTENT_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LockfileSyncResult {
+    /// Files downloaded because they were missing or didn't match their hashes
+    pub downloaded: Vec<String>,
+    pub unchanged: usize,
+    /// Files not in the lockfile that were moved into mod-backups/
+    pub removed: Vec<String>,
+    pub failed: Vec<String>,
+    pub backup_dir: Option<String>,
+}
+
+pub struct LockfileSync;
+
+impl LockfileSync {
+    /// Make the instance's mods, resource packs and shaders match `lockfile`, which becomes the
+    /// instance's lockfile. With `prune`, files the lockfile doesn't list are moved into
+    /// mod-backups/ so the result is exactly what the lockfile describes.
+    pub async fn sync(
+        instance: &Instance,
+        lockfile: ModLockfile,
+        prune: bool,
+    ) -> Result<LockfileSyncResult, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        if let Some(unsafe_file) = lockfile.files.iter().find(|f| !is_safe_pack_path(&f.path)) {
+            return Err(format!("Refusing unsafe path '{}' in the lockfile", unsafe_file.path).into());
+        }
+
+        let client = crate::utils::http::client_builder()
+            .timeout(std::time::Duration::from_secs(300))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+        let managed_host = instance
+            .managed_manifest_url
+            .as_deref()
+            .and_then(|u| url::Url::parse(u).ok())
+            .and_then(|u| u.host_str().map(|h| h.to_string()));
+
+        let mut result = LockfileSyncResult::default();
+        for locked in &lockfile.files {
+            if matches_lock(&instance_dir, locked) {
+                result.unchanged += 1;
+                continue;
+            }
+
+            // CurseForge and managed pack files live outside the .mrpack hosts
+            let trusted_host = match locked.source.as_deref() {
+                Some("curseforge") => locked
+                    .downloads
+                    .iter()
+                    .find_map(|u| validate_curseforge_url(u).ok())
+                    .and_then(|u| u.host_str().map(|h| h.to_string())),
+                Some(MANAGED_SOURCE) => managed_host.clone(),
+                _ => None,
+            };
+            if locked.downloads.is_empty() {
+                result.failed.push(format!(
+                    "{} has no download URL, its author only allows downloads from the website",
+                    locked.path
+                ));
+                continue;
+            }
+
+            let file = MrpackFile {
+                path: locked.path.clone(),
+                hashes: HashMap::from([("sha1".to_string(), locked.sha1.clone())]),
+                env: None,
+                downloads: locked.downloads.clone(),
+                file_size: 0,
+            };
+            if let Err(e) =
+                MrpackReader::download_file_trusting(&client, &file, &instance_dir, trusted_host.as_deref()).await
+            {
+                result.failed.push(e);
+                continue;
+            }
+
+            if !matches_lock(&instance_dir, locked) {
+                let _ = fs::remove_file(long_path(&instance_dir.join(&locked.path)));
+                result.failed.push(format!("SHA512 mismatch for {}", locked.path));
+                continue;
+            }
+            result.downloaded.push(locked.path.clone());
+        }
+
+        if prune {
+            let backup_dir = instance_dir
+                .join(MOD_BACKUPS_DIR)
+                .join(Local::now().format("%Y%m%d-%H%M%S").to_string());
+            for path in unlisted_files(&instance_dir, &lockfile) {
+                let backup = backup_dir.join(&path);
+                if let Some(parent) = backup.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                match fs::rename(long_path(&instance_dir.join(&path)), long_path(&backup)) {
+                    Ok(()) => result.removed.push(path),
+                    Err(e) => result.failed.push(format!("Failed to move {} aside: {}", path, e)),
+                }
+            }
+            if backup_dir.exists() {
+                result.backup_dir = Some(backup_dir.to_string_lossy().to_string());
+            }
+        }
+
+        lockfile.save(&instance_dir)?;
+
+        println!(
+            "✓ Synced '{}' from its lockfile: {} downloaded, {} unchanged, {} removed, {} failed",
+            instance.name,
+            result.downloaded.len(),
+            result.unchanged,
+            result.removed.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    }
+}
+
+/// The file exists and has the locked SHA1, and the locked SHA512 when there is one
+fn matches_lock(instance_dir: &Path, locked: &LockedFile) -> bool {
+    let Ok(bytes) = fs::read(long_path(&instance_dir.join(&locked.path))) else {
+        return false;
+    };
+
+    let mut hasher = Sha1::new();
+    hasher.update(&bytes);
+    if format!("{:x}", hasher.finalize()) != locked.sha1 {
+        return false;
+    }
+    locked.sha512.as_ref().map_or(true, |sha512| sha512_hex(&bytes) == *sha512)
+}
+
+/// Files in the content folders the lockfile doesn't mention, relative to the instance
+fn unlisted_files(instance_dir: &Path, lockfile: &ModLockfile) -> Vec<String> {
+    let mut unlisted = Vec::new();
+    for dir in CONTENT_DIRS {
+        let Ok(entries) = fs::read_dir(instance_dir.join(dir)) else {
+            continue;
+        };
+
+        // Folders hold configs and unpacked packs, only loose files are content
+        for entry in entries.flatten().filter(|e| e.path().is_file()) {
+            let path = format!("{}/{}", dir, entry.file_name().to_string_lossy());
+            if !lockfile.files.iter().any(|f| f.path == path) {
+                unlisted.push(path);
+            }
+        }
+    }
+
+    unlisted.sort();
+    unlisted
+}
@@ -0,0 +1,235 @@
+use crate::models::{Instance, MrpackIndex};
+use crate::services::instance::InstanceManager;
+use crate::services::mod_lock::{LockedFile, ModLockfile};
+use crate::services::mrpack::MrpackReader;
+use crate::services::pack_validator::is_safe_pack_path;
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{fs, path::Path};
+
+/// Lockfile source of files that belong to the managed pack, the only ones an update may remove
+pub const MANAGED_SOURCE: &str = "managed";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManagedFileChange {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Difference between the remote pack manifest and the instance's files
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManagedUpdate {
+    /// `versionId` of the remote manifest
+    pub remote_version: String,
+    pub installed_version: Option<String>,
+    pub to_download: Vec<ManagedFileChange>,
+    pub to_remove: Vec<String>,
+    pub unchanged: usize,
+    /// Things an update doesn't change, like a new Minecraft or loader version
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManagedUpdateResult {
+    pub version: String,
+    pub downloaded: usize,
+    pub removed: usize,
+    pub failed: Vec<String>,
+}
+
+pub struct ManagedInstanceManager;
+
+impl ManagedInstanceManager {
+    /// Only HTTPS, the manifest decides what ends up in the instance
+    pub fn validate_url(manifest_url: &str) -> Result<(), String> {
+        let parsed = url::Url::parse(manifest_url).map_err(|e| format!("Invalid URL: {}", e))?;
+        if parsed.scheme() != "https" {
+            return Err("Pack manifest URL must use HTTPS".to_string());
+        }
+        if parsed.host_str().is_none() {
+            return Err("Pack manifest URL has no host".to_string());
+        }
+        Ok(())
+    }
+
+    /// Fetch the instance's remote manifest, a modrinth.index.json, and compare it to local files
+    pub async fn check(instance: &Instance) -> Result<(MrpackIndex, ManagedUpdate), Box<dyn std::error::Error>> {
+        let manifest_url = instance
+            .managed_manifest_url
+            .as_deref()
+            .ok_or_else(|| format!("'{}' is not a managed instance", instance.name))?;
+
+        let index = fetch_manifest(manifest_url).await?;
+        let update = Self::diff(instance, &index)?;
+        Ok((index, update))
+    }
+
+    fn diff(instance: &Instance, index: &MrpackIndex) -> Result<ManagedUpdate, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let lockfile = ModLockfile::load(&instance_dir)?;
+        let files = MrpackReader::client_files(index);
+
+        let mut update = ManagedUpdate {
+            remote_version: index.version_id.clone(),
+            installed_version: instance.managed_version.clone(),
+            to_download: Vec::new(),
+            to_remove: Vec::new(),
+            unchanged: 0,
+            warnings: Vec::new(),
+        };
+
+        for file in &files {
+            if !is_safe_pack_path(&file.path) {
+                return Err(format!("Manifest contains unsafe path '{}'", file.path).into());
+            }
+            let expected = file.hashes.get("sha1").ok_or_else(|| format!("{} has no sha1 hash", file.path))?;
+
+            if file_sha1(&instance_dir.join(&file.path)).as_deref() == Some(expected.as_str()) {
+                update.unchanged += 1;
+            } else {
+                update.to_download.push(ManagedFileChange {
+                    path: file.path.clone(),
+                    size: file.file_size,
+                });
+            }
+        }
+
+        update.to_remove = lockfile
+            .files
+            .iter()
+            .filter(|f| f.source.as_deref() == Some(MANAGED_SOURCE))
+            .filter(|f| !files.iter().any(|remote| remote.path == f.path))
+            .filter(|f| instance_dir.join(&f.path).exists())
+            .map(|f| f.path.clone())
+            .collect();
+
+        if let Some(minecraft) = index.dependencies.get("minecraft") {
+            if *minecraft != InstanceManager::base_minecraft_version(&instance.version) {
+                update.warnings.push(format!(
+                    "The pack now targets Minecraft {}, recreate the instance to switch versions",
+                    minecraft
+                ));
+            }
+        }
+        if let Some(loader_version) = index.dependencies.get("fabric-loader") {
+            if instance.loader_version.as_ref() != Some(loader_version) {
+                update.warnings.push(format!("The pack now uses Fabric loader {}", loader_version));
+            }
+        }
+
+        Ok(update)
+    }
+
+    /// Download new and changed files and remove the ones the pack dropped. Files the player
+    /// added themselves are never touched.
+    pub async fn apply(instance: &mut Instance) -> Result<ManagedUpdateResult, Box<dyn std::error::Error>> {
+        let (index, update) = Self::check(instance).await?;
+        let instance_dir = get_instance_dir(&instance.name);
+        let trusted_host = instance
+            .managed_manifest_url
+            .as_deref()
+            .and_then(|u| url::Url::parse(u).ok())
+            .and_then(|u| u.host_str().map(|h| h.to_string()));
+
+        let client = crate::utils::http::client_builder()
+            .timeout(std::time::Duration::from_secs(300))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+
+        let mut lockfile = ModLockfile::load(&instance_dir)?;
+        let mut downloaded = 0;
+        let mut failed = Vec::new();
+
+        for file in MrpackReader::client_files(&index) {
+            let needed = update.to_download.iter().any(|c| c.path == file.path);
+            if needed {
+                if let Err(e) =
+                    MrpackReader::download_file_trusting(&client, file, &instance_dir, trusted_host.as_deref()).await
+                {
+                    failed.push(e);
+                    continue;
+                }
+                downloaded += 1;
+            }
+
+            lockfile.record(LockedFile {
+                path: file.path.clone(),
+                sha1: file.hashes.get("sha1").cloned().unwrap_or_default(),
+                sha512: file.hashes.get("sha512").cloned(),
+                downloads: file.downloads.clone(),
+                source: Some(MANAGED_SOURCE.to_string()),
+                project_id: None,
+                version_id: None,
+            });
+        }
+
+        let mut removed = 0;
+        for path in &update.to_remove {
+            match fs::remove_file(long_path(&instance_dir.join(path))) {
+                Ok(()) => {
+                    lockfile.files.retain(|f| &f.path != path);
+                    removed += 1;
+                }
+                Err(e) => failed.push(format!("Failed to remove {}: {}", path, e)),
+            }
+        }
+
+        lockfile.save(&instance_dir)?;
+
+        // A partial update is retried next time instead of being recorded as installed
+        if failed.is_empty() {
+            instance.managed_version = Some(index.version_id.clone());
+            InstanceManager::save(instance)?;
+        }
+
+        println!(
+            "✓ Updated managed instance '{}' to {}: {} downloaded, {} removed, {} failed",
+            instance.name,
+            index.version_id,
+            downloaded,
+            removed,
+            failed.len()
+        );
+
+        Ok(ManagedUpdateResult {
+            version: index.version_id,
+            downloaded,
+            removed,
+            failed,
+        })
+    }
+}
+
+async fn fetch_manifest(manifest_url: &str) -> Result<MrpackIndex, Box<dyn std::error::Error>> {
+    ManagedInstanceManager::validate_url(manifest_url)?;
+
+    let response = crate::utils::http::client_builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .user_agent("AtomicLauncher/2.4.0")
+        .build()?
+        .get(manifest_url)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(format!("Pack manifest returned HTTP {}", response.status()).into());
+    }
+
+    let index: MrpackIndex = response.json().await?;
+    if index.format_version != 1 || index.game != "minecraft" {
+        return Err(format!(
+            "Unsupported pack manifest (formatVersion {}, game '{}')",
+            index.format_version, index.game
+        )
+        .into());
+    }
+
+    Ok(index)
+}
+
+fn file_sha1(path: &Path) -> Option<String> {
+    let contents = fs::read(long_path(path)).ok()?;
+    let mut hasher = Sha1::new();
+    hasher.update(&contents);
+    Some(format!("{:x}", hasher.finalize()))
+}
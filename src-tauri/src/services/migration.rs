@@ -0,0 +1,480 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::settings::SettingsManager;
+use crate::utils::{copy_dir_recursive, get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// (launcher name, data folder name, config file name)
+const KNOWN_LAUNCHERS: &[(&str, &str, &str)] = &[
+    ("PrismLauncher", "PrismLauncher", "prismlauncher.cfg"),
+    ("PolyMC", "PolyMC", "polymc.cfg"),
+    ("MultiMC", "multimc", "multimc.cfg"),
+    ("MultiMC", "MultiMC", "multimc.cfg"),
+    ("ATLauncher", "ATLauncher", "configs/ATLauncher.json"),
+    ("Technic", ".technic", "settings.json"),
+    ("Technic", "technic", "settings.json"),
+];
+
+// Regenerated by us or meaningless outside the source launcher. "bin" holds Technic's own jars.
+const SKIPPED_GAME_DIRS: &[&str] = &["natives", ".fabric", ".cache", "instance.json", "bin", "disabledmods"];
+
+/// Files the game keeps at the root of its folder that carry a player's setup between launchers
+const GLOBAL_GAME_FILES: &[&str] = &["options.txt", "servers.dat"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MigrationSource {
+    pub launcher: String,
+    pub data_dir: String,
+    pub instances_dir: String,
+    pub instance_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportableInstance {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub minecraft_version: Option<String>,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    pub memory_mb: Option<u32>,
+    pub java_path: Option<String>,
+    /// Why the instance can't be imported, if it can't
+    pub unsupported_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MmcPack {
+    #[serde(default)]
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MmcComponent {
+    uid: String,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtlInstance {
+    id: Option<String>,
+    launcher: AtlLauncher,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtlLauncher {
+    name: Option<String>,
+    loader_version: Option<AtlLoaderVersion>,
+    maximum_memory: Option<u32>,
+    java_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtlLoaderVersion {
+    version: String,
+    r#type: String,
+}
+
+/// The parts of a Technic pack's bin/version.json we need
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TechnicVersion {
+    id: String,
+    inherits_from: Option<String>,
+    #[serde(default)]
+    libraries: Vec<TechnicLibrary>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TechnicLibrary {
+    name: String,
+}
+
+pub struct MigrationManager;
+
+impl MigrationManager {
+    /// Look for data directories of the supported launchers in their usual places
+    pub fn detect_sources() -> Vec<MigrationSource> {
+        let mut roots = Vec::new();
+        if let Some(data_dir) = dirs::data_dir() {
+            roots.push(data_dir);
+        }
+        if let Some(home) = dirs::home_dir() {
+            roots.push(home.join(".var/app/org.prismlauncher.PrismLauncher/data"));
+            roots.push(home.clone());
+        }
+
+        let mut sources: Vec<MigrationSource> = Vec::new();
+        for root in &roots {
+            for (launcher, folder, config) in KNOWN_LAUNCHERS {
+                let data_dir = root.join(folder);
+                if !data_dir.join(config).exists()
+                    && !data_dir.join("instances").exists()
+                    && !data_dir.join("modpacks").exists()
+                {
+                    continue;
+                }
+
+                let instances_dir = Self::resolve_instances_dir(&data_dir);
+                if !instances_dir.is_dir() || sources.iter().any(|s| Path::new(&s.instances_dir) == instances_dir) {
+                    continue;
+                }
+
+                sources.push(MigrationSource {
+                    launcher: launcher.to_string(),
+                    data_dir: data_dir.to_string_lossy().to_string(),
+                    instance_count: Self::instance_dirs(&instances_dir).len(),
+                    instances_dir: instances_dir.to_string_lossy().to_string(),
+                });
+            }
+        }
+
+        sources
+    }
+
+    /// The instances folder of a data directory, honouring a custom InstanceDir
+    pub fn resolve_instances_dir(data_dir: &Path) -> PathBuf {
+        for (_, _, config) in KNOWN_LAUNCHERS {
+            if let Ok(content) = fs::read_to_string(data_dir.join(config)) {
+                if let Some(dir) = parse_cfg(&content).get("InstanceDir").filter(|d| !d.is_empty()) {
+                    let dir = PathBuf::from(dir);
+                    return if dir.is_absolute() { dir } else { data_dir.join(dir) };
+                }
+            }
+        }
+
+        // Technic keeps installed packs in "modpacks"
+        if !data_dir.join("instances").is_dir() && data_dir.join("modpacks").is_dir() {
+            return data_dir.join("modpacks");
+        }
+
+        data_dir.join("instances")
+    }
+
+    pub fn list_instances(instances_dir: &Path) -> Result<Vec<ImportableInstance>, Box<dyn std::error::Error>> {
+        if !instances_dir.is_dir() {
+            return Err(format!("{} is not a directory", instances_dir.display()).into());
+        }
+
+        let mut instances: Vec<ImportableInstance> = Self::instance_dirs(instances_dir)
+            .iter()
+            .filter_map(|dir| match Self::read_instance(dir) {
+                Ok(instance) => Some(instance),
+                Err(e) => {
+                    println!("⚠ Skipping {}: {}", dir.display(), e);
+                    None
+                }
+            })
+            .collect();
+
+        instances.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(instances)
+    }
+
+    /// Read a single instance folder of any supported launcher
+    pub fn read_instance(instance_path: &Path) -> Result<ImportableInstance, Box<dyn std::error::Error>> {
+        let mut importable = if instance_path.join("instance.cfg").exists() {
+            Self::read_mmc_instance(instance_path)?
+        } else if instance_path.join("instance.json").exists() {
+            Self::read_atlauncher_instance(instance_path)?
+        } else if instance_path.join("bin").is_dir() {
+            Self::read_technic_instance(instance_path)?
+        } else {
+            return Err("Not an instance folder of a supported launcher".into());
+        };
+
+        if importable.minecraft_version.is_none() {
+            importable.unsupported_reason.get_or_insert_with(|| "Instance has no Minecraft version".to_string());
+        } else if let Some(loader) = importable.loader.as_deref() {
+            if !["fabric", "liteloader", "forge", "neoforge"].contains(&loader) && importable.unsupported_reason.is_none() {
+                importable.unsupported_reason = Some(format!("Instances using '{}' are not supported yet", loader));
+            }
+        }
+
+        Ok(importable)
+    }
+
+    /// instance.cfg and mmc-pack.json of MultiMC, PolyMC and Prism Launcher
+    fn read_mmc_instance(instance_path: &Path) -> Result<ImportableInstance, Box<dyn std::error::Error>> {
+        let cfg = parse_cfg(&fs::read_to_string(instance_path.join("instance.cfg"))?);
+        let id = folder_name(instance_path);
+
+        let mut importable = ImportableInstance {
+            name: cfg.get("name").cloned().unwrap_or_else(|| id.clone()),
+            id,
+            path: instance_path.to_string_lossy().to_string(),
+            minecraft_version: None,
+            loader: None,
+            loader_version: None,
+            memory_mb: None,
+            java_path: None,
+            unsupported_reason: None,
+        };
+
+        if cfg.get("OverrideMemory").map(|v| v == "true").unwrap_or(false) {
+            importable.memory_mb = cfg.get("MaxMemAlloc").and_then(|v| v.parse().ok());
+        }
+        if cfg.get("OverrideJavaLocation").map(|v| v == "true").unwrap_or(false) {
+            importable.java_path = cfg.get("JavaPath").filter(|p| !p.is_empty()).cloned();
+        }
+
+        let pack_path = instance_path.join("mmc-pack.json");
+        if !pack_path.exists() {
+            // Very old MultiMC instances keep the version in instance.cfg
+            importable.minecraft_version = cfg.get("IntendedVersion").cloned();
+            if importable.minecraft_version.is_none() {
+                importable.unsupported_reason = Some("Instance has no mmc-pack.json".to_string());
+            }
+            return Ok(importable);
+        }
+
+        let pack: MmcPack = serde_json::from_str(&fs::read_to_string(pack_path)?)?;
+        for component in &pack.components {
+            let loader = match component.uid.as_str() {
+                "net.minecraft" => {
+                    importable.minecraft_version = component.version.clone();
+                    continue;
+                }
+                "net.fabricmc.fabric-loader" => "fabric",
+                "com.mumfrey.liteloader" => "liteloader",
+                "org.quiltmc.quilt-loader" => "quilt",
+                "net.minecraftforge" => "forge",
+                "net.neoforged" => "neoforge",
+                _ => continue,
+            };
+
+            if importable.loader.is_some() {
+                importable.unsupported_reason = Some("Instance uses more than one mod loader".to_string());
+            }
+            importable.loader = Some(loader.to_string());
+            importable.loader_version = component.version.clone();
+        }
+
+        if importable.minecraft_version.is_none() {
+            importable.unsupported_reason = Some("Instance has no Minecraft component".to_string());
+        }
+
+        Ok(importable)
+    }
+
+    /// ATLauncher's instance.json, the game files live directly in the instance folder
+    fn read_atlauncher_instance(instance_path: &Path) -> Result<ImportableInstance, Box<dyn std::error::Error>> {
+        let json: AtlInstance = serde_json::from_str(&fs::read_to_string(instance_path.join("instance.json"))?)?;
+        let id = folder_name(instance_path);
+
+        let (loader, loader_version) = match json.launcher.loader_version {
+            Some(loader) => {
+                let name = match loader.r#type.as_str() {
+                    "Fabric" => "fabric",
+                    "LegacyFabric" => "legacyfabric",
+                    "Quilt" => "quilt",
+                    "Forge" => "forge",
+                    "NeoForge" => "neoforge",
+                    other => {
+                        println!("⚠ Unknown ATLauncher loader type '{}'", other);
+                        "unknown"
+                    }
+                };
+                (Some(name.to_string()), Some(loader.version))
+            }
+            None => (None, None),
+        };
+
+        Ok(ImportableInstance {
+            name: json.launcher.name.filter(|n| !n.is_empty()).unwrap_or_else(|| id.clone()),
+            id,
+            path: instance_path.to_string_lossy().to_string(),
+            minecraft_version: json.id,
+            loader,
+            loader_version,
+            memory_mb: json.launcher.maximum_memory,
+            java_path: json.launcher.java_path.filter(|p| !p.is_empty()),
+            unsupported_reason: None,
+        })
+    }
+
+    /// An installed Technic pack, its loader comes from the libraries in bin/version.json
+    fn read_technic_instance(instance_path: &Path) -> Result<ImportableInstance, Box<dyn std::error::Error>> {
+        let id = folder_name(instance_path);
+        let mut importable = ImportableInstance {
+            name: id.clone(),
+            id,
+            path: instance_path.to_string_lossy().to_string(),
+            minecraft_version: None,
+            loader: None,
+            loader_version: None,
+            memory_mb: None,
+            java_path: None,
+            unsupported_reason: None,
+        };
+
+        let version_path = instance_path.join("bin").join("version.json");
+        if !version_path.exists() {
+            // Older packs only ship a patched bin/modpack.jar, those are all Forge
+            importable.loader = Some("forge".to_string());
+            importable.unsupported_reason = Some("Pack has no bin/version.json".to_string());
+            return Ok(importable);
+        }
+
+        let version: TechnicVersion = serde_json::from_str(&fs::read_to_string(version_path)?)?;
+        importable.minecraft_version = version.inherits_from.or_else(|| {
+            // Without inheritsFrom the id is the plain Minecraft version
+            let plain = version.id.chars().all(|c| c.is_ascii_digit() || c == '.');
+            plain.then_some(version.id)
+        });
+
+        for library in &version.libraries {
+            let mut parts = library.name.split(':');
+            let (Some(group), Some(artifact)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+
+            let loader = match (group, artifact) {
+                ("net.fabricmc", "fabric-loader") => "fabric",
+                ("org.quiltmc", "quilt-loader") => "quilt",
+                ("net.minecraftforge", "forge" | "minecraftforge") => "forge",
+                ("net.neoforged", "neoforge" | "forge") => "neoforge",
+                ("com.mumfrey", "liteloader") => "liteloader",
+                _ => continue,
+            };
+
+            importable.loader = Some(loader.to_string());
+            importable.loader_version = parts.next().map(|v| v.to_string());
+            break;
+        }
+
+        Ok(importable)
+    }
+
+    /// Game folder of an instance. MultiMC-style instances use ".minecraft" or, in older versions,
+    /// "minecraft". ATLauncher and Technic keep the game files in the instance folder itself.
+    pub fn game_dir(instance_path: &Path) -> Option<PathBuf> {
+        if !instance_path.join("instance.cfg").exists() {
+            return instance_path.is_dir().then(|| instance_path.to_path_buf());
+        }
+
+        [".minecraft", "minecraft"]
+            .iter()
+            .map(|d| instance_path.join(d))
+            .find(|d| d.is_dir())
+    }
+
+    /// Copy game files into an already created instance and carry over its memory/Java overrides
+    pub fn migrate_into(
+        source: &ImportableInstance,
+        instance: &mut Instance,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let mut copied = 0;
+
+        if let Some(game_dir) = Self::game_dir(Path::new(&source.path)) {
+            for entry in fs::read_dir(long_path(&game_dir))? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().to_string();
+
+                if SKIPPED_GAME_DIRS.contains(&name.as_str()) {
+                    continue;
+                }
+
+                let file_type = entry.file_type()?;
+                let target = instance_dir.join(&name);
+                if file_type.is_dir() {
+                    copy_dir_recursive(&entry.path(), &target)?;
+                } else if file_type.is_file() {
+                    fs::copy(long_path(&entry.path()), long_path(&target))?;
+                } else {
+                    continue;
+                }
+                copied += 1;
+            }
+        }
+
+        if source.memory_mb.is_some() || source.java_path.is_some() {
+            let mut settings = SettingsManager::load().unwrap_or_default();
+            if let Some(memory_mb) = source.memory_mb {
+                settings.memory_mb = memory_mb;
+            }
+            if source.java_path.is_some() {
+                settings.java_path = source.java_path.clone();
+            }
+            instance.settings_override = Some(settings);
+        }
+
+        InstanceManager::save(instance)?;
+        Ok(copied)
+    }
+
+    /// The official launcher's game folder, shared by every profile that doesn't set its own
+    pub fn vanilla_game_dir() -> Option<PathBuf> {
+        let dir = if cfg!(target_os = "linux") {
+            dirs::home_dir()?.join(".minecraft")
+        } else if cfg!(target_os = "macos") {
+            dirs::data_dir()?.join("minecraft")
+        } else {
+            dirs::data_dir()?.join(".minecraft")
+        };
+
+        dir.is_dir().then_some(dir)
+    }
+
+    /// Fill in options.txt and servers.dat from the official launcher's folder where the instance
+    /// has none of its own. Returns the files copied.
+    pub fn copy_global_files(instance_dir: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let Some(game_dir) = Self::vanilla_game_dir() else {
+            return Ok(Vec::new());
+        };
+
+        let mut copied = Vec::new();
+        for name in GLOBAL_GAME_FILES {
+            let source = game_dir.join(name);
+            let target = instance_dir.join(name);
+            if !source.is_file() || target.exists() {
+                continue;
+            }
+
+            fs::copy(long_path(&source), long_path(&target))?;
+            copied.push(name.to_string());
+        }
+
+        Ok(copied)
+    }
+
+    fn instance_dirs(instances_dir: &Path) -> Vec<PathBuf> {
+        fs::read_dir(instances_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| {
+                        p.join("instance.cfg").exists()
+                            || p.join("instance.json").exists()
+                            || p.join("bin").join("version.json").exists()
+                            || p.join("bin").join("modpack.jar").exists()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// key=value lines of a MultiMC .cfg file, section headers are ignored
+fn parse_cfg(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter(|line| !line.starts_with('[') && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().trim_matches('"').to_string()))
+        .collect()
+}
@@ -1,14 +1,154 @@
 pub mod instance;
 pub mod fabric;
+pub mod liteloader;
+pub mod forge;
+pub mod optifine;
 pub mod installer;
 pub mod template;
 pub mod settings;
 pub mod accounts;
 pub mod friends;
+pub mod layout;
+pub mod antivirus;
+pub mod preflight;
+pub mod case_check;
+pub mod pack_validator;
+pub mod mrpack;
+pub mod language;
+pub mod export;
+pub mod jarmod;
+pub mod migration;
+pub mod gc_log;
+pub mod profiler;
+pub mod optimize;
+pub mod archival;
+pub mod mod_metadata;
+pub mod compare;
+pub mod mod_list;
+pub mod disk_usage;
+pub mod credits;
+pub mod snapshot;
+pub mod mod_config;
+pub mod mod_details;
+pub mod hooks;
+pub mod screenshots;
+pub mod version_compat;
+pub mod recordings;
+pub mod cleanup;
+pub mod recent;
+pub mod icons;
+pub mod shortcuts;
+pub mod share_code;
+pub mod big_picture;
+pub mod launch_queue;
+pub mod mod_lock;
+pub mod integrity;
+pub mod audio;
+pub mod pack_setup;
+pub mod managed;
+pub mod worlds;
+pub mod java;
+pub mod modrinth;
+pub mod curseforge;
+pub mod mod_updates;
+pub mod jvm_flags;
+pub mod mod_conflicts;
+pub mod trust_store;
+pub mod resource_packs;
+pub mod network_diagnostics;
+pub mod datapacks;
+pub mod pinning;
+pub mod lock_sync;
+pub mod auto_update;
+pub mod local_mods;
+pub mod attestation;
+pub mod legacy_assets;
+pub mod practice_maps;
+pub mod sync_launch;
+pub mod prewarm;
+pub mod pack_update;
+pub mod journal;
+pub mod mod_cache;
+pub mod orphans;
+pub mod adoption;
+pub mod auth;
+pub mod pack_library;
+pub mod modrinth_follows;
+pub mod credentials;
 
 pub use instance::*;
 pub use fabric::*;
+pub use liteloader::*;
+pub use forge::*;
+pub use optifine::*;
 pub use installer::*;
 pub use template::*;
 pub use settings::*;
-pub use accounts::*;
\ No newline at end of file
+pub use accounts::*;
+pub use layout::*;
+pub use antivirus::*;
+pub use preflight::*;
+pub use case_check::*;
+pub use pack_validator::*;
+pub use mrpack::*;
+pub use language::*;
+pub use export::*;
+pub use jarmod::*;
+pub use migration::*;
+pub use gc_log::*;
+pub use profiler::*;
+pub use optimize::*;
+pub use archival::*;
+pub use mod_metadata::*;
+pub use compare::*;
+pub use mod_list::*;
+pub use disk_usage::*;
+pub use credits::*;
+pub use snapshot::*;
+pub use mod_config::*;
+pub use mod_details::*;
+pub use hooks::*;
+pub use screenshots::*;
+pub use version_compat::*;
+pub use recordings::*;
+pub use cleanup::*;
+pub use recent::*;
+pub use icons::*;
+pub use shortcuts::*;
+pub use share_code::*;
+pub use big_picture::*;
+pub use launch_queue::*;
+pub use mod_lock::*;
+pub use integrity::*;
+pub use audio::*;
+pub use pack_setup::*;
+pub use managed::*;
+pub use worlds::*;
+pub use java::*;
+pub use modrinth::*;
+pub use curseforge::*;
+pub use mod_updates::*;
+pub use jvm_flags::*;
+pub use mod_conflicts::*;
+pub use trust_store::*;
+pub use resource_packs::*;
+pub use network_diagnostics::*;
+pub use datapacks::*;
+pub use pinning::*;
+pub use lock_sync::*;
+pub use auto_update::*;
+pub use local_mods::*;
+pub use attestation::*;
+pub use legacy_assets::*;
+pub use practice_maps::*;
+pub use sync_launch::*;
+pub use prewarm::*;
+pub use pack_update::*;
+pub use journal::*;
+pub use mod_cache::*;
+pub use orphans::*;
+pub use adoption::*;
+pub use auth::*;
+pub use pack_library::*;
+pub use modrinth_follows::*;
+pub use credentials::*;
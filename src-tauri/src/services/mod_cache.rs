@@ -0,0 +1,227 @@
+use crate::services::mod_lock::sha512_hex;
+use crate::utils::{get_instances_dir, get_launcher_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Instance folders whose files can come from the cache
+const CACHED_FOLDERS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModCacheStats {
+    pub entries: usize,
+    pub total_bytes: u64,
+    /// Files in instances that match a cache entry
+    pub instance_files: usize,
+    /// Space the instances would take without sharing the cached files
+    pub saved_bytes: u64,
+    /// Entries no instance uses anymore, removed by `prune`
+    pub unused_entries: usize,
+    pub unused_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModCachePruneResult {
+    pub removed_entries: usize,
+    pub freed_bytes: u64,
+}
+
+/// Content-addressed store of downloaded mods, resource packs and shaders, keyed by SHA-512.
+/// Instances get hard links to the entries, or copies where linking isn't possible.
+pub struct ModCache;
+
+impl ModCache {
+    pub fn dir() -> PathBuf {
+        get_launcher_dir().join("cache").join("mods")
+    }
+
+    /// Whether a file at this instance-relative path may be shared. Configs and other files get
+    /// edited in place, which would change the entry for every instance linking it.
+    pub fn covers(relative_path: &str) -> bool {
+        let relative_path = relative_path.replace('\\', "/");
+        match relative_path.split_once('/') {
+            Some((folder, file)) => CACHED_FOLDERS.contains(&folder) && !file.is_empty() && !file.contains('/'),
+            None => false,
+        }
+    }
+
+    /// Put the cached file with this hash at `destination`. False when it isn't cached, or the
+    /// entry no longer matches its hash because an instance changed the file in place.
+    pub fn restore(sha512: &str, destination: &Path) -> bool {
+        let Some(entry) = entry_path(sha512) else {
+            return false;
+        };
+        let Ok(bytes) = fs::read(long_path(&entry)) else {
+            return false;
+        };
+        if sha512_hex(&bytes) != sha512.to_lowercase() {
+            println!("⚠ Cached file {} is damaged, removing it", sha512);
+            let _ = fs::remove_file(long_path(&entry));
+            return false;
+        }
+
+        match link_or_copy(&entry, destination) {
+            Ok(()) => true,
+            Err(e) => {
+                println!("Warning: Failed to reuse cached file for {}: {}", destination.display(), e);
+                false
+            }
+        }
+    }
+
+    /// Move a freshly downloaded file into the cache and link it back in place
+    pub fn adopt(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = fs::read(long_path(path))?;
+        let sha512 = sha512_hex(&bytes);
+        let entry = Self::dir().join(&sha512);
+
+        if !entry.exists() {
+            fs::create_dir_all(Self::dir())?;
+            // Written under a temporary name so a crash never leaves a truncated entry
+            let temp = entry.with_extension("part");
+            fs::write(long_path(&temp), &bytes)?;
+            fs::rename(long_path(&temp), long_path(&entry))?;
+        }
+
+        link_or_copy(&entry, path)
+    }
+
+    /// Like `adopt`, but never fails the download it's called after
+    pub fn adopt_or_warn(path: &Path) {
+        if let Err(e) = Self::adopt(path) {
+            println!("Warning: Failed to add {} to the mod cache: {}", path.display(), e);
+        }
+    }
+
+    pub fn stats() -> ModCacheStats {
+        let entries = cache_entries();
+        let usage = instance_usage(&entries);
+
+        let mut stats = ModCacheStats {
+            entries: entries.len(),
+            total_bytes: entries.values().sum(),
+            ..Default::default()
+        };
+        for (hash, size) in &entries {
+            match usage.get(hash) {
+                Some(count) => {
+                    stats.instance_files += count;
+                    stats.saved_bytes += size * (*count as u64).saturating_sub(1);
+                }
+                None => {
+                    stats.unused_entries += 1;
+                    stats.unused_bytes += size;
+                }
+            }
+        }
+        stats
+    }
+
+    /// Remove entries no instance uses. Instances keep their files, links survive the entry.
+    pub fn prune() -> Result<ModCachePruneResult, Box<dyn std::error::Error>> {
+        let entries = cache_entries();
+        let usage = instance_usage(&entries);
+
+        let mut result = ModCachePruneResult {
+            removed_entries: 0,
+            freed_bytes: 0,
+        };
+        for (hash, size) in entries.iter().filter(|(hash, _)| !usage.contains_key(*hash)) {
+            fs::remove_file(long_path(&Self::dir().join(hash)))?;
+            result.removed_entries += 1;
+            result.freed_bytes += size;
+        }
+
+        // Leftovers of interrupted writes
+        if let Ok(dir) = fs::read_dir(Self::dir()) {
+            for entry in dir.flatten().filter(|e| e.path().extension().is_some_and(|ext| ext == "part")) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+
+        println!(
+            "✓ Pruned {} unused cache entries ({} bytes)",
+            result.removed_entries, result.freed_bytes
+        );
+        Ok(result)
+    }
+}
+
+fn entry_path(sha512: &str) -> Option<PathBuf> {
+    if sha512.len() != 128 || !sha512.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let path = ModCache::dir().join(sha512.to_lowercase());
+    path.is_file().then_some(path)
+}
+
+/// Replace `destination` with a hard link to the cache entry, copying when the two are on
+/// different drives or the filesystem doesn't support links
+pub(crate) fn link_or_copy(entry: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(long_path(parent))?;
+    }
+
+    // Placed beside the destination first, so a failure leaves the existing file alone
+    let temp = destination.with_extension("cache-link");
+    let _ = fs::remove_file(long_path(&temp));
+    if fs::hard_link(long_path(entry), long_path(&temp)).is_err() {
+        fs::copy(long_path(entry), long_path(&temp))?;
+    }
+    if let Err(e) = fs::rename(long_path(&temp), long_path(destination)) {
+        let _ = fs::remove_file(long_path(&temp));
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Hash and size of every cache entry
+fn cache_entries() -> HashMap<String, u64> {
+    let Ok(dir) = fs::read_dir(ModCache::dir()) else {
+        return HashMap::new();
+    };
+    dir.flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let metadata = entry.metadata().ok()?;
+            (name.len() == 128 && metadata.is_file()).then_some((name, metadata.len()))
+        })
+        .collect()
+}
+
+/// How many instance files match each cache entry. Only files with the size of some entry are
+/// hashed, so this stays cheap for instances full of unrelated jars.
+fn instance_usage(entries: &HashMap<String, u64>) -> HashMap<String, usize> {
+    let sizes: HashSet<u64> = entries.values().copied().collect();
+    let mut usage = HashMap::new();
+
+    let Ok(instances) = fs::read_dir(get_instances_dir()) else {
+        return usage;
+    };
+    for instance in instances.flatten() {
+        for folder in CACHED_FOLDERS {
+            let Ok(files) = fs::read_dir(instance.path().join(folder)) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let Ok(metadata) = file.metadata() else {
+                    continue;
+                };
+                if !metadata.is_file() || !sizes.contains(&metadata.len()) {
+                    continue;
+                }
+                let Ok(bytes) = fs::read(long_path(&file.path())) else {
+                    continue;
+                };
+                let hash = sha512_hex(&bytes);
+                if entries.contains_key(&hash) {
+                    *usage.entry(hash).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    usage
+}
@@ -0,0 +1,306 @@
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// Config files that don't follow the `config/<mod id>.*` convention, relative to the instance folder.
+/// Entries ending in `/` are folders whose files all belong to the mod.
+const KNOWN_CONFIGS: &[(&str, &[&str])] = &[
+    ("sodium", &["config/sodium-options.json", "config/sodium-mixins.properties"]),
+    ("embeddium", &["config/embeddium-options.json", "config/embeddium-mixins.properties"]),
+    ("iris", &["config/iris.properties", "config/iris-excluded.json"]),
+    ("oculus", &["config/oculus.properties"]),
+    ("optifine", &["optionsof.txt", "optionsshaders.txt"]),
+    ("xaerominimap", &["config/xaerominimap.txt", "config/xaerominimap-common.txt"]),
+    ("xaeroworldmap", &["config/xaeroworldmap.txt", "config/xaeroworldmap-common.txt"]),
+    ("journeymap", &["journeymap/config/"]),
+    ("jei", &["config/jei/"]),
+    ("rei", &["config/roughlyenoughitems/"]),
+    ("roughlyenoughitems", &["config/roughlyenoughitems/"]),
+    ("emi", &["config/emi.css"]),
+    ("ftbquests", &["config/ftbquests/"]),
+    ("voicechat", &["config/voicechat/"]),
+    ("distanthorizons", &["config/DistantHorizons.toml"]),
+    ("entityculling", &["config/entityculling.json"]),
+    ("fabric-api", &["config/fabric/"]),
+    ("modernfix", &["config/modernfix-mixins.properties"]),
+];
+
+const CONFIG_EXTENSIONS: &[&str] = &["json", "json5", "toml", "properties", "cfg", "conf", "txt", "yml", "yaml", "snbt"];
+
+/// Folders the config editor may read and write, relative to the instance folder
+const EDITABLE_ROOTS: &[&str] = &["config", "defaultconfigs"];
+/// Configs are small text files, anything bigger is data the editor shouldn't load
+const MAX_CONFIG_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModConfigFile {
+    /// Relative to the instance folder, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// "registry" for curated entries, "convention" for files named after the mod id
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigFileContent {
+    pub path: String,
+    pub content: String,
+    /// Milliseconds since the epoch, passed back to `write` to detect changes made meanwhile
+    pub modified: u64,
+}
+
+pub struct ModConfigLocator;
+
+impl ModConfigLocator {
+    pub fn find(instance_name: &str, mod_id: &str) -> Result<Vec<ModConfigFile>, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        if !instance_dir.exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let mod_id = mod_id.trim().to_lowercase();
+        let mut files: Vec<ModConfigFile> = Vec::new();
+        let push = |files: &mut Vec<ModConfigFile>, path: &Path, source: &str| {
+            let Ok(relative) = path.strip_prefix(&instance_dir) else {
+                return;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if files.iter().any(|f| f.path == relative) {
+                return;
+            }
+            if let Ok(metadata) = fs::metadata(long_path(path)) {
+                files.push(ModConfigFile {
+                    path: relative,
+                    size: metadata.len(),
+                    source: source.to_string(),
+                });
+            }
+        };
+
+        if let Some((_, paths)) = KNOWN_CONFIGS.iter().find(|(id, _)| *id == mod_id) {
+            for relative in *paths {
+                let path = instance_dir.join(relative.trim_end_matches('/'));
+                if relative.ends_with('/') {
+                    for file in walk_files(&path) {
+                        push(&mut files, &file, "registry");
+                    }
+                } else if path.is_file() {
+                    push(&mut files, &path, "registry");
+                }
+            }
+        }
+
+        // Mod ids use `_` where file names often use `-` and the other way around
+        let variants = [mod_id.clone(), mod_id.replace('_', "-"), mod_id.replace('-', "_")];
+
+        for root in ["config", "defaultconfigs"] {
+            let root_dir = instance_dir.join(root);
+            let Ok(entries) = fs::read_dir(&root_dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_lowercase();
+
+                if path.is_dir() {
+                    if variants.iter().any(|v| *v == name) {
+                        for file in walk_files(&path) {
+                            push(&mut files, &file, "convention");
+                        }
+                    }
+                    continue;
+                }
+
+                let Some((stem, extension)) = name.rsplit_once('.') else {
+                    continue;
+                };
+                if !CONFIG_EXTENSIONS.contains(&extension) {
+                    continue;
+                }
+
+                // <id>.toml, <id>-client.toml, <id>_common.json, ...
+                let matches = variants.iter().any(|v| {
+                    stem == v
+                        || stem
+                            .strip_prefix(v.as_str())
+                            .is_some_and(|rest| rest.starts_with('-') || rest.starts_with('_'))
+                });
+                if matches {
+                    push(&mut files, &path, "convention");
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+/// Reads and writes text configs under the instance's config folders
+pub struct ConfigFileEditor;
+
+impl ConfigFileEditor {
+    /// Every editable config file, paths relative to the instance folder
+    pub fn list(instance_name: &str) -> Result<Vec<ModConfigFile>, Box<dyn std::error::Error>> {
+        let instance_dir = existing_instance_dir(instance_name)?;
+        let mut files = Vec::new();
+
+        for root in EDITABLE_ROOTS {
+            for path in walk_files(&instance_dir.join(root)) {
+                let Ok(relative) = path.strip_prefix(&instance_dir) else {
+                    continue;
+                };
+                if !has_config_extension(&path) {
+                    continue;
+                }
+                let Ok(metadata) = fs::metadata(long_path(&path)) else {
+                    continue;
+                };
+                if metadata.len() > MAX_CONFIG_SIZE {
+                    continue;
+                }
+                files.push(ModConfigFile {
+                    path: relative.to_string_lossy().replace('\\', "/"),
+                    size: metadata.len(),
+                    source: root.to_string(),
+                });
+            }
+        }
+
+        Ok(files)
+    }
+
+    pub fn read(instance_name: &str, relative_path: &str) -> Result<ConfigFileContent, Box<dyn std::error::Error>> {
+        let path = resolve(instance_name, relative_path)?;
+        let metadata = fs::metadata(long_path(&path)).map_err(|_| format!("'{}' does not exist", relative_path))?;
+        if metadata.len() > MAX_CONFIG_SIZE {
+            return Err(format!("'{}' is larger than {} KB", relative_path, MAX_CONFIG_SIZE / 1024).into());
+        }
+
+        let content = String::from_utf8(fs::read(long_path(&path))?)
+            .map_err(|_| format!("'{}' is not a text file", relative_path))?;
+        Ok(ConfigFileContent {
+            path: relative_path.to_string(),
+            content,
+            modified: modified_millis(&path),
+        })
+    }
+
+    /// Replace or create a config file. With `expected_modified`, refuses to overwrite a file
+    /// that changed since it was read, e.g. by the game saving its settings.
+    pub fn write(
+        instance_name: &str,
+        relative_path: &str,
+        content: &str,
+        expected_modified: Option<u64>,
+    ) -> Result<ConfigFileContent, Box<dyn std::error::Error>> {
+        if content.len() as u64 > MAX_CONFIG_SIZE {
+            return Err(format!("Config files are limited to {} KB", MAX_CONFIG_SIZE / 1024).into());
+        }
+        let path = resolve(instance_name, relative_path)?;
+
+        if let Some(expected) = expected_modified {
+            if path.exists() && modified_millis(&path) != expected {
+                return Err(format!("'{}' was changed by something else, reload it first", relative_path).into());
+            }
+        }
+
+        // Catch a broken edit before the mod resets the whole file to defaults
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        if extension == "json" {
+            serde_json::from_str::<serde_json::Value>(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(long_path(parent))?;
+        }
+        let temp = path.with_extension(format!("{}.tmp", extension));
+        fs::write(long_path(&temp), content)?;
+        fs::rename(long_path(&temp), long_path(&path))?;
+
+        println!("✓ Saved config {} for '{}'", relative_path, instance_name);
+        Ok(ConfigFileContent {
+            path: relative_path.to_string(),
+            content: content.to_string(),
+            modified: modified_millis(&path),
+        })
+    }
+}
+
+fn existing_instance_dir(instance_name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let instance_dir = get_instance_dir(instance_name);
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", instance_name).into());
+    }
+    Ok(instance_dir)
+}
+
+/// Resolve a path like `config/sodium-options.json` inside one of the editable folders. Rejects
+/// `..`, absolute paths, other file types and links leading out of the folder.
+fn resolve(instance_name: &str, relative_path: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let instance_dir = existing_instance_dir(instance_name)?;
+    let relative = Path::new(relative_path);
+
+    let mut components = relative.components();
+    let root = match components.next() {
+        Some(Component::Normal(root)) => root.to_string_lossy().to_string(),
+        _ => return Err(format!("Invalid config path '{}'", relative_path).into()),
+    };
+    if !EDITABLE_ROOTS.contains(&root.as_str()) || !components.all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("'{}' is outside the config folders", relative_path).into());
+    }
+
+    let path = instance_dir.join(relative);
+    if !has_config_extension(&path) {
+        return Err(format!("'{}' is not a config file", relative_path).into());
+    }
+
+    // The deepest existing ancestor must still be inside the root once links are followed
+    let root_dir = instance_dir.join(&root);
+    if root_dir.exists() {
+        let canonical_root = fs::canonicalize(&root_dir)?;
+        let existing = path.ancestors().find(|p| p.exists()).unwrap_or(&root_dir);
+        if !fs::canonicalize(existing)?.starts_with(&canonical_root) {
+            return Err(format!("'{}' is outside the config folders", relative_path).into());
+        }
+    }
+
+    Ok(path)
+}
+
+fn has_config_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| CONFIG_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+fn modified_millis(path: &Path) -> u64 {
+    fs::metadata(long_path(path))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn walk_files(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(long_path(dir)) else {
+        return files;
+    };
+
+    for entry in entries.flatten() {
+        match entry.file_type() {
+            Ok(t) if t.is_dir() => files.extend(walk_files(&entry.path())),
+            Ok(t) if t.is_file() => files.push(entry.path()),
+            _ => {}
+        }
+    }
+
+    files.sort();
+    files
+}
@@ -0,0 +1,392 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_metadata::{ModMetadata, ModMetadataReader};
+use crate::utils::get_instance_dir;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModConflictKind {
+    /// Two jars declare the same mod id
+    DuplicateId,
+    MissingDependency,
+    /// A required mod is installed, but not in a version it accepts
+    DependencyVersion,
+    /// Two installed mods declare each other incompatible
+    Incompatible,
+    /// Built for a different mod loader, or mods/ on an instance without one
+    WrongLoader,
+    GameVersionMismatch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictSeverity {
+    /// The loader refuses to start
+    Error,
+    /// Might run, might crash
+    Warning,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModConflict {
+    pub kind: ModConflictKind,
+    pub severity: ConflictSeverity,
+    pub mod_id: Option<String>,
+    /// File names in mods/
+    pub files: Vec<String>,
+    pub message: String,
+}
+
+struct InstalledMod {
+    filename: String,
+    metadata: ModMetadata,
+}
+
+pub struct ModConflictAnalyzer;
+
+impl ModConflictAnalyzer {
+    /// Check the enabled jars in mods/ against each other and the instance from their metadata,
+    /// so problems surface before the game crashes on them. Jars without metadata are skipped.
+    pub fn analyze(instance: &Instance) -> Result<Vec<ModConflict>, Box<dyn std::error::Error>> {
+        let mods_dir = get_instance_dir(&instance.name).join("mods");
+        let mut conflicts = Vec::new();
+        if !mods_dir.exists() {
+            return Ok(conflicts);
+        }
+
+        let mut mods = Vec::new();
+        for entry in fs::read_dir(&mods_dir)?.flatten() {
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if !filename.ends_with(".jar") {
+                continue;
+            }
+            if let Some(metadata) = ModMetadataReader::read(&entry.path()) {
+                mods.push(InstalledMod { filename, metadata });
+            }
+        }
+        mods.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let loader = instance.loader.as_deref().filter(|l| *l != "vanilla");
+        let Some(loader) = loader else {
+            if !mods.is_empty() {
+                conflicts.push(ModConflict {
+                    kind: ModConflictKind::WrongLoader,
+                    severity: ConflictSeverity::Warning,
+                    mod_id: None,
+                    files: mods.iter().map(|m| m.filename.clone()).collect(),
+                    message: format!("'{}' has no mod loader, the mods folder is ignored", instance.name),
+                });
+            }
+            return Ok(conflicts);
+        };
+
+        let mut by_id: BTreeMap<&str, Vec<&InstalledMod>> = BTreeMap::new();
+        for installed in &mods {
+            by_id.entry(installed.metadata.id.as_str()).or_default().push(installed);
+        }
+        for (id, copies) in by_id.iter().filter(|(_, copies)| copies.len() > 1) {
+            conflicts.push(ModConflict {
+                kind: ModConflictKind::DuplicateId,
+                severity: ConflictSeverity::Error,
+                mod_id: Some(id.to_string()),
+                files: copies.iter().map(|m| m.filename.clone()).collect(),
+                message: format!(
+                    "{} is installed {} times: {}",
+                    id,
+                    copies.len(),
+                    copies.iter().map(|m| m.filename.as_str()).collect::<Vec<_>>().join(", ")
+                ),
+            });
+        }
+
+        let environment = environment_versions(instance, loader, &game_version);
+        let provided: HashSet<&str> = mods
+            .iter()
+            .flat_map(|m| m.metadata.provides.iter().map(String::as_str))
+            .collect();
+
+        for installed in &mods {
+            let metadata = &installed.metadata;
+            if !runs_on(&metadata.loader, loader, &game_version) {
+                conflicts.push(ModConflict {
+                    kind: ModConflictKind::WrongLoader,
+                    severity: ConflictSeverity::Error,
+                    mod_id: Some(metadata.id.clone()),
+                    files: vec![installed.filename.clone()],
+                    message: format!("{} is a {} mod, this instance uses {}", metadata.name, metadata.loader, loader),
+                });
+                // Its dependencies are meaningless on this loader
+                continue;
+            }
+
+            for dependency in &metadata.depends {
+                if let Some(available) = environment.get(dependency.id.as_str()) {
+                    let (Some(range), Some(version)) = (&dependency.versions, available) else {
+                        continue;
+                    };
+                    if version_matches(&metadata.loader, range, version) == Some(false) {
+                        let (kind, severity) = if dependency.id == "minecraft" {
+                            (ModConflictKind::GameVersionMismatch, ConflictSeverity::Warning)
+                        } else {
+                            (ModConflictKind::DependencyVersion, ConflictSeverity::Error)
+                        };
+                        conflicts.push(ModConflict {
+                            kind,
+                            severity,
+                            mod_id: Some(metadata.id.clone()),
+                            files: vec![installed.filename.clone()],
+                            message: format!(
+                                "{} needs {} {}, this instance has {}",
+                                metadata.name, dependency.id, range, version
+                            ),
+                        });
+                    }
+                    continue;
+                }
+
+                match by_id.get(dependency.id.as_str()) {
+                    Some(copies) => {
+                        let Some(range) = &dependency.versions else {
+                            continue;
+                        };
+                        // Forge mod versions are too inconsistent to compare
+                        if !matches!(metadata.loader.as_str(), "fabric" | "quilt") {
+                            continue;
+                        }
+                        let dep = copies[0];
+                        if version_matches(&metadata.loader, range, &dep.metadata.version) == Some(false) {
+                            conflicts.push(ModConflict {
+                                kind: ModConflictKind::DependencyVersion,
+                                severity: ConflictSeverity::Error,
+                                mod_id: Some(metadata.id.clone()),
+                                files: vec![installed.filename.clone(), dep.filename.clone()],
+                                message: format!(
+                                    "{} needs {} {}, {} is installed",
+                                    metadata.name, dep.metadata.name, range, dep.metadata.version
+                                ),
+                            });
+                        }
+                    }
+                    None if provided.contains(dependency.id.as_str()) => {}
+                    None => conflicts.push(ModConflict {
+                        kind: ModConflictKind::MissingDependency,
+                        severity: ConflictSeverity::Error,
+                        mod_id: Some(metadata.id.clone()),
+                        files: vec![installed.filename.clone()],
+                        message: match &dependency.versions {
+                            Some(range) => format!(
+                                "{} needs {} {}, which isn't installed",
+                                metadata.name, dependency.id, range
+                            ),
+                            None => format!("{} needs {}, which isn't installed", metadata.name, dependency.id),
+                        },
+                    }),
+                }
+            }
+
+            for broken in &metadata.breaks {
+                let Some(copies) = by_id.get(broken.id.as_str()) else {
+                    continue;
+                };
+                let other = copies[0];
+                let applies = match &broken.versions {
+                    Some(range) => version_matches(&metadata.loader, range, &other.metadata.version) != Some(false),
+                    None => true,
+                };
+                if applies {
+                    conflicts.push(ModConflict {
+                        kind: ModConflictKind::Incompatible,
+                        severity: ConflictSeverity::Error,
+                        mod_id: Some(metadata.id.clone()),
+                        files: vec![installed.filename.clone(), other.filename.clone()],
+                        message: format!("{} doesn't work with {}", metadata.name, other.metadata.name),
+                    });
+                }
+            }
+        }
+
+        println!("✓ Analyzed {} mods in '{}': {} conflicts", mods.len(), instance.name, conflicts.len());
+        Ok(conflicts)
+    }
+}
+
+/// Ids the game and loader satisfy, with their version when it's known
+fn environment_versions<'a>(
+    instance: &'a Instance,
+    loader: &str,
+    game_version: &'a str,
+) -> HashMap<&'static str, Option<&'a str>> {
+    let loader_version = instance.loader_version.as_deref();
+    let mut environment: HashMap<&'static str, Option<&str>> = HashMap::new();
+    environment.insert("minecraft", Some(game_version));
+    environment.insert("java", None);
+
+    match loader {
+        "fabric" => {
+            environment.insert("fabricloader", loader_version);
+        }
+        "quilt" => {
+            environment.insert("quilt_loader", loader_version);
+            // Quilt reports its own version, not a Fabric one
+            environment.insert("fabricloader", None);
+        }
+        "forge" => {
+            environment.insert("forge", loader_version);
+        }
+        "neoforge" => {
+            environment.insert("neoforge", loader_version);
+            // 1.20.1 NeoForge still answers to Forge's id
+            environment.insert("forge", None);
+        }
+        _ => {}
+    }
+    environment
+}
+
+/// Whether the instance's loader reads jars built for `mod_loader`
+pub fn runs_on(mod_loader: &str, loader: &str, game_version: &str) -> bool {
+    let before = |release: &str| match (numeric_version(game_version), numeric_version(release)) {
+        (Some(a), Some(b)) => compare_versions(&a, &b) == Ordering::Less,
+        _ => false,
+    };
+
+    match loader {
+        "fabric" => mod_loader == "fabric",
+        "quilt" => matches!(mod_loader, "fabric" | "quilt"),
+        // Forge switched from mcmod.info to mods.toml in 1.13
+        "forge" => mod_loader == if before("1.13") { "legacy-forge" } else { "forge" },
+        // NeoForge still read mods.toml until 1.20.5
+        "neoforge" => mod_loader == "neoforge" || (mod_loader == "forge" && before("1.20.5")),
+        _ => true,
+    }
+}
+
+/// None when either side can't be parsed, so only certain mismatches are reported
+pub fn version_matches(mod_loader: &str, range: &str, version: &str) -> Option<bool> {
+    match mod_loader {
+        "fabric" | "quilt" => matches_predicates(range, version),
+        _ => matches_maven_range(range, version),
+    }
+}
+
+/// Fabric's `>=1.20 <1.21 || 1.21.x` syntax
+fn matches_predicates(predicates: &str, version: &str) -> Option<bool> {
+    let mut any = false;
+    for alternative in predicates.split("||") {
+        let mut all = true;
+        for predicate in alternative.split_whitespace() {
+            all &= matches_predicate(predicate, version)?;
+        }
+        any |= all;
+    }
+    Some(any)
+}
+
+fn matches_predicate(predicate: &str, version: &str) -> Option<bool> {
+    if predicate == "*" {
+        return Some(true);
+    }
+    let (op, target) = [">=", "<=", ">", "<", "=", "~", "^"]
+        .iter()
+        .find_map(|op| predicate.strip_prefix(op).map(|t| (*op, t)))
+        .unwrap_or(("", predicate));
+    let actual = numeric_version(version)?;
+
+    // 1.20.x
+    if target.contains(['x', 'X', '*']) {
+        let prefix: Vec<u64> = target
+            .split('.')
+            .take_while(|p| !matches!(*p, "x" | "X" | "*"))
+            .map(|p| p.parse().ok())
+            .collect::<Option<_>>()?;
+        return Some(actual.len() >= prefix.len() && actual[..prefix.len()] == prefix[..]);
+    }
+
+    let target = numeric_version(target)?;
+    let order = compare_versions(&actual, &target);
+    let component = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    Some(match op {
+        ">=" => order != Ordering::Less,
+        "<=" => order != Ordering::Greater,
+        ">" => order == Ordering::Greater,
+        "<" => order == Ordering::Less,
+        // Same major and minor
+        "~" => {
+            order != Ordering::Less
+                && component(&actual, 0) == component(&target, 0)
+                && component(&actual, 1) == component(&target, 1)
+        }
+        // Same major
+        "^" => order != Ordering::Less && component(&actual, 0) == component(&target, 0),
+        _ => order == Ordering::Equal,
+    })
+}
+
+/// Forge's Maven ranges, e.g. `[1.20,1.21)` or `[1.19.2],[1.20.1]`
+fn matches_maven_range(range: &str, version: &str) -> Option<bool> {
+    let range = range.trim();
+    // A bare version is only a recommendation in Maven
+    if !range.starts_with(['[', '(']) {
+        return Some(true);
+    }
+    let actual = numeric_version(version)?;
+
+    let mut any = false;
+    let mut rest = range;
+    while !rest.is_empty() {
+        if !rest.starts_with(['[', '(']) {
+            return None;
+        }
+        let end = rest.find([']', ')'])?;
+        any |= in_interval(&rest[..=end], &actual)?;
+        rest = rest[end + 1..].trim_start_matches([',', ' ']);
+    }
+    Some(any)
+}
+
+fn in_interval(interval: &str, actual: &[u64]) -> Option<bool> {
+    let inner = &interval[1..interval.len() - 1];
+    let Some((low, high)) = inner.split_once(',') else {
+        return Some(compare_versions(actual, &numeric_version(inner.trim())?) == Ordering::Equal);
+    };
+
+    let above_low = match low.trim() {
+        "" => true,
+        low => match compare_versions(actual, &numeric_version(low)?) {
+            Ordering::Greater => true,
+            Ordering::Equal => interval.starts_with('['),
+            Ordering::Less => false,
+        },
+    };
+    let below_high = match high.trim() {
+        "" => true,
+        high => match compare_versions(actual, &numeric_version(high)?) {
+            Ordering::Less => true,
+            Ordering::Equal => interval.ends_with(']'),
+            Ordering::Greater => false,
+        },
+    };
+    Some(above_low && below_high)
+}
+
+/// Release numbers of a version, `0.92.2+1.20.1` -> [0, 92, 2]. None for snapshots and other
+/// versions with letters.
+fn numeric_version(version: &str) -> Option<Vec<u64>> {
+    let release = version.trim().split(['-', '+']).next()?;
+    release.split('.').map(|p| p.parse().ok()).collect()
+}
+
+/// Missing trailing parts count as 0, so 1.20 equals 1.20.0
+fn compare_versions(a: &[u64], b: &[u64]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).copied().unwrap_or(0).cmp(&b.get(i).copied().unwrap_or(0)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
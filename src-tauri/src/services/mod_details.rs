@@ -0,0 +1,153 @@
+use crate::utils::get_meta_dir;
+use crate::utils::modrinth::ModrinthClient;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashMap, fs, path::Path, path::PathBuf};
+
+/// Cached details older than this are refetched
+const CACHE_TTL_HOURS: i64 = 24;
+
+/// Only the latest changelog entries are shown in the detail pane
+const CHANGELOG_ENTRIES: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangelogEntry {
+    pub version_number: String,
+    pub date_published: String,
+    pub changelog: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModDetails {
+    pub project_id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    /// Long-form markdown description
+    pub body: String,
+    pub icon_url: Option<String>,
+    pub gallery: Vec<String>,
+    pub changelog: Vec<ChangelogEntry>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+pub struct ModDetailsCache;
+
+impl ModDetailsCache {
+    fn get_cache_dir() -> PathBuf {
+        get_meta_dir().join("mod-details")
+    }
+
+    fn get_cache_path(project_id: &str) -> PathBuf {
+        Self::get_cache_dir().join(format!("{}.json", project_id))
+    }
+
+    /// sha1 of a jar -> Modrinth project id, so installed files resolve without a lookup
+    fn get_hash_index_path() -> PathBuf {
+        Self::get_cache_dir().join("hashes.json")
+    }
+
+    fn load_cached(project_id: &str) -> Option<ModDetails> {
+        let content = fs::read_to_string(Self::get_cache_path(project_id)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Details from the cache when fresh, otherwise from Modrinth. A stale cache entry is still
+    /// returned when Modrinth can't be reached.
+    pub async fn get(project_id: &str, refresh: bool) -> Result<ModDetails, Box<dyn std::error::Error>> {
+        let cached = Self::load_cached(project_id);
+
+        if let Some(details) = &cached {
+            let age = Utc::now() - details.fetched_at;
+            if !refresh && age.num_hours() < CACHE_TTL_HOURS {
+                return Ok(details.clone());
+            }
+        }
+
+        match Self::fetch(project_id).await {
+            Ok(details) => {
+                fs::create_dir_all(Self::get_cache_dir())?;
+                fs::write(
+                    Self::get_cache_path(&details.project_id),
+                    serde_json::to_string_pretty(&details)?,
+                )?;
+                Ok(details)
+            }
+            Err(e) => match cached {
+                Some(details) => {
+                    println!("⚠ Using cached details for {}: {}", project_id, e);
+                    Ok(details)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Details for an installed jar, identified by its hash. `None` when the file isn't on Modrinth.
+    pub async fn get_for_file(jar_path: &Path, refresh: bool) -> Result<Option<ModDetails>, Box<dyn std::error::Error>> {
+        let mut hasher = Sha1::new();
+        hasher.update(fs::read(jar_path)?);
+        let hash = format!("{:x}", hasher.finalize());
+
+        let mut index: HashMap<String, String> = fs::read_to_string(Self::get_hash_index_path())
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default();
+
+        let project_id = match index.get(&hash) {
+            Some(id) => id.clone(),
+            None => {
+                let versions = ModrinthClient::new()
+                    .get_versions_from_hashes(std::slice::from_ref(&hash))
+                    .await?;
+                let Some(version) = versions.get(&hash) else {
+                    return Ok(None);
+                };
+
+                index.insert(hash, version.project_id.clone());
+                fs::create_dir_all(Self::get_cache_dir())?;
+                fs::write(Self::get_hash_index_path(), serde_json::to_string(&index)?)?;
+                version.project_id.clone()
+            }
+        };
+
+        Self::get(&project_id, refresh).await.map(Some)
+    }
+
+    async fn fetch(project_id: &str) -> Result<ModDetails, Box<dyn std::error::Error>> {
+        let client = ModrinthClient::new();
+        let project = client.get_project(project_id).await?;
+        let versions = client.get_project_versions(&project.id, None, None).await?;
+
+        let changelog = versions
+            .into_iter()
+            .filter_map(|v| {
+                let changelog = v.changelog.filter(|c| !c.trim().is_empty())?;
+                Some(ChangelogEntry {
+                    version_number: v.version_number,
+                    date_published: v.date_published,
+                    changelog,
+                })
+            })
+            .take(CHANGELOG_ENTRIES)
+            .collect();
+
+        Ok(ModDetails {
+            project_id: project.id,
+            slug: project.slug,
+            title: project.title,
+            description: project.description,
+            body: project.body,
+            icon_url: project.icon_url,
+            gallery: project
+                .gallery
+                .unwrap_or_default()
+                .into_iter()
+                .map(|image| image.url)
+                .collect(),
+            changelog,
+            fetched_at: Utc::now(),
+        })
+    }
+}
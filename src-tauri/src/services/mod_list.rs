@@ -0,0 +1,206 @@
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::ModrinthClient;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashMap, fs};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModListFormat {
+    Markdown,
+    Csv,
+    Html,
+}
+
+impl ModListFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ModListFormat::Markdown => "md",
+            ModListFormat::Csv => "csv",
+            ModListFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModListEntry {
+    pub name: String,
+    pub version: Option<String>,
+    pub authors: Vec<String>,
+    pub url: Option<String>,
+    pub filename: String,
+    pub enabled: bool,
+}
+
+pub struct ModListExporter;
+
+impl ModListExporter {
+    /// Installed mods with metadata from the jars, linked to Modrinth where the file is known there
+    pub async fn collect(instance_name: &str) -> Result<Vec<ModListEntry>, Box<dyn std::error::Error>> {
+        let mods_dir = get_instance_dir(instance_name).join("mods");
+        let mut entries = Vec::new();
+        let mut hashes = Vec::new();
+
+        if !mods_dir.exists() {
+            return Ok(entries);
+        }
+
+        for entry in fs::read_dir(&mods_dir)? {
+            let entry = entry?;
+            let filename = entry.file_name().to_string_lossy().to_string();
+            let enabled = filename.ends_with(".jar");
+            if !enabled && !filename.ends_with(".jar.disabled") {
+                continue;
+            }
+
+            let metadata = ModMetadataReader::read(&entry.path());
+            let mut hasher = Sha1::new();
+            hasher.update(fs::read(entry.path())?);
+            hashes.push(format!("{:x}", hasher.finalize()));
+
+            entries.push(ModListEntry {
+                name: metadata.as_ref().map(|m| m.name.clone()).unwrap_or_else(|| filename.clone()),
+                version: metadata.as_ref().map(|m| m.version.clone()).filter(|v| !v.is_empty()),
+                authors: metadata.map(|m| m.authors).unwrap_or_default(),
+                url: None,
+                filename,
+                enabled,
+            });
+        }
+
+        // Links are a nice-to-have, an offline export still works
+        if let Err(e) = Self::add_modrinth_links(&mut entries, &hashes).await {
+            println!("⚠ Could not look up mod links on Modrinth: {}", e);
+        }
+
+        entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(entries)
+    }
+
+    async fn add_modrinth_links(
+        entries: &mut [ModListEntry],
+        hashes: &[String],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let client = ModrinthClient::new();
+        let versions = client.get_versions_from_hashes(hashes).await?;
+
+        let mut project_ids: Vec<String> = versions.values().map(|v| v.project_id.clone()).collect();
+        project_ids.sort();
+        project_ids.dedup();
+        if project_ids.is_empty() {
+            return Ok(());
+        }
+
+        let projects: HashMap<String, _> = client
+            .get_projects(&project_ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect();
+
+        for (entry, hash) in entries.iter_mut().zip(hashes) {
+            let Some(project) = versions.get(hash).and_then(|v| projects.get(&v.project_id)) else {
+                continue;
+            };
+
+            entry.url = Some(format!("https://modrinth.com/{}/{}", project.project_type, project.slug));
+            // Jar metadata often only has the file name as a fallback
+            if entry.name == entry.filename {
+                entry.name = project.title.clone();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn render(instance_name: &str, entries: &[ModListEntry], format: ModListFormat) -> String {
+        match format {
+            ModListFormat::Markdown => render_markdown(instance_name, entries),
+            ModListFormat::Csv => render_csv(entries),
+            ModListFormat::Html => render_html(instance_name, entries),
+        }
+    }
+}
+
+fn render_markdown(instance_name: &str, entries: &[ModListEntry]) -> String {
+    let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+
+    let mut out = format!("# {} - Mod List\n\n", cell(instance_name));
+    out.push_str("| Mod | Version | Authors |\n|---|---|---|\n");
+
+    for entry in entries {
+        let name = match &entry.url {
+            Some(url) => format!("[{}]({})", cell(&entry.name), url),
+            None => cell(&entry.name),
+        };
+        let disabled = if entry.enabled { "" } else { " (disabled)" };
+
+        out.push_str(&format!(
+            "| {}{} | {} | {} |\n",
+            name,
+            disabled,
+            cell(entry.version.as_deref().unwrap_or("-")),
+            cell(&entry.authors.join(", "))
+        ));
+    }
+
+    out.push_str(&format!("\n{} mods\n", entries.len()));
+    out
+}
+
+fn render_csv(entries: &[ModListEntry]) -> String {
+    fn field(s: &str) -> String {
+        if s.contains([',', '"', '\n']) {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        } else {
+            s.to_string()
+        }
+    }
+
+    let mut out = String::from("name,version,authors,url,filename,enabled\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            field(&entry.name),
+            field(entry.version.as_deref().unwrap_or("")),
+            field(&entry.authors.join("; ")),
+            field(entry.url.as_deref().unwrap_or("")),
+            field(&entry.filename),
+            entry.enabled
+        ));
+    }
+    out
+}
+
+fn render_html(instance_name: &str, entries: &[ModListEntry]) -> String {
+    fn escape(s: &str) -> String {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    let title = escape(instance_name);
+    let mut rows = String::new();
+    for entry in entries {
+        let name = match &entry.url {
+            Some(url) => format!("<a href=\"{}\">{}</a>", escape(url), escape(&entry.name)),
+            None => escape(&entry.name),
+        };
+        rows.push_str(&format!(
+            "      <tr{}><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            if entry.enabled { "" } else { " class=\"disabled\"" },
+            name,
+            escape(entry.version.as_deref().unwrap_or("-")),
+            escape(&entry.authors.join(", "))
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n  <meta charset=\"utf-8\">\n  <title>{title} - Mod List</title>\n  <style>\n    body {{ font-family: sans-serif; }}\n    table {{ border-collapse: collapse; }}\n    td, th {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}\n    .disabled {{ color: #999; }}\n  </style>\n</head>\n<body>\n  <h1>{title} - Mod List</h1>\n  <table>\n    <thead><tr><th>Mod</th><th>Version</th><th>Authors</th></tr></thead>\n    <tbody>\n{rows}    </tbody>\n  </table>\n  <p>{count} mods</p>\n</body>\n</html>\n",
+        title = title,
+        rows = rows,
+        count = entries.len()
+    )
+}
@@ -0,0 +1,129 @@
+use crate::models::MrpackFile;
+use crate::utils::long_path;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::{fs, path::Path};
+
+pub const LOCKFILE_NAME: &str = "atomic.lock";
+/// Name used before the lockfile covered resource packs and shaders, moved on the next save
+const LEGACY_LOCKFILE_NAME: &str = "mods.lock.json";
+
+/// A downloaded file the launcher knows the origin and expected hash of
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LockedFile {
+    /// Relative to the instance directory, e.g. `mods/sodium.jar`
+    pub path: String,
+    pub sha1: String,
+    /// Filled in from the file on disk when saving if the source didn't provide it
+    #[serde(default)]
+    pub sha512: Option<String>,
+    #[serde(default)]
+    pub downloads: Vec<String>,
+    /// Platform the file came from, "modrinth" or "curseforge" when known
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Modrinth version ID or CurseForge file ID
+    #[serde(default)]
+    pub version_id: Option<String>,
+}
+
+/// Per-instance record of installed mods, resource packs and shaders, kept as `atomic.lock`
+/// next to instance.json. Enough to rebuild the content on another machine.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModLockfile {
+    pub files: Vec<LockedFile>,
+}
+
+impl ModLockfile {
+    /// The instance's lockfile, empty if it has none yet
+    pub fn load(instance_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = [LOCKFILE_NAME, LEGACY_LOCKFILE_NAME]
+            .iter()
+            .map(|name| instance_dir.join(name))
+            .find(|p| p.exists());
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, instance_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut lockfile = self.clone();
+        for file in lockfile.files.iter_mut().filter(|f| f.sha512.is_none()) {
+            if let Ok(bytes) = fs::read(long_path(&instance_dir.join(&file.path))) {
+                file.sha512 = Some(sha512_hex(&bytes));
+            }
+        }
+
+        let json = serde_json::to_string_pretty(&lockfile)?;
+        fs::write(instance_dir.join(LOCKFILE_NAME), json)?;
+
+        let legacy = instance_dir.join(LEGACY_LOCKFILE_NAME);
+        if legacy.exists() {
+            let _ = fs::remove_file(legacy);
+        }
+        Ok(())
+    }
+
+    /// Add or replace the entry for `file.path`
+    pub fn record(&mut self, file: LockedFile) {
+        self.files.retain(|f| f.path != file.path);
+        self.files.push(file);
+        self.files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    /// Drop the entry for a file that was deleted, if there is one
+    pub fn forget(instance_dir: &Path, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut lockfile = Self::load(instance_dir)?;
+        let before = lockfile.files.len();
+        lockfile.files.retain(|f| f.path != path);
+
+        if lockfile.files.len() != before {
+            lockfile.save(instance_dir)?;
+        }
+        Ok(())
+    }
+
+    /// Follow a file that was renamed, e.g. disabled as `.jar.disabled`
+    pub fn moved(instance_dir: &Path, from: &str, to: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut lockfile = Self::load(instance_dir)?;
+        let Some(file) = lockfile.files.iter_mut().find(|f| f.path == from) else {
+            return Ok(());
+        };
+
+        file.path = to.to_string();
+        lockfile.save(instance_dir)
+    }
+
+    /// Record files that were just downloaded from a Modrinth pack index
+    pub fn record_mrpack_files(instance_dir: &Path, files: &[&MrpackFile]) -> Result<(), Box<dyn std::error::Error>> {
+        let mut lockfile = Self::load(instance_dir)?;
+
+        for file in files {
+            let Some(sha1) = file.hashes.get("sha1") else {
+                continue;
+            };
+
+            lockfile.record(LockedFile {
+                path: file.path.clone(),
+                sha1: sha1.clone(),
+                sha512: file.hashes.get("sha512").cloned(),
+                downloads: file.downloads.clone(),
+                source: Some("modrinth".to_string()),
+                project_id: None,
+                version_id: None,
+            });
+        }
+
+        lockfile.save(instance_dir)
+    }
+}
+
+pub fn sha512_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
@@ -0,0 +1,371 @@
+use crate::utils::long_path;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Cursor, Read, Seek},
+    path::Path,
+};
+use zip::ZipArchive;
+
+/// What a mod jar says about itself
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModMetadata {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub description: Option<String>,
+    /// SPDX id or free-form license text, as declared by the mod
+    #[serde(default)]
+    pub license: Option<String>,
+    /// fabric, quilt, forge, neoforge or legacy-forge
+    pub loader: String,
+    /// Required mods, including `minecraft` and the loader itself
+    #[serde(default)]
+    pub depends: Vec<ModDependency>,
+    /// Mods it refuses to run next to
+    #[serde(default)]
+    pub breaks: Vec<ModDependency>,
+    /// Extra ids it satisfies: declared aliases and the mods of jars bundled inside it
+    #[serde(default)]
+    pub provides: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModDependency {
+    pub id: String,
+    /// Version predicate in the loader's own syntax, None for any version
+    pub versions: Option<String>,
+}
+
+pub struct ModMetadataReader;
+
+impl ModMetadataReader {
+    /// Read fabric.mod.json, quilt.mod.json, META-INF/mods.toml or mcmod.info, whichever the jar has
+    pub fn read(jar_path: &Path) -> Option<ModMetadata> {
+        let archive = ZipArchive::new(fs::File::open(long_path(jar_path)).ok()?).ok()?;
+        read_archive(archive, true)
+    }
+}
+
+/// `bundled` also reads the jars nested inside, which only goes one level deep
+fn read_archive<R: Read + Seek>(mut archive: ZipArchive<R>, bundled: bool) -> Option<ModMetadata> {
+    let mut metadata = if let Some(content) = read_text(&mut archive, "fabric.mod.json") {
+        parse_fabric(&content)?
+    } else if let Some(content) = read_text(&mut archive, "quilt.mod.json") {
+        parse_quilt(&content)?
+    } else if let Some((content, loader)) = read_text(&mut archive, "META-INF/neoforge.mods.toml")
+        .map(|c| (c, "neoforge"))
+        .or_else(|| read_text(&mut archive, "META-INF/mods.toml").map(|c| (c, "forge")))
+    {
+        let manifest_version = read_text(&mut archive, "META-INF/MANIFEST.MF")
+            .and_then(|m| manifest_value(&m, "Implementation-Version"));
+        parse_mods_toml(&content, manifest_version, loader)?
+    } else {
+        parse_mcmod_info(&read_text(&mut archive, "mcmod.info")?)?
+    };
+
+    if bundled {
+        // Fabric's META-INF/jars and Forge's Jar-in-Jar folder
+        let nested: Vec<String> = archive
+            .file_names()
+            .filter(|n| (n.starts_with("META-INF/jars/") || n.starts_with("META-INF/jarjar/")) && n.ends_with(".jar"))
+            .map(String::from)
+            .collect();
+        for name in nested {
+            let mut bytes = Vec::new();
+            let Ok(mut entry) = archive.by_name(&name) else {
+                continue;
+            };
+            if entry.read_to_end(&mut bytes).is_err() {
+                continue;
+            }
+            let inner = ZipArchive::new(Cursor::new(bytes)).ok().and_then(|a| read_archive(a, false));
+            if let Some(inner) = inner {
+                metadata.provides.push(inner.id);
+                metadata.provides.extend(inner.provides);
+            }
+        }
+    }
+
+    Some(metadata)
+}
+
+fn read_text<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+/// Licenses are a string, a list of strings or (quilt) `{ "id": ... }` objects
+fn licenses(value: Option<&serde_json::Value>) -> Option<String> {
+    let value = value?;
+    let single = |v: &serde_json::Value| {
+        v.as_str()
+            .or_else(|| v.get("id").and_then(|i| i.as_str()))
+            .map(String::from)
+    };
+
+    let joined = match value.as_array() {
+        Some(list) => list.iter().filter_map(single).collect::<Vec<_>>().join(" AND "),
+        None => single(value)?,
+    };
+    Some(joined).filter(|l| !l.trim().is_empty())
+}
+
+/// Authors are either plain strings or `{ "name": ... }` objects
+fn people(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| p.as_str().or_else(|| p.get("name").and_then(|n| n.as_str())))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fabric maps ids to a version predicate or a list of alternatives
+fn fabric_dependencies(value: Option<&serde_json::Value>) -> Vec<ModDependency> {
+    let Some(map) = value.and_then(|v| v.as_object()) else {
+        return Vec::new();
+    };
+    map.iter()
+        .map(|(id, versions)| ModDependency {
+            id: id.clone(),
+            versions: version_predicates(versions),
+        })
+        .collect()
+}
+
+/// Quilt lists plain ids or `{ "id", "versions", "optional" }` objects
+fn quilt_dependencies(value: Option<&serde_json::Value>) -> Vec<ModDependency> {
+    let Some(list) = value.and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    list.iter()
+        .filter_map(|entry| match entry.as_str() {
+            Some(id) => Some(ModDependency {
+                id: id.to_string(),
+                versions: None,
+            }),
+            None if entry.get("optional").and_then(|o| o.as_bool()) == Some(true) => None,
+            None => Some(ModDependency {
+                id: entry.get("id")?.as_str()?.to_string(),
+                versions: entry.get("versions").and_then(version_predicates),
+            }),
+        })
+        .collect()
+}
+
+/// A list of alternatives becomes one predicate joined with `||`
+fn version_predicates(value: &serde_json::Value) -> Option<String> {
+    let joined = match value.as_array() {
+        Some(list) => list.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(" || "),
+        None => value.as_str()?.to_string(),
+    };
+    Some(joined).filter(|v| !v.trim().is_empty() && v.trim() != "*")
+}
+
+/// Plain ids or `{ "id": ... }` objects
+fn id_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| p.as_str().or_else(|| p.get("id").and_then(|i| i.as_str())))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_fabric(content: &str) -> Option<ModMetadata> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let id = json.get("id")?.as_str()?.to_string();
+
+    Some(ModMetadata {
+        name: json.get("name").and_then(|v| v.as_str()).unwrap_or(&id).to_string(),
+        version: json.get("version").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        authors: people(json.get("authors")),
+        description: json.get("description").and_then(|v| v.as_str()).map(String::from),
+        license: licenses(json.get("license")),
+        loader: "fabric".to_string(),
+        depends: fabric_dependencies(json.get("depends")),
+        breaks: fabric_dependencies(json.get("breaks")),
+        provides: id_list(json.get("provides")),
+        id,
+    })
+}
+
+fn parse_quilt(content: &str) -> Option<ModMetadata> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let loader = json.get("quilt_loader")?;
+    let metadata = loader.get("metadata");
+    let id = loader.get("id")?.as_str()?.to_string();
+
+    let authors = metadata
+        .and_then(|m| m.get("contributors"))
+        .and_then(|c| c.as_object())
+        .map(|c| c.keys().cloned().collect())
+        .unwrap_or_default();
+
+    Some(ModMetadata {
+        name: metadata.and_then(|m| m.get("name")).and_then(|v| v.as_str()).unwrap_or(&id).to_string(),
+        version: loader.get("version").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        authors,
+        description: metadata.and_then(|m| m.get("description")).and_then(|v| v.as_str()).map(String::from),
+        license: licenses(metadata.and_then(|m| m.get("license"))),
+        loader: "quilt".to_string(),
+        depends: quilt_dependencies(loader.get("depends")),
+        breaks: quilt_dependencies(loader.get("breaks")),
+        provides: id_list(loader.get("provides")),
+        id,
+    })
+}
+
+/// Only the first [[mods]] table is read; multi-mod jars are rare and the first entry is the main one
+fn parse_mods_toml(content: &str, manifest_version: Option<String>, loader: &str) -> Option<ModMetadata> {
+    let mut section = String::new();
+    let mut mods_tables = 0;
+    let mut license = None;
+    let mut values = HashMap::new();
+    // [[dependencies.<owner>]] tables in file order
+    let mut dependencies: Vec<(String, HashMap<String, String>)> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line.trim_matches(['[', ']']).trim().to_string();
+            if section == "mods" {
+                mods_tables += 1;
+            }
+            if let Some(owner) = section.strip_prefix("dependencies.") {
+                dependencies.push((owner.trim_matches('"').to_string(), HashMap::new()));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = value.trim();
+        // Multi-line strings are reduced to their first line
+        let value = value.trim_start_matches("'''").trim_start_matches("\"\"\"");
+        let value = value.split('#').next().unwrap_or(value).trim().trim_matches('"').trim_matches('\'');
+
+        if section == "mods" && mods_tables == 1 {
+            values.insert(key, value.to_string());
+        } else if section.starts_with("dependencies.") {
+            if let Some((_, table)) = dependencies.last_mut() {
+                table.insert(key, value.to_string());
+            }
+        } else if section.is_empty() && key == "license" {
+            // license is declared once for the whole file, above the [[mods]] tables
+            license = Some(value.to_string()).filter(|l| !l.is_empty());
+        }
+    }
+
+    let id = values.get("modId")?.clone();
+    let version = match values.get("version") {
+        Some(v) if v.contains("${") => manifest_version.unwrap_or_default(),
+        Some(v) => v.clone(),
+        None => String::new(),
+    };
+
+    let mut depends = Vec::new();
+    let mut breaks = Vec::new();
+    for (_, table) in dependencies.iter().filter(|(owner, _)| *owner == id) {
+        let Some(dep_id) = table.get("modId") else {
+            continue;
+        };
+        // Server-only dependencies aren't needed to start the client
+        if table.get("side").is_some_and(|s| s.eq_ignore_ascii_case("server")) {
+            continue;
+        }
+        let dependency = ModDependency {
+            id: dep_id.clone(),
+            versions: table.get("versionRange").filter(|v| !v.is_empty() && *v != "*").cloned(),
+        };
+        // Forge uses `mandatory`, NeoForge `type`
+        match table.get("type").map(|t| t.to_lowercase()).as_deref() {
+            Some("required") => depends.push(dependency),
+            Some("incompatible") => breaks.push(dependency),
+            Some(_) => {}
+            None if table.get("mandatory").map(String::as_str) == Some("true") => depends.push(dependency),
+            None => {}
+        }
+    }
+
+    Some(ModMetadata {
+        name: values.get("displayName").cloned().unwrap_or_else(|| id.clone()),
+        version,
+        authors: values
+            .get("authors")
+            .map(|a| a.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default(),
+        description: values.get("description").filter(|d| !d.is_empty()).cloned(),
+        license,
+        loader: loader.to_string(),
+        depends,
+        breaks,
+        provides: Vec::new(),
+        id,
+    })
+}
+
+fn parse_mcmod_info(content: &str) -> Option<ModMetadata> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    // Either a bare list or { "modList": [...] }
+    let first = json
+        .as_array()
+        .or_else(|| json.get("modList").and_then(|l| l.as_array()))?
+        .first()?
+        .clone();
+    let id = first.get("modid")?.as_str()?.to_string();
+
+    Some(ModMetadata {
+        name: first.get("name").and_then(|v| v.as_str()).unwrap_or(&id).to_string(),
+        version: first.get("version").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        authors: people(first.get("authorList").or_else(|| first.get("authors"))),
+        description: first.get("description").and_then(|v| v.as_str()).map(String::from),
+        // mcmod.info has no license field
+        license: None,
+        loader: "legacy-forge".to_string(),
+        // Entries look like `modid@[1.0,)`
+        depends: first
+            .get("requiredMods")
+            .and_then(|r| r.as_array())
+            .map(|list| {
+                list.iter()
+                    .filter_map(|r| r.as_str())
+                    .map(|r| match r.split_once('@') {
+                        Some((id, versions)) => ModDependency {
+                            id: id.to_string(),
+                            versions: Some(versions.to_string()),
+                        },
+                        None => ModDependency {
+                            id: r.to_string(),
+                            versions: None,
+                        },
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        breaks: Vec::new(),
+        provides: Vec::new(),
+        id,
+    })
+}
+
+fn manifest_value(manifest: &str, key: &str) -> Option<String> {
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .map(|v| v.trim().to_string())
+}
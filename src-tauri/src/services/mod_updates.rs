@@ -0,0 +1,265 @@
+use crate::models::Instance;
+use crate::services::curseforge::CurseForgeInstaller;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_lock::{LockedFile, ModLockfile};
+use crate::services::modrinth::ModrinthInstaller;
+use crate::utils::modrinth::ModrinthClient;
+use crate::utils::{get_instance_dir, long_path};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{fs, path::Path};
+
+/// Replaced jars are moved here, one folder per update run
+pub const MOD_BACKUPS_DIR: &str = "mod-backups";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModUpdate {
+    /// Lockfile path of the installed file, e.g. `mods/sodium.jar`
+    pub path: String,
+    pub source: String,
+    pub project_id: String,
+    pub current_version_id: Option<String>,
+    pub new_version_id: String,
+    /// Modrinth version number, or the file name on CurseForge
+    pub new_version: String,
+    pub new_filename: String,
+    /// None when a CurseForge author only allows downloads from their website
+    pub download_url: Option<String>,
+    pub sha1: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModUpdateResult {
+    pub updated: Vec<String>,
+    pub failed: Vec<String>,
+    /// Folder holding the replaced jars, None when nothing was replaced
+    pub backup_dir: Option<String>,
+}
+
+pub struct ModUpdater;
+
+impl ModUpdater {
+    /// Newer builds for the instance's game version and loader of every mod the lockfile knows
+    /// the origin of, following the instance's per-mod preferences. Files that belong to a
+    /// managed pack are left to the pack.
+    pub async fn check(instance: &Instance) -> Result<Vec<ModUpdate>, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let lockfile = ModLockfile::load(&instance_dir)?;
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let loaders = ModrinthInstaller::loaders_for(instance)?;
+        let loader_types = CurseForgeInstaller::loader_types(instance);
+
+        let modrinth = ModrinthClient::new();
+        let mut curseforge = None;
+        let mut updates = Vec::new();
+
+        for file in &lockfile.files {
+            let (Some(source), Some(project_id)) = (file.source.as_deref(), file.project_id.as_deref()) else {
+                continue;
+            };
+            if !file.path.starts_with("mods/") || !instance_dir.join(&file.path).exists() {
+                continue;
+            }
+            let preference = instance.mod_update_preferences.get(project_id).cloned().unwrap_or_default();
+            if preference.ignore {
+                continue;
+            }
+
+            let update = match source {
+                "modrinth" => {
+                    let version = match preference.channel {
+                        Some(channel) => {
+                            ModrinthInstaller::resolve_in_channel(&modrinth, project_id, &loaders, &game_version, channel)
+                                .await?
+                        }
+                        None => ModrinthInstaller::resolve(&modrinth, project_id, &loaders, &game_version).await?,
+                    };
+                    let Some(version) = version else {
+                        continue;
+                    };
+                    let Some(new_file) = version.files.iter().find(|f| f.primary).or_else(|| version.files.first())
+                    else {
+                        continue;
+                    };
+                    ModUpdate {
+                        path: file.path.clone(),
+                        source: source.to_string(),
+                        project_id: project_id.to_string(),
+                        current_version_id: file.version_id.clone(),
+                        new_version_id: version.id.clone(),
+                        new_version: version.version_number.clone(),
+                        new_filename: new_file.filename.clone(),
+                        download_url: Some(new_file.url.clone()),
+                        sha1: Some(new_file.hashes.sha1.clone()),
+                    }
+                }
+                "curseforge" => {
+                    let Ok(mod_id) = project_id.parse::<u32>() else {
+                        continue;
+                    };
+                    if curseforge.is_none() {
+                        curseforge = Some(CurseForgeInstaller::client()?);
+                    }
+                    let Some(client) = curseforge.as_ref() else {
+                        continue;
+                    };
+                    let new_file = CurseForgeInstaller::resolve_in_channel(
+                        client,
+                        mod_id,
+                        &game_version,
+                        &loader_types,
+                        preference.channel,
+                    )
+                    .await?;
+                    let Some(new_file) = new_file else {
+                        continue;
+                    };
+                    ModUpdate {
+                        path: file.path.clone(),
+                        source: source.to_string(),
+                        project_id: project_id.to_string(),
+                        current_version_id: file.version_id.clone(),
+                        new_version_id: new_file.id.to_string(),
+                        new_version: new_file.file_name.clone(),
+                        new_filename: new_file.file_name.clone(),
+                        download_url: new_file.download_url.clone(),
+                        sha1: new_file.sha1().map(|s| s.to_string()),
+                    }
+                }
+                _ => continue,
+            };
+
+            if update.current_version_id.as_deref() != Some(update.new_version_id.as_str())
+                && update.sha1.as_deref() != Some(file.sha1.as_str())
+            {
+                updates.push(update);
+            }
+        }
+
+        println!("✓ {} mod updates available for '{}'", updates.len(), instance.name);
+        Ok(updates)
+    }
+
+    /// Apply the updates for `project_ids`. Old jars are moved into mod-backups/ first and put
+    /// back if their replacement fails to download.
+    pub async fn apply(
+        instance: &Instance,
+        project_ids: &[String],
+    ) -> Result<ModUpdateResult, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let updates: Vec<ModUpdate> = Self::check(instance)
+            .await?
+            .into_iter()
+            .filter(|u| project_ids.contains(&u.project_id))
+            .collect();
+
+        let backup_dir = instance_dir
+            .join(MOD_BACKUPS_DIR)
+            .join(Local::now().format("%Y%m%d-%H%M%S").to_string());
+        let client = crate::utils::http::client_builder()
+            .timeout(std::time::Duration::from_secs(300))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+
+        let mut lockfile = ModLockfile::load(&instance_dir)?;
+        let mut result = ModUpdateResult::default();
+
+        for update in &updates {
+            let Some(url) = update.download_url.as_deref() else {
+                result.failed.push(format!(
+                    "{} has to be downloaded manually from CurseForge",
+                    update.new_filename
+                ));
+                continue;
+            };
+
+            match replace_file(&client, update, url, &instance_dir, &backup_dir).await {
+                Ok((new_path, sha1)) => {
+                    let old_path = update.path.clone();
+                    lockfile.files.retain(|f| f.path != old_path);
+                    lockfile.record(LockedFile {
+                        path: new_path,
+                        sha1,
+                        sha512: None,
+                        downloads: vec![url.to_string()],
+                        source: Some(update.source.clone()),
+                        project_id: Some(update.project_id.clone()),
+                        version_id: Some(update.new_version_id.clone()),
+                    });
+                    result.updated.push(update.new_filename.clone());
+                }
+                Err(e) => result.failed.push(e),
+            }
+        }
+
+        lockfile.save(&instance_dir)?;
+        if backup_dir.exists() {
+            result.backup_dir = Some(backup_dir.to_string_lossy().to_string());
+        }
+
+        println!(
+            "✓ Updated {} mods in '{}', {} failed",
+            result.updated.len(),
+            instance.name,
+            result.failed.len()
+        );
+        Ok(result)
+    }
+}
+
+/// Back up the installed jar, download its replacement and return the replacement's lockfile
+/// path and SHA1. A disabled mod stays disabled.
+async fn replace_file(
+    client: &reqwest::Client,
+    update: &ModUpdate,
+    url: &str,
+    instance_dir: &Path,
+    backup_dir: &Path,
+) -> Result<(String, String), String> {
+    let filename = crate::commands::validation::sanitize_filename(&update.new_filename)?;
+    let disabled = update.path.ends_with(".disabled");
+    let new_path = if disabled {
+        format!("mods/{}.disabled", filename)
+    } else {
+        format!("mods/{}", filename)
+    };
+
+    if update.source == "curseforge" {
+        crate::commands::validation::validate_curseforge_url(url)?;
+    }
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", filename, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", filename, response.status()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", filename, e))?;
+
+    let mut hasher = Sha1::new();
+    hasher.update(&bytes);
+    let sha1 = format!("{:x}", hasher.finalize());
+    if update.sha1.as_ref().is_some_and(|expected| *expected != sha1) {
+        return Err(format!("Hash mismatch for {}", filename));
+    }
+
+    let old_file = instance_dir.join(&update.path);
+    let backup = backup_dir.join(old_file.file_name().unwrap_or_default());
+    fs::create_dir_all(backup_dir).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    fs::rename(long_path(&old_file), long_path(&backup))
+        .map_err(|e| format!("Failed to back up {}: {}", update.path, e))?;
+
+    if let Err(e) = fs::write(long_path(&instance_dir.join(&new_path)), &bytes) {
+        let _ = fs::rename(long_path(&backup), long_path(&old_file));
+        return Err(format!("Failed to write {}: {}", filename, e));
+    }
+
+    println!("  ✓ {} -> {}", update.path, new_path);
+    Ok((new_path, sha1))
+}
@@ -0,0 +1,277 @@
+use crate::models::{Instance, ReleaseChannel};
+use crate::services::instance::InstanceManager;
+use crate::services::mod_cache::ModCache;
+use crate::services::mod_lock::{LockedFile, ModLockfile};
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::{ModrinthClient, ModrinthVersion};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashSet, fs, path::Path};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledModrinthFile {
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    pub filename: String,
+    /// Pulled in as a required dependency rather than picked by the player
+    pub dependency: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModrinthInstallResult {
+    pub installed: Vec<InstalledModrinthFile>,
+    /// Dependencies that were already installed
+    pub already_installed: Vec<String>,
+    /// Required dependencies left out because dependency installation was turned off
+    pub skipped_dependencies: Vec<String>,
+    /// Required dependencies with no build for this instance, or mods marked incompatible
+    pub warnings: Vec<String>,
+}
+
+pub struct ModrinthInstaller;
+
+impl ModrinthInstaller {
+    /// Modrinth loader ids whose mods run on the instance. Quilt also loads Fabric mods.
+    pub fn loaders_for(instance: &Instance) -> Result<Vec<String>, String> {
+        match instance.loader.as_deref() {
+            Some("quilt") => Ok(vec!["quilt".to_string(), "fabric".to_string()]),
+            Some(loader) if loader != "vanilla" => Ok(vec![loader.to_string()]),
+            _ => Err(format!("'{}' has no mod loader", instance.name)),
+        }
+    }
+
+    /// Install a project into mods/ together with its required dependencies, unless
+    /// `with_dependencies` is off. Without `version_id` the newest build for the instance's game
+    /// version and loader is used.
+    pub async fn install(
+        instance: &Instance,
+        project_id: &str,
+        version_id: Option<&str>,
+        with_dependencies: bool,
+    ) -> Result<ModrinthInstallResult, Box<dyn std::error::Error>> {
+        let loaders = Self::loaders_for(instance)?;
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let instance_dir = get_instance_dir(&instance.name);
+        let mods_dir = instance_dir.join("mods");
+        fs::create_dir_all(&mods_dir)?;
+
+        let client = ModrinthClient::new();
+        let mut lockfile = ModLockfile::load(&instance_dir)?;
+        let mut result = ModrinthInstallResult::default();
+
+        let requested = match version_id {
+            Some(id) => {
+                let version = client
+                    .get_versions(&[id.to_string()])
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| format!("Version '{}' not found", id))?;
+                if !is_compatible(&version, &loaders, &game_version) {
+                    return Err(format!(
+                        "{} {} doesn't support {} {}",
+                        project_id, version.version_number, loaders[0], game_version
+                    )
+                    .into());
+                }
+                version
+            }
+            None => Self::resolve(&client, project_id, &loaders, &game_version)
+                .await?
+                .ok_or_else(|| format!("{} has no build for {} {}", project_id, loaders[0], game_version))?,
+        };
+
+        let mut installed_projects: HashSet<String> =
+            lockfile.files.iter().filter_map(|f| f.project_id.clone()).collect();
+        installed_projects.extend(untracked_projects(&client, &instance_dir, &lockfile).await);
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut queue = vec![(requested, false)];
+
+        while let Some((version, dependency)) = queue.pop() {
+            if !seen.insert(version.project_id.clone()) {
+                continue;
+            }
+            // The requested project is reinstalled on purpose, e.g. to switch versions
+            if dependency && installed_projects.contains(&version.project_id) {
+                result.already_installed.push(version.project_id.clone());
+                continue;
+            }
+
+            for dep in &version.dependencies {
+                match dep.dependency_type.as_str() {
+                    "required" if !with_dependencies => {
+                        let name = dep.project_id.clone().or_else(|| dep.version_id.clone());
+                        if let Some(name) = name.filter(|n| !installed_projects.contains(n)) {
+                            result.skipped_dependencies.push(name);
+                        }
+                    }
+                    "required" => {
+                        let resolved = match (&dep.version_id, &dep.project_id) {
+                            (Some(id), _) => client.get_versions(&[id.clone()]).await?.into_iter().next(),
+                            (None, Some(project)) if !seen.contains(project) => {
+                                Self::resolve(&client, project, &loaders, &game_version).await?
+                            }
+                            _ => continue,
+                        };
+                        match resolved {
+                            Some(dep_version) => queue.push((dep_version, true)),
+                            None => result.warnings.push(format!(
+                                "{} requires {}, which has no build for {} {}",
+                                version.project_id,
+                                dep.project_id.as_deref().unwrap_or("an unknown project"),
+                                loaders[0],
+                                game_version
+                            )),
+                        }
+                    }
+                    "incompatible" => {
+                        if let Some(project) = dep.project_id.as_ref().filter(|p| installed_projects.contains(*p)) {
+                            result
+                                .warnings
+                                .push(format!("{} is incompatible with installed mod {}", version.project_id, project));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            let file = version
+                .files
+                .iter()
+                .find(|f| f.primary)
+                .or_else(|| version.files.first())
+                .ok_or_else(|| format!("{} {} has no files", version.project_id, version.version_number))?;
+            let filename = crate::commands::validation::sanitize_filename(&file.filename)?;
+            let destination = mods_dir.join(&filename);
+
+            if !ModCache::restore(&file.hashes.sha512, &destination) {
+                client.download_mod_file(&file.url, &destination).await?;
+                let contents = fs::read(crate::utils::long_path(&destination))?;
+                let mut hasher = Sha1::new();
+                hasher.update(&contents);
+                if format!("{:x}", hasher.finalize()) != file.hashes.sha1 {
+                    let _ = fs::remove_file(&destination);
+                    return Err(format!("Hash mismatch for {}", filename).into());
+                }
+                ModCache::adopt_or_warn(&destination);
+            }
+
+            // Replacing a version leaves the old jar behind under a different name
+            let path = format!("mods/{}", filename);
+            for old in lockfile
+                .files
+                .iter()
+                .filter(|f| f.project_id.as_deref() == Some(version.project_id.as_str()) && f.path != path)
+            {
+                let _ = fs::remove_file(instance_dir.join(&old.path));
+            }
+            lockfile
+                .files
+                .retain(|f| f.project_id.as_deref() != Some(version.project_id.as_str()));
+            lockfile.record(LockedFile {
+                path,
+                sha1: file.hashes.sha1.clone(),
+                sha512: Some(file.hashes.sha512.clone()),
+                downloads: vec![file.url.clone()],
+                source: Some("modrinth".to_string()),
+                project_id: Some(version.project_id.clone()),
+                version_id: Some(version.id.clone()),
+            });
+            // Saved per file so a failed dependency still leaves the others tracked
+            lockfile.save(&instance_dir)?;
+
+            println!("  ✓ Installed {}", filename);
+            result.installed.push(InstalledModrinthFile {
+                project_id: version.project_id.clone(),
+                version_id: version.id.clone(),
+                version_number: version.version_number.clone(),
+                filename,
+                dependency,
+            });
+        }
+
+        println!(
+            "✓ Installed {} from Modrinth into '{}': {} files ({} dependencies), {} warnings",
+            project_id,
+            instance.name,
+            result.installed.len(),
+            result.installed.iter().filter(|f| f.dependency).count(),
+            result.warnings.len()
+        );
+        Ok(result)
+    }
+
+    /// Newest compatible build of a project, None if it has none for this instance
+    pub async fn resolve(
+        client: &ModrinthClient,
+        project: &str,
+        loaders: &[String],
+        game_version: &str,
+    ) -> Result<Option<ModrinthVersion>, Box<dyn std::error::Error>> {
+        let versions = client
+            .get_project_versions(project, Some(loaders.to_vec()), Some(vec![game_version.to_string()]))
+            .await?;
+        Ok(pick_version(&versions).cloned())
+    }
+
+    /// Like `resolve`, but only builds at least as stable as `channel`, with no fallback
+    pub async fn resolve_in_channel(
+        client: &ModrinthClient,
+        project: &str,
+        loaders: &[String],
+        game_version: &str,
+        channel: ReleaseChannel,
+    ) -> Result<Option<ModrinthVersion>, Box<dyn std::error::Error>> {
+        let versions = client
+            .get_project_versions(project, Some(loaders.to_vec()), Some(vec![game_version.to_string()]))
+            .await?;
+        Ok(versions
+            .into_iter()
+            .find(|v| ReleaseChannel::from_modrinth(&v.version_type).is_some_and(|c| c <= channel)))
+    }
+}
+
+/// Projects of the jars in mods/ the lockfile doesn't track, e.g. copied in by hand or from a
+/// pack, so a dependency the player already has isn't downloaded a second time
+async fn untracked_projects(client: &ModrinthClient, instance_dir: &Path, lockfile: &ModLockfile) -> HashSet<String> {
+    let tracked: HashSet<&str> = lockfile.files.iter().map(|f| f.path.as_str()).collect();
+    let hashes: Vec<String> = fs::read_dir(instance_dir.join("mods"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().ends_with(".jar"))
+                .filter(|e| !tracked.contains(format!("mods/{}", e.file_name().to_string_lossy()).as_str()))
+                .filter_map(|e| fs::read(crate::utils::long_path(&e.path())).ok())
+                .map(|bytes| {
+                    let mut hasher = Sha1::new();
+                    hasher.update(&bytes);
+                    format!("{:x}", hasher.finalize())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if hashes.is_empty() {
+        return HashSet::new();
+    }
+
+    match client.get_versions_from_hashes(&hashes).await {
+        Ok(versions) => versions.into_values().map(|v| v.project_id).collect(),
+        Err(e) => {
+            println!("Warning: Could not identify untracked mods: {}", e);
+            HashSet::new()
+        }
+    }
+}
+
+/// Newest release, or the newest build of any type if there is no release
+pub fn pick_version(versions: &[ModrinthVersion]) -> Option<&ModrinthVersion> {
+    versions
+        .iter()
+        .find(|v| v.version_type == "release")
+        .or_else(|| versions.first())
+}
+
+fn is_compatible(version: &ModrinthVersion, loaders: &[String], game_version: &str) -> bool {
+    version.game_versions.iter().any(|v| v == game_version) && version.loaders.iter().any(|l| loaders.contains(l))
+}
@@ -0,0 +1,234 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_lock::ModLockfile;
+use crate::services::modrinth::ModrinthInstaller;
+use crate::services::resource_packs::ResourcePackManager;
+use crate::services::settings::SettingsManager;
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::{ModrinthClient, ModrinthCollection, ModrinthProjectDetails, ModrinthUser};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A new version of a followed project, from the account's Modrinth notifications
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FollowedUpdate {
+    /// Pass to `dismiss_updates` once seen
+    pub notification_id: String,
+    pub project_id: String,
+    pub project_title: String,
+    pub icon_url: Option<String>,
+    pub version_id: String,
+    pub version_number: Option<String>,
+    pub created: String,
+    /// Instances that have the project installed
+    pub instances: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CollectionInstallResult {
+    /// Titles of the projects installed
+    pub installed: Vec<String>,
+    /// Projects that can't go into an instance, such as modpacks, or have no build for it
+    pub skipped: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// The player's Modrinth account, linked with a personal access token
+pub struct ModrinthFollows;
+
+impl ModrinthFollows {
+    /// Check the token against Modrinth before keeping it
+    pub async fn link(token: &str) -> Result<ModrinthUser, Box<dyn std::error::Error>> {
+        let token = token.trim();
+        let user = ModrinthClient::new().get_authenticated_user(token).await?;
+
+        let mut settings = SettingsManager::load()?;
+        settings.modrinth_token = Some(token.to_string());
+        SettingsManager::save(&settings)?;
+
+        println!("✓ Linked Modrinth account {}", user.username);
+        Ok(user)
+    }
+
+    pub fn unlink() -> Result<(), Box<dyn std::error::Error>> {
+        let mut settings = SettingsManager::load()?;
+        settings.modrinth_token = None;
+        SettingsManager::save(&settings)
+    }
+
+    /// None when no account is linked
+    pub async fn account() -> Result<Option<ModrinthUser>, Box<dyn std::error::Error>> {
+        let Some(token) = token() else {
+            return Ok(None);
+        };
+        Ok(Some(ModrinthClient::new().get_authenticated_user(&token).await?))
+    }
+
+    pub async fn followed_projects() -> Result<Vec<ModrinthProjectDetails>, Box<dyn std::error::Error>> {
+        let (client, user, token) = linked().await?;
+        let mut projects = client.get_followed_projects(&user.id, &token).await?;
+        projects.sort_by_key(|p| p.title.to_lowercase());
+        Ok(projects)
+    }
+
+    pub async fn collections() -> Result<Vec<ModrinthCollection>, Box<dyn std::error::Error>> {
+        let (client, user, token) = linked().await?;
+        client.get_user_collections(&user.id, &token).await
+    }
+
+    /// Unread update notifications, newest first
+    pub async fn updates() -> Result<Vec<FollowedUpdate>, Box<dyn std::error::Error>> {
+        let (client, user, token) = linked().await?;
+
+        let pending: Vec<(String, String, String, String)> = client
+            .get_notifications(&user.id, &token)
+            .await?
+            .into_iter()
+            .filter(|n| !n.read && n.notification_type.as_deref() == Some("project_update"))
+            .filter_map(|n| {
+                let project_id = n.body.get("project_id")?.as_str()?.to_string();
+                let version_id = n.body.get("version_id")?.as_str()?.to_string();
+                Some((n.id, project_id, version_id, n.created))
+            })
+            .collect();
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut project_ids: Vec<String> = pending.iter().map(|(_, p, _, _)| p.clone()).collect();
+        project_ids.sort();
+        project_ids.dedup();
+        let version_ids: Vec<String> = pending.iter().map(|(_, _, v, _)| v.clone()).collect();
+
+        let projects: HashMap<String, ModrinthProjectDetails> = client
+            .get_projects(&project_ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect();
+        let versions: HashMap<String, String> = client
+            .get_versions(&version_ids)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|v| (v.id, v.version_number))
+            .collect();
+        let installed = installed_projects();
+
+        let mut updates: Vec<FollowedUpdate> = pending
+            .into_iter()
+            .map(|(notification_id, project_id, version_id, created)| {
+                let project = projects.get(&project_id);
+                FollowedUpdate {
+                    notification_id,
+                    project_title: project.map(|p| p.title.clone()).unwrap_or_else(|| project_id.clone()),
+                    icon_url: project.and_then(|p| p.icon_url.clone()),
+                    version_number: versions.get(&version_id).cloned(),
+                    instances: installed.get(&project_id).cloned().unwrap_or_default(),
+                    project_id,
+                    version_id,
+                    created,
+                }
+            })
+            .collect();
+
+        updates.sort_by(|a, b| b.created.cmp(&a.created));
+        Ok(updates)
+    }
+
+    pub async fn dismiss_updates(notification_ids: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        let token = token().ok_or("No Modrinth account is linked")?;
+        ModrinthClient::new().mark_notifications_read(notification_ids, &token).await
+    }
+
+    /// Install every mod and resource pack of a collection that has a build for the instance.
+    /// One project failing doesn't stop the rest.
+    pub async fn install_collection(
+        instance: &Instance,
+        collection_id: &str,
+    ) -> Result<CollectionInstallResult, Box<dyn std::error::Error>> {
+        let client = ModrinthClient::new();
+        let token = token();
+        let collection = client.get_collection(collection_id, token.as_deref()).await?;
+        let projects = client.get_projects(&collection.projects).await?;
+
+        let already: Vec<String> = ModLockfile::load(&get_instance_dir(&instance.name))?
+            .files
+            .into_iter()
+            .filter_map(|f| f.project_id)
+            .collect();
+
+        let mut result = CollectionInstallResult::default();
+        for project in projects {
+            if already.contains(&project.id) {
+                result.skipped.push(format!("{} (already installed)", project.title));
+                continue;
+            }
+
+            let outcome = match project.project_type.as_str() {
+                "mod" => ModrinthInstaller::install(instance, &project.id, None, true)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                "resourcepack" => ResourcePackManager::install_modrinth(instance, &project.id, None)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                other => {
+                    result.skipped.push(format!("{} ({})", project.title, other));
+                    continue;
+                }
+            };
+
+            match outcome {
+                Ok(()) => result.installed.push(project.title),
+                Err(e) => {
+                    println!("⚠ Failed to install {} from collection {}: {}", project.title, collection.name, e);
+                    result.failed.push(format!("{}: {}", project.title, e));
+                }
+            }
+        }
+
+        println!(
+            "✓ Installed {} of {} projects from collection '{}' into '{}'",
+            result.installed.len(),
+            collection.projects.len(),
+            collection.name,
+            instance.name
+        );
+        Ok(result)
+    }
+}
+
+fn token() -> Option<String> {
+    SettingsManager::load().ok()?.modrinth_token.filter(|t| !t.is_empty())
+}
+
+async fn linked() -> Result<(ModrinthClient, ModrinthUser, String), Box<dyn std::error::Error>> {
+    let token = token().ok_or("No Modrinth account is linked")?;
+    let client = ModrinthClient::new();
+    let user = client.get_authenticated_user(&token).await?;
+    Ok((client, user, token))
+}
+
+/// Modrinth project id to the instances whose lockfile has it
+fn installed_projects() -> HashMap<String, Vec<String>> {
+    let mut installed: HashMap<String, Vec<String>> = HashMap::new();
+    for instance in InstanceManager::get_all().unwrap_or_default() {
+        let Ok(lockfile) = ModLockfile::load(&get_instance_dir(&instance.name)) else {
+            continue;
+        };
+        for file in lockfile.files {
+            if file.source.as_deref() != Some("modrinth") {
+                continue;
+            }
+            if let Some(project_id) = file.project_id {
+                let names = installed.entry(project_id).or_default();
+                if !names.contains(&instance.name) {
+                    names.push(instance.name.clone());
+                }
+            }
+        }
+    }
+    installed
+}
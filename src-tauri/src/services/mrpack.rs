@@ -0,0 +1,138 @@
+use crate::models::{MrpackFile, MrpackIndex};
+use crate::services::mod_cache::ModCache;
+use crate::services::pack_validator::{is_allowed_pack_url, is_safe_pack_path};
+use crate::utils::archive::extract_prefixed;
+use crate::utils::long_path;
+use sha1::{Digest, Sha1};
+use std::{fs, io::Read, path::Path};
+use zip::ZipArchive;
+
+pub struct MrpackReader;
+
+impl MrpackReader {
+    pub fn read_index(archive_path: &Path) -> Result<MrpackIndex, Box<dyn std::error::Error>> {
+        let mut archive = ZipArchive::new(fs::File::open(archive_path)?)?;
+        let mut entry = archive
+            .by_name("modrinth.index.json")
+            .map_err(|_| "Invalid modpack: modrinth.index.json not found")?;
+
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+
+        let index: MrpackIndex = serde_json::from_str(&content)?;
+        if index.format_version != 1 || index.game != "minecraft" {
+            return Err(format!(
+                "Unsupported modpack format (formatVersion {}, game '{}')",
+                index.format_version, index.game
+            )
+            .into());
+        }
+
+        Ok(index)
+    }
+
+    /// Extract `overrides/` then `client-overrides/` into the instance, client files winning
+    pub fn extract_overrides(archive_path: &Path, instance_dir: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+        extract_prefixed(archive_path, &["overrides/", "client-overrides/"], instance_dir)
+    }
+
+    /// Files the client should download. Server-only entries are skipped.
+    pub fn client_files(index: &MrpackIndex) -> Vec<&MrpackFile> {
+        index
+            .files
+            .iter()
+            .filter(|f| f.env.as_ref().map(|env| env.client != "unsupported").unwrap_or(true))
+            .collect()
+    }
+
+    /// Download a pack file into the instance, trying each mirror until one matches the SHA1
+    pub async fn download_file(
+        client: &reqwest::Client,
+        file: &MrpackFile,
+        instance_dir: &Path,
+    ) -> Result<(), String> {
+        Self::download_file_trusting(client, file, instance_dir, None).await
+    }
+
+    /// Like `download_file`, but also accepts URLs on `trusted_host`, for manifests the player
+    /// subscribed to that host their files themselves
+    pub async fn download_file_trusting(
+        client: &reqwest::Client,
+        file: &MrpackFile,
+        instance_dir: &Path,
+        trusted_host: Option<&str>,
+    ) -> Result<(), String> {
+        if !is_safe_pack_path(&file.path) {
+            return Err(format!("Refusing unsafe path '{}'", file.path));
+        }
+
+        let expected_sha1 = file
+            .hashes
+            .get("sha1")
+            .ok_or_else(|| format!("{} has no sha1 hash", file.path))?;
+
+        let dest_path = long_path(&instance_dir.join(&file.path));
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+
+        let cacheable = ModCache::covers(&file.path);
+        if cacheable && file.hashes.get("sha512").is_some_and(|sha512| ModCache::restore(sha512, &dest_path)) {
+            return Ok(());
+        }
+
+        let mut last_error = format!("{} has no download URLs", file.path);
+        for url in &file.downloads {
+            let trusted = trusted_host.is_some_and(|host| {
+                url::Url::parse(url)
+                    .map(|u| u.scheme() == "https" && u.host_str() == Some(host))
+                    .unwrap_or(false)
+            });
+            if !trusted {
+                if let Err(e) = is_allowed_pack_url(url) {
+                    last_error = format!("{}: {}", file.path, e);
+                    continue;
+                }
+            }
+
+            let bytes = match fetch(client, url).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    last_error = format!("Failed to download {}: {}", file.path, e);
+                    continue;
+                }
+            };
+
+            let mut hasher = Sha1::new();
+            hasher.update(&bytes);
+            let hash = format!("{:x}", hasher.finalize());
+
+            if &hash != expected_sha1 {
+                last_error = format!("Hash mismatch for {} (expected {}, got {})", file.path, expected_sha1, hash);
+                continue;
+            }
+
+            fs::write(&dest_path, &bytes).map_err(|e| format!("Failed to write {}: {}", file.path, e))?;
+            if cacheable {
+                ModCache::adopt_or_warn(&dest_path);
+            }
+            return Ok(());
+        }
+
+        Err(last_error)
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    response
+        .bytes()
+        .await
+        .map(|b| b.to_vec())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,179 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// (name, category, url) of every service the launcher talks to
+const ENDPOINTS: &[(&str, &str, &str)] = &[
+    ("Microsoft login", "auth", "https://login.microsoftonline.com/"),
+    ("Xbox Live", "auth", "https://user.auth.xboxlive.com/"),
+    ("Xbox Live XSTS", "auth", "https://xsts.auth.xboxlive.com/"),
+    ("Minecraft services", "auth", "https://api.minecraftservices.com/"),
+    ("Session server", "session", "https://sessionserver.mojang.com/"),
+    ("Version manifest", "downloads", "https://launchermeta.mojang.com/mc/game/version_manifest.json"),
+    ("Version metadata", "downloads", "https://piston-meta.mojang.com/"),
+    ("Game downloads", "downloads", "https://piston-data.mojang.com/"),
+    ("Libraries", "downloads", "https://libraries.minecraft.net/"),
+    ("Assets", "downloads", "https://resources.download.minecraft.net/"),
+    ("Fabric", "downloads", "https://meta.fabricmc.net/v2/versions/loader"),
+    ("Modrinth API", "mods", "https://api.modrinth.com/v2/"),
+    ("Modrinth CDN", "mods", "https://cdn.modrinth.com/"),
+    ("CurseForge API", "mods", "https://api.curseforge.com/v1/games/432"),
+    ("CurseForge CDN", "mods", "https://edge.forgecdn.net/"),
+];
+
+/// Answers slower than this are reported as slow
+const SLOW_MS: u64 = 2000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EndpointCheck {
+    pub name: String,
+    /// auth, session, downloads or mods
+    pub category: String,
+    pub url: String,
+    /// Any HTTP answer counts, a 404 still proves the server can be reached
+    pub reachable: bool,
+    pub status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+    /// What the failure most likely means for the player
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkDiagnosticsReport {
+    pub checked_at: String,
+    pub endpoints: Vec<EndpointCheck>,
+    /// Proxy from the environment without credentials, reqwest picks it up automatically
+    pub proxy: Option<String>,
+    /// One line per problem, empty when everything answered in time
+    pub summary: Vec<String>,
+}
+
+pub struct NetworkDiagnostics;
+
+impl NetworkDiagnostics {
+    /// Request every endpoint at once and time the answers
+    pub async fn run() -> Result<NetworkDiagnosticsReport, Box<dyn std::error::Error>> {
+        let client = crate::utils::http::client_builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (index, (name, category, url)) in ENDPOINTS.iter().enumerate() {
+            let client = client.clone();
+            tasks.spawn(async move { (index, check(&client, name, category, url).await) });
+        }
+
+        let mut endpoints = Vec::new();
+        while let Some(result) = tasks.join_next().await {
+            endpoints.push(result?);
+        }
+        endpoints.sort_by_key(|(index, _)| *index);
+        let endpoints: Vec<EndpointCheck> = endpoints.into_iter().map(|(_, check)| check).collect();
+
+        let mut summary = Vec::new();
+        for endpoint in &endpoints {
+            match (&endpoint.hint, endpoint.latency_ms) {
+                (Some(hint), _) => summary.push(format!("{}: {}", endpoint.name, hint)),
+                (None, Some(ms)) if ms > SLOW_MS => {
+                    summary.push(format!("{} took {} ms to answer, downloads from it will be slow", endpoint.name, ms))
+                }
+                _ => {}
+            }
+        }
+        if endpoints.iter().all(|e| !e.reachable) {
+            summary.insert(0, "No service could be reached, check the internet connection".to_string());
+        }
+
+        println!(
+            "✓ Network diagnostics: {} of {} endpoints reachable",
+            endpoints.iter().filter(|e| e.reachable).count(),
+            endpoints.len()
+        );
+        Ok(NetworkDiagnosticsReport {
+            checked_at: Utc::now().to_rfc3339(),
+            endpoints,
+            proxy: proxy_from_env(),
+            summary,
+        })
+    }
+}
+
+async fn check(client: &reqwest::Client, name: &str, category: &str, url: &str) -> EndpointCheck {
+    let started = Instant::now();
+    let response = client.get(url).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let mut check = EndpointCheck {
+        name: name.to_string(),
+        category: category.to_string(),
+        url: url.to_string(),
+        reachable: false,
+        status: None,
+        latency_ms: None,
+        error: None,
+        hint: None,
+    };
+
+    match response {
+        Ok(response) => {
+            let status = response.status();
+            check.reachable = true;
+            check.status = Some(status.as_u16());
+            check.latency_ms = Some(latency_ms);
+            check.hint = if status.is_server_error() {
+                Some(format!("The service answered {}, it's having problems on its end", status))
+            } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                Some("Rate limited, too many requests from this network".to_string())
+            } else {
+                None
+            };
+        }
+        Err(e) => {
+            check.hint = Some(failure_hint(&e));
+            check.error = Some(e.to_string());
+        }
+    }
+    check
+}
+
+fn failure_hint(error: &reqwest::Error) -> String {
+    // reqwest wraps the cause, the chain holds the DNS or TLS details
+    let mut details = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        details.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+    let details = details.to_lowercase();
+
+    if error.is_timeout() {
+        "Timed out, a firewall or antivirus may be dropping the connection".to_string()
+    } else if details.contains("certificate") || details.contains("tls") || details.contains("ssl") {
+        "The server's certificate isn't trusted. Behind a proxy that inspects TLS, add its CA certificate in settings"
+            .to_string()
+    } else if details.contains("dns") || details.contains("lookup") || details.contains("resolve") {
+        "The name couldn't be resolved, check DNS settings or whether the domain is blocked".to_string()
+    } else if details.contains("refused") || details.contains("reset") {
+        "The connection was refused or cut off, likely by a firewall or proxy".to_string()
+    } else {
+        "The server couldn't be reached".to_string()
+    }
+}
+
+/// The proxy reqwest will use, with any user name and password removed
+fn proxy_from_env() -> Option<String> {
+    let value = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))?;
+
+    match reqwest::Url::parse(&value) {
+        Ok(mut url) => {
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            Some(url.to_string())
+        }
+        Err(_) => Some("(set, but not a valid URL)".to_string()),
+    }
+}
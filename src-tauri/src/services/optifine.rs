@@ -0,0 +1,337 @@
+use crate::models::*;
+use crate::services::installer::MinecraftInstaller;
+use crate::services::java::JavaManager;
+use crate::services::liteloader::{maven_path, LAUNCHWRAPPER_MAIN_CLASS};
+use crate::utils::archive::read_entry;
+use crate::utils::long_path;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use zip::ZipArchive;
+
+const OPTIFINE_TWEAK_CLASS: &str = "optifine.OptiFineTweaker";
+/// Used when the OptiFine jar doesn't bundle its own LaunchWrapper fork
+const LAUNCHWRAPPER_NAME: &str = "net.minecraft:launchwrapper:1.12";
+const MOJANG_LIBRARIES_URL: &str = "https://libraries.minecraft.net/";
+/// Only optifine.net's own mirror pages are scraped for the real download link
+const OPTIFINE_HOST: &str = "optifine.net";
+
+/// Where the OptiFine jar comes from
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum OptiFineSource {
+    /// A jar the player downloaded themselves
+    File(String),
+    /// An optifine.net download page (adloadx) or a direct link to the jar
+    Url(String),
+}
+
+/// What's inside an OptiFine jar, read from its file name or its Config class
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OptiFineBuild {
+    pub minecraft_version: String,
+    /// e.g. "HD_U_I6"
+    pub edition: String,
+}
+
+pub struct OptiFineInstaller {
+    http_client: reqwest::Client,
+    launcher_dir: PathBuf,
+}
+
+impl OptiFineInstaller {
+    pub fn new(launcher_dir: PathBuf) -> Self {
+        let http_client = crate::utils::http::client_builder()
+            .user_agent("AtomicLauncher/2.4.0")
+            .timeout(Duration::from_secs(120))
+            .build()
+            .unwrap();
+
+        Self {
+            http_client,
+            launcher_dir,
+        }
+    }
+
+    /// Version id of the installed profile, following the vanilla launcher's naming
+    pub fn profile_id(build: &OptiFineBuild) -> String {
+        format!("{}-OptiFine_{}", build.minecraft_version, build.edition)
+    }
+
+    /// Copy or download the OptiFine jar into the libraries folder's cache and identify it
+    pub async fn fetch_jar(&self, source: &OptiFineSource) -> Result<(PathBuf, OptiFineBuild), Box<dyn std::error::Error>> {
+        let cache_dir = self.launcher_dir.join("libraries").join("optifine").join("installers");
+        fs::create_dir_all(&cache_dir)?;
+
+        let (bytes, file_name) = match source {
+            OptiFineSource::File(path) => {
+                let path = Path::new(path);
+                let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                (fs::read(path)?, file_name)
+            }
+            OptiFineSource::Url(url) => self.download_jar(url).await?,
+        };
+
+        let temp_path = cache_dir.join("download.jar.tmp");
+        fs::write(&temp_path, &bytes)?;
+        let build = match identify(&temp_path, &file_name) {
+            Ok(build) => build,
+            Err(e) => {
+                let _ = fs::remove_file(&temp_path);
+                return Err(e);
+            }
+        };
+
+        let jar_path = cache_dir.join(format!("OptiFine_{}_{}.jar", build.minecraft_version, build.edition));
+        fs::rename(&temp_path, &jar_path)?;
+        Ok((jar_path, build))
+    }
+
+    /// Build the OptiFine library from the installer jar and the vanilla client, and write a
+    /// LaunchWrapper profile for it. Returns the profile id.
+    pub async fn install_optifine(&self, jar_path: &Path, build: &OptiFineBuild) -> Result<String, Box<dyn std::error::Error>> {
+        println!("=== Installing OptiFine {} for Minecraft {} ===", build.edition, build.minecraft_version);
+
+        let installer = MinecraftInstaller::new(self.launcher_dir.clone());
+        if !installer.check_version_installed(&build.minecraft_version) {
+            installer
+                .install_version(&build.minecraft_version)
+                .await
+                .map_err(|e| format!("Failed to install Minecraft {}: {}", build.minecraft_version, e))?;
+        }
+
+        let libraries_dir = self.launcher_dir.join("libraries");
+        let optifine_name = format!("optifine:OptiFine:{}_{}", build.minecraft_version, build.edition);
+        let optifine_path = long_path(&libraries_dir.join(maven_path(&optifine_name)?));
+        if let Some(parent) = optifine_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if has_entry(jar_path, "optifine/Patcher.class") {
+            // Newer builds ship binary patches against the client jar instead of the classes
+            let client_jar = self
+                .launcher_dir
+                .join("versions")
+                .join(&build.minecraft_version)
+                .join(format!("{}.jar", build.minecraft_version));
+            let java_path = JavaManager::ensure_for_version(&build.minecraft_version).await?;
+
+            println!("Extracting OptiFine...");
+            let output = tokio::process::Command::new(&java_path)
+                .arg("-cp")
+                .arg(jar_path)
+                .arg("optifine.Patcher")
+                .arg(&client_jar)
+                .arg(jar_path)
+                .arg(&optifine_path)
+                .output()
+                .await?;
+            if !output.status.success() || !optifine_path.exists() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let lines: Vec<&str> = stderr.lines().collect();
+                let tail = lines[lines.len().saturating_sub(10)..].join("\n");
+                return Err(format!("OptiFine's patcher failed ({}): {}", output.status, tail).into());
+            }
+        } else {
+            // Old builds are the library as is
+            fs::copy(jar_path, &optifine_path)?;
+        }
+        println!("  ✓ Built {}", optifine_name);
+
+        let launchwrapper = self.install_launchwrapper(jar_path, &libraries_dir).await?;
+
+        let profile_id = Self::profile_id(build);
+        let now = Utc::now().to_rfc3339();
+        let profile = FabricProfileJson {
+            id: profile_id.clone(),
+            inherits_from: build.minecraft_version.clone(),
+            release_time: now.clone(),
+            time: now,
+            profile_type: "release".to_string(),
+            main_class: LAUNCHWRAPPER_MAIN_CLASS.to_string(),
+            arguments: Some(FabricArguments {
+                game: vec![
+                    serde_json::Value::String("--tweakClass".to_string()),
+                    serde_json::Value::String(OPTIFINE_TWEAK_CLASS.to_string()),
+                ],
+                jvm: Vec::new(),
+            }),
+            libraries: vec![
+                FabricProfileLibrary {
+                    name: optifine_name,
+                    url: String::new(),
+                    downloads: None,
+                },
+                launchwrapper,
+            ],
+        };
+
+        let versions_dir = self.launcher_dir.join("versions").join(&profile_id);
+        fs::create_dir_all(&versions_dir)?;
+
+        let profile_path = versions_dir.join(format!("{}.json", profile_id));
+        fs::write(&profile_path, serde_json::to_string_pretty(&profile)?)?;
+        println!("✓ Created profile at: {}", profile_path.display());
+
+        println!("=== OptiFine Installation Complete ===");
+        Ok(profile_id)
+    }
+
+    /// OptiFine's own LaunchWrapper fork when the jar bundles one, which also runs on newer Java,
+    /// otherwise Mojang's
+    async fn install_launchwrapper(
+        &self,
+        jar_path: &Path,
+        libraries_dir: &Path,
+    ) -> Result<FabricProfileLibrary, Box<dyn std::error::Error>> {
+        let bundled_version = read_entry(jar_path, "launchwrapper-of.txt")
+            .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+            .filter(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_alphanumeric() || c == '.'));
+
+        if let Some(version) = bundled_version {
+            let name = format!("optifine:launchwrapper-of:{}", version);
+            let bytes = read_entry(jar_path, &format!("launchwrapper-of-{}.jar", version))
+                .ok_or_else(|| format!("launchwrapper-of-{}.jar is missing from the OptiFine jar", version))?;
+            let path = long_path(&libraries_dir.join(maven_path(&name)?));
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, bytes)?;
+
+            return Ok(FabricProfileLibrary {
+                name,
+                url: String::new(),
+                downloads: None,
+            });
+        }
+
+        let path = long_path(&libraries_dir.join(maven_path(LAUNCHWRAPPER_NAME)?));
+        if !path.exists() {
+            let url = format!("{}{}", MOJANG_LIBRARIES_URL, maven_path(LAUNCHWRAPPER_NAME)?);
+            let response = self.http_client.get(&url).send().await?;
+            if !response.status().is_success() {
+                return Err(format!("Failed to download {}: HTTP {}", url, response.status()).into());
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, response.bytes().await?)?;
+        }
+
+        Ok(FabricProfileLibrary {
+            name: LAUNCHWRAPPER_NAME.to_string(),
+            url: MOJANG_LIBRARIES_URL.to_string(),
+            downloads: None,
+        })
+    }
+
+    /// Download the jar, following an optifine.net mirror page to its real link. Returns the
+    /// bytes and the file name.
+    async fn download_jar(&self, url: &str) -> Result<(Vec<u8>, String), Box<dyn std::error::Error>> {
+        let mut parsed = url::Url::parse(url)?;
+        if parsed.scheme() != "https" && parsed.scheme() != "http" {
+            return Err(format!("Unsupported URL: {}", url).into());
+        }
+
+        let is_optifine = parsed
+            .host_str()
+            .is_some_and(|host| host == OPTIFINE_HOST || host.ends_with(&format!(".{}", OPTIFINE_HOST)));
+        if is_optifine && parsed.path().starts_with("/adload") {
+            let page = self.http_client.get(parsed.as_str()).send().await?.text().await?;
+            let link = find_download_link(&page)
+                .ok_or("Could not find the download link on the OptiFine page, download the jar manually")?;
+            parsed = parsed.join(&link)?;
+        }
+
+        let file_name = parsed
+            .query_pairs()
+            .find(|(key, _)| key == "f")
+            .map(|(_, value)| value.to_string())
+            .or_else(|| parsed.path_segments().and_then(|mut s| s.next_back()).map(String::from))
+            .unwrap_or_default();
+
+        let response = self.http_client.get(parsed.as_str()).send().await?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to download OptiFine: HTTP {}", response.status()).into());
+        }
+        Ok((response.bytes().await?.to_vec(), file_name))
+    }
+}
+
+/// The "downloadx?f=...&x=..." link on an adloadx page
+fn find_download_link(page: &str) -> Option<String> {
+    let start = page.find("downloadx?")?;
+    let rest = &page[start..];
+    let end = rest.find(['\'', '"', ' ', '>'])?;
+    Some(rest[..end].replace("&amp;", "&"))
+}
+
+/// Read the Minecraft version and edition from a name like "OptiFine_1.20.1_HD_U_I6.jar",
+/// falling back to the version string compiled into OptiFine's Config class
+fn identify(jar_path: &Path, file_name: &str) -> Result<OptiFineBuild, Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(fs::File::open(jar_path)?)
+        .map_err(|_| "This isn't an OptiFine jar, it can't be opened as an archive")?;
+    let is_optifine = (0..archive.len()).any(|i| {
+        archive
+            .by_index(i)
+            .map(|e| e.name().starts_with("optifine/") || e.name().starts_with("net/optifine/"))
+            .unwrap_or(false)
+    });
+    if !is_optifine {
+        return Err("This isn't an OptiFine jar".into());
+    }
+
+    if let Some(build) = parse_version_string(file_name.trim_start_matches("preview_").trim_end_matches(".jar")) {
+        return Ok(build);
+    }
+
+    for class in ["net/optifine/Config.class", "Config.class"] {
+        let Ok(mut entry) = archive.by_name(class) else {
+            continue;
+        };
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+
+        let content = String::from_utf8_lossy(&bytes);
+        for (index, _) in content.match_indices("OptiFine_") {
+            let candidate: String = content[index..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.')
+                .collect();
+            if let Some(build) = parse_version_string(&candidate) {
+                return Ok(build);
+            }
+        }
+    }
+
+    Err("Could not tell which Minecraft version this OptiFine jar is for".into())
+}
+
+/// "OptiFine_1.20.1_HD_U_I6" -> 1.20.1, HD_U_I6
+fn parse_version_string(value: &str) -> Option<OptiFineBuild> {
+    let rest = value.strip_prefix("OptiFine_")?;
+    let (minecraft_version, edition) = rest.split_once('_')?;
+    let valid_version = minecraft_version.starts_with(|c: char| c.is_ascii_digit())
+        && minecraft_version.chars().all(|c| c.is_ascii_digit() || c == '.');
+    let valid_edition = edition.starts_with("HD") && edition.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_version || !valid_edition {
+        return None;
+    }
+
+    Some(OptiFineBuild {
+        minecraft_version: minecraft_version.to_string(),
+        edition: edition.to_string(),
+    })
+}
+
+fn has_entry(jar_path: &Path, name: &str) -> bool {
+    fs::File::open(jar_path)
+        .ok()
+        .and_then(|file| ZipArchive::new(file).ok())
+        .is_some_and(|mut archive| archive.by_name(name).is_ok())
+}
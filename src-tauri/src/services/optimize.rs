@@ -0,0 +1,170 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_cache::ModCache;
+use crate::utils::get_instance_dir;
+use crate::services::modrinth::pick_version;
+use crate::utils::modrinth::ModrinthClient;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs, path::PathBuf};
+
+/// Records what the preset installed so it can be removed again
+const PRESET_STATE_NAME: &str = "performance-preset.json";
+
+/// Modrinth slugs of the curated performance mods for each loader
+fn preset_for_loader(loader: &str) -> Option<&'static [&'static str]> {
+    match loader {
+        "fabric" | "quilt" => Some(&["sodium", "lithium", "ferrite-core"]),
+        "forge" => Some(&["embeddium", "canary", "ferrite-core"]),
+        "neoforge" => Some(&["sodium", "ferrite-core", "modernfix"]),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PresetMod {
+    pub project_id: String,
+    pub version_number: String,
+    pub filename: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PresetState {
+    pub installed: Vec<PresetMod>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PresetInstallReport {
+    pub installed: Vec<PresetMod>,
+    /// Preset mods with no build for this loader/version, or already present
+    pub skipped: Vec<String>,
+}
+
+pub struct PerformancePreset;
+
+impl PerformancePreset {
+    fn get_state_path(instance_name: &str) -> PathBuf {
+        get_instance_dir(instance_name).join(PRESET_STATE_NAME)
+    }
+
+    pub fn load_state(instance_name: &str) -> Result<PresetState, Box<dyn std::error::Error>> {
+        let path = Self::get_state_path(instance_name);
+        if !path.exists() {
+            return Ok(PresetState::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save_state(instance_name: &str, state: &PresetState) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(Self::get_state_path(instance_name), serde_json::to_string_pretty(state)?)?;
+        Ok(())
+    }
+
+    /// Install the preset plus required dependencies. Mods the preset already installed are left alone.
+    pub async fn install(instance: &Instance) -> Result<PresetInstallReport, Box<dyn std::error::Error>> {
+        let loader = instance.loader.as_deref().unwrap_or("vanilla");
+        let preset = preset_for_loader(loader)
+            .ok_or_else(|| format!("No performance preset available for '{}' instances", loader))?;
+
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let mods_dir = get_instance_dir(&instance.name).join("mods");
+        fs::create_dir_all(&mods_dir)?;
+
+        let client = ModrinthClient::new();
+        let mut state = Self::load_state(&instance.name)?;
+        let mut report = PresetInstallReport::default();
+
+        let mut seen: HashSet<String> = state.installed.iter().map(|m| m.project_id.clone()).collect();
+        let mut queue: Vec<String> = preset.iter().map(|s| s.to_string()).collect();
+
+        while let Some(project) = queue.pop() {
+            let versions = client
+                .get_project_versions(&project, Some(vec![loader.to_string()]), Some(vec![game_version.clone()]))
+                .await?;
+
+            let Some(version) = pick_version(&versions) else {
+                println!("⚠ {} has no build for {} {}", project, loader, game_version);
+                report.skipped.push(project);
+                continue;
+            };
+
+            if !seen.insert(version.project_id.clone()) {
+                continue;
+            }
+
+            for dependency in &version.dependencies {
+                if dependency.dependency_type == "required" {
+                    if let Some(project_id) = &dependency.project_id {
+                        if !seen.contains(project_id) {
+                            queue.push(project_id.clone());
+                        }
+                    }
+                }
+            }
+
+            let file = version
+                .files
+                .iter()
+                .find(|f| f.primary)
+                .or_else(|| version.files.first())
+                .ok_or_else(|| format!("{} {} has no files", project, version.version_number))?;
+
+            let filename = crate::commands::validation::sanitize_filename(&file.filename)?;
+            let destination = mods_dir.join(&filename);
+            if destination.exists() {
+                report.skipped.push(project);
+                continue;
+            }
+
+            if !ModCache::restore(&file.hashes.sha512, &destination) {
+                client.download_mod_file(&file.url, &destination).await?;
+                ModCache::adopt_or_warn(&destination);
+            }
+            println!("  ✓ Installed {}", filename);
+
+            let installed = PresetMod {
+                project_id: version.project_id.clone(),
+                version_number: version.version_number.clone(),
+                filename,
+            };
+            state.installed.push(installed.clone());
+            report.installed.push(installed);
+
+            // Saved as we go so a failed download still leaves the rest removable
+            Self::save_state(&instance.name, &state)?;
+        }
+
+        println!(
+            "✓ Performance preset for '{}': {} installed, {} skipped",
+            instance.name,
+            report.installed.len(),
+            report.skipped.len()
+        );
+        Ok(report)
+    }
+
+    /// Delete every mod the preset installed. Returns how many files were removed.
+    pub fn remove(instance_name: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        let state = Self::load_state(instance_name)?;
+        let mods_dir = get_instance_dir(instance_name).join("mods");
+        let mut removed = 0;
+
+        for installed in &state.installed {
+            // Also catch files the user disabled in the meantime
+            for name in [installed.filename.clone(), format!("{}.disabled", installed.filename)] {
+                let path = mods_dir.join(&name);
+                if path.exists() {
+                    fs::remove_file(path)?;
+                    removed += 1;
+                }
+            }
+        }
+
+        let state_path = Self::get_state_path(instance_name);
+        if state_path.exists() {
+            fs::remove_file(state_path)?;
+        }
+
+        Ok(removed)
+    }
+}
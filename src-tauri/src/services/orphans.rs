@@ -0,0 +1,330 @@
+use crate::models::Instance;
+use crate::services::cleanup::path_size;
+use crate::services::instance::InstanceManager;
+use crate::services::journal::TaskJournal;
+use crate::services::liteloader::LiteLoaderInstaller;
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::trash::move_to_trash;
+use crate::utils::{get_instances_dir, get_meta_dir, long_path};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum OrphanReason {
+    MissingMetadata,
+    InvalidMetadata { error: String },
+    /// instance.json names a different folder, so saving it would write elsewhere
+    NameMismatch { recorded_name: String },
+    /// An install or import was cut off, the contents are likely incomplete
+    InterruptedTask { task_id: String, step: String },
+}
+
+/// What the folder's own files say it was set up as
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DetectedSetup {
+    pub minecraft_version: Option<String>,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    /// Where the versions came from: instance.json, latest.log or mods
+    pub source: Option<String>,
+    pub mod_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrphanedInstance {
+    pub folder: String,
+    #[serde(flatten)]
+    pub reason: OrphanReason,
+    pub size_bytes: u64,
+    pub detected: DetectedSetup,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepairedInstance {
+    pub instance: Instance,
+    /// Things the repair couldn't restore and the player should check
+    pub notes: Vec<String>,
+}
+
+pub struct OrphanScanner;
+
+impl OrphanScanner {
+    /// Instance folders the launcher can't list or that an interrupted task left behind
+    pub fn scan() -> Vec<OrphanedInstance> {
+        let Ok(entries) = fs::read_dir(get_instances_dir()) else {
+            return Vec::new();
+        };
+        let interrupted: HashMap<String, (String, String)> = TaskJournal::interrupted()
+            .into_iter()
+            .filter(|t| t.instance_created)
+            .map(|t| (t.instance_name, (t.id, t.step)))
+            .collect();
+        let running = crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().keys().cloned().collect::<Vec<_>>();
+
+        let mut orphans = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let folder = entry.file_name().to_string_lossy().to_string();
+            if !path.is_dir() || folder.starts_with('.') || running.contains(&folder) {
+                continue;
+            }
+
+            let reason = match fs::read_to_string(path.join("instance.json")) {
+                Err(_) => OrphanReason::MissingMetadata,
+                Ok(content) => match serde_json::from_str::<Instance>(&content) {
+                    Err(e) => OrphanReason::InvalidMetadata { error: e.to_string() },
+                    Ok(instance) if instance.name != folder => OrphanReason::NameMismatch {
+                        recorded_name: instance.name,
+                    },
+                    Ok(_) => match interrupted.get(&folder) {
+                        Some((task_id, step)) => OrphanReason::InterruptedTask {
+                            task_id: task_id.clone(),
+                            step: step.clone(),
+                        },
+                        None => continue,
+                    },
+                },
+            };
+
+            orphans.push(OrphanedInstance {
+                size_bytes: path_size(&path),
+                detected: detect_setup(&path),
+                folder,
+                reason,
+            });
+        }
+
+        orphans.sort_by(|a, b| a.folder.to_lowercase().cmp(&b.folder.to_lowercase()));
+        orphans
+    }
+
+    /// Write a working instance.json for the folder, keeping whatever the old one still has.
+    /// `minecraft_version` overrides the detected version.
+    pub fn repair(folder: &str, minecraft_version: Option<&str>) -> Result<RepairedInstance, Box<dyn std::error::Error>> {
+        let orphan = Self::find(folder)?;
+        let instance_dir = get_instances_dir().join(folder);
+        let mut notes = Vec::new();
+
+        if let OrphanReason::InterruptedTask { task_id, step } = &orphan.reason {
+            TaskJournal::forget(task_id)?;
+            notes.push(format!("The install stopped at \"{}\", some files may be missing", step));
+            return Ok(RepairedInstance {
+                instance: InstanceManager::load(folder)?,
+                notes,
+            });
+        }
+
+        let detected = orphan.detected;
+        let minecraft_version = minecraft_version
+            .map(String::from)
+            .or(detected.minecraft_version)
+            .ok_or("Could not tell which Minecraft version this instance used, choose one to repair it")?;
+        if !minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_') {
+            return Err("Invalid Minecraft version".into());
+        }
+
+        // A loader only sticks when its version profile is installed, the launch would fail otherwise
+        let mut version = minecraft_version.clone();
+        let mut loader = None;
+        let mut loader_version = None;
+        match (detected.loader.as_deref(), detected.loader_version.as_deref()) {
+            (Some(name), Some(lv)) => match profile_id(name, lv, &minecraft_version) {
+                Some(id) if get_meta_dir().join("versions").join(&id).exists() => {
+                    version = id;
+                    loader = Some(name.to_string());
+                    loader_version = Some(lv.to_string());
+                }
+                _ => notes.push(format!(
+                    "{} {} isn't installed, the instance was set up as vanilla. Install the loader from its settings.",
+                    name, lv
+                )),
+            },
+            (Some(name), None) => notes.push(format!(
+                "The mods need {}, but its version is unknown. Install the loader from the instance settings.",
+                name
+            )),
+            _ => {}
+        }
+        if !get_meta_dir().join("versions").join(&minecraft_version).exists() {
+            notes.push(format!("Minecraft {} will be downloaded on the next launch", minecraft_version));
+        }
+
+        // Salvage settings, playtime and the rest from a damaged file where possible
+        let mut fields = fs::read_to_string(instance_dir.join("instance.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|value| value.as_object().cloned())
+            .unwrap_or_default();
+        fields.insert("name".into(), folder.into());
+        fields.insert("version".into(), version.into());
+        fields.insert("loader".into(), loader.into());
+        fields.insert("loader_version".into(), loader_version.into());
+        if !fields.get("created_at").is_some_and(|v| v.is_string()) {
+            fields.insert("created_at".into(), Utc::now().to_rfc3339().into());
+        }
+
+        let instance = match serde_json::from_value::<Instance>(serde_json::Value::Object(fields.clone())) {
+            Ok(instance) => instance,
+            Err(e) => {
+                notes.push(format!("Instance settings could not be recovered ({}) and were reset", e));
+                let keep = ["name", "version", "loader", "loader_version", "created_at"];
+                fields.retain(|key, _| keep.contains(&key.as_str()));
+                serde_json::from_value(serde_json::Value::Object(fields))?
+            }
+        };
+
+        InstanceManager::save(&instance)?;
+        println!("✓ Repaired instance '{}' as {}", folder, instance.version);
+        Ok(RepairedInstance { instance, notes })
+    }
+
+    /// Move the folder to the OS trash, or clean up after the interrupted task that left it.
+    /// Returns the bytes freed.
+    pub fn remove(folder: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        let orphan = Self::find(folder)?;
+
+        if let OrphanReason::InterruptedTask { task_id, .. } = &orphan.reason {
+            TaskJournal::clean_up(task_id)?;
+        } else {
+            move_to_trash(&get_instances_dir().join(folder))?;
+        }
+
+        println!("✓ Removed orphaned instance folder '{}' ({} bytes)", folder, orphan.size_bytes);
+        Ok(orphan.size_bytes)
+    }
+
+    /// Only folders the scan reports can be touched, never a healthy instance
+    fn find(folder: &str) -> Result<OrphanedInstance, Box<dyn std::error::Error>> {
+        Self::scan()
+            .into_iter()
+            .find(|o| o.folder == folder)
+            .ok_or_else(|| format!("'{}' is not an orphaned instance folder", folder).into())
+    }
+}
+
+/// Version id the loader's installer writes, as used for `Instance::version`
+fn profile_id(loader: &str, loader_version: &str, minecraft_version: &str) -> Option<String> {
+    match loader {
+        "fabric" => Some(format!("fabric-loader-{}-{}", loader_version, minecraft_version)),
+        "forge" | "neoforge" => Some(format!("{}-{}-{}", minecraft_version, loader, loader_version)),
+        "liteloader" => Some(LiteLoaderInstaller::profile_id(minecraft_version, loader_version)),
+        "optifine" => Some(format!("{}-OptiFine_{}", minecraft_version, loader_version)),
+        _ => None,
+    }
+}
+
+/// Detect the setup from a damaged instance.json, then the last game log, then the mods
+pub(crate) fn detect_setup(instance_dir: &Path) -> DetectedSetup {
+    let mods: Vec<_> = fs::read_dir(long_path(&instance_dir.join("mods")))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "jar"))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut detected = DetectedSetup {
+        mod_count: mods.len(),
+        ..Default::default()
+    };
+
+    let recorded = fs::read_to_string(instance_dir.join("instance.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+    if let Some(version) = recorded.as_ref().and_then(|v| v.get("version")).and_then(|v| v.as_str()) {
+        detected.minecraft_version = Some(InstanceManager::base_minecraft_version(version));
+        detected.loader = recorded.as_ref().and_then(|v| v.get("loader")).and_then(|v| v.as_str()).map(String::from);
+        detected.loader_version = recorded
+            .as_ref()
+            .and_then(|v| v.get("loader_version"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        detected.source = Some("instance.json".to_string());
+        return detected;
+    }
+
+    if let Ok(log) = fs::read_to_string(instance_dir.join("logs").join("latest.log")) {
+        if let Some(found) = setup_from_log(&log) {
+            return DetectedSetup {
+                mod_count: detected.mod_count,
+                ..found
+            };
+        }
+    }
+
+    // Most mods pin the game version they were built for
+    let mut versions: HashMap<String, usize> = HashMap::new();
+    let mut loaders: HashMap<String, usize> = HashMap::new();
+    for jar in &mods {
+        let Some(metadata) = ModMetadataReader::read(jar) else {
+            continue;
+        };
+        *loaders.entry(metadata.loader.replace("legacy-forge", "forge")).or_insert(0) += 1;
+        let pinned = metadata
+            .depends
+            .iter()
+            .find(|d| d.id == "minecraft")
+            .and_then(|d| d.versions.as_deref())
+            .and_then(exact_version);
+        if let Some(version) = pinned {
+            *versions.entry(version).or_insert(0) += 1;
+        }
+    }
+    detected.minecraft_version = most_common(versions);
+    detected.loader = most_common(loaders);
+    if detected.minecraft_version.is_some() || detected.loader.is_some() {
+        detected.source = Some("mods".to_string());
+    }
+    detected
+}
+
+/// Fabric prints "Loading Minecraft 1.20.1 with Fabric Loader 0.15.7", Forge logs its launch arguments
+fn setup_from_log(log: &str) -> Option<DetectedSetup> {
+    let found = |minecraft: &str, loader: &str, version: &str| DetectedSetup {
+        minecraft_version: Some(minecraft.to_string()),
+        loader: Some(loader.to_string()),
+        loader_version: Some(version.to_string()),
+        source: Some("latest.log".to_string()),
+        mod_count: 0,
+    };
+
+    for line in log.lines() {
+        if let Some(rest) = line.split("Loading Minecraft ").nth(1) {
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            if let ["with", "Fabric", "Loader", ..] = words.get(1..4).unwrap_or_default() {
+                if let Some(loader_version) = words.get(4) {
+                    return Some(found(words[0], "fabric", loader_version));
+                }
+            }
+        }
+    }
+
+    let argument = |name: &str| {
+        log.split(&format!("--{}, ", name))
+            .nth(1)
+            .and_then(|rest| rest.split([',', ']']).next())
+            .map(|v| v.trim().to_string())
+    };
+    let minecraft = argument("fml.mcVersion")?;
+    if let Some(version) = argument("fml.neoForgeVersion") {
+        return Some(found(&minecraft, "neoforge", &version));
+    }
+    let version = argument("fml.forgeVersion")?;
+    Some(found(&minecraft, "forge", &version))
+}
+
+/// "1.20.1", "=1.20.1" or "~1.20.1" name one version, ranges don't
+fn exact_version(predicate: &str) -> Option<String> {
+    let version = predicate.trim().trim_start_matches(['=', '~', '[']).trim_end_matches(']');
+    let exact = !version.is_empty()
+        && version.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && version.chars().next().is_some_and(|c| c.is_ascii_digit());
+    exact.then(|| version.to_string())
+}
+
+fn most_common(counts: HashMap<String, usize>) -> Option<String> {
+    counts.into_iter().max_by_key(|(_, count)| *count).map(|(value, _)| value)
+}
@@ -0,0 +1,263 @@
+use crate::models::Instance;
+use crate::services::cleanup::path_size;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_cache::link_or_copy;
+use crate::utils::links::{create_dir_link, is_link, relink_dir, remove_link};
+use crate::utils::{copy_dir_recursive, get_instance_dir, get_launcher_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PackKind {
+    ResourcePacks,
+    ShaderPacks,
+}
+
+impl PackKind {
+    /// Same name in the library and in the instance
+    pub fn folder(self) -> &'static str {
+        match self {
+            PackKind::ResourcePacks => "resourcepacks",
+            PackKind::ShaderPacks => "shaderpacks",
+        }
+    }
+
+    fn enabled(self, instance: &Instance) -> &Vec<String> {
+        match self {
+            PackKind::ResourcePacks => &instance.library_resource_packs,
+            PackKind::ShaderPacks => &instance.library_shader_packs,
+        }
+    }
+
+    fn enabled_mut(self, instance: &mut Instance) -> &mut Vec<String> {
+        match self {
+            PackKind::ResourcePacks => &mut instance.library_resource_packs,
+            PackKind::ShaderPacks => &mut instance.library_shader_packs,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LibraryPack {
+    pub name: String,
+    pub is_folder: bool,
+    pub size: u64,
+    /// Instances the pack is enabled in
+    pub instances: Vec<String>,
+}
+
+/// Resource packs and shaders kept once in the launcher folder. Instances enable them by name
+/// and get a link in their own folder, a hard link for zips and a directory link for folders.
+pub struct PackLibrary;
+
+impl PackLibrary {
+    pub fn dir(kind: PackKind) -> PathBuf {
+        get_launcher_dir().join("library").join(kind.folder())
+    }
+
+    pub fn list(kind: PackKind) -> Result<Vec<LibraryPack>, Box<dyn std::error::Error>> {
+        let dir = Self::dir(kind);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let instances = InstanceManager::get_all().unwrap_or_default();
+        let mut packs: Vec<LibraryPack> = fs::read_dir(&dir)?
+            .flatten()
+            .filter(|entry| is_pack(&entry.path()))
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                LibraryPack {
+                    is_folder: entry.path().is_dir(),
+                    size: path_size(&entry.path()),
+                    instances: instances
+                        .iter()
+                        .filter(|i| kind.enabled(i).contains(&name))
+                        .map(|i| i.name.clone())
+                        .collect(),
+                    name,
+                }
+            })
+            .collect();
+
+        packs.sort_by_key(|p| p.name.to_lowercase());
+        Ok(packs)
+    }
+
+    /// Copy a pack from anywhere into the library, returning its name there
+    pub fn import(kind: PackKind, source: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        if !is_pack(source) {
+            return Err(format!("{} is not a .zip or a pack folder", source.display()).into());
+        }
+        let name = source
+            .file_name()
+            .ok_or("Invalid pack path")?
+            .to_string_lossy()
+            .to_string();
+
+        let target = Self::dir(kind).join(&name);
+        if target.exists() {
+            return Err(format!("'{}' is already in the library", name).into());
+        }
+        fs::create_dir_all(Self::dir(kind))?;
+
+        if source.is_dir() {
+            copy_dir_recursive(source, &target)?;
+        } else {
+            fs::copy(long_path(source), long_path(&target))?;
+        }
+
+        println!("✓ Added '{}' to the {} library", name, kind.folder());
+        Ok(name)
+    }
+
+    /// Move a pack out of an instance into the library and link it back, so other instances
+    /// can enable it without another copy
+    pub fn adopt_from_instance(instance: &mut Instance, kind: PackKind, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let source = pack_path(&get_instance_dir(&instance.name).join(kind.folder()), name)?;
+        if !source.exists() || is_link(&source) || kind.enabled(instance).iter().any(|n| n == name) {
+            return Err(format!("'{}' is not one of the instance's own packs", name).into());
+        }
+
+        let target = Self::dir(kind).join(name);
+        if target.exists() {
+            return Err(format!("'{}' is already in the library", name).into());
+        }
+        fs::create_dir_all(Self::dir(kind))?;
+
+        if fs::rename(long_path(&source), long_path(&target)).is_err() {
+            // The library is on another drive
+            if source.is_dir() {
+                copy_dir_recursive(&source, &target)?;
+                fs::remove_dir_all(long_path(&source))?;
+            } else {
+                fs::copy(long_path(&source), long_path(&target))?;
+                fs::remove_file(long_path(&source))?;
+            }
+        }
+
+        kind.enabled_mut(instance).push(name.to_string());
+        InstanceManager::save(instance)?;
+        Self::materialize(instance)
+    }
+
+    /// Enable exactly these library packs in the instance
+    pub fn set_enabled(instance: &mut Instance, kind: PackKind, names: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        let folder = get_instance_dir(&instance.name).join(kind.folder());
+        if is_link(&folder) {
+            return Err(format!(
+                "{} of '{}' is shared with other folders, library packs can't be enabled for it alone",
+                kind.folder(),
+                instance.name
+            )
+            .into());
+        }
+
+        for name in names {
+            if !pack_path(&Self::dir(kind), name)?.exists() {
+                return Err(format!("'{}' is not in the library", name).into());
+            }
+        }
+
+        let removed: Vec<String> = kind
+            .enabled(instance)
+            .iter()
+            .filter(|n| !names.contains(n))
+            .cloned()
+            .collect();
+        for name in &removed {
+            unlink(&pack_path(&folder, name)?)?;
+        }
+
+        let enabled = kind.enabled_mut(instance);
+        enabled.clear();
+        for name in names {
+            if !enabled.contains(name) {
+                enabled.push(name.clone());
+            }
+        }
+        InstanceManager::save(instance)?;
+        Self::materialize(instance)
+    }
+
+    /// Remove a pack from the library and from every instance it is enabled in
+    pub fn remove(kind: PackKind, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = pack_path(&Self::dir(kind), name)?;
+
+        for mut instance in InstanceManager::get_all().unwrap_or_default() {
+            if !kind.enabled(&instance).iter().any(|n| n == name) {
+                continue;
+            }
+            unlink(&pack_path(&get_instance_dir(&instance.name).join(kind.folder()), name)?)?;
+            kind.enabled_mut(&mut instance).retain(|n| n != name);
+            InstanceManager::save(&instance)?;
+        }
+
+        if path.is_dir() {
+            fs::remove_dir_all(long_path(&path))?;
+        } else if path.exists() {
+            fs::remove_file(long_path(&path))?;
+        }
+
+        println!("✓ Removed '{}' from the {} library", name, kind.folder());
+        Ok(())
+    }
+
+    /// Put a link for every enabled library pack in the instance, replacing copies left by a
+    /// clone or restore. Packs that left the library are skipped.
+    pub fn materialize(instance: &Instance) -> Result<(), Box<dyn std::error::Error>> {
+        for kind in [PackKind::ResourcePacks, PackKind::ShaderPacks] {
+            let folder = get_instance_dir(&instance.name).join(kind.folder());
+            if kind.enabled(instance).is_empty() || is_link(&folder) {
+                continue;
+            }
+            fs::create_dir_all(&folder)?;
+
+            for name in kind.enabled(instance) {
+                let source = pack_path(&Self::dir(kind), name)?;
+                let target = pack_path(&folder, name)?;
+
+                if source.is_dir() {
+                    if is_link(&target) {
+                        relink_dir(&source, &target)?;
+                    } else if target.exists() {
+                        println!("⚠ '{}' in '{}' is a folder of its own, not linking it", name, instance.name);
+                    } else {
+                        create_dir_link(&source, &target)?;
+                    }
+                } else if source.is_file() {
+                    link_or_copy(&source, &target)?;
+                } else {
+                    println!("⚠ Library pack '{}' of '{}' is missing", name, instance.name);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_pack(path: &Path) -> bool {
+    path.is_dir() || path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+/// `name` must be a plain file or folder name inside `dir`
+fn pack_path(dir: &Path, name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(format!("Invalid pack name '{}'", name).into());
+    }
+    Ok(dir.join(name))
+}
+
+fn unlink(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if is_link(path) {
+        remove_link(path)?;
+    } else if path.is_file() {
+        fs::remove_file(long_path(path))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::pack_validator::is_safe_pack_path;
+use crate::utils::game_options::set_options;
+use crate::utils::{copy_dir_recursive, get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Shipped by packs at the instance root, usually through the pack's overrides
+pub const SETUP_FILE_NAME: &str = "atomic-setup.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PackSetup {
+    pub steps: Vec<SetupStep>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetupStep {
+    /// Recorded in instance.json once done, so renaming a step makes it run again
+    pub id: String,
+    #[serde(flatten)]
+    pub action: SetupAction,
+}
+
+/// Declarative on purpose: packs come from strangers, so they can't run commands on the
+/// player's machine and every path stays inside the instance folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum SetupAction {
+    /// Set `key:value` entries in options.txt
+    SetOptions { values: BTreeMap<String, String> },
+    /// Set `key=value` entries in a properties-style config, e.g. to accept a mod's first-run prompt
+    SetConfig {
+        path: String,
+        values: BTreeMap<String, String>,
+    },
+    /// Copy a file or folder shipped with the pack, e.g. a world preset into saves/
+    Copy {
+        from: String,
+        to: String,
+        #[serde(default)]
+        overwrite: bool,
+    },
+    WriteFile {
+        path: String,
+        content: String,
+        #[serde(default)]
+        overwrite: bool,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetupStepStatus {
+    pub id: String,
+    #[serde(flatten)]
+    pub action: SetupAction,
+    pub completed: bool,
+}
+
+pub struct PackSetupRunner;
+
+impl PackSetupRunner {
+    pub fn load(instance_dir: &Path) -> Result<Option<PackSetup>, Box<dyn std::error::Error>> {
+        let path = instance_dir.join(SETUP_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let setup: PackSetup = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Self::validate(&setup)?;
+        Ok(Some(setup))
+    }
+
+    pub fn status(instance: &Instance) -> Result<Vec<SetupStepStatus>, Box<dyn std::error::Error>> {
+        let Some(setup) = Self::load(&get_instance_dir(&instance.name))? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(setup
+            .steps
+            .into_iter()
+            .map(|step| SetupStepStatus {
+                completed: instance.completed_setup_steps.contains(&step.id),
+                id: step.id,
+                action: step.action,
+            })
+            .collect())
+    }
+
+    /// Run the steps this instance hasn't completed yet, in order, saving progress after each.
+    /// Stops at the first failing step so later steps never run on a half-done setup.
+    pub fn run_pending(instance: &mut Instance) -> Result<usize, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let Some(setup) = Self::load(&instance_dir)? else {
+            return Ok(0);
+        };
+
+        let mut ran = 0;
+        for step in &setup.steps {
+            if instance.completed_setup_steps.contains(&step.id) {
+                continue;
+            }
+
+            println!("  → Running pack setup step '{}'", step.id);
+            apply(&step.action, &instance_dir).map_err(|e| format!("Setup step '{}' failed: {}", step.id, e))?;
+
+            instance.completed_setup_steps.push(step.id.clone());
+            InstanceManager::save(instance)?;
+            ran += 1;
+        }
+
+        if ran > 0 {
+            println!("✓ Ran {} pack setup steps for '{}'", ran, instance.name);
+        }
+        Ok(ran)
+    }
+
+    fn validate(setup: &PackSetup) -> Result<(), String> {
+        let mut ids: Vec<&str> = Vec::new();
+
+        for step in &setup.steps {
+            if step.id.trim().is_empty() {
+                return Err("Setup step without an id".to_string());
+            }
+            if ids.contains(&step.id.as_str()) {
+                return Err(format!("Duplicate setup step id '{}'", step.id));
+            }
+            ids.push(&step.id);
+
+            let paths: Vec<&str> = match &step.action {
+                SetupAction::SetOptions { .. } => Vec::new(),
+                SetupAction::SetConfig { path, .. } | SetupAction::WriteFile { path, .. } => vec![path.as_str()],
+                SetupAction::Copy { from, to, .. } => vec![from.as_str(), to.as_str()],
+            };
+            if let Some(path) = paths.iter().find(|p| !is_safe_pack_path(p)) {
+                return Err(format!("Setup step '{}' uses unsafe path '{}'", step.id, path));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn apply(action: &SetupAction, instance_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        SetupAction::SetOptions { values } => {
+            let values: Vec<(&str, String)> = values.iter().map(|(k, v)| (k.as_str(), v.clone())).collect();
+            set_options(&instance_dir.join("options.txt"), &values)?;
+        }
+        SetupAction::SetConfig { path, values } => {
+            let config_path = long_path(&instance_dir.join(path));
+            let existing = fs::read_to_string(&config_path).unwrap_or_default();
+
+            let mut lines: Vec<String> = existing.lines().map(|s| s.to_string()).collect();
+            for (key, value) in values {
+                let entry = format!("{}={}", key, value);
+                let existing_line = lines.iter().position(|l| {
+                    l.split_once('=').map(|(k, _)| k.trim() == key).unwrap_or(false)
+                });
+                match existing_line {
+                    Some(pos) => lines[pos] = entry,
+                    None => lines.push(entry),
+                }
+            }
+
+            if let Some(parent) = config_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&config_path, lines.join("\n") + "\n")?;
+        }
+        SetupAction::Copy { from, to, overwrite } => {
+            let source = instance_dir.join(from);
+            let target = instance_dir.join(to);
+            if target.exists() && !overwrite {
+                return Ok(());
+            }
+
+            if source.is_dir() {
+                copy_dir_recursive(&source, &target)?;
+            } else if source.is_file() {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(long_path(parent))?;
+                }
+                fs::copy(long_path(&source), long_path(&target))?;
+            } else {
+                return Err(format!("'{}' is not in the pack", from).into());
+            }
+        }
+        SetupAction::WriteFile { path, content, overwrite } => {
+            let target = long_path(&instance_dir.join(path));
+            if target.exists() && !overwrite {
+                return Ok(());
+            }
+
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, content)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,543 @@
+use crate::commands::validation::{sanitize_pack_filename, validate_curseforge_url, validate_download_url};
+use crate::models::{CurseForgeManifest, Instance};
+use crate::services::curseforge::CurseForgeInstaller;
+use crate::services::instance::InstanceManager;
+use crate::services::mrpack::MrpackReader;
+use crate::utils::archive::read_entry;
+use crate::utils::curseforge::{fingerprint, folder_for_class};
+use crate::utils::modrinth::ModrinthClient;
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+/// Folders pack content is installed into, compared file by file
+const CONTENT_FOLDERS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+/// The pack version an instance would be updated to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "platform", rename_all = "snake_case")]
+pub enum PackUpdateSource {
+    Modrinth { version_id: String },
+    Curseforge { file_id: u32 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PackModChange {
+    /// Modrinth project id or CurseForge mod id, the path for files neither platform knows
+    pub project_id: String,
+    pub name: String,
+    /// Installed file, relative to the instance folder
+    pub from_path: Option<String>,
+    pub to_path: Option<String>,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideChangeKind {
+    Added,
+    Modified,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OverrideChange {
+    pub path: String,
+    pub kind: OverrideChangeKind,
+}
+
+/// What updating the instance to a pack version would change, worked out without touching it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PackUpdatePreview {
+    pub pack_name: String,
+    pub pack_version: String,
+    pub added: Vec<PackModChange>,
+    pub removed: Vec<PackModChange>,
+    pub updated: Vec<PackModChange>,
+    pub unchanged: usize,
+    /// Files in the content folders that aren't from either platform, most likely added by the player
+    pub untracked: Vec<String>,
+    /// Pack overrides, e.g. config files, that differ from the instance's copy
+    pub overrides: Vec<OverrideChange>,
+    pub unchanged_overrides: usize,
+    /// Things an update doesn't change on its own, like a new Minecraft or loader version
+    pub warnings: Vec<String>,
+}
+
+/// One content file on either side of the comparison
+struct PackEntry {
+    /// Project id, or "path:<path>" when the platform doesn't know the file
+    key: String,
+    path: String,
+    sha1: Option<String>,
+    version: Option<String>,
+}
+
+/// The new pack version, downloaded to a temporary file
+struct FetchedPack {
+    archive: PathBuf,
+    name: String,
+    version: String,
+    entries: Vec<PackEntry>,
+    override_prefixes: Vec<String>,
+    minecraft_version: Option<String>,
+    loader: Option<(String, String)>,
+}
+
+pub struct PackUpdatePreviewer;
+
+impl PackUpdatePreviewer {
+    /// Download the pack version to a temporary file and compare it with the instance's mods and
+    /// overrides. Nothing in the instance is changed.
+    pub async fn preview(
+        instance: &Instance,
+        source: &PackUpdateSource,
+    ) -> Result<PackUpdatePreview, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(&instance.name);
+        let local_files = content_files(&instance_dir);
+
+        let (mut pack, mut names) = match source {
+            PackUpdateSource::Modrinth { version_id } => (fetch_modrinth(instance, version_id).await?, HashMap::new()),
+            PackUpdateSource::Curseforge { file_id } => fetch_curseforge(instance, *file_id).await?,
+        };
+
+        let identified = match source {
+            PackUpdateSource::Modrinth { .. } => identify_modrinth(&local_files, &mut pack.entries).await,
+            PackUpdateSource::Curseforge { .. } => identify_curseforge(&local_files).await,
+        };
+        let result = identified.and_then(|(local_entries, local_names)| {
+            names.extend(local_names);
+            compare(instance, &instance_dir, &pack, &local_entries, &names)
+        });
+        let _ = fs::remove_file(&pack.archive);
+        let preview = result?;
+
+        println!(
+            "✓ Previewed update of '{}' to {} {}: {} added, {} removed, {} updated, {} overrides changed",
+            instance.name,
+            preview.pack_name,
+            preview.pack_version,
+            preview.added.len(),
+            preview.removed.len(),
+            preview.updated.len(),
+            preview.overrides.len()
+        );
+        Ok(preview)
+    }
+}
+
+fn compare(
+    instance: &Instance,
+    instance_dir: &Path,
+    pack: &FetchedPack,
+    local_entries: &[PackEntry],
+    names: &HashMap<String, String>,
+) -> Result<PackUpdatePreview, Box<dyn std::error::Error>> {
+    let name_of = |key: &str, path: &str| {
+        names.get(key).cloned().unwrap_or_else(|| {
+            Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        })
+    };
+
+    let mut preview = PackUpdatePreview {
+        pack_name: pack.name.clone(),
+        pack_version: pack.version.clone(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        updated: Vec::new(),
+        unchanged: 0,
+        untracked: Vec::new(),
+        overrides: Vec::new(),
+        unchanged_overrides: 0,
+        warnings: Vec::new(),
+    };
+
+    let local_by_key: HashMap<&str, &PackEntry> = local_entries.iter().map(|e| (e.key.as_str(), e)).collect();
+    for entry in &pack.entries {
+        match local_by_key.get(entry.key.as_str()) {
+            Some(local) if local.sha1.is_some() && local.sha1 == entry.sha1 => preview.unchanged += 1,
+            Some(local) if entry.sha1.is_none() && local.path == entry.path => preview.unchanged += 1,
+            Some(local) => preview.updated.push(PackModChange {
+                project_id: entry.key.clone(),
+                name: name_of(&entry.key, &entry.path),
+                from_path: Some(local.path.clone()),
+                to_path: Some(entry.path.clone()),
+                from_version: local.version.clone(),
+                to_version: entry.version.clone(),
+            }),
+            None => preview.added.push(PackModChange {
+                project_id: entry.key.clone(),
+                name: name_of(&entry.key, &entry.path),
+                from_path: None,
+                to_path: Some(entry.path.clone()),
+                from_version: None,
+                to_version: entry.version.clone(),
+            }),
+        }
+    }
+
+    let pack_keys: HashSet<&str> = pack.entries.iter().map(|e| e.key.as_str()).collect();
+    for local in local_entries.iter().filter(|e| !pack_keys.contains(e.key.as_str())) {
+        if local.key.starts_with("path:") {
+            preview.untracked.push(local.path.clone());
+        } else {
+            preview.removed.push(PackModChange {
+                project_id: local.key.clone(),
+                name: name_of(&local.key, &local.path),
+                from_path: Some(local.path.clone()),
+                to_path: None,
+                from_version: local.version.clone(),
+                to_version: None,
+            });
+        }
+    }
+
+    compare_overrides(&pack.archive, &pack.override_prefixes, instance_dir, &mut preview)?;
+
+    let installed_minecraft = InstanceManager::base_minecraft_version(&instance.version);
+    if let Some(minecraft) = &pack.minecraft_version {
+        if *minecraft != installed_minecraft {
+            preview.warnings.push(format!(
+                "The pack moves from Minecraft {} to {}",
+                installed_minecraft, minecraft
+            ));
+        }
+    }
+    if let Some((loader, loader_version)) = &pack.loader {
+        if instance.loader.as_deref() != Some(loader.as_str()) {
+            preview.warnings.push(format!(
+                "The pack uses {} {}, the instance runs {}",
+                loader,
+                loader_version,
+                instance.loader.as_deref().unwrap_or("vanilla")
+            ));
+        } else if instance.loader_version.as_deref() != Some(loader_version.as_str()) {
+            preview.warnings.push(format!("The pack now uses {} {}", loader, loader_version));
+        }
+    }
+
+    Ok(preview)
+}
+
+/// Override files that would be added or would replace a different local copy
+fn compare_overrides(
+    archive_path: &Path,
+    prefixes: &[String],
+    instance_dir: &Path,
+    preview: &mut PackUpdatePreview,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(fs::File::open(archive_path)?)?;
+    // Later prefixes win, like when they're extracted
+    let mut changes: HashMap<String, Option<OverrideChangeKind>> = HashMap::new();
+
+    for prefix in prefixes {
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let Some(enclosed) = entry.enclosed_name() else {
+                continue;
+            };
+            let name = enclosed.to_string_lossy().replace('\\', "/");
+            let Some(relative) = name.strip_prefix(prefix.as_str()) else {
+                continue;
+            };
+            if relative.is_empty() {
+                continue;
+            }
+
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            let kind = match fs::read(long_path(&instance_dir.join(relative))) {
+                Ok(local) if local == bytes => None,
+                Ok(_) => Some(OverrideChangeKind::Modified),
+                Err(_) => Some(OverrideChangeKind::Added),
+            };
+            changes.insert(relative.to_string(), kind);
+        }
+    }
+
+    let mut overrides: Vec<OverrideChange> = changes
+        .into_iter()
+        .filter_map(|(path, kind)| match kind {
+            Some(kind) => Some(OverrideChange { path, kind }),
+            None => {
+                preview.unchanged_overrides += 1;
+                None
+            }
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.path.cmp(&b.path));
+    preview.overrides = overrides;
+    Ok(())
+}
+
+/// Files in the instance's content folders with their SHA1, paths relative to the instance
+fn content_files(instance_dir: &Path) -> Vec<(String, Vec<u8>)> {
+    let mut files = Vec::new();
+    for folder in CONTENT_FOLDERS {
+        let Ok(entries) = fs::read_dir(instance_dir.join(folder)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            if let Ok(bytes) = fs::read(long_path(&path)) {
+                files.push((format!("{}/{}", folder, entry.file_name().to_string_lossy()), bytes));
+            }
+        }
+    }
+    files
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+async fn fetch_modrinth(instance: &Instance, version_id: &str) -> Result<FetchedPack, Box<dyn std::error::Error>> {
+    if !version_id.chars().all(|c| c.is_alphanumeric()) {
+        return Err("Invalid version ID format".into());
+    }
+
+    let client = ModrinthClient::new();
+    let version = client
+        .get_versions(&[version_id.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Modrinth version {} not found", version_id))?;
+    let file = version
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+        .ok_or("The pack version has no files")?;
+    validate_download_url(&file.url)?;
+
+    let archive = std::env::temp_dir().join(format!("pack_update_{}_{}.mrpack", instance.name, version.id));
+    client.download_mod_file(&file.url, &archive).await?;
+
+    let index = match MrpackReader::read_index(&archive) {
+        Ok(index) => index,
+        Err(e) => {
+            let _ = fs::remove_file(&archive);
+            return Err(e);
+        }
+    };
+
+    let entries = MrpackReader::client_files(&index)
+        .into_iter()
+        .map(|f| PackEntry {
+            key: format!("path:{}", f.path),
+            path: f.path.clone(),
+            sha1: f.hashes.get("sha1").cloned(),
+            version: None,
+        })
+        .collect();
+
+    let loader = ["fabric-loader", "quilt-loader", "forge", "neoforge"]
+        .iter()
+        .find_map(|name| index.dependencies.get(*name).map(|v| (name.trim_end_matches("-loader").to_string(), v.clone())));
+
+    Ok(FetchedPack {
+        archive,
+        name: index.name.clone(),
+        version: version.version_number,
+        entries,
+        override_prefixes: vec!["overrides/".to_string(), "client-overrides/".to_string()],
+        minecraft_version: index.dependencies.get("minecraft").cloned(),
+        loader,
+    })
+}
+
+/// Look up the pack's and the instance's files on Modrinth by hash, so the same project is
+/// matched across versions. Keys the pack entries in place, returns the local entries and
+/// project titles.
+async fn identify_modrinth(
+    local_files: &[(String, Vec<u8>)],
+    pack_entries: &mut [PackEntry],
+) -> Result<(Vec<PackEntry>, HashMap<String, String>), Box<dyn std::error::Error>> {
+    let mut local_entries: Vec<PackEntry> = local_files
+        .iter()
+        .map(|(path, bytes)| PackEntry {
+            key: format!("path:{}", path),
+            path: path.clone(),
+            sha1: Some(sha1_hex(bytes)),
+            version: None,
+        })
+        .collect();
+
+    let hashes: Vec<String> = local_entries
+        .iter()
+        .chain(pack_entries.iter())
+        .filter_map(|e| e.sha1.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if hashes.is_empty() {
+        return Ok((local_entries, HashMap::new()));
+    }
+
+    let client = ModrinthClient::new();
+    let versions = client.get_versions_from_hashes(&hashes).await?;
+    let project_ids: Vec<String> = versions
+        .values()
+        .map(|v| v.project_id.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let names: HashMap<String, String> = if project_ids.is_empty() {
+        HashMap::new()
+    } else {
+        client
+            .get_projects(&project_ids)
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p.title))
+            .collect()
+    };
+
+    for entry in local_entries.iter_mut().chain(pack_entries.iter_mut()) {
+        if let Some(version) = entry.sha1.as_ref().and_then(|h| versions.get(h)) {
+            entry.key = version.project_id.clone();
+            entry.version = Some(version.version_number.clone());
+        }
+    }
+
+    Ok((local_entries, names))
+}
+
+async fn fetch_curseforge(
+    instance: &Instance,
+    file_id: u32,
+) -> Result<(FetchedPack, HashMap<String, String>), Box<dyn std::error::Error>> {
+    let client = CurseForgeInstaller::client()?;
+    let pack_file = client
+        .get_files(&[file_id])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("CurseForge file {} not found", file_id))?;
+    let url = pack_file
+        .download_url
+        .as_deref()
+        .ok_or("The pack's author disabled third-party downloads, download it from CurseForge and import it instead")?;
+    validate_curseforge_url(url)?;
+
+    let archive = std::env::temp_dir().join(format!("pack_update_{}_{}.zip", instance.name, file_id));
+    fs::write(&archive, client.download_file(url).await?)?;
+
+    let manifest: CurseForgeManifest = match read_entry(&archive, "manifest.json").map(|b| serde_json::from_slice(&b)) {
+        Some(Ok(manifest)) => manifest,
+        Some(Err(e)) => {
+            let _ = fs::remove_file(&archive);
+            return Err(format!("Failed to parse manifest: {}", e).into());
+        }
+        None => {
+            let _ = fs::remove_file(&archive);
+            return Err("Invalid modpack: manifest.json not found".into());
+        }
+    };
+
+    // Optional files are left out, the same as on import
+    let file_ids: Vec<u32> = manifest.files.iter().filter(|f| f.required).map(|f| f.file_id).collect();
+    let files = client.get_files(&file_ids).await?;
+    let mod_ids: Vec<u32> = files.iter().map(|f| f.mod_id).collect();
+    let mods = client.get_mods(&mod_ids).await?;
+
+    let classes: HashMap<u32, Option<u32>> = mods.iter().map(|m| (m.id, m.class_id)).collect();
+    let names: HashMap<String, String> = mods.into_iter().map(|m| (m.id.to_string(), m.name)).collect();
+
+    let entries = files
+        .iter()
+        .map(|f| {
+            let folder = folder_for_class(classes.get(&f.mod_id).copied().flatten());
+            let file_name = sanitize_pack_filename(&f.file_name)?;
+            Ok(PackEntry {
+                key: f.mod_id.to_string(),
+                path: format!("{}/{}", folder, file_name),
+                sha1: f.sha1().map(String::from),
+                version: Some(f.file_name.clone()),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let loader = manifest
+        .minecraft
+        .mod_loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| manifest.minecraft.mod_loaders.first())
+        .and_then(|l| l.id.split_once('-'))
+        .map(|(name, version)| (name.to_string(), version.to_string()));
+
+    Ok((
+        FetchedPack {
+            archive,
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            entries,
+            override_prefixes: vec![format!("{}/", manifest.overrides.trim_end_matches('/'))],
+            minecraft_version: Some(manifest.minecraft.version.clone()),
+            loader,
+        },
+        names,
+    ))
+}
+
+/// Match the instance's files to CurseForge mods by fingerprint. Returns the entries and mod names.
+async fn identify_curseforge(
+    local_files: &[(String, Vec<u8>)],
+) -> Result<(Vec<PackEntry>, HashMap<String, String>), Box<dyn std::error::Error>> {
+    let fingerprints: Vec<u32> = local_files.iter().map(|(_, bytes)| fingerprint(bytes)).collect();
+    if fingerprints.is_empty() {
+        return Ok((Vec::new(), HashMap::new()));
+    }
+
+    let client = CurseForgeInstaller::client()?;
+    let matches = client.get_fingerprint_matches(&fingerprints).await?;
+    let mod_ids: Vec<u32> = matches.values().map(|f| f.mod_id).collect::<HashSet<_>>().into_iter().collect();
+    let names: HashMap<String, String> = if mod_ids.is_empty() {
+        HashMap::new()
+    } else {
+        client
+            .get_mods(&mod_ids)
+            .await?
+            .into_iter()
+            .map(|m| (m.id.to_string(), m.name))
+            .collect()
+    };
+
+    let entries = local_files
+        .iter()
+        .zip(fingerprints)
+        .map(|((path, bytes), print)| match matches.get(&print) {
+            Some(file) => PackEntry {
+                key: file.mod_id.to_string(),
+                path: path.clone(),
+                sha1: Some(sha1_hex(bytes)),
+                version: Some(file.file_name.clone()),
+            },
+            None => PackEntry {
+                key: format!("path:{}", path),
+                path: path.clone(),
+                sha1: Some(sha1_hex(bytes)),
+                version: None,
+            },
+        })
+        .collect();
+
+    Ok((entries, names))
+}
@@ -0,0 +1,548 @@
+use crate::models::{CurseForgeManifest, CurseForgeManifestFile, MrpackFile, MrpackIndex};
+use crate::services::curseforge::CurseForgeInstaller;
+use crate::services::fabric::FabricInstaller;
+use crate::services::installer::MinecraftInstaller;
+use crate::utils::curseforge::CurseForgeFile;
+use crate::utils::get_meta_dir;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashMap, fs, io::Read, path::Path, sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
+use zip::ZipArchive;
+
+/// Hosts allowed by the .mrpack specification
+pub const MRPACK_ALLOWED_HOSTS: &[&str] = &[
+    "cdn.modrinth.com",
+    "github.com",
+    "raw.githubusercontent.com",
+    "gitlab.com",
+];
+const MAX_CONCURRENT_CHECKS: usize = 16;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PackFileCheck {
+    pub path: String,
+    pub url: Option<String>,
+    /// "ok", "invalid", "unreachable" or "hash_mismatch"
+    pub status: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PackValidationReport {
+    pub file: String,
+    pub format: String,
+    pub name: Option<String>,
+    pub pack_version: Option<String>,
+    pub game_version: Option<String>,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    pub files_total: usize,
+    pub files_ok: usize,
+    pub files: Vec<PackFileCheck>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub valid: bool,
+}
+
+pub struct PackValidator;
+
+impl PackValidator {
+    /// Fully check a .mrpack or CurseForge zip without installing anything.
+    /// With `verify_hashes` every file is downloaded and its SHA1 compared, otherwise URLs are only probed.
+    pub async fn validate(path: &Path, verify_hashes: bool) -> PackValidationReport {
+        let mut report = PackValidationReport {
+            file: path.to_string_lossy().to_string(),
+            format: "unknown".to_string(),
+            ..Default::default()
+        };
+
+        let entries = match read_archive_manifests(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                report.errors.push(format!("Failed to read archive: {}", e));
+                return report;
+            }
+        };
+
+        if let Some(index) = entries.mrpack_index {
+            report.format = "mrpack".to_string();
+            match serde_json::from_str::<MrpackIndex>(&index) {
+                Ok(index) => Self::validate_mrpack(&index, verify_hashes, &mut report).await,
+                Err(e) => report.errors.push(format!("Invalid modrinth.index.json: {}", e)),
+            }
+        } else if let Some(manifest) = entries.curseforge_manifest {
+            report.format = "curseforge".to_string();
+            match serde_json::from_str::<CurseForgeManifest>(&manifest) {
+                Ok(manifest) => Self::validate_curseforge(&manifest, &entries.names, verify_hashes, &mut report).await,
+                Err(e) => report.errors.push(format!("Invalid manifest.json: {}", e)),
+            }
+        } else {
+            report
+                .errors
+                .push("Archive contains neither modrinth.index.json nor manifest.json".to_string());
+        }
+
+        for name in entries.unsafe_entries {
+            report.errors.push(format!("Archive entry escapes the pack directory: {}", name));
+        }
+
+        report.files_ok = report.files.iter().filter(|f| f.status == "ok").count();
+        report.valid = report.errors.is_empty() && report.files.iter().all(|f| f.status == "ok");
+        report
+    }
+
+    async fn validate_mrpack(index: &MrpackIndex, verify_hashes: bool, report: &mut PackValidationReport) {
+        report.name = Some(index.name.clone());
+        report.pack_version = Some(index.version_id.clone());
+
+        if index.format_version != 1 {
+            report
+                .errors
+                .push(format!("Unsupported formatVersion {}", index.format_version));
+        }
+
+        if index.game != "minecraft" {
+            report.errors.push(format!("Unsupported game '{}'", index.game));
+        }
+
+        match index.dependencies.get("minecraft") {
+            Some(version) => report.game_version = Some(version.clone()),
+            None => report
+                .errors
+                .push("Missing 'minecraft' dependency".to_string()),
+        }
+
+        let loaders: Vec<(&String, &String)> = index
+            .dependencies
+            .iter()
+            .filter(|(k, _)| k.as_str() != "minecraft")
+            .collect();
+
+        if loaders.len() > 1 {
+            report.errors.push(format!(
+                "Pack declares more than one loader: {}",
+                loaders.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(", ")
+            ));
+        }
+
+        if let Some((loader, version)) = loaders.first() {
+            let loader = match loader.as_str() {
+                "fabric-loader" => "fabric",
+                "quilt-loader" => "quilt",
+                "forge" => "forge",
+                "neoforge" => "neoforge",
+                other => {
+                    report.errors.push(format!("Unknown loader dependency '{}'", other));
+                    other
+                }
+            };
+            report.loader = Some(loader.to_string());
+            report.loader_version = Some((*version).clone());
+        }
+
+        Self::check_versions(report).await;
+
+        report.files_total = index.files.len();
+        report.files = check_mrpack_files(&index.files, verify_hashes).await;
+    }
+
+    async fn validate_curseforge(
+        manifest: &CurseForgeManifest,
+        entry_names: &[String],
+        verify_hashes: bool,
+        report: &mut PackValidationReport,
+    ) {
+        report.name = Some(manifest.name.clone());
+        report.pack_version = Some(manifest.version.clone());
+        report.game_version = Some(manifest.minecraft.version.clone());
+
+        if manifest.manifest_type != "minecraftModpack" {
+            report
+                .errors
+                .push(format!("Unsupported manifestType '{}'", manifest.manifest_type));
+        }
+
+        let primary_loaders: Vec<_> = manifest.minecraft.mod_loaders.iter().filter(|l| l.primary).collect();
+        if primary_loaders.len() > 1 {
+            report.errors.push("Pack declares more than one primary loader".to_string());
+        }
+
+        if let Some(loader) = primary_loaders.first().copied().or(manifest.minecraft.mod_loaders.first()) {
+            // CurseForge loader ids look like "forge-47.2.0" or "fabric-0.15.7"
+            match loader.id.split_once('-') {
+                Some((name, version)) => {
+                    report.loader = Some(name.to_string());
+                    report.loader_version = Some(version.to_string());
+                }
+                None => report.errors.push(format!("Malformed loader id '{}'", loader.id)),
+            }
+        }
+
+        Self::check_versions(report).await;
+
+        let overrides_prefix = format!("{}/", manifest.overrides);
+        if !entry_names.iter().any(|n| n.starts_with(&overrides_prefix)) {
+            report
+                .warnings
+                .push(format!("No '{}' folder found in archive", manifest.overrides));
+        }
+
+        report.files_total = manifest.files.len();
+
+        let client = match CurseForgeInstaller::client() {
+            Ok(client) => client,
+            Err(e) => {
+                report.errors.push(format!("Could not resolve CurseForge files: {}", e));
+                return;
+            }
+        };
+
+        let file_ids: Vec<u32> = manifest.files.iter().map(|f| f.file_id).collect();
+        let resolved = match client.get_files(&file_ids).await {
+            Ok(files) => files,
+            Err(e) => {
+                report.errors.push(format!("Could not resolve CurseForge files: {}", e));
+                return;
+            }
+        };
+
+        let manual = resolved.iter().filter(|f| f.download_url.is_none()).count();
+        if manual > 0 {
+            report.warnings.push(format!(
+                "{} file(s) can only be downloaded from the CurseForge website",
+                manual
+            ));
+        }
+
+        report.files = check_curseforge_files(&manifest.files, resolved, verify_hashes).await;
+    }
+
+    /// Confirm the game version exists and the loader version matches it
+    async fn check_versions(report: &mut PackValidationReport) {
+        let Some(game_version) = report.game_version.clone() else {
+            return;
+        };
+
+        let installer = MinecraftInstaller::new(get_meta_dir());
+        match installer.get_versions().await {
+            Ok(versions) if !versions.contains(&game_version) => {
+                report
+                    .errors
+                    .push(format!("Minecraft version '{}' does not exist", game_version));
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => report
+                .warnings
+                .push(format!("Could not verify Minecraft version: {}", e)),
+        }
+
+        let (Some(loader), Some(loader_version)) = (report.loader.clone(), report.loader_version.clone()) else {
+            return;
+        };
+
+        if loader == "fabric" {
+            let fabric = FabricInstaller::new(get_meta_dir());
+            if let Err(e) = fabric.get_fabric_profile(&game_version, &loader_version).await {
+                report.errors.push(format!(
+                    "Fabric loader {} is not available for Minecraft {}: {}",
+                    loader_version, game_version, e
+                ));
+            }
+        } else {
+            report.warnings.push(format!(
+                "{} {} compatibility with Minecraft {} was not verified",
+                loader, loader_version, game_version
+            ));
+        }
+    }
+}
+
+struct ArchiveManifests {
+    names: Vec<String>,
+    unsafe_entries: Vec<String>,
+    mrpack_index: Option<String>,
+    curseforge_manifest: Option<String>,
+}
+
+fn read_archive_manifests(path: &Path) -> Result<ArchiveManifests, Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(fs::File::open(path)?)?;
+    let mut manifests = ArchiveManifests {
+        names: Vec::new(),
+        unsafe_entries: Vec::new(),
+        mrpack_index: None,
+        curseforge_manifest: None,
+    };
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry.name().to_string();
+
+        if entry.enclosed_name().is_none() {
+            manifests.unsafe_entries.push(name.clone());
+        }
+
+        if name == "modrinth.index.json" || name == "manifest.json" {
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+
+            if name == "modrinth.index.json" {
+                manifests.mrpack_index = Some(content);
+            } else {
+                manifests.curseforge_manifest = Some(content);
+            }
+        }
+
+        manifests.names.push(name);
+    }
+
+    Ok(manifests)
+}
+
+/// Relative, no parent traversal, no drive letters
+pub fn is_safe_pack_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.starts_with('\\')
+        && !path.contains(':')
+        && !path.split(['/', '\\']).any(|part| part == "..")
+}
+
+pub fn is_allowed_pack_url(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+
+    if parsed.scheme() != "https" {
+        return Err("URL is not HTTPS".to_string());
+    }
+
+    match parsed.host_str() {
+        Some(host) if MRPACK_ALLOWED_HOSTS.contains(&host) => Ok(()),
+        Some(host) => Err(format!("Host '{}' is not allowed by the .mrpack format", host)),
+        None => Err("URL has no host".to_string()),
+    }
+}
+
+async fn check_mrpack_files(files: &[MrpackFile], verify_hashes: bool) -> Vec<PackFileCheck> {
+    let client = crate::utils::http::client_builder()
+        .timeout(Duration::from_secs(60))
+        .user_agent("AtomicLauncher/2.4.0")
+        .build()
+        .unwrap();
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_CHECKS));
+    let mut handles = Vec::new();
+
+    for file in files.iter().cloned() {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.unwrap();
+            check_mrpack_file(&client, &file, verify_hashes).await
+        }));
+    }
+
+    let mut checks = Vec::new();
+    for handle in handles {
+        if let Ok(check) = handle.await {
+            checks.push(check);
+        }
+    }
+
+    checks
+}
+
+async fn check_mrpack_file(client: &reqwest::Client, file: &MrpackFile, verify_hashes: bool) -> PackFileCheck {
+    let invalid = |detail: String| PackFileCheck {
+        path: file.path.clone(),
+        url: file.downloads.first().cloned(),
+        status: "invalid".to_string(),
+        detail: Some(detail),
+    };
+
+    if !is_safe_pack_path(&file.path) {
+        return invalid("Path escapes the instance directory".to_string());
+    }
+
+    if let Err(e) = check_hash_fields(&file.hashes) {
+        return invalid(e);
+    }
+
+    if file.downloads.is_empty() {
+        return invalid("No download URLs".to_string());
+    }
+
+    // Any working mirror is enough
+    let mut last_failure = ("invalid", String::new());
+    for url in &file.downloads {
+        if let Err(e) = is_allowed_pack_url(url) {
+            last_failure = ("invalid", e);
+            continue;
+        }
+
+        let result = if verify_hashes {
+            match download_and_hash(client, url).await {
+                Ok(sha1) if Some(&sha1) == file.hashes.get("sha1") => Ok(()),
+                Ok(sha1) => Err(("hash_mismatch", format!("Expected SHA1 {}, got {}", file.hashes["sha1"], sha1))),
+                Err(e) => Err(("unreachable", e)),
+            }
+        } else {
+            probe_url(client, url).await.map_err(|e| ("unreachable", e))
+        };
+
+        match result {
+            Ok(()) => {
+                return PackFileCheck {
+                    path: file.path.clone(),
+                    url: Some(url.clone()),
+                    status: "ok".to_string(),
+                    detail: None,
+                }
+            }
+            Err(failure) => last_failure = failure,
+        }
+    }
+
+    PackFileCheck {
+        path: file.path.clone(),
+        url: file.downloads.first().cloned(),
+        status: last_failure.0.to_string(),
+        detail: Some(last_failure.1),
+    }
+}
+
+async fn check_curseforge_files(
+    files: &[CurseForgeManifestFile],
+    resolved: Vec<CurseForgeFile>,
+    verify_hashes: bool,
+) -> Vec<PackFileCheck> {
+    let client = crate::utils::http::client_builder()
+        .timeout(Duration::from_secs(60))
+        .user_agent("AtomicLauncher/2.4.0")
+        .build()
+        .unwrap();
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_CHECKS));
+    let resolved: HashMap<u32, CurseForgeFile> = resolved.into_iter().map(|f| (f.id, f)).collect();
+    let mut handles = Vec::new();
+
+    for file in files {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let wanted = (file.project_id, file.file_id);
+        let found = resolved.get(&file.file_id).cloned();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.unwrap();
+            check_curseforge_file(&client, wanted, found, verify_hashes).await
+        }));
+    }
+
+    let mut checks = Vec::new();
+    for handle in handles {
+        if let Ok(check) = handle.await {
+            checks.push(check);
+        }
+    }
+
+    checks
+}
+
+async fn check_curseforge_file(
+    client: &reqwest::Client,
+    (project_id, file_id): (u32, u32),
+    file: Option<CurseForgeFile>,
+    verify_hashes: bool,
+) -> PackFileCheck {
+    let path = format!("{}/{}", project_id, file_id);
+    let check = |url: Option<String>, status: &str, detail: Option<String>| PackFileCheck {
+        path: path.clone(),
+        url,
+        status: status.to_string(),
+        detail,
+    };
+
+    let Some(file) = file else {
+        return check(None, "invalid", Some("CurseForge has no such file".to_string()));
+    };
+
+    if file.mod_id != project_id {
+        return check(
+            file.download_url.clone(),
+            "invalid",
+            Some(format!("File belongs to project {}, not {}", file.mod_id, project_id)),
+        );
+    }
+
+    let sha1 = match file.sha1() {
+        Some(hash) if hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()) => hash.to_lowercase(),
+        Some(_) => return check(file.download_url.clone(), "invalid", Some("Malformed sha1 hash".to_string())),
+        None => return check(file.download_url.clone(), "invalid", Some("Missing sha1 hash".to_string())),
+    };
+
+    // Files with third-party downloads disabled are fetched by hand during import
+    let Some(url) = file.download_url.clone() else {
+        return check(None, "ok", Some(format!("Manual download: {}", file.file_name)));
+    };
+
+    let result = if verify_hashes {
+        match download_and_hash(client, &url).await {
+            Ok(actual) if actual == sha1 => Ok(()),
+            Ok(actual) => Err(("hash_mismatch", format!("Expected SHA1 {}, got {}", sha1, actual))),
+            Err(e) => Err(("unreachable", e)),
+        }
+    } else {
+        probe_url(client, &url).await.map_err(|e| ("unreachable", e))
+    };
+
+    match result {
+        Ok(()) => check(Some(url), "ok", None),
+        Err((status, detail)) => check(Some(url), status, Some(detail)),
+    }
+}
+
+fn check_hash_fields(hashes: &HashMap<String, String>) -> Result<(), String> {
+    for (algorithm, length) in [("sha1", 40), ("sha512", 128)] {
+        match hashes.get(algorithm) {
+            Some(hash) if hash.len() == length && hash.chars().all(|c| c.is_ascii_hexdigit()) => {}
+            Some(_) => return Err(format!("Malformed {} hash", algorithm)),
+            None => return Err(format!("Missing {} hash", algorithm)),
+        }
+    }
+
+    Ok(())
+}
+
+async fn probe_url(client: &reqwest::Client, url: &str) -> Result<(), String> {
+    let response = client.head(url).send().await.map_err(|e| e.to_string())?;
+
+    if response.status().is_success() {
+        return Ok(());
+    }
+
+    // Some hosts reject HEAD, fall back to fetching a single byte
+    let response = client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+async fn download_and_hash(client: &reqwest::Client, url: &str) -> Result<String, String> {
+    let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    let mut hasher = Sha1::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,192 @@
+use crate::models::{AssetIndexData, FabricProfileJson, Instance, VersionDetails};
+use crate::services::installer::library_artifacts;
+use crate::services::instance::InstanceManager;
+use crate::services::forge::library_path;
+use crate::utils::{get_current_os, get_instance_dir, get_meta_dir, long_path};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{fs, path::Path};
+
+/// Copies of the game files inside the instance folder, laid out like the meta folder
+pub const PINNED_DIR: &str = "pinned";
+const PIN_MANIFEST: &str = "pin.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PinnedFile {
+    /// Relative to the meta folder, e.g. `versions/1.7.10/1.7.10.jar`
+    pub path: String,
+    pub sha1: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PinManifest {
+    pub version: String,
+    pub pinned_at: String,
+    pub files: Vec<PinnedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PinReport {
+    pub files: usize,
+    pub size_bytes: u64,
+}
+
+pub struct VersionPinner;
+
+impl VersionPinner {
+    /// Copy the version JSONs, client jar, libraries, asset index and every asset the instance
+    /// uses into pinned/ and turn archive mode on, so it still launches after the remote
+    /// manifests change or disappear
+    pub fn pin(instance: &mut Instance) -> Result<PinReport, Box<dyn std::error::Error>> {
+        let meta_dir = get_meta_dir();
+        let pinned_dir = get_instance_dir(&instance.name).join(PINNED_DIR);
+        let mut files = Vec::new();
+
+        for path in required_files(instance)? {
+            let source = meta_dir.join(&path);
+            let bytes = fs::read(long_path(&source))
+                .map_err(|e| format!("{} is missing ({}), repair the instance before pinning it", path, e))?;
+            let sha1 = sha1_hex(&bytes);
+
+            let target = pinned_dir.join(&path);
+            let unchanged = fs::read(long_path(&target)).is_ok_and(|existing| sha1_hex(&existing) == sha1);
+            if !unchanged {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(long_path(parent))?;
+                }
+                fs::write(long_path(&target), &bytes)?;
+            }
+
+            files.push(PinnedFile {
+                path,
+                sha1,
+                size: bytes.len() as u64,
+            });
+        }
+
+        let manifest = PinManifest {
+            version: instance.version.clone(),
+            pinned_at: Utc::now().to_rfc3339(),
+            files,
+        };
+        fs::write(pinned_dir.join(PIN_MANIFEST), serde_json::to_string_pretty(&manifest)?)?;
+
+        instance.archive_mode = true;
+        InstanceManager::save(instance)?;
+
+        let report = PinReport {
+            files: manifest.files.len(),
+            size_bytes: manifest.files.iter().map(|f| f.size).sum(),
+        };
+        println!("✓ Pinned {} files ({} bytes) for '{}'", report.files, report.size_bytes, instance.name);
+        Ok(report)
+    }
+
+    /// Put back game files that went missing or changed since pinning. Returns how many were
+    /// restored.
+    pub fn restore(instance: &Instance) -> Result<usize, Box<dyn std::error::Error>> {
+        let pinned_dir = get_instance_dir(&instance.name).join(PINNED_DIR);
+        let manifest: PinManifest = serde_json::from_str(&fs::read_to_string(pinned_dir.join(PIN_MANIFEST))?)?;
+        if manifest.version != instance.version {
+            return Err(format!(
+                "Pinned files are for {}, the instance now uses {}. Pin it again.",
+                manifest.version, instance.version
+            )
+            .into());
+        }
+
+        let meta_dir = get_meta_dir();
+        let mut restored = 0;
+        for file in &manifest.files {
+            let target = meta_dir.join(&file.path);
+            if is_intact(&target, file) {
+                continue;
+            }
+
+            let bytes = fs::read(long_path(&pinned_dir.join(&file.path)))
+                .map_err(|e| format!("Pinned copy of {} is missing: {}", file.path, e))?;
+            if sha1_hex(&bytes) != file.sha1 {
+                return Err(format!("Pinned copy of {} is corrupt", file.path).into());
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(long_path(parent))?;
+            }
+            fs::write(long_path(&target), &bytes)?;
+            restored += 1;
+        }
+
+        if restored > 0 {
+            println!("✓ Restored {} pinned files for '{}'", restored, instance.name);
+        }
+        Ok(restored)
+    }
+
+    /// Turn archive mode off and delete the pinned copies
+    pub fn unpin(instance: &mut Instance) -> Result<(), Box<dyn std::error::Error>> {
+        let pinned_dir = get_instance_dir(&instance.name).join(PINNED_DIR);
+        if pinned_dir.exists() {
+            fs::remove_dir_all(&pinned_dir)?;
+        }
+
+        instance.archive_mode = false;
+        InstanceManager::save(instance)?;
+        println!("✓ Unpinned '{}'", instance.name);
+        Ok(())
+    }
+}
+
+/// Meta folder paths the instance launches from
+fn required_files(instance: &Instance) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let meta_dir = get_meta_dir();
+    let base = InstanceManager::base_minecraft_version(&instance.version);
+    let mut files = Vec::new();
+
+    // Loader profile and its libraries, which have no hashes in the profile
+    if instance.version != base {
+        let profile_path = format!("versions/{0}/{0}.json", instance.version);
+        let profile: FabricProfileJson = serde_json::from_str(&fs::read_to_string(meta_dir.join(&profile_path))?)?;
+        files.push(profile_path);
+        for library in &profile.libraries {
+            let path = format!("libraries/{}", library_path(library)?);
+            // Libraries Minecraft also ships are loaded from the vanilla version
+            if meta_dir.join(&path).exists() {
+                files.push(path);
+            }
+        }
+    }
+
+    let json_path = format!("versions/{0}/{0}.json", base);
+    let details: VersionDetails = serde_json::from_str(&fs::read_to_string(meta_dir.join(&json_path))?)?;
+    files.push(json_path);
+    files.push(format!("versions/{0}/{0}.jar", base));
+    for (_, _, artifact) in library_artifacts(&details, &get_current_os()) {
+        files.push(format!("libraries/{}", artifact.path));
+    }
+
+    let index_path = format!("assets/indexes/{}.json", details.asset_index.id);
+    let index: AssetIndexData = serde_json::from_str(&fs::read_to_string(meta_dir.join(&index_path))?)?;
+    files.push(index_path);
+    for object in index.objects.values() {
+        files.push(format!("assets/objects/{}/{}", &object.hash[..2], object.hash));
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Assets are named by their hash, so a matching size is enough. Everything else is hashed.
+fn is_intact(path: &Path, file: &PinnedFile) -> bool {
+    if file.path.starts_with("assets/objects/") {
+        return fs::metadata(long_path(path)).is_ok_and(|m| m.len() == file.size);
+    }
+    fs::read(long_path(path)).is_ok_and(|bytes| sha1_hex(&bytes) == file.sha1)
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
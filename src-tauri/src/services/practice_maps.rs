@@ -0,0 +1,284 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::worlds::unique_folder_name;
+use crate::utils::archive::extract_prefixed;
+use crate::utils::{get_instance_dir, get_meta_dir, long_path};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+/// A catalog file as hosted at one of the URLs in settings
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PracticeMapCatalog {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub maps: Vec<PracticeMap>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PracticeMap {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Zip of the world folder
+    pub url: String,
+    pub sha256: String,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Versions the map was made for, "1.16.x" covers every 1.16 release. Empty means any.
+    #[serde(default)]
+    pub minecraft_versions: Vec<String>,
+    /// Seed the map was generated from, for seed-based practice
+    #[serde(default)]
+    pub seed: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CatalogMap {
+    pub catalog_url: String,
+    pub catalog_name: Option<String>,
+    pub map: PracticeMap,
+    /// None when no instance was given
+    pub compatible: Option<bool>,
+    /// A finished download is cached and installs without fetching again
+    pub downloaded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledPracticeMap {
+    /// Folder under saves/
+    pub folder: String,
+    pub files: usize,
+}
+
+pub struct PracticeMapCatalogs;
+
+impl PracticeMapCatalogs {
+    /// Every map from the configured catalogs. A catalog that can't be fetched is skipped
+    /// with a warning so the others still show.
+    pub async fn list(
+        catalog_urls: &[String],
+        instance: Option<&Instance>,
+    ) -> Result<Vec<CatalogMap>, Box<dyn std::error::Error>> {
+        let client = crate::utils::http::client_builder()
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+        let minecraft_version = instance.map(|i| InstanceManager::base_minecraft_version(&i.version));
+
+        let mut maps = Vec::new();
+        for catalog_url in catalog_urls {
+            let catalog = match fetch_catalog(&client, catalog_url).await {
+                Ok(catalog) => catalog,
+                Err(e) => {
+                    println!("Warning: Skipping practice map catalog {}: {}", catalog_url, e);
+                    continue;
+                }
+            };
+
+            for map in catalog.maps {
+                maps.push(CatalogMap {
+                    catalog_url: catalog_url.clone(),
+                    catalog_name: catalog.name.clone(),
+                    compatible: minecraft_version.as_deref().map(|v| supports_version(&map, v)),
+                    downloaded: archive_path(&map).exists(),
+                    map,
+                });
+            }
+        }
+
+        Ok(maps)
+    }
+
+    /// Download a map from a configured catalog and unpack it into the instance's saves/.
+    /// Interrupted downloads continue where they stopped on the next attempt.
+    pub async fn install(
+        catalog_urls: &[String],
+        instance: &Instance,
+        map_id: &str,
+        ignore_version: bool,
+    ) -> Result<InstalledPracticeMap, Box<dyn std::error::Error>> {
+        let client = crate::utils::http::client_builder()
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()?;
+
+        // Look the map up again so the URL and hash always come from a catalog the player added
+        let mut found = None;
+        for catalog_url in catalog_urls {
+            if let Ok(catalog) = fetch_catalog(&client, catalog_url).await {
+                if let Some(map) = catalog.maps.into_iter().find(|m| m.id == map_id) {
+                    found = Some(map);
+                    break;
+                }
+            }
+        }
+        let map = found.ok_or_else(|| format!("Practice map '{}' is not in any catalog", map_id))?;
+
+        let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+        if !ignore_version && !supports_version(&map, &minecraft_version) {
+            return Err(format!(
+                "'{}' is made for {}, this instance runs {}",
+                map.name,
+                map.minecraft_versions.join(", "),
+                minecraft_version
+            )
+            .into());
+        }
+
+        let archive = download_resumable(&client, &map).await?;
+
+        let saves_dir = get_instance_dir(&instance.name).join("saves");
+        let folder = unique_folder_name(&saves_dir, &map.name);
+        let world_dir = saves_dir.join(&folder);
+        let prefix = world_prefix(&archive)?;
+        fs::create_dir_all(long_path(&world_dir))?;
+
+        let files = match extract_prefixed(&archive, &[prefix.as_str()], &world_dir) {
+            Ok(files) => files,
+            Err(e) => {
+                let _ = fs::remove_dir_all(long_path(&world_dir));
+                return Err(e);
+            }
+        };
+
+        println!("✓ Installed practice map '{}' into {}/saves/{}", map.name, instance.name, folder);
+        Ok(InstalledPracticeMap { folder, files })
+    }
+}
+
+async fn fetch_catalog(client: &reqwest::Client, url: &str) -> Result<PracticeMapCatalog, Box<dyn std::error::Error>> {
+    require_https(url)?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()).into());
+    }
+    let mut catalog: PracticeMapCatalog = response.json().await?;
+
+    // The hash names the cached download, so anything but a SHA256 is refused
+    catalog.maps.retain(|map| {
+        let valid = map.sha256.len() == 64 && map.sha256.chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            println!("Warning: Skipping practice map '{}' from {}: invalid sha256", map.id, url);
+        }
+        valid
+    });
+    Ok(catalog)
+}
+
+fn require_https(url: &str) -> Result<(), String> {
+    match url::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "https" => Ok(()),
+        Ok(_) => Err(format!("{} is not an https URL", url)),
+        Err(e) => Err(format!("Invalid URL {}: {}", url, e)),
+    }
+}
+
+/// True when the map lists no versions, or one of them matches exactly or by its ".x" wildcard
+fn supports_version(map: &PracticeMap, minecraft_version: &str) -> bool {
+    map.minecraft_versions.is_empty()
+        || map.minecraft_versions.iter().any(|v| match v.strip_suffix(".x") {
+            Some(series) => minecraft_version == series || minecraft_version.starts_with(&format!("{}.", series)),
+            None => v == minecraft_version,
+        })
+}
+
+/// Downloads are kept by hash, so the same map is fetched once for every instance
+fn archive_path(map: &PracticeMap) -> PathBuf {
+    get_meta_dir()
+        .join("practice_maps")
+        .join(format!("{}.zip", map.sha256.to_lowercase()))
+}
+
+async fn download_resumable(client: &reqwest::Client, map: &PracticeMap) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = archive_path(map);
+    if path.exists() {
+        return Ok(path);
+    }
+    require_https(&map.url)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let part_path = path.with_extension("zip.part");
+    let existing = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = client.get(&map.url);
+    if existing > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing));
+    }
+    let mut response = request.send().await?;
+
+    // Servers without range support send the whole file again
+    let resumed = existing > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        let _ = fs::remove_file(&part_path);
+        return Err("The partial download no longer matches the server's file, try again".into());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", map.name, response.status()).into());
+    }
+    if resumed {
+        println!("Resuming {} at {} bytes", map.name, existing);
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(resumed)
+        .write(true)
+        .truncate(!resumed)
+        .open(&part_path)?;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+    drop(file);
+
+    let mut hasher = Sha256::new();
+    let mut reader = fs::File::open(&part_path)?;
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let hash = format!("{:x}", hasher.finalize());
+
+    if !hash.eq_ignore_ascii_case(&map.sha256) {
+        let _ = fs::remove_file(&part_path);
+        return Err(format!("Hash mismatch for {} (expected {}, got {})", map.name, map.sha256, hash).into());
+    }
+
+    fs::rename(&part_path, &path)?;
+    println!("✓ Downloaded practice map {}", map.name);
+    Ok(path)
+}
+
+/// Archive folder holding level.dat: "" when the world is at the root, otherwise e.g. "MyMap/"
+fn world_prefix(archive_path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(fs::File::open(archive_path)?)?;
+    let mut best: Option<String> = None;
+
+    for name in archive.file_names() {
+        let name = name.replace('\\', "/");
+        let Some(prefix) = name.strip_suffix("level.dat") else {
+            continue;
+        };
+        if !(prefix.is_empty() || prefix.ends_with('/')) || prefix.starts_with("__MACOSX/") {
+            continue;
+        }
+        if best.as_ref().is_none_or(|b| prefix.len() < b.len()) {
+            best = Some(prefix.to_string());
+        }
+    }
+
+    best.ok_or_else(|| "The download has no level.dat, it isn't a world".into())
+}
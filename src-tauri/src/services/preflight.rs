@@ -0,0 +1,81 @@
+use crate::utils::{get_instances_dir, get_launcher_dir};
+use serde::{Deserialize, Serialize};
+
+// Classic Win32 limit that many mods and older Java file APIs still respect
+const WINDOWS_MAX_PATH: usize = 260;
+// Deepest paths we regularly see below an instance dir (config trees, resource caches, natives)
+const DEEPEST_INSTANCE_SUBPATH: usize = 140;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PathPreflightReport {
+    pub launcher_dir: String,
+    pub path_length: usize,
+    pub non_ascii: bool,
+    pub long_paths_enabled: Option<bool>,
+    pub warnings: Vec<String>,
+}
+
+pub struct PathPreflight;
+
+impl PathPreflight {
+    pub fn check() -> PathPreflightReport {
+        let launcher_dir = get_launcher_dir().to_string_lossy().to_string();
+        let instances_dir_len = get_instances_dir().to_string_lossy().chars().count();
+        let path_length = launcher_dir.chars().count();
+        let non_ascii = !launcher_dir.is_ascii();
+        let long_paths_enabled = Self::long_paths_enabled();
+
+        let mut warnings = Vec::new();
+
+        // Leave room for an instance name as well as the deepest mod paths
+        let headroom = WINDOWS_MAX_PATH.saturating_sub(DEEPEST_INSTANCE_SUBPATH + 32);
+        if cfg!(target_os = "windows") && long_paths_enabled != Some(true) && instances_dir_len > headroom {
+            warnings.push(format!(
+                "The launcher directory path is {} characters long. Some mods can't handle files deeper than {} characters; \
+                 enable Windows long path support or use a shorter user/launcher path.",
+                path_length, WINDOWS_MAX_PATH
+            ));
+        }
+
+        if non_ascii {
+            warnings.push(
+                "The launcher directory contains non-ASCII characters (usually from the user name). \
+                 Older Minecraft versions, Forge and some mods may fail to load files from such paths."
+                    .to_string(),
+            );
+        }
+
+        PathPreflightReport {
+            launcher_dir,
+            path_length,
+            non_ascii,
+            long_paths_enabled,
+            warnings,
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn long_paths_enabled() -> Option<bool> {
+        let output = std::process::Command::new("reg")
+            .args([
+                "query",
+                r"HKLM\SYSTEM\CurrentControlSet\Control\FileSystem",
+                "/v",
+                "LongPathsEnabled",
+            ])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return Some(false);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Some(stdout.contains("0x1"))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn long_paths_enabled() -> Option<bool> {
+        None
+    }
+}
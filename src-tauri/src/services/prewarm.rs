@@ -0,0 +1,130 @@
+use crate::models::{AssetIndexData, VersionDetails};
+use crate::services::accounts::AccountManager;
+use crate::services::instance::InstanceManager;
+use crate::services::integrity::InstanceVerifier;
+use crate::services::legacy_assets::LegacyAssets;
+use crate::utils::{get_instance_dir, get_meta_dir};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Selecting an instance again within this long doesn't repeat the work
+const PREWARM_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+lazy_static::lazy_static! {
+    static ref PREWARMED: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrewarmReport {
+    pub instance: String,
+    pub checked_files: usize,
+    /// Files `verify_instance` would report, launching as is may fail
+    pub problems: usize,
+    /// The active account's token is good for the launch, refreshed if it was about to expire
+    pub token_ready: bool,
+    pub asset_objects: usize,
+    pub elapsed_ms: u64,
+}
+
+pub struct InstancePrewarmer;
+
+impl InstancePrewarmer {
+    /// Do the slow parts of a launch ahead of time: hash the game files, which also pulls them into
+    /// the OS file cache, refresh the sign-in and read the asset index. Legacy asset layouts are
+    /// copied here too. None when the instance was warmed recently or is running.
+    pub async fn prewarm(instance_name: &str) -> Result<Option<PrewarmReport>, Box<dyn std::error::Error>> {
+        if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(instance_name) {
+            return Ok(None);
+        }
+        {
+            let mut prewarmed = PREWARMED.lock().unwrap();
+            if prewarmed.get(instance_name).is_some_and(|at| at.elapsed() < PREWARM_COOLDOWN) {
+                return Ok(None);
+            }
+            // Claimed up front so a second hover doesn't start another run alongside this one
+            prewarmed.insert(instance_name.to_string(), Instant::now());
+        }
+
+        let started = Instant::now();
+        let name = instance_name.to_string();
+        let files = tokio::task::spawn_blocking(move || {
+            let integrity = InstanceVerifier::verify(&name).map_err(|e| e.to_string())?;
+            let asset_objects = warm_asset_index(&name).map_err(|e| e.to_string())?;
+            Ok::<_, String>((integrity, asset_objects))
+        })
+        .await?;
+
+        let (integrity, asset_objects) = match files {
+            Ok(result) => result,
+            Err(e) => {
+                PREWARMED.lock().unwrap().remove(instance_name);
+                return Err(e.into());
+            }
+        };
+
+        let active_uuid = AccountManager::get_active_account().ok().flatten().map(|a| a.uuid);
+        let token_ready = match active_uuid {
+            Some(uuid) => match AccountManager::ensure_fresh_token(&uuid).await {
+                Ok(_) => true,
+                Err(e) => {
+                    println!("Warning: Could not refresh the sign-in ahead of launch: {}", e);
+                    false
+                }
+            },
+            None => false,
+        };
+
+        let report = PrewarmReport {
+            instance: instance_name.to_string(),
+            checked_files: integrity.checked_files,
+            problems: integrity.problems.len(),
+            token_ready,
+            asset_objects,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        println!(
+            "✓ Prewarmed '{}' in {} ms ({} files, {} assets)",
+            instance_name, report.elapsed_ms, report.checked_files, report.asset_objects
+        );
+        Ok(Some(report))
+    }
+
+    /// Forget an instance's last prewarm, e.g. after its files changed
+    pub fn invalidate(instance_name: &str) {
+        PREWARMED.lock().unwrap().remove(instance_name);
+    }
+}
+
+/// Parse the instance's asset index and lay out legacy assets. Returns the number of objects,
+/// 0 when the version isn't installed yet.
+fn warm_asset_index(instance_name: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let instance = InstanceManager::load(instance_name)?;
+    let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+    let version_json = get_meta_dir()
+        .join("versions")
+        .join(&minecraft_version)
+        .join(format!("{}.json", minecraft_version));
+    let Ok(content) = fs::read_to_string(&version_json) else {
+        return Ok(0);
+    };
+    let details: VersionDetails = serde_json::from_str(&content)?;
+
+    let index_path = get_meta_dir()
+        .join("assets")
+        .join("indexes")
+        .join(format!("{}.json", details.assets));
+    let Ok(content) = fs::read_to_string(&index_path) else {
+        return Ok(0);
+    };
+    let index: AssetIndexData = serde_json::from_str(&content)?;
+
+    if index.is_virtual || index.map_to_resources {
+        LegacyAssets::prepare(&details.assets, &get_instance_dir(instance_name))?;
+    }
+    Ok(index.objects.len())
+}
@@ -0,0 +1,117 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::ModrinthClient;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const SPARK_PROJECT: &str = "spark";
+const SPARK_VIEWER_URL: &str = "https://spark.lucko.me/";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SparkProfileLink {
+    pub url: String,
+    /// Log file the link was found in
+    pub log_file: String,
+    /// The log line, e.g. "[12:01:33] [Server thread/INFO]: [⚡] https://spark.lucko.me/abc"
+    pub line: String,
+}
+
+pub struct SparkProfiler;
+
+impl SparkProfiler {
+    pub fn is_installed(instance: &Instance) -> bool {
+        let mods_dir = get_instance_dir(&instance.name).join("mods");
+
+        fs::read_dir(mods_dir)
+            .map(|entries| {
+                entries.flatten().any(|e| {
+                    let name = e.file_name().to_string_lossy().to_lowercase();
+                    name.starts_with("spark") && name.ends_with(".jar")
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Download the newest spark build for the instance's loader and game version
+    pub async fn install(instance: &Instance) -> Result<String, Box<dyn std::error::Error>> {
+        let loader = instance
+            .loader
+            .clone()
+            .filter(|l| l != "vanilla")
+            .ok_or("spark needs a mod loader, vanilla instances are not supported")?;
+
+        if Self::is_installed(instance) {
+            return Err("spark is already installed in this instance".into());
+        }
+
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let client = ModrinthClient::new();
+        let versions = client
+            .get_project_versions(SPARK_PROJECT, Some(vec![loader.clone()]), Some(vec![game_version.clone()]))
+            .await?;
+
+        let version = versions
+            .first()
+            .ok_or_else(|| format!("No spark build for {} {}", loader, game_version))?;
+        let file = version
+            .files
+            .iter()
+            .find(|f| f.primary)
+            .or_else(|| version.files.first())
+            .ok_or("spark release has no files")?;
+
+        let mods_dir = get_instance_dir(&instance.name).join("mods");
+        fs::create_dir_all(&mods_dir)?;
+        client.download_mod_file(&file.url, &mods_dir.join(&file.filename)).await?;
+
+        println!("✓ Installed {} into '{}'", file.filename, instance.name);
+        Ok(file.filename.clone())
+    }
+
+    /// spark viewer links printed to the game logs, newest first
+    pub fn find_profile_links(instance_name: &str) -> Result<Vec<SparkProfileLink>, Box<dyn std::error::Error>> {
+        let logs_dir = get_instance_dir(instance_name).join("logs");
+        let mut links = Vec::new();
+
+        if !logs_dir.exists() {
+            return Ok(links);
+        }
+
+        let mut log_files: Vec<_> = fs::read_dir(&logs_dir)?
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".log"))
+            .collect();
+        log_files.sort_by_key(|e| e.metadata().and_then(|m| m.modified()).ok());
+
+        for entry in log_files {
+            let Ok(content) = fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            let log_file = entry.file_name().to_string_lossy().to_string();
+
+            for line in content.lines() {
+                let Some(start) = line.find(SPARK_VIEWER_URL) else {
+                    continue;
+                };
+
+                let url: String = line[start..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '/' | '.' | '-' | '_' | '#'))
+                    .collect();
+
+                if url.len() > SPARK_VIEWER_URL.len() {
+                    links.push(SparkProfileLink {
+                        url,
+                        log_file: log_file.clone(),
+                        line: line.trim().to_string(),
+                    });
+                }
+            }
+        }
+
+        links.reverse();
+        links.dedup_by(|a, b| a.url == b.url);
+        Ok(links)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::utils::get_launcher_dir;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, sync::Mutex};
+
+/// Older launches are dropped from the log
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecentLaunch {
+    pub id: String,
+    pub instance: String,
+    pub account: String,
+    pub version: String,
+    pub launched_at: DateTime<Utc>,
+    /// Filled in when the game exits, `None` while it's running or if the launcher was closed first
+    pub duration_seconds: Option<u64>,
+}
+
+lazy_static::lazy_static! {
+    // The exit thread and a new launch can write at the same time
+    static ref LOG_LOCK: Mutex<()> = Mutex::new(());
+}
+
+pub struct RecentLaunches;
+
+impl RecentLaunches {
+    fn get_log_path() -> PathBuf {
+        get_launcher_dir().join("recent_launches.json")
+    }
+
+    fn load() -> Vec<RecentLaunch> {
+        fs::read_to_string(Self::get_log_path())
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(entries: &[RecentLaunch]) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(Self::get_log_path(), serde_json::to_string_pretty(entries)?)?;
+        Ok(())
+    }
+
+    /// Log a launch and return its id for `record_exit`
+    pub fn record_start(instance_name: &str, account: &str, version: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let _guard = LOG_LOCK.lock().unwrap();
+
+        let launched_at = Utc::now();
+        let id = format!("{}-{}", launched_at.timestamp_millis(), instance_name);
+
+        let mut entries = Self::load();
+        entries.insert(0, RecentLaunch {
+            id: id.clone(),
+            instance: instance_name.to_string(),
+            account: account.to_string(),
+            version: version.to_string(),
+            launched_at,
+            duration_seconds: None,
+        });
+        entries.truncate(MAX_ENTRIES);
+        Self::save(&entries)?;
+
+        Ok(id)
+    }
+
+    pub fn record_exit(id: &str, duration_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
+        let _guard = LOG_LOCK.lock().unwrap();
+
+        let mut entries = Self::load();
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.duration_seconds = Some(duration_seconds);
+            Self::save(&entries)?;
+        }
+
+        Ok(())
+    }
+
+    /// Newest first. With `unique_instances` each instance appears once, for "jump back in" lists.
+    pub fn get(limit: usize, unique_instances: bool) -> Vec<RecentLaunch> {
+        let mut entries = Self::load();
+
+        if unique_instances {
+            let mut seen = std::collections::HashSet::new();
+            entries.retain(|e| seen.insert(e.instance.clone()));
+        }
+
+        // Deleted instances can't be jumped back into
+        entries.retain(|e| crate::utils::get_instance_dir(&e.instance).join("instance.json").exists());
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Keep the log in step with instance renames
+    pub fn rename_instance(old_name: &str, new_name: &str) {
+        let _guard = LOG_LOCK.lock().unwrap();
+
+        let mut entries = Self::load();
+        let mut changed = false;
+        for entry in entries.iter_mut().filter(|e| e.instance == old_name) {
+            entry.instance = new_name.to_string();
+            changed = true;
+        }
+
+        if changed {
+            if let Err(e) = Self::save(&entries) {
+                println!("⚠ Failed to update recent launches: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::utils::{get_instance_dir, long_path};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Output folders of ReplayMod and similar recording mods, with what they contain
+const RECORDING_DIRS: &[(&str, &str)] = &[
+    ("replay_recordings", "replay"),
+    ("replay_videos", "video"),
+    ("flashback/replays", "replay"),
+];
+
+const RECORDING_EXTENSIONS: &[&str] = &["mcpr", "zip", "mp4", "mkv", "webm", "avi", "mov"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Recording {
+    /// Relative to the instance folder, with `/` separators
+    pub path: String,
+    pub filename: String,
+    /// "replay" for replay files, "video" for rendered videos
+    pub kind: String,
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+pub struct RecordingManager;
+
+impl RecordingManager {
+    /// Newest first
+    pub fn list(instance_name: &str) -> Result<Vec<Recording>, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        if !instance_dir.exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let mut recordings = Vec::new();
+        for (dir, kind) in RECORDING_DIRS {
+            let Ok(entries) = fs::read_dir(instance_dir.join(dir)) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let filename = entry.file_name().to_string_lossy().to_string();
+                let is_recording = Path::new(&filename)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| RECORDING_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if !is_recording || !metadata.is_file() {
+                    continue;
+                }
+
+                recordings.push(Recording {
+                    path: format!("{}/{}", dir, filename),
+                    filename,
+                    kind: kind.to_string(),
+                    size: metadata.len(),
+                    modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                });
+            }
+        }
+
+        recordings.sort_by(|a, b| b.modified.cmp(&a.modified));
+        Ok(recordings)
+    }
+
+    /// Resolve a path returned by `list`, refusing anything outside the recording folders
+    fn resolve(instance_name: &str, relative: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let (dir, filename) = relative.rsplit_once('/').ok_or("Invalid recording path")?;
+        if !RECORDING_DIRS.iter().any(|(d, _)| *d == dir)
+            || filename.is_empty()
+            || filename.contains(['/', '\\'])
+            || filename.contains("..")
+        {
+            return Err("Invalid recording path".into());
+        }
+
+        let path = get_instance_dir(instance_name).join(dir).join(filename);
+        if !path.is_file() {
+            return Err(format!("Recording '{}' not found", filename).into());
+        }
+        Ok(path)
+    }
+
+    /// Rename in place, the original extension is kept
+    pub fn rename(instance_name: &str, relative: &str, new_name: &str) -> Result<Recording, Box<dyn std::error::Error>> {
+        let path = Self::resolve(instance_name, relative)?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_string();
+
+        let new_name = new_name.trim();
+        let new_filename = if new_name.to_lowercase().ends_with(&format!(".{}", extension.to_lowercase())) {
+            new_name.to_string()
+        } else {
+            format!("{}.{}", new_name, extension)
+        };
+
+        let destination = path.with_file_name(&new_filename);
+        if destination.exists() {
+            return Err(format!("A recording named '{}' already exists", new_filename).into());
+        }
+
+        fs::rename(&path, &destination)?;
+
+        let dir = relative.rsplit_once('/').map(|(d, _)| d).unwrap_or_default();
+        Self::list(instance_name)?
+            .into_iter()
+            .find(|r| r.path == format!("{}/{}", dir, new_filename))
+            .ok_or_else(|| "Renamed recording disappeared".into())
+    }
+
+    /// Move recordings into `media_dir/<instance>/`, returning where each one ended up
+    pub fn move_to(
+        instance_name: &str,
+        relatives: &[String],
+        media_dir: &Path,
+    ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        let target_dir = media_dir.join(instance_name);
+        fs::create_dir_all(&target_dir)?;
+
+        let mut moved = Vec::new();
+        for relative in relatives {
+            let path = Self::resolve(instance_name, relative)?;
+            let filename = path.file_name().ok_or("Recording has no file name")?;
+
+            let destination = target_dir.join(filename);
+            if destination.exists() {
+                return Err(format!("'{}' already exists in the media folder", filename.to_string_lossy()).into());
+            }
+
+            move_file(&path, &destination)?;
+            println!("✓ Moved {} to {}", relative, destination.display());
+            moved.push(destination);
+        }
+
+        Ok(moved)
+    }
+}
+
+/// The media folder is usually on another drive, where a rename fails
+fn move_file(source: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+
+    if let Err(e) = fs::copy(long_path(source), long_path(destination)) {
+        let _ = fs::remove_file(destination);
+        return Err(e.into());
+    }
+    fs::remove_file(long_path(source))?;
+    Ok(())
+}
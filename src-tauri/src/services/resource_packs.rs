@@ -0,0 +1,286 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_cache::ModCache;
+use crate::services::mod_lock::{LockedFile, ModLockfile};
+use crate::services::modrinth::ModrinthInstaller;
+use crate::utils::game_options::{get_option, set_options};
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::ModrinthClient;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResourcePack {
+    /// File or folder name in resourcepacks/
+    pub name: String,
+    pub enabled: bool,
+    pub is_folder: bool,
+    /// From pack.mcmeta, None when the pack has none
+    pub pack_format: Option<u32>,
+    pub description: Option<String>,
+    pub size: u64,
+}
+
+pub struct ResourcePackManager;
+
+impl ResourcePackManager {
+    /// Enabled packs first, highest priority first, then the disabled ones by name
+    pub fn list(instance: &Instance) -> Result<Vec<ResourcePack>, Box<dyn std::error::Error>> {
+        let packs_dir = get_instance_dir(&instance.name).join("resourcepacks");
+        let enabled = enabled_packs(instance);
+        let mut packs = Vec::new();
+
+        if packs_dir.exists() {
+            for entry in fs::read_dir(&packs_dir)?.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let path = entry.path();
+                let is_folder = path.is_dir();
+                if !is_folder && !name.ends_with(".zip") {
+                    continue;
+                }
+
+                let (pack_format, description) = read_mcmeta(&path).unwrap_or((None, None));
+                packs.push(ResourcePack {
+                    enabled: enabled.contains(&name),
+                    size: if is_folder { 0 } else { entry.metadata().map(|m| m.len()).unwrap_or(0) },
+                    name,
+                    is_folder,
+                    pack_format,
+                    description,
+                });
+            }
+        }
+
+        packs.sort_by_key(|p| match enabled.iter().position(|e| *e == p.name) {
+            Some(pos) => (0, pos, String::new()),
+            None => (1, 0, p.name.to_lowercase()),
+        });
+        Ok(packs)
+    }
+
+    /// Copy a pack into resourcepacks/. It starts out disabled.
+    pub fn install_file(instance: &Instance, source_path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let file_name = source_path
+            .file_name()
+            .ok_or("Invalid resource pack path")?
+            .to_string_lossy()
+            .to_string();
+        let file_name = crate::commands::validation::sanitize_pack_filename(&file_name)?;
+        if !file_name.ends_with(".zip") {
+            return Err("Resource packs must be .zip files".into());
+        }
+        if read_mcmeta(source_path).is_none() {
+            return Err(format!("{} is not a resource pack, it has no pack.mcmeta", file_name).into());
+        }
+
+        let packs_dir = get_instance_dir(&instance.name).join("resourcepacks");
+        fs::create_dir_all(&packs_dir)?;
+        fs::copy(source_path, crate::utils::long_path(&packs_dir.join(&file_name)))?;
+
+        println!("✓ Installed resource pack {} into '{}'", file_name, instance.name);
+        Ok(file_name)
+    }
+
+    /// Install a Modrinth resource pack. Without `version_id` the newest build for the
+    /// instance's game version is used.
+    pub async fn install_modrinth(
+        instance: &Instance,
+        project_id: &str,
+        version_id: Option<&str>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let game_version = InstanceManager::base_minecraft_version(&instance.version);
+        let client = ModrinthClient::new();
+
+        // Resource packs are published under the "minecraft" loader
+        let version = match version_id {
+            Some(id) => client.get_versions(&[id.to_string()]).await?.into_iter().next(),
+            None => ModrinthInstaller::resolve(&client, project_id, &["minecraft".to_string()], &game_version).await?,
+        }
+        .ok_or_else(|| format!("{} has no resource pack for Minecraft {}", project_id, game_version))?;
+
+        let file = version
+            .files
+            .iter()
+            .find(|f| f.primary)
+            .or_else(|| version.files.first())
+            .ok_or_else(|| format!("{} {} has no files", project_id, version.version_number))?;
+        let file_name = crate::commands::validation::sanitize_pack_filename(&file.filename)?;
+
+        let instance_dir = get_instance_dir(&instance.name);
+        let packs_dir = instance_dir.join("resourcepacks");
+        fs::create_dir_all(&packs_dir)?;
+        let destination = packs_dir.join(&file_name);
+
+        if !ModCache::restore(&file.hashes.sha512, &destination) {
+            client.download_mod_file(&file.url, &destination).await?;
+            let mut hasher = Sha1::new();
+            hasher.update(fs::read(crate::utils::long_path(&destination))?);
+            if format!("{:x}", hasher.finalize()) != file.hashes.sha1 {
+                let _ = fs::remove_file(&destination);
+                return Err(format!("Hash mismatch for {}", file_name).into());
+            }
+            ModCache::adopt_or_warn(&destination);
+        }
+
+        let mut lockfile = ModLockfile::load(&instance_dir)?;
+        lockfile.record(LockedFile {
+            path: format!("resourcepacks/{}", file_name),
+            sha1: file.hashes.sha1.clone(),
+            sha512: Some(file.hashes.sha512.clone()),
+            downloads: vec![file.url.clone()],
+            source: Some("modrinth".to_string()),
+            project_id: Some(version.project_id.clone()),
+            version_id: Some(version.id.clone()),
+        });
+        lockfile.save(&instance_dir)?;
+
+        println!("✓ Installed resource pack {} from Modrinth into '{}'", file_name, instance.name);
+        Ok(file_name)
+    }
+
+    /// Enabling puts the pack on top of the others
+    pub fn set_enabled(instance: &Instance, name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
+        if enabled && !pack_path(instance, name)?.exists() {
+            return Err(format!("Resource pack '{}' not found", name).into());
+        }
+
+        let mut packs = enabled_packs(instance);
+        packs.retain(|p| p != name);
+        if enabled {
+            packs.insert(0, name.to_string());
+        }
+        write_enabled_packs(instance, &packs)
+    }
+
+    /// Set the order of the enabled packs, highest priority first. Every enabled pack has to be
+    /// listed; packs not enabled yet are enabled.
+    pub fn reorder(instance: &Instance, order: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        for name in order {
+            if !pack_path(instance, name)?.exists() {
+                return Err(format!("Resource pack '{}' not found", name).into());
+            }
+        }
+        if let Some(missing) = enabled_packs(instance).iter().find(|p| !order.contains(p)) {
+            return Err(format!("The new order is missing enabled pack '{}'", missing).into());
+        }
+
+        write_enabled_packs(instance, order)
+    }
+}
+
+/// Path of a pack in resourcepacks/, refusing names that would leave the folder
+fn pack_path(instance: &Instance, name: &str) -> Result<PathBuf, String> {
+    if name.is_empty() || name.contains("..") || name.contains('/') || name.contains('\\') || name.contains('\0') {
+        return Err(format!("Invalid resource pack name '{}'", name));
+    }
+    Ok(get_instance_dir(&instance.name).join("resourcepacks").join(name))
+}
+
+/// Before 1.13 options.txt lists bare file names, later versions prefix them with `file/`
+fn uses_file_prefix(instance: &Instance) -> bool {
+    let version = InstanceManager::base_minecraft_version(&instance.version);
+    let minor = version
+        .strip_prefix("1.")
+        .and_then(|rest| rest.split(['.', '-']).next())
+        .and_then(|minor| minor.parse::<u32>().ok());
+    // Snapshots like 23w45a are all newer than 1.13
+    minor.map_or(true, |minor| minor >= 13)
+}
+
+/// Enabled pack names, highest priority first. options.txt lists them the other way round.
+fn enabled_packs(instance: &Instance) -> Vec<String> {
+    let options_path = get_instance_dir(&instance.name).join("options.txt");
+    let Some(value) = get_option(&options_path, "resourcePacks") else {
+        return Vec::new();
+    };
+
+    serde_json::from_str::<Vec<String>>(&value)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .filter_map(|entry| match entry.strip_prefix("file/") {
+            Some(name) => Some(name.to_string()),
+            // Built-in packs like vanilla or programmer_art aren't files
+            None if !uses_file_prefix(instance) => Some(entry),
+            None => None,
+        })
+        .collect()
+}
+
+/// Write the enabled packs, keeping built-in entries such as `vanilla` below them
+fn write_enabled_packs(instance: &Instance, packs: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let options_path = get_instance_dir(&instance.name).join("options.txt");
+    let file_prefix = uses_file_prefix(instance);
+    let existing: Vec<String> = get_option(&options_path, "resourcePacks")
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default();
+
+    let mut entries: Vec<String> = if file_prefix {
+        existing.iter().filter(|e| !e.starts_with("file/")).cloned().collect()
+    } else {
+        Vec::new()
+    };
+    if file_prefix && entries.is_empty() {
+        entries.push("vanilla".to_string());
+    }
+    entries.extend(packs.iter().rev().map(|p| if file_prefix { format!("file/{}", p) } else { p.clone() }));
+
+    // Packs the game flagged as incompatible stay flagged only while enabled
+    let incompatible: Vec<String> = get_option(&options_path, "incompatibleResourcePacks")
+        .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| entries.contains(e))
+        .collect();
+
+    set_options(
+        &options_path,
+        &[
+            ("resourcePacks", serde_json::to_string(&entries)?),
+            ("incompatibleResourcePacks", serde_json::to_string(&incompatible)?),
+        ],
+    )?;
+    Ok(())
+}
+
+/// pack_format and description from a zip or folder pack's pack.mcmeta
+fn read_mcmeta(path: &Path) -> Option<(Option<u32>, Option<String>)> {
+    let content = if path.is_dir() {
+        fs::read_to_string(path.join("pack.mcmeta")).ok()?
+    } else {
+        let mut archive = ZipArchive::new(fs::File::open(crate::utils::long_path(path)).ok()?).ok()?;
+        let mut entry = archive.by_name("pack.mcmeta").ok()?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content).ok()?;
+        content
+    };
+
+    let json: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}')).ok()?;
+    let pack = json.get("pack")?;
+    let pack_format = pack.get("pack_format").and_then(|f| f.as_u64()).map(|f| f as u32);
+    let description = pack.get("description").and_then(text_component);
+    Some((pack_format, description))
+}
+
+/// Plain text of a description, which is a string or a JSON text component
+fn text_component(value: &serde_json::Value) -> Option<String> {
+    let text = match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts.iter().filter_map(text_component).collect(),
+        serde_json::Value::Object(_) => {
+            let mut text = value.get("text").and_then(|t| t.as_str()).unwrap_or_default().to_string();
+            if let Some(extra) = value.get("extra").and_then(text_component) {
+                text.push_str(&extra);
+            }
+            text
+        }
+        _ => return None,
+    };
+    Some(text).filter(|t| !t.is_empty())
+}
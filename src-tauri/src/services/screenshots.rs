@@ -0,0 +1,163 @@
+use crate::utils::get_instance_dir;
+use chrono::Local;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+use tauri::Emitter;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct ScreenshotWatcher;
+
+impl ScreenshotWatcher {
+    /// Poll the instance's screenshots folder on a background thread until the game exits,
+    /// emitting `screenshot-taken` for every new image
+    pub fn start(instance_name: &str, copy_to_clipboard: bool, organize: bool, app_handle: tauri::AppHandle) {
+        let instance_name = instance_name.to_string();
+        let screenshots_dir = get_instance_dir(&instance_name).join("screenshots");
+
+        std::thread::spawn(move || {
+            let mut known = list_screenshots(&screenshots_dir);
+
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+
+                let running = crate::commands::instances::RUNNING_PROCESSES
+                    .lock()
+                    .unwrap()
+                    .contains_key(&instance_name);
+                if !running {
+                    break;
+                }
+
+                for name in list_screenshots(&screenshots_dir) {
+                    if !known.insert(name.clone()) {
+                        continue;
+                    }
+
+                    let mut path = screenshots_dir.join(&name);
+                    // The game may still be writing the file
+                    wait_until_written(&path);
+
+                    if organize {
+                        match move_to_dated_folder(&path) {
+                            Ok(moved) => path = moved,
+                            Err(e) => println!("⚠ Failed to organize screenshot {}: {}", name, e),
+                        }
+                    }
+
+                    let copied = copy_to_clipboard && match copy_image_to_clipboard(&path) {
+                        Ok(()) => true,
+                        Err(e) => {
+                            println!("⚠ Failed to copy screenshot to clipboard: {}", e);
+                            false
+                        }
+                    };
+
+                    println!("✓ New screenshot in '{}': {}", instance_name, path.display());
+                    let _ = app_handle.emit("screenshot-taken", serde_json::json!({
+                        "instance": instance_name,
+                        "path": path.to_string_lossy(),
+                        "copied_to_clipboard": copied
+                    }));
+                }
+            }
+        });
+    }
+}
+
+/// Top-level image files only, organized screenshots live in subfolders
+fn list_screenshots(dir: &Path) -> HashSet<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return HashSet::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| {
+            let lower = name.to_lowercase();
+            lower.ends_with(".png") || lower.ends_with(".jpg")
+        })
+        .collect()
+}
+
+fn wait_until_written(path: &Path) {
+    let mut last_size = None;
+    for _ in 0..20 {
+        let size = fs::metadata(path).map(|m| m.len()).ok();
+        if size.is_some() && size == last_size {
+            return;
+        }
+        last_size = size;
+        std::thread::sleep(Duration::from_millis(250));
+    }
+}
+
+fn move_to_dated_folder(path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let parent = path.parent().ok_or("Screenshot has no parent folder")?;
+    let file_name = path.file_name().ok_or("Screenshot has no file name")?;
+
+    let dated_dir = parent.join(Local::now().format("%Y-%m-%d").to_string());
+    fs::create_dir_all(&dated_dir)?;
+
+    let destination = dated_dir.join(file_name);
+    fs::rename(path, &destination)?;
+    Ok(destination)
+}
+
+/// Uses the platform's own tools, on Linux either wl-copy or xclip has to be installed
+fn copy_image_to_clipboard(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let path_str = path.to_string_lossy().to_string();
+
+    #[cfg(target_os = "windows")]
+    let status = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-STA",
+            "-Command",
+            &format!(
+                "Add-Type -AssemblyName System.Windows.Forms, System.Drawing; \
+                 [System.Windows.Forms.Clipboard]::SetImage([System.Drawing.Image]::FromFile('{}'))",
+                path_str.replace('\'', "''")
+            ),
+        ])
+        .status()?;
+
+    #[cfg(target_os = "macos")]
+    let status = Command::new("osascript")
+        .args([
+            "-e",
+            &format!(
+                "set the clipboard to (read (POSIX file \"{}\") as «class PNGf»)",
+                path_str.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+        ])
+        .status()?;
+
+    #[cfg(target_os = "linux")]
+    let status = {
+        let is_wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+        if is_wayland {
+            Command::new("wl-copy")
+                .args(["--type", "image/png"])
+                .stdin(fs::File::open(path)?)
+                .status()?
+        } else {
+            Command::new("xclip")
+                .args(["-selection", "clipboard", "-t", "image/png", "-i", &path_str])
+                .status()?
+        }
+    };
+
+    if !status.success() {
+        return Err(format!("Clipboard command exited with {}", status).into());
+    }
+
+    Ok(())
+}
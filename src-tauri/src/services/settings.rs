@@ -35,4 +35,9 @@ impl SettingsManager {
         
         Ok(())
     }
+
+    /// Whether loader builds marked unstable are listed and picked for upgrades
+    pub fn unstable_loaders_enabled() -> bool {
+        Self::load().map(|s| s.unstable_loaders).unwrap_or(false)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,346 @@
+use crate::models::MrpackFile;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_lock::{LockedFile, ModLockfile};
+use crate::services::mrpack::MrpackReader;
+use crate::services::curseforge::CurseForgeInstaller;
+use crate::utils::curseforge::{self, CurseForgeClient};
+use crate::utils::modrinth::ModrinthClient;
+use crate::utils::{get_instance_dir, long_path};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::{collections::HashMap, fs, path::Path};
+
+/// Codes start with this so a pasted code can be recognised before decoding
+pub const SHARE_CODE_PREFIX: &str = "atomic1:";
+
+/// Everything needed to rebuild an instance, field names kept short to keep the code short
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstanceShareCode {
+    #[serde(rename = "n")]
+    pub name: String,
+    /// Base Minecraft version, not the loader's version id
+    #[serde(rename = "v")]
+    pub minecraft_version: String,
+    #[serde(rename = "l", default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<String>,
+    #[serde(rename = "lv", default, skip_serializing_if = "Option::is_none")]
+    pub loader_version: Option<String>,
+    #[serde(rename = "m", default, skip_serializing_if = "Vec::is_empty")]
+    pub mods: Vec<SharedMod>,
+    #[serde(rename = "s", default)]
+    pub settings: SharedSettings,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "t")]
+pub enum SharedMod {
+    #[serde(rename = "mr")]
+    Modrinth {
+        #[serde(rename = "p")]
+        project_id: String,
+        #[serde(rename = "f")]
+        version_id: String,
+    },
+    #[serde(rename = "cf")]
+    CurseForge {
+        #[serde(rename = "p")]
+        project_id: u32,
+        #[serde(rename = "f")]
+        file_id: u32,
+    },
+}
+
+/// Only settings that mean the same on every machine, Java paths and hooks stay local
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SharedSettings {
+    #[serde(rename = "mem", default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u32>,
+    #[serde(rename = "lang", default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(rename = "args", default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_game_args: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShareCodeExport {
+    pub code: String,
+    pub mod_count: usize,
+    /// Mods found on neither Modrinth nor CurseForge, friends have to add these themselves
+    pub unresolved: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShareCodeImport {
+    pub instance_name: String,
+    pub installed: usize,
+    /// Files the friend has to download by hand, e.g. CurseForge files with third-party downloads disabled
+    pub failed: Vec<String>,
+}
+
+pub struct ShareCodeManager;
+
+impl ShareCodeManager {
+    pub async fn export(instance_name: &str) -> Result<ShareCodeExport, Box<dyn std::error::Error>> {
+        let instance = InstanceManager::load(instance_name)?;
+        let mods_dir = get_instance_dir(instance_name).join("mods");
+
+        // Disabled mods are left out, the friend gets what actually runs
+        let mut files = Vec::new();
+        if mods_dir.exists() {
+            for entry in fs::read_dir(&mods_dir)? {
+                let entry = entry?;
+                let filename = entry.file_name().to_string_lossy().to_string();
+                if filename.ends_with(".jar") {
+                    files.push((filename, fs::read(long_path(&entry.path()))?));
+                }
+            }
+        }
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let hashes: Vec<String> = files
+            .iter()
+            .map(|(_, bytes)| {
+                let mut hasher = Sha1::new();
+                hasher.update(bytes);
+                format!("{:x}", hasher.finalize())
+            })
+            .collect();
+
+        let modrinth_versions = if hashes.is_empty() {
+            HashMap::new()
+        } else {
+            ModrinthClient::new().get_versions_from_hashes(&hashes).await?
+        };
+
+        let mut mods = Vec::new();
+        let mut missing = Vec::new();
+        for ((filename, bytes), hash) in files.iter().zip(&hashes) {
+            match modrinth_versions.get(hash) {
+                Some(version) => mods.push(SharedMod::Modrinth {
+                    project_id: version.project_id.clone(),
+                    version_id: version.id.clone(),
+                }),
+                None => missing.push((filename.clone(), curseforge::fingerprint(bytes))),
+            }
+        }
+
+        // CurseForge is only asked about what Modrinth doesn't know, and only with an API key
+        let mut unresolved = Vec::new();
+        if !missing.is_empty() {
+            let matches = match CurseForgeInstaller::client() {
+                Ok(client) => {
+                    let fingerprints: Vec<u32> = missing.iter().map(|(_, f)| *f).collect();
+                    client.get_fingerprint_matches(&fingerprints).await.unwrap_or_else(|e| {
+                        println!("⚠ Could not look up mods on CurseForge: {}", e);
+                        HashMap::new()
+                    })
+                }
+                Err(_) => HashMap::new(),
+            };
+
+            for (filename, fingerprint) in missing {
+                match matches.get(&fingerprint) {
+                    Some(file) => mods.push(SharedMod::CurseForge {
+                        project_id: file.mod_id,
+                        file_id: file.id,
+                    }),
+                    None => unresolved.push(filename),
+                }
+            }
+        }
+
+        let share = InstanceShareCode {
+            name: instance.name.clone(),
+            minecraft_version: InstanceManager::base_minecraft_version(&instance.version),
+            loader: instance.loader.clone().filter(|l| l != "vanilla"),
+            loader_version: instance.loader_version.clone(),
+            settings: SharedSettings {
+                memory_mb: instance.settings_override.as_ref().map(|s| s.memory_mb),
+                language: instance.language.clone(),
+                extra_game_args: instance.extra_game_args.clone(),
+            },
+            mods,
+        };
+
+        let code = Self::encode(&share)?;
+        println!(
+            "✓ Created share code for '{}' ({} mods, {} unresolved, {} characters)",
+            instance_name,
+            share.mods.len(),
+            unresolved.len(),
+            code.len()
+        );
+
+        Ok(ShareCodeExport {
+            code,
+            mod_count: share.mods.len(),
+            unresolved,
+        })
+    }
+
+    pub fn encode(share: &InstanceShareCode) -> Result<String, Box<dyn std::error::Error>> {
+        let json = serde_json::to_vec(share)?;
+        Ok(format!("{}{}", SHARE_CODE_PREFIX, general_purpose::URL_SAFE_NO_PAD.encode(json)))
+    }
+
+    /// Accepts codes with surrounding whitespace or line breaks from chat apps
+    pub fn decode(code: &str) -> Result<InstanceShareCode, Box<dyn std::error::Error>> {
+        let compact: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let payload = compact
+            .strip_prefix(SHARE_CODE_PREFIX)
+            .ok_or("This is not an Atomic Launcher instance code")?;
+
+        let json = general_purpose::URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| "The instance code is damaged or incomplete")?;
+        let share: InstanceShareCode =
+            serde_json::from_slice(&json).map_err(|_| "The instance code is damaged or incomplete")?;
+
+        if !share
+            .minecraft_version
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
+        {
+            return Err("Invalid Minecraft version in instance code".into());
+        }
+        if let Some(loader_version) = &share.loader_version {
+            if !loader_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_' || c == '+') {
+                return Err("Invalid loader version in instance code".into());
+            }
+        }
+
+        Ok(share)
+    }
+
+    /// Download every mod in the code into an already created instance, returning how many
+    /// were installed and what failed. `on_progress` gets (done, total) after each mod.
+    pub async fn download_mods(
+        share: &InstanceShareCode,
+        instance_dir: &Path,
+        on_progress: impl Fn(usize, usize),
+    ) -> Result<(usize, Vec<String>), Box<dyn std::error::Error>> {
+        let total = share.mods.len();
+        let mut installed = 0;
+        let mut failed = Vec::new();
+
+        let version_ids: Vec<String> = share
+            .mods
+            .iter()
+            .filter_map(|m| match m {
+                SharedMod::Modrinth { version_id, .. } => Some(version_id.clone()),
+                SharedMod::CurseForge { .. } => None,
+            })
+            .collect();
+        let file_ids: Vec<u32> = share
+            .mods
+            .iter()
+            .filter_map(|m| match m {
+                SharedMod::CurseForge { file_id, .. } => Some(*file_id),
+                SharedMod::Modrinth { .. } => None,
+            })
+            .collect();
+
+        if !version_ids.is_empty() {
+            let versions = ModrinthClient::new().get_versions(&version_ids).await?;
+            let http_client = crate::utils::http::client_builder()
+                .timeout(std::time::Duration::from_secs(300))
+                .user_agent("AtomicLauncher/2.4.0")
+                .build()?;
+
+            let mut lockfile = ModLockfile::load(instance_dir)?;
+
+            for version_id in &version_ids {
+                let version = versions.iter().find(|v| &v.id == version_id);
+                let file = version.and_then(|v| v.files.iter().find(|f| f.primary).or_else(|| v.files.first()));
+
+                let Some(file) = file else {
+                    failed.push(format!("Modrinth version {} no longer exists", version_id));
+                    on_progress(installed + failed.len(), total);
+                    continue;
+                };
+
+                // Same checks as a .mrpack download: allowed hosts, safe path, SHA1
+                let pack_file = MrpackFile {
+                    path: format!("mods/{}", file.filename),
+                    hashes: HashMap::from([("sha1".to_string(), file.hashes.sha1.clone())]),
+                    env: None,
+                    downloads: vec![file.url.clone()],
+                    file_size: file.size,
+                };
+                match MrpackReader::download_file(&http_client, &pack_file, instance_dir).await {
+                    Ok(()) => {
+                        installed += 1;
+                        lockfile.record(LockedFile {
+                            path: pack_file.path,
+                            sha1: file.hashes.sha1.clone(),
+                            sha512: Some(file.hashes.sha512.clone()),
+                            downloads: pack_file.downloads,
+                            source: Some("modrinth".to_string()),
+                            project_id: version.map(|v| v.project_id.clone()),
+                            version_id: Some(version_id.clone()),
+                        });
+                    }
+                    Err(e) => failed.push(e),
+                }
+                on_progress(installed + failed.len(), total);
+            }
+
+            lockfile.save(instance_dir)?;
+        }
+
+        if !file_ids.is_empty() {
+            let client = CurseForgeInstaller::client()?;
+            let files = client.get_files(&file_ids).await?;
+
+            for file_id in &file_ids {
+                let Some(file) = files.iter().find(|f| f.id == *file_id) else {
+                    failed.push(format!("CurseForge file {} no longer exists", file_id));
+                    on_progress(installed + failed.len(), total);
+                    continue;
+                };
+
+                match download_curseforge_file(&client, file, instance_dir).await {
+                    Ok(()) => installed += 1,
+                    Err(e) => failed.push(e),
+                }
+                on_progress(installed + failed.len(), total);
+            }
+        }
+
+        Ok((installed, failed))
+    }
+}
+
+async fn download_curseforge_file(
+    client: &CurseForgeClient,
+    file: &curseforge::CurseForgeFile,
+    instance_dir: &Path,
+) -> Result<(), String> {
+    use crate::commands::validation::{sanitize_pack_filename, validate_curseforge_url};
+
+    let url = file
+        .download_url
+        .as_ref()
+        .ok_or_else(|| format!("{} has to be downloaded manually from CurseForge", file.file_name))?;
+    validate_curseforge_url(url)?;
+
+    let bytes = client
+        .download_file(url)
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", file.file_name, e))?;
+
+    if let Some(expected) = file.sha1() {
+        let mut hasher = Sha1::new();
+        hasher.update(&bytes);
+        if format!("{:x}", hasher.finalize()) != expected {
+            return Err(format!("Hash mismatch for {}", file.file_name));
+        }
+    }
+
+    let mods_dir = instance_dir.join("mods");
+    fs::create_dir_all(&mods_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let file_name = sanitize_pack_filename(&file.file_name)?;
+    fs::write(long_path(&mods_dir.join(file_name)), &bytes)
+        .map_err(|e| format!("Failed to write {}: {}", file.file_name, e))
+}
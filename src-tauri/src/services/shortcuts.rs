@@ -0,0 +1,463 @@
+use crate::models::Instance;
+use crate::services::icons::InstanceIcons;
+use crate::services::instance::InstanceManager;
+use crate::utils::get_instance_dir;
+use crate::utils::vdf::{self, VdfValue};
+use image::{imageops::FilterType, DynamicImage, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Command-line flag that makes the launcher start an instance with the active account
+pub const LAUNCH_ARG: &str = "--launch";
+
+/// Card background when the instance has no accent color
+const DEFAULT_ARTWORK_BACKGROUND: [u8; 3] = [30, 30, 46];
+
+/// Quotes PowerShell, .desktop files and Steam's launch options would read as the end of the name
+const SHORTCUT_UNSAFE: &[char] = &['"', '\u{201C}', '\u{201D}', '\u{201E}', '`', '\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SteamShortcutReport {
+    /// Steam's id for the non-Steam game, also the prefix of its grid artwork
+    pub app_id: u32,
+    /// shortcuts.vdf files that were updated, one per Steam account on this machine
+    pub updated: Vec<String>,
+}
+
+pub struct ShortcutManager;
+
+impl ShortcutManager {
+    /// Put a shortcut on the desktop that starts the launcher with `--launch <instance>`
+    pub fn create_desktop_shortcut(instance_name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        // Make sure it's a real instance before pointing a shortcut at it
+        InstanceManager::load(instance_name)?;
+        check_shortcut_name(instance_name)?;
+
+        let desktop = dirs::desktop_dir().ok_or("Could not find the desktop folder")?;
+        fs::create_dir_all(&desktop)?;
+
+        let exe = std::env::current_exe()?;
+        let icon = get_instance_dir(instance_name).join("icon.png");
+        let icon = icon.exists().then_some(icon);
+
+        let path = write_shortcut(&desktop, instance_name, &exe, icon.as_deref())?;
+        println!("✓ Created shortcut for '{}' at {}", instance_name, path.display());
+        Ok(path)
+    }
+
+    /// Add the instance to every local Steam account as a non-Steam game, with grid artwork made from
+    /// its icon, banner and accent color. Adding it again updates the entry and artwork in place.
+    pub fn add_to_steam(instance_name: &str) -> Result<SteamShortcutReport, Box<dyn std::error::Error>> {
+        let instance = InstanceManager::load(instance_name)?;
+        check_shortcut_name(&instance.name)?;
+
+        // Steam keeps shortcuts in memory and overwrites the file on exit
+        if is_steam_running() {
+            return Err("Close Steam before adding shortcuts, it would overwrite them".into());
+        }
+
+        let steam_dir = find_steam_dir().ok_or("Could not find a Steam installation")?;
+        let user_dirs: Vec<PathBuf> = fs::read_dir(steam_dir.join("userdata"))
+            .map_err(|_| "Steam has no user data yet, sign in to Steam once first")?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n != "0" && n.chars().all(|c| c.is_ascii_digit()))
+            })
+            .collect();
+        if user_dirs.is_empty() {
+            return Err("Steam has no user data yet, sign in to Steam once first".into());
+        }
+
+        let exe = std::env::current_exe()?;
+        let exe_field = format!("\"{}\"", exe.display());
+        let app_id = steam_app_id(&exe_field, &instance.name);
+        let artwork = steam_artwork(&instance)?;
+
+        let mut updated = Vec::new();
+        for user_dir in user_dirs {
+            let config_dir = user_dir.join("config");
+            let grid_dir = config_dir.join("grid");
+            fs::create_dir_all(&grid_dir)?;
+
+            for (suffix, art) in &artwork {
+                art.save(grid_dir.join(format!("{}{}.png", app_id, suffix)))?;
+            }
+
+            let entry = VdfValue::Map(vec![
+                ("appid".to_string(), VdfValue::Int(app_id)),
+                ("AppName".to_string(), VdfValue::String(instance.name.clone())),
+                ("Exe".to_string(), VdfValue::String(exe_field.clone())),
+                (
+                    "StartDir".to_string(),
+                    VdfValue::String(format!("\"{}\"", exe.parent().unwrap_or(&exe).display())),
+                ),
+                (
+                    "icon".to_string(),
+                    VdfValue::String(grid_dir.join(format!("{}_icon.png", app_id)).to_string_lossy().to_string()),
+                ),
+                ("ShortcutPath".to_string(), VdfValue::String(String::new())),
+                (
+                    "LaunchOptions".to_string(),
+                    VdfValue::String(format!("{} \"{}\"", LAUNCH_ARG, instance.name)),
+                ),
+                ("IsHidden".to_string(), VdfValue::Int(0)),
+                ("AllowDesktopConfig".to_string(), VdfValue::Int(1)),
+                ("AllowOverlay".to_string(), VdfValue::Int(1)),
+                ("OpenVR".to_string(), VdfValue::Int(0)),
+                ("Devkit".to_string(), VdfValue::Int(0)),
+                ("DevkitGameID".to_string(), VdfValue::String(String::new())),
+                ("DevkitOverrideAppID".to_string(), VdfValue::Int(0)),
+                ("LastPlayTime".to_string(), VdfValue::Int(0)),
+                ("FlatpakAppID".to_string(), VdfValue::String(String::new())),
+                (
+                    "tags".to_string(),
+                    VdfValue::Map(vec![("0".to_string(), VdfValue::String("Minecraft".to_string()))]),
+                ),
+            ]);
+
+            let vdf_path = config_dir.join("shortcuts.vdf");
+            let mut shortcuts: Vec<VdfValue> = match fs::read(&vdf_path) {
+                Ok(bytes) => match vdf::parse(&bytes)?.into_iter().find(|(k, _)| k.eq_ignore_ascii_case("shortcuts")) {
+                    Some((_, VdfValue::Map(entries))) => entries.into_iter().map(|(_, v)| v).collect(),
+                    _ => Vec::new(),
+                },
+                Err(_) => Vec::new(),
+            };
+
+            shortcuts.retain(|s| s.get("appid").and_then(|v| v.as_int()) != Some(app_id));
+            shortcuts.push(entry);
+
+            // Keys are positions, rewrite them so they stay contiguous
+            let shortcuts = shortcuts
+                .into_iter()
+                .enumerate()
+                .map(|(i, s)| (i.to_string(), s))
+                .collect();
+            fs::write(&vdf_path, vdf::write(&[("shortcuts".to_string(), VdfValue::Map(shortcuts))]))?;
+
+            println!("✓ Added '{}' to Steam ({})", instance.name, vdf_path.display());
+            updated.push(vdf_path.to_string_lossy().to_string());
+        }
+
+        Ok(SteamShortcutReport { app_id, updated })
+    }
+}
+
+/// Names end up inside quoted command lines, imported packs and share codes can name instances anything
+fn check_shortcut_name(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if name.chars().any(|c| c.is_control() || SHORTCUT_UNSAFE.contains(&c)) {
+        return Err(format!("Rename '{}' without quotes or control characters to make a shortcut for it", name).into());
+    }
+    Ok(())
+}
+
+/// Id Steam derives for a non-Steam game, its grid artwork is looked up by this
+fn steam_app_id(exe: &str, app_name: &str) -> u32 {
+    crc32(format!("{}{}", exe, app_name).as_bytes()) | 0x8000_0000
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn find_steam_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let candidates = {
+        let mut candidates = vec![PathBuf::from(r"C:\Program Files (x86)\Steam")];
+        if let Ok(output) = Command::new("reg")
+            .args(["query", r"HKCU\Software\Valve\Steam", "/v", "SteamPath"])
+            .output()
+        {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if let Some(path) = stdout.lines().find_map(|l| l.split_once("REG_SZ").map(|(_, p)| p.trim().to_string())) {
+                candidates.insert(0, PathBuf::from(path));
+            }
+        }
+        candidates
+    };
+
+    #[cfg(target_os = "macos")]
+    let candidates = vec![dirs::home_dir()?.join("Library").join("Application Support").join("Steam")];
+
+    #[cfg(target_os = "linux")]
+    let candidates = {
+        let home = dirs::home_dir()?;
+        vec![
+            home.join(".steam").join("steam"),
+            home.join(".local").join("share").join("Steam"),
+            // Flatpak Steam
+            home.join(".var").join("app").join("com.valvesoftware.Steam").join(".local").join("share").join("Steam"),
+        ]
+    };
+
+    candidates.into_iter().find(|p| p.join("userdata").is_dir())
+}
+
+fn is_steam_running() -> bool {
+    let mut system = sysinfo::System::new();
+    system.refresh_processes();
+    system
+        .processes()
+        .values()
+        .any(|p| matches!(p.name().to_lowercase().as_str(), "steam" | "steam.exe" | "steam_osx"))
+}
+
+/// Capsule, wide capsule, hero and icon images in the sizes Steam's library uses,
+/// as (file name suffix, image) pairs
+fn steam_artwork(instance: &Instance) -> Result<Vec<(&'static str, DynamicImage)>, Box<dyn std::error::Error>> {
+    let instance_dir = get_instance_dir(&instance.name);
+
+    let icon = match image::open(instance_dir.join("icon.png")) {
+        Ok(icon) => icon,
+        Err(_) => image::load_from_memory(InstanceIcons::bundled("grass").ok_or("Missing bundled icon")?)?,
+    };
+    let banner = image::open(instance_dir.join("banner.png")).ok();
+
+    let background = instance
+        .accent_color
+        .as_deref()
+        .and_then(|c| u32::from_str_radix(c.trim_start_matches('#'), 16).ok())
+        .map(|c| [(c >> 16) as u8, (c >> 8) as u8, c as u8])
+        .unwrap_or(DEFAULT_ARTWORK_BACKGROUND);
+
+    let card = |width: u32, height: u32, icon_size: u32| {
+        let mut canvas = RgbaImage::from_pixel(width, height, Rgba([background[0], background[1], background[2], 255]));
+        // Icons are mostly pixel art, Nearest keeps them sharp when scaled up
+        let scaled = icon.resize_exact(icon_size, icon_size, FilterType::Nearest);
+        image::imageops::overlay(
+            &mut canvas,
+            &scaled,
+            ((width - icon_size) / 2) as i64,
+            ((height - icon_size) / 2) as i64,
+        );
+        DynamicImage::ImageRgba8(canvas)
+    };
+    let wide = |width: u32, height: u32, icon_size: u32| match &banner {
+        Some(banner) => banner.resize_to_fill(width, height, FilterType::Lanczos3),
+        None => card(width, height, icon_size),
+    };
+
+    Ok(vec![
+        ("p", card(600, 900, 400)),
+        ("", wide(920, 430, 300)),
+        ("_hero", wide(1920, 620, 400)),
+        ("_icon", icon.resize_exact(256, 256, FilterType::Nearest)),
+    ])
+}
+
+#[cfg(target_os = "windows")]
+fn write_shortcut(
+    desktop: &Path,
+    instance_name: &str,
+    exe: &Path,
+    icon: Option<&Path>,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let path = desktop.join(format!("{}.lnk", instance_name));
+
+    // Shortcuts can only use .ico files for their icon
+    let icon_location = match icon {
+        Some(icon) => {
+            let ico_path = get_instance_dir(instance_name).join("shortcut.ico");
+            image::open(icon)?
+                .resize_exact(256, 256, image::imageops::FilterType::Lanczos3)
+                .save_with_format(&ico_path, image::ImageFormat::Ico)?;
+            ico_path
+        }
+        None => exe.to_path_buf(),
+    };
+
+    // PowerShell ends single-quoted strings on the typographic single quotes too, doubling escapes each
+    let quote = |s: &str| {
+        let escaped: String = s
+            .chars()
+            .flat_map(|c| match c {
+                '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => vec![c, c],
+                _ => vec![c],
+            })
+            .collect();
+        format!("'{}'", escaped)
+    };
+    let script = format!(
+        "$s = (New-Object -ComObject WScript.Shell).CreateShortcut({}); \
+         $s.TargetPath = {}; $s.Arguments = {}; $s.WorkingDirectory = {}; $s.IconLocation = {}; $s.Save()",
+        quote(&path.to_string_lossy()),
+        quote(&exe.to_string_lossy()),
+        quote(&format!("{} \"{}\"", LAUNCH_ARG, instance_name)),
+        quote(&exe.parent().unwrap_or(exe).to_string_lossy()),
+        quote(&icon_location.to_string_lossy()),
+    );
+
+    // -EncodedCommand expects UTF-16LE and leaves no second round of command-line parsing
+    let utf16: Vec<u8> = script.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+    let encoded = general_purpose::STANDARD.encode(utf16);
+
+    let status = Command::new("powershell")
+        .args(["-NoProfile", "-EncodedCommand", &encoded])
+        .status()?;
+    if !status.success() {
+        return Err(format!("PowerShell could not create the shortcut ({})", status).into());
+    }
+
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn write_shortcut(
+    desktop: &Path,
+    instance_name: &str,
+    exe: &Path,
+    icon: Option<&Path>,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A minimal app bundle whose only job is to start the launcher
+    let path = desktop.join(format!("{}.app", instance_name));
+    let contents = path.join("Contents");
+    let macos_dir = contents.join("MacOS");
+    let resources = contents.join("Resources");
+    fs::create_dir_all(&macos_dir)?;
+    fs::create_dir_all(&resources)?;
+
+    let script_path = macos_dir.join("launch");
+    fs::write(
+        &script_path,
+        format!(
+            "#!/bin/sh\nexec {} {} {}\n",
+            shell_quote(&exe.to_string_lossy()),
+            LAUNCH_ARG,
+            shell_quote(instance_name)
+        ),
+    )?;
+    fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))?;
+
+    // sips ships with macOS and can turn the PNG into an .icns
+    let has_icon = icon.is_some_and(|icon| {
+        Command::new("sips")
+            .args(["-s", "format", "icns"])
+            .arg(icon)
+            .arg("--out")
+            .arg(resources.join("icon.icns"))
+            .output()
+            .is_ok_and(|o| o.status.success())
+    });
+
+    let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    fs::write(
+        contents.join("Info.plist"),
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleName</key>
+    <string>{}</string>
+    <key>CFBundleExecutable</key>
+    <string>launch</string>
+    <key>CFBundleIdentifier</key>
+    <string>com.atomiclauncher.shortcut.{}</string>
+    <key>CFBundlePackageType</key>
+    <string>APPL</string>{}
+</dict>
+</plist>
+"#,
+            escape(instance_name),
+            instance_name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                .collect::<String>(),
+            if has_icon {
+                "\n    <key>CFBundleIconFile</key>\n    <string>icon</string>"
+            } else {
+                ""
+            }
+        ),
+    )?;
+
+    Ok(path)
+}
+
+#[cfg(target_os = "linux")]
+fn write_shortcut(
+    desktop: &Path,
+    instance_name: &str,
+    exe: &Path,
+    icon: Option<&Path>,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Exec arguments are double-quoted, where these characters need a backslash
+    let exec_quote = |s: &str| {
+        let escaped: String = s
+            .chars()
+            .flat_map(|c| match c {
+                '"' | '`' | '$' | '\\' => vec!['\\', c],
+                '%' => vec!['%', '%'],
+                _ => vec![c],
+            })
+            .collect();
+        format!("\"{}\"", escaped)
+    };
+
+    // The spec's string escapes apply to every value, Exec is unescaped this way before its quoting is read
+    let value_escape = |s: &str| {
+        s.chars()
+            .map(|c| match c {
+                '\\' => "\\\\".to_string(),
+                '\n' => "\\n".to_string(),
+                '\t' => "\\t".to_string(),
+                '\r' => "\\r".to_string(),
+                _ => c.to_string(),
+            })
+            .collect::<String>()
+    };
+
+    let mut entry = format!(
+        "[Desktop Entry]\nType=Application\nName={}\nComment=Launch {} with Atomic Launcher\nExec={} {} {}\nTerminal=false\nCategories=Game;\n",
+        value_escape(instance_name),
+        value_escape(instance_name),
+        value_escape(&exec_quote(&exe.to_string_lossy())),
+        LAUNCH_ARG,
+        value_escape(&exec_quote(instance_name))
+    );
+    if let Some(icon) = icon {
+        entry.push_str(&format!("Icon={}\n", value_escape(&icon.to_string_lossy())));
+    }
+
+    let file_stem: String = instance_name
+        .chars()
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .collect();
+    let path = desktop.join(format!("{}.desktop", file_stem));
+    fs::write(&path, entry)?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+
+    // GNOME won't run desktop files it doesn't trust, not every desktop has gio
+    let _ = Command::new("gio")
+        .args(["set"])
+        .arg(&path)
+        .args(["metadata::trusted", "true"])
+        .output();
+
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
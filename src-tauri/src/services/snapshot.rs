@@ -0,0 +1,176 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::utils::links::is_link;
+use crate::utils::{copy_dir_recursive, get_instance_dir, long_path};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// What a snapshot captures. Saves are left out on purpose, a rollback shouldn't undo play progress.
+const SNAPSHOT_DIRS: &[&str] = &["mods", "config"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstanceSnapshot {
+    pub id: String,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    /// Folders left out because they are shared with other instances
+    #[serde(default)]
+    pub skipped_shared: Vec<String>,
+}
+
+pub struct SnapshotManager;
+
+impl SnapshotManager {
+    pub fn get_snapshots_dir(instance_name: &str) -> PathBuf {
+        get_instance_dir(instance_name).join("snapshots")
+    }
+
+    /// Copy mods, config and instance.json into snapshots/<timestamp>
+    pub fn create(instance_name: &str, label: Option<String>) -> Result<InstanceSnapshot, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        if !instance_dir.join("instance.json").exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let created_at = Utc::now();
+        let id = created_at.format("%Y%m%d-%H%M%S%3f").to_string();
+        let snapshot_dir = Self::get_snapshots_dir(instance_name).join(&id);
+        if snapshot_dir.exists() {
+            return Err(format!("Snapshot '{}' already exists", id).into());
+        }
+
+        fs::create_dir_all(&snapshot_dir)?;
+
+        let result = (|| -> Result<InstanceSnapshot, Box<dyn std::error::Error>> {
+            let mut skipped_shared = Vec::new();
+
+            for dir in SNAPSHOT_DIRS {
+                let source = instance_dir.join(dir);
+                if !source.exists() {
+                    continue;
+                }
+                // Rolling back a shared folder would change every instance using it
+                if is_link(&source) {
+                    skipped_shared.push(dir.to_string());
+                    continue;
+                }
+                copy_dir_recursive(&source, &snapshot_dir.join(dir))?;
+            }
+
+            fs::copy(instance_dir.join("instance.json"), snapshot_dir.join("instance.json"))?;
+
+            let snapshot = InstanceSnapshot {
+                id: id.clone(),
+                label: label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+                created_at,
+                size_bytes: dir_size(&snapshot_dir),
+                skipped_shared,
+            };
+            fs::write(
+                snapshot_dir.join("snapshot.json"),
+                serde_json::to_string_pretty(&snapshot)?,
+            )?;
+
+            Ok(snapshot)
+        })();
+
+        match result {
+            Ok(snapshot) => {
+                println!("✓ Created snapshot '{}' of '{}'", snapshot.id, instance_name);
+                Ok(snapshot)
+            }
+            Err(e) => {
+                let _ = fs::remove_dir_all(&snapshot_dir);
+                Err(e)
+            }
+        }
+    }
+
+    /// Newest first
+    pub fn list(instance_name: &str) -> Result<Vec<InstanceSnapshot>, Box<dyn std::error::Error>> {
+        let snapshots_dir = Self::get_snapshots_dir(instance_name);
+        let mut snapshots = Vec::new();
+
+        if !snapshots_dir.exists() {
+            return Ok(snapshots);
+        }
+
+        for entry in fs::read_dir(&snapshots_dir)? {
+            let path = entry?.path();
+            let Some(snapshot) = fs::read_to_string(path.join("snapshot.json"))
+                .ok()
+                .and_then(|c| serde_json::from_str::<InstanceSnapshot>(&c).ok())
+            else {
+                println!("⚠ Skipping unreadable snapshot {}", path.display());
+                continue;
+            };
+            snapshots.push(snapshot);
+        }
+
+        snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(snapshots)
+    }
+
+    /// Put the snapshot's mods, config and instance.json back. The current state is snapshotted first
+    /// so a rollback can itself be undone.
+    pub fn rollback(instance_name: &str, snapshot_id: &str) -> Result<Instance, Box<dyn std::error::Error>> {
+        let snapshot_dir = Self::get_snapshots_dir(instance_name).join(snapshot_id);
+        if !snapshot_dir.join("snapshot.json").exists() {
+            return Err(format!("Snapshot '{}' not found", snapshot_id).into());
+        }
+
+        if crate::commands::instances::RUNNING_PROCESSES.lock().unwrap().contains_key(instance_name) {
+            return Err(format!("Instance '{}' is running", instance_name).into());
+        }
+
+        let current = InstanceManager::load(instance_name)?;
+        Self::create(instance_name, Some(format!("Before rollback to {}", snapshot_id)))?;
+
+        let instance_dir = get_instance_dir(instance_name);
+        for dir in SNAPSHOT_DIRS {
+            let target = instance_dir.join(dir);
+            let source = snapshot_dir.join(dir);
+            if is_link(&target) {
+                continue;
+            }
+
+            if target.exists() {
+                fs::remove_dir_all(long_path(&target))?;
+            }
+            if source.exists() {
+                copy_dir_recursive(&source, &target)?;
+            }
+        }
+
+        let mut instance: Instance =
+            serde_json::from_str(&fs::read_to_string(snapshot_dir.join("instance.json"))?)?;
+        // The instance may have been renamed or played since, those aren't part of the rollback
+        instance.name = current.name;
+        instance.last_played = current.last_played;
+        instance.total_playtime_seconds = current.total_playtime_seconds;
+        InstanceManager::save(&instance)?;
+
+        println!("✓ Rolled back '{}' to snapshot '{}'", instance_name, snapshot_id);
+        Ok(instance)
+    }
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(long_path(path)) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
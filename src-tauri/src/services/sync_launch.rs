@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Condvar, Mutex},
+    time::{Duration, Instant},
+};
+use tauri::Emitter;
+
+/// How long prepared instances wait for the slowest one before everyone gives up
+const READY_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Launch error of a prepared instance held back because the group was called off
+pub const CALLED_OFF: &str = "Not started, another instance in the synchronized launch failed";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SyncLaunchStatus {
+    Preparing,
+    /// Prepared and waiting for the others
+    Ready,
+    Started,
+    Failed { error: String },
+    /// Prepared, but not started because another instance failed
+    Aborted,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncLaunchReport {
+    pub instance_name: String,
+    #[serde(flatten)]
+    pub status: SyncLaunchStatus,
+}
+
+#[derive(Default)]
+struct GateState {
+    ready: Vec<String>,
+    failed: Vec<String>,
+}
+
+/// Holds prepared launches back until every instance in the group is ready, then releases them
+/// together. If any of them fails, none are started.
+pub struct StartGate {
+    expected: usize,
+    state: Mutex<GateState>,
+    changed: Condvar,
+    app_handle: tauri::AppHandle,
+}
+
+impl StartGate {
+    pub fn new(expected: usize, app_handle: tauri::AppHandle) -> Self {
+        Self {
+            expected,
+            state: Mutex::new(GateState::default()),
+            changed: Condvar::new(),
+            app_handle,
+        }
+    }
+
+    /// Mark the instance ready and block until the rest are. False means the group was called
+    /// off and the instance must not be started.
+    pub fn arrive(&self, instance_name: &str) -> bool {
+        self.report(instance_name, SyncLaunchStatus::Ready);
+        let deadline = Instant::now() + READY_TIMEOUT;
+
+        let mut state = self.state.lock().unwrap();
+        state.ready.push(instance_name.to_string());
+        self.changed.notify_all();
+
+        while state.failed.is_empty() && state.ready.len() < self.expected {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                state.failed.push(instance_name.to_string());
+                self.changed.notify_all();
+                break;
+            }
+            state = self.changed.wait_timeout(state, remaining).unwrap().0;
+        }
+
+        state.failed.is_empty()
+    }
+
+    /// Call the group off, releasing everyone waiting in `arrive`
+    pub fn abandon(&self, instance_name: &str) {
+        let mut state = self.state.lock().unwrap();
+        if !state.failed.iter().any(|n| n == instance_name) {
+            state.failed.push(instance_name.to_string());
+        }
+        self.changed.notify_all();
+    }
+
+    /// Send an instance's progress to the UI as `sync-launch-status`
+    pub fn report(&self, instance_name: &str, status: SyncLaunchStatus) {
+        let _ = self.app_handle.emit(
+            "sync-launch-status",
+            SyncLaunchReport {
+                instance_name: instance_name.to_string(),
+                status,
+            },
+        );
+    }
+}
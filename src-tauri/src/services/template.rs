@@ -1,5 +1,6 @@
-use crate::models::{InstanceTemplate, MinecraftOptions};
-use crate::utils::get_launcher_dir;
+use crate::models::{InstanceTemplate, LauncherSettings, MinecraftOptions};
+use crate::utils::game_options::translate_keybind;
+use crate::utils::{get_current_os, get_launcher_dir};
 use chrono::Utc;
 use std::{fs, path::PathBuf};
 
@@ -17,9 +18,15 @@ impl TemplateManager {
     pub fn create_template(
         name: String,
         description: Option<String>,
-        launcher_settings: Option<crate::models::LauncherSettings>,
+        mut launcher_settings: Option<crate::models::LauncherSettings>,
         minecraft_options: Option<MinecraftOptions>,
     ) -> Result<InstanceTemplate, Box<dyn std::error::Error>> {
+        // Templates get shared, the API keys are the player's own
+        if let Some(settings) = launcher_settings.as_mut() {
+            settings.curseforge_api_key = None;
+            settings.modrinth_token = None;
+        }
+
         let templates_dir = Self::get_templates_dir();
         fs::create_dir_all(&templates_dir)?;
 
@@ -34,6 +41,7 @@ impl TemplateManager {
             created_at: Utc::now().to_rfc3339(),
             launcher_settings,
             minecraft_options,
+            source_os: Some(get_current_os()),
         };
 
         let template_path = Self::get_template_path(&template.id);
@@ -258,11 +266,37 @@ impl TemplateManager {
         Ok(options)
     }
 
+    /// Adapt settings captured on `source_os` to this machine: key bindings are translated and a
+    /// Java path from another OS is dropped so the launcher picks its own
+    pub fn localize(
+        launcher_settings: &mut Option<LauncherSettings>,
+        minecraft_options: &mut Option<MinecraftOptions>,
+        source_os: Option<&str>,
+    ) {
+        let current_os = get_current_os();
+        let Some(source_os) = source_os.filter(|os| *os != current_os.as_str()) else {
+            return;
+        };
+
+        if let Some(settings) = launcher_settings {
+            if settings.java_path.take().is_some() {
+                println!("  ⚠ Ignoring Java path from a {} template", source_os);
+            }
+        }
+
+        if let Some(keybinds) = minecraft_options.as_mut().and_then(|o| o.keybinds.as_mut()) {
+            for value in keybinds.values_mut() {
+                *value = translate_keybind(value, source_os, &current_os);
+            }
+        }
+    }
+
     pub fn apply_template_to_instance(
         template_id: &str,
         instance_name: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let template = Self::get_template(template_id)?;
+        let mut template = Self::get_template(template_id)?;
+        Self::localize(&mut template.launcher_settings, &mut template.minecraft_options, template.source_os.as_deref());
         let instance_dir = crate::utils::get_instance_dir(instance_name);
 
         if !instance_dir.exists() {
@@ -0,0 +1,165 @@
+use crate::services::settings::SettingsManager;
+use crate::utils::get_launcher_dir;
+use sha1::{Digest, Sha1};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Password of the JDK's cacerts, kept for the copies so tools that assume it still work
+const STORE_PASSWORD: &str = "changeit";
+
+pub struct TrustStoreManager;
+
+impl TrustStoreManager {
+    /// Load the CA certificates from settings into the launcher's HTTP clients
+    pub fn apply(paths: &[String]) -> Result<(), String> {
+        let certificates = Self::load_certificates(paths)?;
+        if !certificates.is_empty() {
+            println!("✓ Trusting {} extra CA certificates", certificates.len());
+        }
+        crate::utils::http::set_extra_certificates(certificates);
+        Ok(())
+    }
+
+    /// Parse every certificate in the files, failing on the first one that isn't valid
+    pub fn load_certificates(paths: &[String]) -> Result<Vec<reqwest::Certificate>, String> {
+        let mut certificates = Vec::new();
+        for path in paths {
+            for block in Self::read_certificates(path)? {
+                let certificate = match block {
+                    Certificate::Pem(pem) => reqwest::Certificate::from_pem(pem.as_bytes()),
+                    Certificate::Der(der) => reqwest::Certificate::from_der(&der),
+                }
+                .map_err(|e| format!("Invalid certificate in {}: {}", path, e))?;
+                certificates.push(certificate);
+            }
+        }
+
+        Ok(certificates)
+    }
+
+    /// JVM flags that make the game trust the certificates from settings as well, empty when
+    /// there are none
+    pub fn jvm_args(java_path: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let paths = SettingsManager::load()?.ca_certificates;
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let store = Self::java_trust_store(java_path, &paths)?;
+        Ok(vec![
+            format!("-Djavax.net.ssl.trustStore={}", store.display()),
+            format!("-Djavax.net.ssl.trustStorePassword={}", STORE_PASSWORD),
+        ])
+    }
+
+    /// A copy of the Java's own cacerts with the extra certificates imported, so Mojang's and
+    /// Microsoft's servers stay trusted. Built once per Java and certificate set.
+    fn java_trust_store(java_path: &str, paths: &[String]) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let bin_dir = Path::new(java_path).parent().ok_or("Invalid Java path")?;
+        let java_home = bin_dir.parent().ok_or("Invalid Java path")?;
+        let cacerts = ["lib/security/cacerts", "jre/lib/security/cacerts"]
+            .iter()
+            .map(|p| java_home.join(p))
+            .find(|p| p.exists())
+            .ok_or_else(|| format!("No cacerts found in {}", java_home.display()))?;
+        let keytool = bin_dir.join(if cfg!(target_os = "windows") { "keytool.exe" } else { "keytool" });
+        if !keytool.exists() {
+            return Err(format!("No keytool found next to {}", java_path).into());
+        }
+
+        let mut blocks = Vec::new();
+        let mut hasher = Sha1::new();
+        hasher.update(java_path.as_bytes());
+        for path in paths {
+            for block in Self::read_certificates(path)? {
+                hasher.update(block.bytes());
+                blocks.push(block);
+            }
+        }
+
+        let stores_dir = get_launcher_dir().join("truststores");
+        let store = stores_dir.join(format!("{:x}.jks", hasher.finalize()));
+        if store.exists() {
+            return Ok(store);
+        }
+
+        fs::create_dir_all(&stores_dir)?;
+        let building = store.with_extension("tmp");
+        fs::copy(&cacerts, &building)?;
+
+        for (i, block) in blocks.iter().enumerate() {
+            let cert_file = stores_dir.join(format!("import-{}.crt", i));
+            fs::write(&cert_file, block.bytes())?;
+
+            let output = Command::new(&keytool)
+                .args(["-importcert", "-noprompt", "-trustcacerts"])
+                .arg("-alias")
+                .arg(format!("atomic-launcher-{}", i))
+                .arg("-file")
+                .arg(&cert_file)
+                .arg("-keystore")
+                .arg(&building)
+                .arg("-storepass")
+                .arg(STORE_PASSWORD)
+                .output();
+            let _ = fs::remove_file(&cert_file);
+
+            let output = output?;
+            if !output.status.success() {
+                let _ = fs::remove_file(&building);
+                return Err(format!(
+                    "keytool failed to import certificate {}: {}",
+                    i + 1,
+                    String::from_utf8_lossy(&output.stdout).trim()
+                )
+                .into());
+            }
+        }
+
+        fs::rename(&building, &store)?;
+        println!("✓ Built trust store for {} with {} extra certificates", java_path, blocks.len());
+        Ok(store)
+    }
+
+    /// Every certificate in a PEM bundle, or the file itself if it's DER
+    fn read_certificates(path: &str) -> Result<Vec<Certificate>, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read certificate {}: {}", path, e))?;
+
+        let Ok(text) = std::str::from_utf8(&bytes) else {
+            return Ok(vec![Certificate::Der(bytes)]);
+        };
+        if !text.contains("-----BEGIN CERTIFICATE-----") {
+            return Err(format!("{} contains no certificates", path));
+        }
+
+        const END: &str = "-----END CERTIFICATE-----";
+        let mut certificates = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("-----BEGIN CERTIFICATE-----") {
+            let Some(end) = rest[start..].find(END) else {
+                return Err(format!("Truncated certificate in {}", path));
+            };
+            let end = start + end + END.len();
+            certificates.push(Certificate::Pem(format!("{}\n", &rest[start..end])));
+            rest = &rest[end..];
+        }
+        Ok(certificates)
+    }
+}
+
+enum Certificate {
+    Pem(String),
+    Der(Vec<u8>),
+}
+
+impl Certificate {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Certificate::Pem(pem) => pem.as_bytes(),
+            Certificate::Der(der) => der,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::services::mod_metadata::ModMetadataReader;
+use crate::utils::get_instance_dir;
+use crate::utils::modrinth::ModrinthClient;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::fs;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityStatus {
+    /// The installed file lists the target version
+    Compatible,
+    /// The installed file doesn't support the target, but the project has a version that does
+    UpdateAvailable,
+    /// Neither the file nor the project support the target
+    Incompatible,
+    /// Not from Modrinth, nothing to check against
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModCompatibility {
+    pub filename: String,
+    pub name: String,
+    pub status: CompatibilityStatus,
+    /// Version number of the project's newest build for the target, when there is one
+    pub available_version: Option<String>,
+}
+
+pub struct VersionCompatChecker;
+
+impl VersionCompatChecker {
+    /// How the instance's enabled mods would fare on `target_version`
+    pub async fn check(
+        instance_name: &str,
+        loader: Option<&str>,
+        target_version: &str,
+    ) -> Result<Vec<ModCompatibility>, Box<dyn std::error::Error>> {
+        let mods_dir = get_instance_dir(instance_name).join("mods");
+        let mut report = Vec::new();
+        let mut hashes = Vec::new();
+
+        if !mods_dir.exists() {
+            return Ok(report);
+        }
+
+        for entry in fs::read_dir(&mods_dir)? {
+            let entry = entry?;
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if !filename.ends_with(".jar") {
+                continue;
+            }
+
+            let mut hasher = Sha1::new();
+            hasher.update(fs::read(entry.path())?);
+            hashes.push(format!("{:x}", hasher.finalize()));
+
+            report.push(ModCompatibility {
+                name: ModMetadataReader::read(&entry.path())
+                    .map(|m| m.name)
+                    .unwrap_or_else(|| filename.clone()),
+                filename,
+                status: CompatibilityStatus::Unknown,
+                available_version: None,
+            });
+        }
+
+        if report.is_empty() {
+            return Ok(report);
+        }
+
+        let client = ModrinthClient::new();
+        let versions = match client.get_versions_from_hashes(&hashes).await {
+            Ok(versions) => versions,
+            Err(e) => {
+                println!("⚠ Could not check mod compatibility on Modrinth: {}", e);
+                return Ok(report);
+            }
+        };
+
+        let loaders = loader.filter(|l| *l != "vanilla").map(|l| vec![l.to_string()]);
+
+        for (entry, hash) in report.iter_mut().zip(&hashes) {
+            let Some(version) = versions.get(hash) else {
+                continue;
+            };
+
+            if version.game_versions.iter().any(|v| v == target_version) {
+                entry.status = CompatibilityStatus::Compatible;
+                continue;
+            }
+
+            let candidates = client
+                .get_project_versions(&version.project_id, loaders.clone(), Some(vec![target_version.to_string()]))
+                .await
+                .unwrap_or_default();
+
+            match candidates.first() {
+                Some(candidate) => {
+                    entry.status = CompatibilityStatus::UpdateAvailable;
+                    entry.available_version = Some(candidate.version_number.clone());
+                }
+                None => entry.status = CompatibilityStatus::Incompatible,
+            }
+        }
+
+        report.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(report)
+    }
+}
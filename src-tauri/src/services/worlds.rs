@@ -0,0 +1,254 @@
+use crate::models::Instance;
+use crate::services::instance::InstanceManager;
+use crate::utils::nbt::{write_gzip, NbtValue};
+use crate::utils::{get_instance_dir, long_path};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// 1.13's data version. Worlds are written in its level.dat format and the game's own data
+/// fixers upgrade them on first load, so one format works for every newer version.
+const WORLD_DATA_VERSION: i32 = 1519;
+/// level.dat format of Anvil worlds
+const ANVIL_VERSION: i32 = 19133;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldPreset {
+    Default,
+    Flat,
+    LargeBiomes,
+    Amplified,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldGameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Hardcore,
+}
+
+/// One layer of a superflat world, listed bottom to top
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlatLayer {
+    /// e.g. "minecraft:bedrock"
+    pub block: String,
+    pub height: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorldConfig {
+    pub name: String,
+    /// Numbers are used as is, anything else is hashed like the game does
+    pub seed: Option<String>,
+    pub game_mode: WorldGameMode,
+    #[serde(default)]
+    pub allow_cheats: bool,
+    pub preset: WorldPreset,
+    /// Only for `Flat`, empty means the classic bedrock, dirt and grass
+    #[serde(default)]
+    pub flat_layers: Vec<FlatLayer>,
+    /// Only for `Flat` on 1.13+, e.g. "minecraft:plains"
+    #[serde(default)]
+    pub flat_biome: Option<String>,
+    /// Gamerule -> value, e.g. "doDaylightCycle" -> "false"
+    #[serde(default)]
+    pub game_rules: BTreeMap<String, String>,
+    /// Open the world straight away on the next launch, where the game supports Quick Play
+    #[serde(default)]
+    pub open_on_launch: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreatedWorld {
+    /// Folder under saves/, what Quick Play and the world list refer to
+    pub folder: String,
+    pub seed: i64,
+}
+
+pub struct WorldCreator;
+
+impl WorldCreator {
+    /// Write a fresh level.dat into saves/. The game generates terrain and finds a spawn point
+    /// the first time the world is opened.
+    pub fn create(instance: &mut Instance, config: &WorldConfig) -> Result<CreatedWorld, Box<dyn std::error::Error>> {
+        Self::validate(config)?;
+
+        let saves_dir = get_instance_dir(&instance.name).join("saves");
+        let folder = unique_folder_name(&saves_dir, &config.name);
+        let world_dir = saves_dir.join(&folder);
+        fs::create_dir_all(long_path(&world_dir))?;
+
+        let seed = config
+            .seed
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(parse_seed)
+            .unwrap_or_else(rand_seed);
+        let minecraft_version = InstanceManager::base_minecraft_version(&instance.version);
+        let legacy = is_legacy(&minecraft_version);
+
+        let (game_type, hardcore) = match config.game_mode {
+            WorldGameMode::Survival => (0, false),
+            WorldGameMode::Creative => (1, false),
+            WorldGameMode::Adventure => (2, false),
+            WorldGameMode::Hardcore => (0, true),
+        };
+        let generator_name = match config.preset {
+            WorldPreset::Default => "default",
+            WorldPreset::Flat => "flat",
+            WorldPreset::LargeBiomes => "largeBiomes",
+            WorldPreset::Amplified => "amplified",
+        };
+        let generator_options = if config.preset == WorldPreset::Flat {
+            flat_generator_options(config, legacy)
+        } else {
+            String::new()
+        };
+
+        let mut data = vec![
+            ("LevelName".to_string(), NbtValue::String(config.name.trim().to_string())),
+            ("RandomSeed".to_string(), NbtValue::Long(seed)),
+            ("generatorName".to_string(), NbtValue::String(generator_name.to_string())),
+            ("generatorVersion".to_string(), NbtValue::Int(1)),
+            ("generatorOptions".to_string(), NbtValue::String(generator_options)),
+            ("GameType".to_string(), NbtValue::Int(game_type)),
+            ("hardcore".to_string(), NbtValue::Byte(hardcore as i8)),
+            ("allowCommands".to_string(), NbtValue::Byte(config.allow_cheats as i8)),
+            ("MapFeatures".to_string(), NbtValue::Byte(1)),
+            ("initialized".to_string(), NbtValue::Byte(0)),
+            ("LastPlayed".to_string(), NbtValue::Long(chrono::Utc::now().timestamp_millis())),
+            ("version".to_string(), NbtValue::Int(ANVIL_VERSION)),
+            (
+                "GameRules".to_string(),
+                NbtValue::Compound(
+                    config
+                        .game_rules
+                        .iter()
+                        .map(|(rule, value)| (rule.clone(), NbtValue::String(value.clone())))
+                        .collect(),
+                ),
+            ),
+        ];
+        if !legacy {
+            data.push(("DataVersion".to_string(), NbtValue::Int(WORLD_DATA_VERSION)));
+        }
+
+        let root = NbtValue::Compound(vec![("Data".to_string(), NbtValue::Compound(data))]);
+        if let Err(e) = write_gzip(&long_path(&world_dir.join("level.dat")), &root) {
+            let _ = fs::remove_dir_all(long_path(&world_dir));
+            return Err(e.into());
+        }
+
+        if config.open_on_launch {
+            instance.quick_play_world = Some(folder.clone());
+            InstanceManager::save(instance)?;
+        }
+
+        println!("✓ Created world '{}' in '{}' (seed {})", folder, instance.name, seed);
+        Ok(CreatedWorld { folder, seed })
+    }
+
+    fn validate(config: &WorldConfig) -> Result<(), String> {
+        let name = config.name.trim();
+        if name.is_empty() || name.len() > 64 {
+            return Err("World name must be 1-64 characters".to_string());
+        }
+
+        let valid_id = |id: &str| {
+            !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_:./-".contains(c))
+        };
+        for layer in &config.flat_layers {
+            if !valid_id(&layer.block) {
+                return Err(format!("Invalid block id '{}'", layer.block));
+            }
+            if layer.height == 0 || layer.height > 384 {
+                return Err(format!("Layer of {} must be 1-384 blocks high", layer.block));
+            }
+        }
+        if let Some(biome) = config.flat_biome.as_deref().filter(|b| !valid_id(b)) {
+            return Err(format!("Invalid biome id '{}'", biome));
+        }
+
+        for rule in config.game_rules.keys() {
+            if rule.is_empty() || !rule.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("Invalid gamerule '{}'", rule));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Before 1.13 flat worlds used the numbered "3;..." format and level.dat had no DataVersion
+fn is_legacy(minecraft_version: &str) -> bool {
+    let parts: Vec<u32> = minecraft_version
+        .split('.')
+        .filter_map(|p| p.parse().ok())
+        .collect();
+
+    matches!(parts.as_slice(), [1, minor, ..] if *minor < 13)
+}
+
+fn flat_generator_options(config: &WorldConfig, legacy: bool) -> String {
+    let layers: Vec<String> = if config.flat_layers.is_empty() {
+        vec![
+            "minecraft:bedrock".to_string(),
+            "2*minecraft:dirt".to_string(),
+            if legacy { "minecraft:grass" } else { "minecraft:grass_block" }.to_string(),
+        ]
+    } else {
+        config
+            .flat_layers
+            .iter()
+            .map(|l| if l.height == 1 { l.block.clone() } else { format!("{}*{}", l.height, l.block) })
+            .collect()
+    };
+
+    if legacy {
+        // Version 3 of the old format, biome 1 is plains
+        format!("3;{};1;village", layers.join(","))
+    } else {
+        let biome = config.flat_biome.as_deref().unwrap_or("minecraft:plains");
+        format!("{};{};village", layers.join(","), biome)
+    }
+}
+
+/// Same as the create world screen: numbers are the seed, text goes through String.hashCode()
+fn parse_seed(seed: &str) -> i64 {
+    let seed = seed.trim();
+    if let Ok(number) = seed.parse::<i64>() {
+        return number;
+    }
+
+    seed.encode_utf16()
+        .fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32)) as i64
+}
+
+fn rand_seed() -> i64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    // RandomState is seeded from the OS, good enough for a world seed
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+    hasher.finish() as i64
+}
+
+/// The game's own rule: strip characters Windows doesn't allow and add " (n)" on collisions
+pub(crate) fn unique_folder_name(saves_dir: &Path, name: &str) -> String {
+    let base: String = name
+        .trim()
+        .chars()
+        .map(|c| if "/\\:*?\"<>|.".contains(c) || c.is_control() { '_' } else { c })
+        .collect();
+    let base = if base.trim_matches('_').is_empty() { "World".to_string() } else { base };
+
+    let mut folder = base.clone();
+    let mut n = 1;
+    while saves_dir.join(&folder).exists() {
+        folder = format!("{} ({})", base, n);
+        n += 1;
+    }
+    folder
+}
@@ -0,0 +1,52 @@
+use super::long_path;
+use std::{fs, io::Read, path::Path};
+use zip::ZipArchive;
+
+/// Raw bytes of a single entry, e.g. a manifest or icon at the archive root
+pub fn read_entry(archive_path: &Path, name: &str) -> Option<Vec<u8>> {
+    let mut archive = ZipArchive::new(fs::File::open(archive_path).ok()?).ok()?;
+    let mut entry = archive.by_name(name).ok()?;
+
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// Extract everything below each prefix (e.g. "overrides/") into `dest`, in order, so later prefixes win.
+/// Entries that would escape `dest` are skipped.
+pub fn extract_prefixed(archive_path: &Path, prefixes: &[&str], dest: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut archive = ZipArchive::new(fs::File::open(archive_path)?)?;
+    let mut extracted = 0;
+
+    for prefix in prefixes {
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let Some(enclosed) = entry.enclosed_name() else {
+                continue;
+            };
+            let name = enclosed.to_string_lossy().replace('\\', "/");
+
+            let Some(relative) = name.strip_prefix(prefix) else {
+                continue;
+            };
+            if relative.is_empty() {
+                continue;
+            }
+
+            let out_path = long_path(&dest.join(relative));
+            if entry.is_dir() {
+                fs::create_dir_all(&out_path)?;
+                continue;
+            }
+
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut out_file = fs::File::create(&out_path)?;
+            std::io::copy(&mut entry, &mut out_file)?;
+            extracted += 1;
+        }
+    }
+
+    Ok(extracted)
+}
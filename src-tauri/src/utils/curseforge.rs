@@ -0,0 +1,360 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const CURSEFORGE_API_BASE: &str = "https://api.curseforge.com/v1";
+const MINECRAFT_GAME_ID: u32 = 432;
+
+// CurseForge class ids, used to decide where a file goes inside the instance
+pub const CLASS_RESOURCE_PACKS: u32 = 12;
+pub const CLASS_SHADERS: u32 = 6552;
+pub const CLASS_MODS: u32 = 6;
+
+// File relation types
+pub const RELATION_REQUIRED: u32 = 3;
+pub const RELATION_INCOMPATIBLE: u32 = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurseForgeHash {
+    pub value: String,
+    /// 1 = SHA1, 2 = MD5
+    pub algo: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u32,
+    pub mod_id: u32,
+    pub file_name: String,
+    /// None when the author has disabled third-party downloads
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeHash>,
+    #[serde(default)]
+    pub file_length: u64,
+    #[serde(default)]
+    pub file_fingerprint: u32,
+    /// 1 = release, 2 = beta, 3 = alpha
+    #[serde(default)]
+    pub release_type: u32,
+    /// Game versions and loader names mixed together, e.g. ["1.20.1", "Fabric"]
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<CurseForgeDependency>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeDependency {
+    pub mod_id: u32,
+    /// See the `RELATION_` constants
+    pub relation_type: u32,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes.iter().find(|h| h.algo == 1).map(|h| h.value.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMod {
+    pub id: u32,
+    pub name: String,
+    pub slug: String,
+    pub class_id: Option<u32>,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub download_count: u64,
+    #[serde(default)]
+    pub logo: Option<CurseForgeLogo>,
+    #[serde(default)]
+    pub links: Option<CurseForgeLinks>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeLogo {
+    pub thumbnail_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeLinks {
+    pub website_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgePagination {
+    pub index: u32,
+    pub page_size: u32,
+    pub result_count: u32,
+    pub total_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CurseForgeSearchResult {
+    pub data: Vec<CurseForgeMod>,
+    pub pagination: CurseForgePagination,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<FingerprintMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FingerprintMatch {
+    file: CurseForgeFile,
+}
+
+pub struct CurseForgeClient {
+    http_client: reqwest::Client,
+    api_key: String,
+}
+
+impl CurseForgeClient {
+    pub fn new(api_key: String) -> Self {
+        let http_client = crate::utils::http::client_builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("AtomicLauncher/2.4.0")
+            .build()
+            .unwrap();
+
+        Self { http_client, api_key }
+    }
+
+    /// Uses the key from settings, falling back to the CURSEFORGE_API_KEY environment variable
+    pub fn with_key(api_key: Option<String>) -> Result<Self, String> {
+        let api_key = api_key
+            .filter(|k| !k.trim().is_empty())
+            .or_else(|| std::env::var("CURSEFORGE_API_KEY").ok())
+            .ok_or_else(|| "CurseForge API key is not configured, add one in Settings".to_string())?;
+
+        Ok(Self::new(api_key.trim().to_string()))
+    }
+
+    pub async fn search_mods(
+        &self,
+        query: &str,
+        class_id: u32,
+        game_version: Option<&str>,
+        mod_loader_type: Option<u32>,
+        index: u32,
+        page_size: u32,
+    ) -> Result<CurseForgeSearchResult, Box<dyn std::error::Error>> {
+        let url = format!("{}/mods/search", CURSEFORGE_API_BASE);
+        let mut params = vec![
+            ("gameId", MINECRAFT_GAME_ID.to_string()),
+            ("classId", class_id.to_string()),
+            ("searchFilter", query.to_string()),
+            ("index", index.to_string()),
+            ("pageSize", page_size.to_string()),
+            // Popularity, like the CurseForge site
+            ("sortField", "2".to_string()),
+            ("sortOrder", "desc".to_string()),
+        ];
+
+        if let Some(game_version) = game_version {
+            params.push(("gameVersion", game_version.to_string()));
+        }
+
+        if let Some(mod_loader_type) = mod_loader_type {
+            params.push(("modLoaderType", mod_loader_type.to_string()));
+        }
+
+        let response = self
+            .http_client
+            .get(&url)
+            .header("x-api-key", &self.api_key)
+            .query(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("CurseForge API error: {}", error_text).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Files of a mod, newest first
+    pub async fn get_mod_files(
+        &self,
+        mod_id: u32,
+        game_version: Option<&str>,
+        mod_loader_type: Option<u32>,
+    ) -> Result<Vec<CurseForgeFile>, Box<dyn std::error::Error>> {
+        let url = format!("{}/mods/{}/files", CURSEFORGE_API_BASE, mod_id);
+        let mut params = vec![("pageSize", "50".to_string())];
+
+        if let Some(game_version) = game_version {
+            params.push(("gameVersion", game_version.to_string()));
+        }
+
+        if let Some(mod_loader_type) = mod_loader_type {
+            params.push(("modLoaderType", mod_loader_type.to_string()));
+        }
+
+        let response = self
+            .http_client
+            .get(&url)
+            .header("x-api-key", &self.api_key)
+            .query(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("CurseForge API error: {}", error_text).into());
+        }
+
+        let result: CurseForgeResponse<Vec<CurseForgeFile>> = response.json().await?;
+        Ok(result.data)
+    }
+
+    pub async fn get_files(&self, file_ids: &[u32]) -> Result<Vec<CurseForgeFile>, Box<dyn std::error::Error>> {
+        let url = format!("{}/mods/files", CURSEFORGE_API_BASE);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("x-api-key", &self.api_key)
+            .json(&serde_json::json!({ "fileIds": file_ids }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("CurseForge API error: {}", error_text).into());
+        }
+
+        let result: CurseForgeResponse<Vec<CurseForgeFile>> = response.json().await?;
+        Ok(result.data)
+    }
+
+    pub async fn get_mods(&self, mod_ids: &[u32]) -> Result<Vec<CurseForgeMod>, Box<dyn std::error::Error>> {
+        let url = format!("{}/mods", CURSEFORGE_API_BASE);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("x-api-key", &self.api_key)
+            .json(&serde_json::json!({ "modIds": mod_ids }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("CurseForge API error: {}", error_text).into());
+        }
+
+        let result: CurseForgeResponse<Vec<CurseForgeMod>> = response.json().await?;
+        Ok(result.data)
+    }
+
+    /// Identify local files by their `fingerprint`, keyed by fingerprint. Unknown files are missing from the map.
+    pub async fn get_fingerprint_matches(
+        &self,
+        fingerprints: &[u32],
+    ) -> Result<std::collections::HashMap<u32, CurseForgeFile>, Box<dyn std::error::Error>> {
+        let url = format!("{}/fingerprints/{}", CURSEFORGE_API_BASE, MINECRAFT_GAME_ID);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("x-api-key", &self.api_key)
+            .json(&serde_json::json!({ "fingerprints": fingerprints }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("CurseForge API error: {}", error_text).into());
+        }
+
+        let result: CurseForgeResponse<FingerprintMatches> = response.json().await?;
+        Ok(result
+            .data
+            .exact_matches
+            .into_iter()
+            .map(|m| (m.file.file_fingerprint, m.file))
+            .collect())
+    }
+
+    pub async fn download_file(&self, url: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let response = self.http_client.get(url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to download file: HTTP {}", response.status()).into());
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// CurseForge's ModLoaderType for a launcher loader id
+pub fn mod_loader_type(loader: &str) -> Option<u32> {
+    match loader {
+        "forge" => Some(1),
+        "liteloader" => Some(3),
+        "fabric" => Some(4),
+        "quilt" => Some(5),
+        "neoforge" => Some(6),
+        _ => None,
+    }
+}
+
+/// Instance subfolder for a project of the given class
+pub fn folder_for_class(class_id: Option<u32>) -> &'static str {
+    match class_id {
+        Some(CLASS_RESOURCE_PACKS) => "resourcepacks",
+        Some(CLASS_SHADERS) => "shaderpacks",
+        _ => "mods",
+    }
+}
+
+/// CurseForge's file fingerprint: MurmurHash2 (seed 1) of the file with whitespace bytes removed
+pub fn fingerprint(bytes: &[u8]) -> u32 {
+    const M: u32 = 0x5bd1e995;
+
+    let data: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, 9 | 10 | 13 | 32))
+        .collect();
+
+    let mut hash = 1 ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        hash = hash.wrapping_mul(M) ^ k;
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, b) in tail.iter().enumerate() {
+            hash ^= (*b as u32) << (8 * i);
+        }
+        hash = hash.wrapping_mul(M);
+    }
+
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(M);
+    hash ^ (hash >> 15)
+}
@@ -0,0 +1,91 @@
+use super::long_path;
+use std::{fs, io, path::Path};
+
+/// Entries tied to one computer's monitor or audio device, dropped when options move between machines
+pub const MACHINE_SPECIFIC_OPTIONS: &[&str] = &["fullscreenResolution", "soundDevice", "overrideWidth", "overrideHeight"];
+
+/// Read a single `key:value` entry from options.txt
+pub fn get_option(options_path: &Path, key: &str) -> Option<String> {
+    let content = fs::read_to_string(long_path(options_path)).ok()?;
+    let prefix = format!("{}:", key);
+
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix).map(|v| v.to_string()))
+}
+
+/// Set `key:value` entries in options.txt, creating the file if needed and keeping everything else
+pub fn set_options(options_path: &Path, values: &[(&str, String)]) -> io::Result<()> {
+    let options_path = long_path(options_path);
+    let existing = if options_path.exists() {
+        fs::read_to_string(&options_path)?
+    } else {
+        String::new()
+    };
+
+    let mut lines: Vec<String> = existing.lines().map(|s| s.to_string()).collect();
+    for (key, value) in values {
+        let prefix = format!("{}:", key);
+        match lines.iter().position(|l| l.starts_with(&prefix)) {
+            Some(pos) => lines[pos] = format!("{}{}", prefix, value),
+            None => lines.push(format!("{}{}", prefix, value)),
+        }
+    }
+
+    if let Some(parent) = options_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&options_path, lines.join("\n") + "\n")
+}
+
+/// Translate a key binding written on `from_os` for use on `to_os` ("windows", "osx" or "linux").
+/// macOS players bind Cmd, which is the Windows key elsewhere and taken by the system there, so it
+/// becomes Ctrl. Handles both 1.13+ key names and the numeric LWJGL 2 codes of older versions.
+pub fn translate_keybind(value: &str, from_os: &str, to_os: &str) -> String {
+    if from_os != "osx" || to_os == "osx" {
+        return value.to_string();
+    }
+
+    match value {
+        "key.keyboard.left.win" => "key.keyboard.left.control".to_string(),
+        "key.keyboard.right.win" => "key.keyboard.right.control".to_string(),
+        // LWJGL 2 LMETA / RMETA to LCONTROL / RCONTROL
+        "219" => "29".to_string(),
+        "220" => "157".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Rewrite a whole options.txt from another computer: machine-specific entries are dropped,
+/// key bindings translated and resource pack paths use forward slashes.
+pub fn translate_options(content: &str, from_os: &str, to_os: &str) -> String {
+    let mut lines = Vec::new();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            lines.push(line.to_string());
+            continue;
+        };
+
+        if MACHINE_SPECIFIC_OPTIONS.contains(&key) {
+            continue;
+        }
+
+        let value = if key.starts_with("key_") {
+            // Some versions append modifiers as "key:modifier"
+            match value.split_once(':') {
+                Some((bind, modifier)) => format!("{}:{}", translate_keybind(bind, from_os, to_os), modifier),
+                None => translate_keybind(value, from_os, to_os),
+            }
+        } else if key == "resourcePacks" || key == "incompatibleResourcePacks" {
+            // JSON list, a Windows path separator shows up as an escaped backslash
+            value.replace("\\\\", "/")
+        } else {
+            value.to_string()
+        };
+
+        lines.push(format!("{}:{}", key, value));
+    }
+
+    lines.join("\n") + "\n"
+}
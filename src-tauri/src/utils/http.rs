@@ -0,0 +1,26 @@
+use std::sync::RwLock;
+
+/// CA certificates from settings, trusted on top of the system roots
+static EXTRA_CERTIFICATES: RwLock<Vec<reqwest::Certificate>> = RwLock::new(Vec::new());
+
+/// Trust these in every client built afterwards
+pub fn set_extra_certificates(certificates: Vec<reqwest::Certificate>) {
+    *EXTRA_CERTIFICATES.write().unwrap() = certificates;
+}
+
+/// `reqwest::Client::builder()` that also trusts the certificates from settings, so downloads
+/// work behind proxies that intercept TLS
+pub fn client_builder() -> reqwest::ClientBuilder {
+    EXTRA_CERTIFICATES
+        .read()
+        .unwrap()
+        .iter()
+        .fold(reqwest::Client::builder(), |builder, certificate| {
+            builder.add_root_certificate(certificate.clone())
+        })
+}
+
+/// `reqwest::Client::new()` that also trusts the certificates from settings
+pub fn client() -> reqwest::Client {
+    client_builder().build().unwrap_or_default()
+}
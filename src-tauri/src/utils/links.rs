@@ -0,0 +1,168 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// True for symlinks and Windows junctions (the link itself, not its target)
+pub fn is_link(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+pub fn read_link(path: &Path) -> io::Result<PathBuf> {
+    if !is_link(path) {
+        return Err(io::Error::other(format!("{} is not a link", path.display())));
+    }
+
+    let target = fs::read_link(path)?;
+
+    // Junction targets come back in NT form, e.g. \??\C:\Users\...
+    #[cfg(target_os = "windows")]
+    let target = match target.to_str().and_then(|t| t.strip_prefix(r"\??\")) {
+        Some(stripped) => PathBuf::from(stripped),
+        None => target,
+    };
+
+    // Relative symlinks are resolved against the link's parent
+    if target.is_relative() {
+        if let Some(parent) = path.parent() {
+            return Ok(parent.join(target));
+        }
+    }
+
+    Ok(target)
+}
+
+/// A link whose target no longer exists
+pub fn is_broken_link(path: &Path) -> bool {
+    is_link(path) && fs::metadata(path).is_err()
+}
+
+/// Create a directory link (symlink on Unix, junction on Windows) at `link` pointing to `target`
+pub fn create_dir_link(target: &Path, link: &Path) -> io::Result<()> {
+    if !target.is_dir() {
+        return Err(io::Error::other(format!(
+            "Link target {} is not a directory",
+            target.display()
+        )));
+    }
+
+    if fs::symlink_metadata(link).is_ok() {
+        return Err(io::Error::other(format!("{} already exists", link.display())));
+    }
+
+    if would_create_cycle(target, link)? {
+        return Err(io::Error::other(format!(
+            "Linking {} to {} would create a cycle",
+            link.display(),
+            target.display()
+        )));
+    }
+
+    if let Some(parent) = link.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // Junctions don't require developer mode or elevation
+        let output = std::process::Command::new("cmd")
+            .args(["/C", "mklink", "/J"])
+            .arg(link)
+            .arg(target)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "Failed to create junction: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        std::os::unix::fs::symlink(target, link)?;
+    }
+
+    Ok(())
+}
+
+/// Remove a link without touching the data it points to. Refuses to remove real directories.
+pub fn remove_link(path: &Path) -> io::Result<()> {
+    if !is_link(path) {
+        return Err(io::Error::other(format!(
+            "Refusing to remove {}: not a link",
+            path.display()
+        )));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // Junctions and directory symlinks are removed as directories, file symlinks as files
+        fs::remove_dir(path).or_else(|_| fs::remove_file(path))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        fs::remove_file(path)
+    }
+}
+
+/// Point an existing link (or empty slot) at a new target
+pub fn relink_dir(target: &Path, link: &Path) -> io::Result<()> {
+    if is_link(link) {
+        if read_link(link).is_ok_and(|current| same_path(&current, target)) {
+            return Ok(());
+        }
+        remove_link(link)?;
+    }
+
+    create_dir_link(target, link)
+}
+
+/// Both paths lead to the same place, however they're spelled
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// A link placed inside its own target (or any descendant of it) makes the tree infinitely deep
+fn would_create_cycle(target: &Path, link: &Path) -> io::Result<bool> {
+    let target = target.canonicalize()?;
+
+    let link_parent = match link.parent() {
+        Some(parent) if parent.exists() => parent.canonicalize()?,
+        _ => return Ok(false),
+    };
+
+    Ok(link_parent.starts_with(&target))
+}
+
+/// Walk a directory without following links and collect every broken link
+pub fn find_broken_links(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut broken = Vec::new();
+
+    if !root.is_dir() {
+        return Ok(broken);
+    }
+
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            if is_broken_link(&path) {
+                broken.push(path);
+            }
+        } else if file_type.is_dir() {
+            broken.extend(find_broken_links(&path)?);
+        }
+    }
+
+    Ok(broken)
+}
@@ -1,5 +1,14 @@
 pub mod modrinth;
 pub mod utils;
+pub mod links;
+pub mod curseforge;
+pub mod archive;
+pub mod game_options;
+pub mod servers_dat;
+pub mod trash;
+pub mod vdf;
+pub mod nbt;
+pub mod http;
 
 pub use modrinth::*;
 pub use utils::*;
\ No newline at end of file
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 const MODRINTH_API_BASE: &str = "https://api.modrinth.com/v2";
+/// Collections only exist in the v3 API
+const MODRINTH_API_V3_BASE: &str = "https://api.modrinth.com/v3";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ModrinthSearchResult {
@@ -123,13 +125,45 @@ pub struct Dependency {
     pub dependency_type: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModrinthUser {
+    pub id: String,
+    pub username: String,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModrinthCollection {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon_url: Option<String>,
+    /// Project ids
+    pub projects: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModrinthNotification {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub notification_type: Option<String>,
+    pub title: String,
+    pub text: String,
+    pub link: String,
+    pub read: bool,
+    pub created: String,
+    /// For `project_update`, holds `project_id` and `version_id`
+    #[serde(default)]
+    pub body: serde_json::Value,
+}
+
 pub struct ModrinthClient {
     http_client: reqwest::Client,
 }
 
 impl ModrinthClient {
     pub fn new() -> Self {
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::utils::http::client_builder()
             .timeout(Duration::from_secs(30))
             .user_agent("AtomicLauncher/2.4.0")
             .build()
@@ -230,6 +264,148 @@ impl ModrinthClient {
         Ok(versions)
     }
 
+    /// Look up installed files by SHA1. Files Modrinth doesn't know are missing from the map.
+    pub async fn get_versions_from_hashes(
+        &self,
+        sha1_hashes: &[String],
+    ) -> Result<std::collections::HashMap<String, ModrinthVersion>, Box<dyn std::error::Error>> {
+        let url = format!("{}/version_files", MODRINTH_API_BASE);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .json(&serde_json::json!({ "hashes": sha1_hashes, "algorithm": "sha1" }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Look up specific versions by id, unknown ids are left out
+    pub async fn get_versions(&self, ids: &[String]) -> Result<Vec<ModrinthVersion>, Box<dyn std::error::Error>> {
+        let url = format!("{}/versions", MODRINTH_API_BASE);
+        let ids_param = serde_json::to_string(ids)?;
+
+        let response = self.http_client.get(&url).query(&[("ids", ids_param)]).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_projects(
+        &self,
+        ids: &[String],
+    ) -> Result<Vec<ModrinthProjectDetails>, Box<dyn std::error::Error>> {
+        let url = format!("{}/projects", MODRINTH_API_BASE);
+        let ids_param = serde_json::to_string(ids)?;
+
+        let response = self.http_client.get(&url).query(&[("ids", ids_param)]).send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// The account a personal access token belongs to
+    pub async fn get_authenticated_user(&self, token: &str) -> Result<ModrinthUser, Box<dyn std::error::Error>> {
+        let url = format!("{}/user", MODRINTH_API_BASE);
+        self.get_authenticated(&url, token).await
+    }
+
+    pub async fn get_followed_projects(
+        &self,
+        user_id: &str,
+        token: &str,
+    ) -> Result<Vec<ModrinthProjectDetails>, Box<dyn std::error::Error>> {
+        let url = format!("{}/user/{}/follows", MODRINTH_API_BASE, user_id);
+        self.get_authenticated(&url, token).await
+    }
+
+    pub async fn get_user_collections(
+        &self,
+        user_id: &str,
+        token: &str,
+    ) -> Result<Vec<ModrinthCollection>, Box<dyn std::error::Error>> {
+        let url = format!("{}/user/{}/collections", MODRINTH_API_V3_BASE, user_id);
+        self.get_authenticated(&url, token).await
+    }
+
+    /// Private collections need the token of their owner
+    pub async fn get_collection(&self, id: &str, token: Option<&str>) -> Result<ModrinthCollection, Box<dyn std::error::Error>> {
+        let url = format!("{}/collection/{}", MODRINTH_API_V3_BASE, id);
+        match token {
+            Some(token) => self.get_authenticated(&url, token).await,
+            None => {
+                let response = self.http_client.get(&url).send().await?;
+                if !response.status().is_success() {
+                    let error_text = response.text().await?;
+                    return Err(format!("Modrinth API error: {}", error_text).into());
+                }
+                Ok(response.json().await?)
+            }
+        }
+    }
+
+    pub async fn get_notifications(
+        &self,
+        user_id: &str,
+        token: &str,
+    ) -> Result<Vec<ModrinthNotification>, Box<dyn std::error::Error>> {
+        let url = format!("{}/user/{}/notifications", MODRINTH_API_BASE, user_id);
+        self.get_authenticated(&url, token).await
+    }
+
+    pub async fn mark_notifications_read(&self, ids: &[String], token: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/notifications", MODRINTH_API_BASE);
+        let ids_param = serde_json::to_string(ids)?;
+
+        let response = self
+            .http_client
+            .patch(&url)
+            .header("Authorization", token)
+            .query(&[("ids", ids_param)])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+
+        Ok(())
+    }
+
+    /// Modrinth takes the bare token in the Authorization header, without a scheme
+    async fn get_authenticated<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        token: &str,
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let response = self.http_client.get(url).header("Authorization", token).send().await?;
+
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err("The Modrinth token is invalid or has expired".into());
+        }
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+
+        Ok(response.json().await?)
+    }
+
     pub async fn download_mod_file(
         &self,
         url: &str,
@@ -242,7 +418,7 @@ impl ModrinthClient {
         }
 
         let bytes = response.bytes().await?;
-        std::fs::write(destination, bytes)?;
+        std::fs::write(super::long_path(destination), bytes)?;
 
         Ok(())
     }
@@ -0,0 +1,60 @@
+use flate2::{write::GzEncoder, Compression};
+use std::{fs, io::Write, path::Path};
+
+/// An NBT value to write. Only the tag types level.dat needs.
+#[derive(Debug, Clone)]
+pub enum NbtValue {
+    Byte(i8),
+    Int(i32),
+    Long(i64),
+    String(String),
+    Compound(Vec<(String, NbtValue)>),
+}
+
+impl NbtValue {
+    fn tag(&self) -> u8 {
+        match self {
+            NbtValue::Byte(_) => 1,
+            NbtValue::Int(_) => 3,
+            NbtValue::Long(_) => 4,
+            NbtValue::String(_) => 8,
+            NbtValue::Compound(_) => 10,
+        }
+    }
+
+    fn write_payload(&self, out: &mut Vec<u8>) {
+        match self {
+            NbtValue::Byte(v) => out.push(*v as u8),
+            NbtValue::Int(v) => out.extend_from_slice(&v.to_be_bytes()),
+            NbtValue::Long(v) => out.extend_from_slice(&v.to_be_bytes()),
+            NbtValue::String(v) => write_string(v, out),
+            NbtValue::Compound(entries) => {
+                for (name, value) in entries {
+                    out.push(value.tag());
+                    write_string(name, out);
+                    value.write_payload(out);
+                }
+                out.push(0);
+            }
+        }
+    }
+}
+
+/// Write `root` as an unnamed root compound, gzipped like the game's level.dat
+pub fn write_gzip(path: &Path, root: &NbtValue) -> std::io::Result<()> {
+    let mut data = vec![root.tag()];
+    write_string("", &mut data);
+    root.write_payload(&mut data);
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&data)?;
+    fs::write(path, encoder.finish()?)
+}
+
+/// NBT strings are modified UTF-8, which only differs from UTF-8 for nulls and astral characters
+fn write_string(value: &str, out: &mut Vec<u8>) {
+    let bytes = value.as_bytes();
+    let len = bytes.len().min(u16::MAX as usize);
+    out.extend_from_slice(&(len as u16).to_be_bytes());
+    out.extend_from_slice(&bytes[..len]);
+}
@@ -0,0 +1,199 @@
+use std::{fs, io, path::Path};
+
+/// One entry of the in-game multiplayer list
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerEntry {
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+}
+
+/// Read the server list from a servers.dat. Only the uncompressed NBT the game writes is supported.
+pub fn read_servers(path: &Path) -> io::Result<Vec<ServerEntry>> {
+    let data = fs::read(path)?;
+    let mut reader = NbtReader { data: &data, pos: 0 };
+
+    if reader.u8()? != TAG_COMPOUND {
+        return Err(invalid("servers.dat does not start with a compound"));
+    }
+    reader.string()?;
+
+    let mut servers = Vec::new();
+    loop {
+        let tag = reader.u8()?;
+        if tag == TAG_END {
+            break;
+        }
+        let name = reader.string()?;
+
+        if tag == TAG_LIST && name == "servers" {
+            let element = reader.u8()?;
+            let count = reader.i32()?.max(0);
+            for _ in 0..count {
+                if element != TAG_COMPOUND {
+                    reader.skip(element)?;
+                    continue;
+                }
+                if let Some(server) = read_server(&mut reader)? {
+                    servers.push(server);
+                }
+            }
+        } else {
+            reader.skip(tag)?;
+        }
+    }
+
+    Ok(servers)
+}
+
+/// `ip` is "host" or "host:port", bracketed for IPv6 literals
+pub fn split_address(ip: &str) -> (String, u16) {
+    if let Some(rest) = ip.strip_prefix('[') {
+        if let Some((host, port)) = rest.split_once(']') {
+            let port = port.strip_prefix(':').and_then(|p| p.parse().ok()).unwrap_or(25565);
+            return (host.to_string(), port);
+        }
+    }
+
+    match ip.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => match port.parse() {
+            Ok(port) => (host.to_string(), port),
+            Err(_) => (ip.to_string(), 25565),
+        },
+        _ => (ip.to_string(), 25565),
+    }
+}
+
+fn read_server(reader: &mut NbtReader) -> io::Result<Option<ServerEntry>> {
+    let mut name = None;
+    let mut ip = None;
+
+    loop {
+        let tag = reader.u8()?;
+        if tag == TAG_END {
+            break;
+        }
+        let key = reader.string()?;
+
+        match (tag, key.as_str()) {
+            (TAG_STRING, "name") => name = Some(reader.string()?),
+            (TAG_STRING, "ip") => ip = Some(reader.string()?),
+            _ => reader.skip(tag)?,
+        }
+    }
+
+    Ok(ip.filter(|ip| !ip.trim().is_empty()).map(|ip| {
+        let (address, port) = split_address(ip.trim());
+        ServerEntry {
+            name: name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| address.clone()),
+            address,
+            port,
+        }
+    }))
+}
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+struct NbtReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl NbtReader<'_> {
+    fn take(&mut self, len: usize) -> io::Result<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| invalid("Unexpected end of servers.dat"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i32(&mut self) -> io::Result<i32> {
+        let bytes = self.take(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// NBT strings are modified UTF-8, which only differs from UTF-8 for nulls and astral characters
+    fn string(&mut self) -> io::Result<String> {
+        let len = self.u16()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).to_string())
+    }
+
+    fn skip(&mut self, tag: u8) -> io::Result<()> {
+        match tag {
+            TAG_END => {}
+            TAG_BYTE => {
+                self.take(1)?;
+            }
+            TAG_SHORT => {
+                self.take(2)?;
+            }
+            TAG_INT | TAG_FLOAT => {
+                self.take(4)?;
+            }
+            TAG_LONG | TAG_DOUBLE => {
+                self.take(8)?;
+            }
+            TAG_BYTE_ARRAY => {
+                let len = self.i32()?.max(0) as usize;
+                self.take(len)?;
+            }
+            TAG_STRING => {
+                self.string()?;
+            }
+            TAG_LIST => {
+                let element = self.u8()?;
+                let count = self.i32()?.max(0);
+                for _ in 0..count {
+                    self.skip(element)?;
+                }
+            }
+            TAG_COMPOUND => loop {
+                let tag = self.u8()?;
+                if tag == TAG_END {
+                    break;
+                }
+                self.string()?;
+                self.skip(tag)?;
+            },
+            TAG_INT_ARRAY => {
+                let len = self.i32()?.max(0) as usize;
+                self.take(len.saturating_mul(4))?;
+            }
+            TAG_LONG_ARRAY => {
+                let len = self.i32()?.max(0) as usize;
+                self.take(len.saturating_mul(8))?;
+            }
+            _ => return Err(invalid("Unknown NBT tag in servers.dat")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use std::{io, path::Path, process::Command};
+
+/// Move a file or folder to the OS trash / recycle bin using the platform's own tools.
+/// Fails instead of deleting when no trash is available, callers decide whether to fall back.
+pub fn move_to_trash(path: &Path) -> io::Result<()> {
+    let path_str = path.to_string_lossy().to_string();
+
+    #[cfg(target_os = "windows")]
+    let status = {
+        let method = if path.is_dir() { "DeleteDirectory" } else { "DeleteFile" };
+        Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                &format!(
+                    "Add-Type -AssemblyName Microsoft.VisualBasic; \
+                     [Microsoft.VisualBasic.FileIO.FileSystem]::{}('{}', 'OnlyErrorDialogs', 'SendToRecycleBin')",
+                    method,
+                    path_str.replace('\'', "''")
+                ),
+            ])
+            .status()?
+    };
+
+    #[cfg(target_os = "macos")]
+    let status = Command::new("osascript")
+        .args([
+            "-e",
+            &format!(
+                "tell application \"Finder\" to delete POSIX file \"{}\"",
+                path_str.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+        ])
+        .status()?;
+
+    #[cfg(target_os = "linux")]
+    let status = Command::new("gio").args(["trash", &path_str]).status()?;
+
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "Could not move {} to the trash ({})",
+            path.display(),
+            status
+        )));
+    }
+
+    Ok(())
+}
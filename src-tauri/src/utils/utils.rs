@@ -1,4 +1,4 @@
-use std::{path::PathBuf, process::Command, fs};
+use std::{path::{Path, PathBuf}, process::Command, fs};
 
 pub fn get_current_os() -> String {
     #[cfg(target_os = "windows")]
@@ -45,6 +45,83 @@ pub fn get_instance_dir(instance_name: &str) -> PathBuf {
     get_instances_dir().join(instance_name)
 }
 
+pub fn get_shared_dir() -> PathBuf {
+    get_launcher_dir().join("shared")
+}
+
+/// Extended-length (\\?\) form of an absolute path on Windows so deep mod and asset trees
+/// aren't capped at MAX_PATH. Paths are kept as OsStr the whole way, so non-ASCII user names survive.
+/// Returns the path unchanged on other platforms.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        use std::ffi::OsString;
+        use std::path::{Component, Prefix};
+
+        let mut components = path.components();
+        let Some(Component::Prefix(prefix)) = components.next() else {
+            return path.to_path_buf();
+        };
+
+        let mut extended = match prefix.kind() {
+            Prefix::Disk(_) => {
+                let mut s = OsString::from(r"\\?\");
+                s.push(prefix.as_os_str());
+                PathBuf::from(s)
+            }
+            Prefix::UNC(server, share) => {
+                let mut s = OsString::from(r"\\?\UNC\");
+                s.push(server);
+                s.push(r"\");
+                s.push(share);
+                PathBuf::from(s)
+            }
+            // Already verbatim or a device path
+            _ => return path.to_path_buf(),
+        };
+
+        // Verbatim paths skip normalization, so resolve . and .. ourselves
+        for component in components {
+            match component {
+                Component::RootDir => extended.push(r"\"),
+                Component::CurDir | Component::Prefix(_) => {}
+                Component::ParentDir => {
+                    extended.pop();
+                }
+                Component::Normal(part) => extended.push(part),
+            }
+        }
+
+        extended
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        path.to_path_buf()
+    }
+}
+
+/// Copy files and directories below `src` into `dst`. Links are not followed.
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if !dst.exists() {
+        fs::create_dir_all(long_path(dst))?;
+    }
+
+    for entry in fs::read_dir(long_path(src))? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let dst_path = dst.join(entry.file_name());
+
+        if file_type.is_dir() {
+            copy_dir_recursive(&entry.path(), &dst_path)?;
+        } else if file_type.is_file() {
+            fs::copy(long_path(&entry.path()), long_path(&dst_path))?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn find_java() -> Option<String> {
     if let Ok(java_home) = std::env::var("JAVA_HOME") {
         let java_path = PathBuf::from(java_home)
@@ -131,6 +208,55 @@ pub fn open_folder(path: PathBuf) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Open the file manager with `path` selected. On Linux the freedesktop FileManager1 interface is
+/// tried first, falling back to opening the parent folder.
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), std::io::Error> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(path.as_os_str());
+        Command::new("explorer").arg(select).spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg("-R").arg(path).spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let uri = url::Url::from_file_path(path)
+            .map(|u| u.to_string())
+            .unwrap_or_default();
+        let shown = Command::new("dbus-send")
+            .args([
+                "--session",
+                "--dest=org.freedesktop.FileManager1",
+                "--type=method_call",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+                &format!("array:string:{}", uri),
+                "string:",
+            ])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+
+        if !shown {
+            let folder = if path.is_dir() { path } else { path.parent().unwrap_or(path) };
+            Command::new("xdg-open").arg(folder).spawn()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Open a file with the application the OS associates with it
+pub fn open_with_default_app(path: &Path) -> Result<(), std::io::Error> {
+    // The path goes to the OS as a single argument, never through a shell
+    open::that_detached(path)
+}
+
 /// Generate a comprehensive debug report for troubleshooting
 pub fn generate_debug_report(version: &str) -> String {
     let mut report = String::new();
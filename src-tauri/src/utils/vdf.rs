@@ -0,0 +1,103 @@
+const TYPE_MAP: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT: u8 = 0x02;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VdfValue {
+    /// Entries keep their order, Steam writes shortcuts as "0", "1", ...
+    Map(Vec<(String, VdfValue)>),
+    String(String),
+    Int(u32),
+    UInt64(u64),
+}
+
+impl VdfValue {
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        match self {
+            VdfValue::Map(entries) => entries.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<u32> {
+        match self {
+            VdfValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a Steam binary KeyValues file such as `shortcuts.vdf`, which is a single root map
+pub fn parse(bytes: &[u8]) -> Result<Vec<(String, VdfValue)>, String> {
+    parse_map(bytes, &mut 0)
+}
+
+pub fn write(entries: &[(String, VdfValue)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_map(&mut out, entries);
+    out
+}
+
+fn parse_map(bytes: &[u8], pos: &mut usize) -> Result<Vec<(String, VdfValue)>, String> {
+    let mut entries = Vec::new();
+
+    loop {
+        let kind = *bytes.get(*pos).ok_or("Unexpected end of file")?;
+        *pos += 1;
+
+        if kind == TYPE_END {
+            return Ok(entries);
+        }
+
+        let key = read_cstring(bytes, pos)?;
+        let value = match kind {
+            TYPE_MAP => VdfValue::Map(parse_map(bytes, pos)?),
+            TYPE_STRING => VdfValue::String(read_cstring(bytes, pos)?),
+            TYPE_INT => VdfValue::Int(u32::from_le_bytes(read_array(bytes, pos)?)),
+            TYPE_UINT64 => VdfValue::UInt64(u64::from_le_bytes(read_array(bytes, pos)?)),
+            other => return Err(format!("Unsupported value type 0x{:02x}", other)),
+        };
+        entries.push((key, value));
+    }
+}
+
+fn write_map(out: &mut Vec<u8>, entries: &[(String, VdfValue)]) {
+    for (key, value) in entries {
+        let kind = match value {
+            VdfValue::Map(_) => TYPE_MAP,
+            VdfValue::String(_) => TYPE_STRING,
+            VdfValue::Int(_) => TYPE_INT,
+            VdfValue::UInt64(_) => TYPE_UINT64,
+        };
+        out.push(kind);
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+
+        match value {
+            VdfValue::Map(children) => write_map(out, children),
+            VdfValue::String(s) => {
+                out.extend_from_slice(s.as_bytes());
+                out.push(0);
+            }
+            VdfValue::Int(i) => out.extend_from_slice(&i.to_le_bytes()),
+            VdfValue::UInt64(i) => out.extend_from_slice(&i.to_le_bytes()),
+        }
+    }
+    out.push(TYPE_END);
+}
+
+fn read_cstring(bytes: &[u8], pos: &mut usize) -> Result<String, String> {
+    let rest = bytes.get(*pos..).ok_or("Unexpected end of file")?;
+    let len = rest.iter().position(|b| *b == 0).ok_or("Unterminated string")?;
+    let value = String::from_utf8_lossy(&rest[..len]).to_string();
+    *pos += len + 1;
+    Ok(value)
+}
+
+fn read_array<const N: usize>(bytes: &[u8], pos: &mut usize) -> Result<[u8; N], String> {
+    let slice = bytes.get(*pos..*pos + N).ok_or("Unexpected end of file")?;
+    *pos += N;
+    Ok(slice.try_into().unwrap())
+}
@@ -2,7 +2,7 @@ import { useState, useEffect, useRef } from "react"
 import { invoke } from "@tauri-apps/api/core"
 import { Loader2, Coffee, Cpu, ImagePlus, FolderOpen, ChevronDown, X } from "lucide-react"
 import { AlertModal } from "./ConfirmModal"
-import type { LauncherSettings } from "../../types"
+import type { JavaInstallation, LauncherSettings } from "../../types"
 
 interface SystemInfo {
   total_memory_mb: number
@@ -27,7 +27,7 @@ export function SettingsModal({
   onSettingsChange, 
   onBackgroundChanged 
 }: SettingsModalProps) {
-  const [javaInstallations, setJavaInstallations] = useState<string[]>([])
+  const [javaInstallations, setJavaInstallations] = useState<JavaInstallation[]>([])
   const [isLoadingJava, setIsLoadingJava] = useState(false)
   const [showCustomPath, setShowCustomPath] = useState(false)
   const [customPathValue, setCustomPathValue] = useState("")
@@ -60,7 +60,7 @@ export function SettingsModal({
     }
 
     if (javaInstallations.length > 0) {
-      const isCustomPath = !javaInstallations.includes(settings.java_path)
+      const isCustomPath = !javaInstallations.some((java) => java.path === settings.java_path)
       setShowCustomPath(isCustomPath)
       if (isCustomPath) {
         setCustomPathValue(settings.java_path)
@@ -100,7 +100,7 @@ export function SettingsModal({
   const loadJavaInstallations = async () => {
     setIsLoadingJava(true)
     try {
-      const installations = await invoke<string[]>("detect_java_installations")
+      const installations = await invoke<JavaInstallation[]>("detect_java_installations")
       setJavaInstallations(installations)
     } catch (error) {
       console.error("Failed to detect Java installations:", error)
@@ -420,8 +420,10 @@ export function SettingsModal({
                           }}
                         >
                           <option value="auto">Auto-detect (Recommended)</option>
-                          {javaInstallations.map((path) => (
-                            <option key={path} value={path}>{path}</option>
+                          {javaInstallations.map((java) => (
+                            <option key={java.path} value={java.path}>
+                              Java {java.major_version}{java.vendor ? ` (${java.vendor})` : ""}{java.is_64bit ? "" : " [32-bit]"} - {java.path}
+                            </option>
                           ))}
                           <option value="custom">Custom Path...</option>
                         </select>
@@ -33,6 +33,14 @@ export interface LauncherSettings {
   discord_rpc_enabled: boolean
 }
 
+export interface JavaInstallation {
+  path: string
+  major_version: number
+  version: string
+  vendor: string | null
+  is_64bit: boolean
+}
+
 export interface ConsoleLog {
   instance: string
   type: "stdout" | "stderr"